@@ -7,7 +7,7 @@
 //! - Strict mode: Fails on syntax errors (default, for clean code)
 //! - Tolerant mode: Extracts what it can even with errors (for real-world code)
 
-use codegraph_parser_api::{CallRelation, CodeIR, ModuleEntity, ParserConfig, ParserError};
+use codegraph_parser_api::{naming, CallRelation, CodeIR, ModuleEntity, ParserConfig, ParserError};
 use std::path::Path;
 use tree_sitter::Parser;
 
@@ -200,6 +200,7 @@ pub fn extract_with_options(
     // Visit the AST - the visitor will skip ERROR nodes gracefully
     let mut visitor = CVisitor::new(processed_source.as_bytes());
     visitor.set_extract_calls(options.extract_calls);
+    visitor.set_file_path(naming::file_path(file_path));
     visitor.visit_node(root_node);
 
     ir.functions = visitor.functions;
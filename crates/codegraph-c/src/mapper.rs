@@ -4,7 +4,7 @@
 //! Mapper for converting CodeIR to CodeGraph nodes and edges
 
 use codegraph::{CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
-use codegraph_parser_api::{CodeIR, FileInfo, ParserError};
+use codegraph_parser_api::{CodeIR, FileInfo, ParserError, SymbolTable};
 use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
@@ -14,7 +14,7 @@ pub fn ir_to_graph(
     graph: &mut CodeGraph,
     file_path: &Path,
 ) -> Result<FileInfo, ParserError> {
-    let mut node_map: HashMap<String, NodeId> = HashMap::new();
+    let mut symbols = SymbolTable::new();
     let mut function_ids = Vec::new();
     let mut class_ids = Vec::new();
     let trait_ids = Vec::new();
@@ -35,7 +35,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(module.name.clone(), id);
+        symbols.insert(&module.name, id);
         id
     } else {
         let name = file_path
@@ -51,7 +51,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(name, id);
+        symbols.insert(&name, id);
         id
     };
 
@@ -59,6 +59,7 @@ pub fn ir_to_graph(
     for func in &ir.functions {
         let mut props = PropertyMap::new()
             .with("name", func.name.clone())
+            .with("qualified_name", func.full_name())
             .with("path", file_path.display().to_string())
             .with("signature", func.signature.clone())
             .with("visibility", func.visibility.clone())
@@ -67,6 +68,10 @@ pub fn ir_to_graph(
             .with("is_async", func.is_async)
             .with("is_static", func.is_static);
 
+        if let Some(ref span) = func.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = func.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -97,7 +102,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Function, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(func.name.clone(), func_id);
+        symbols.insert_entity(
+            func.full_name(),
+            &func.name,
+            func_id,
+            func.line_start,
+            func.line_end,
+        );
         function_ids.push(func_id);
 
         // Link function to file
@@ -110,12 +121,17 @@ pub fn ir_to_graph(
     for class in &ir.classes {
         let mut props = PropertyMap::new()
             .with("name", class.name.clone())
+            .with("qualified_name", class.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", class.visibility.clone())
             .with("line_start", class.line_start as i64)
             .with("line_end", class.line_end as i64)
             .with("is_abstract", class.is_abstract);
 
+        if let Some(ref span) = class.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = class.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -129,7 +145,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Class, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(class.name.clone(), class_id);
+        symbols.insert_entity(
+            class.full_name(),
+            &class.name,
+            class_id,
+            class.line_start,
+            class.line_end,
+        );
         class_ids.push(class_id);
 
         // Link class to file
@@ -166,7 +188,7 @@ pub fn ir_to_graph(
         let imported_module = &import.imported;
 
         // Create or get import node
-        let import_id = if let Some(&existing_id) = node_map.get(imported_module) {
+        let import_id = if let Some(existing_id) = symbols.get(imported_module) {
             existing_id
         } else {
             let mut props = PropertyMap::new()
@@ -181,7 +203,7 @@ pub fn ir_to_graph(
             let id = graph
                 .add_node(NodeType::Module, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(imported_module.clone(), id);
+            symbols.insert(imported_module, id);
             id
         };
 
@@ -195,6 +217,9 @@ pub fn ir_to_graph(
         if import.is_wildcard {
             edge_props = edge_props.with("is_wildcard", "true");
         }
+        if let Some(line) = import.line {
+            edge_props = edge_props.with("line", line as i64);
+        }
 
         graph
             .add_edge(file_id, import_id, EdgeType::Imports, edge_props)
@@ -203,11 +228,11 @@ pub fn ir_to_graph(
 
     // Add call relationships
     // Track unresolved calls per caller for cross-file resolution
-    let mut unresolved_calls: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unresolved_calls: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for call in &ir.calls {
         // Resolve caller: function node, or file node for vtable assignments
-        let caller_id = node_map.get(&call.caller).copied().unwrap_or_else(|| {
+        let caller_id = symbols.caller(&call.caller, call.call_site_line).unwrap_or_else(|| {
             // Vtable/struct initializer calls have synthetic caller names
             // (e.g. "vtable_readlink") — use file node as the caller
             if call.caller.starts_with("vtable_") {
@@ -222,7 +247,7 @@ pub fn ir_to_graph(
             continue;
         }
 
-        if let Some(&callee_id) = node_map.get(&call.callee) {
+        if let Some(callee_id) = symbols.resolve(&call.callee, caller_id) {
             // Both caller and callee are in this file - create direct edge
             let mut edge_props = PropertyMap::new()
                 .with("call_site_line", call.call_site_line as i64)
@@ -241,45 +266,32 @@ pub fn ir_to_graph(
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
         } else {
             // Callee not found in this file - store for cross-file resolution
-            // For vtable calls, store on the file node so cross-file resolution
-            // can find the target function
-            let store_on = if call.caller.starts_with("vtable_") {
-                "file".to_string()
-            } else {
-                call.caller.clone()
-            };
+            // For vtable calls the caller is the file node, so cross-file
+            // resolution can find the target function
             unresolved_calls
-                .entry(store_on)
+                .entry(caller_id)
                 .or_default()
                 .push(call.callee.clone());
         }
     }
 
     // Store unresolved calls on caller nodes for post-processing
-    for (caller_name, callees) in unresolved_calls {
-        // "file" is a special key for vtable calls — store on the file node
-        let caller_id_opt = if caller_name == "file" {
-            Some(file_id)
-        } else {
-            node_map.get(&caller_name).copied()
-        };
-        if let Some(caller_id) = caller_id_opt {
-            if let Ok(node) = graph.get_node(caller_id) {
-                let mut all_callees: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_calls")
-                    .unwrap_or_default();
-                for callee in &callees {
-                    if !all_callees.iter().any(|c| c == callee) {
-                        all_callees.push(callee.clone());
-                    }
+    for (caller_id, callees) in unresolved_calls {
+        if let Ok(node) = graph.get_node(caller_id) {
+            let mut all_callees: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_calls")
+                .unwrap_or_default();
+            for callee in &callees {
+                if !all_callees.iter().any(|c| c == callee) {
+                    all_callees.push(callee.clone());
                 }
-                let new_props = node
-                    .properties
-                    .clone()
-                    .with("unresolved_calls", all_callees);
-                let _ = graph.update_node_properties(caller_id, new_props);
             }
+            let new_props = node
+                .properties
+                .clone()
+                .with("unresolved_calls", all_callees);
+            let _ = graph.update_node_properties(caller_id, new_props);
         }
     }
 
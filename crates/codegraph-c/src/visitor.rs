@@ -43,6 +43,8 @@ pub struct CVisitor<'a> {
     pub exported_symbols: Vec<String>,
     /// Whether to extract function calls
     extract_calls: bool,
+    /// Path of the file being visited, used to qualify names
+    file_path: String,
     /// Current function being visited (for tracking caller)
     current_function: Option<String>,
 }
@@ -58,6 +60,7 @@ impl<'a> CVisitor<'a> {
             entry_points: Vec::new(),
             exported_symbols: Vec::new(),
             extract_calls: false,
+            file_path: String::new(),
             current_function: None,
         }
     }
@@ -67,6 +70,19 @@ impl<'a> CVisitor<'a> {
         self.extract_calls = extract;
     }
 
+    /// Set the file path used to qualify names as `path/to/file.c:name`
+    pub fn set_file_path(&mut self, path: impl Into<String>) {
+        self.file_path = path.into();
+    }
+
+    fn qualify(&self, name: &str) -> String {
+        if self.file_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}:{}", self.file_path, name)
+        }
+    }
+
     fn node_text(&self, node: Node) -> String {
         node.utf8_text(self.source).unwrap_or("").to_string()
     }
@@ -154,8 +170,8 @@ impl<'a> CVisitor<'a> {
                 // Detect kernel registration macros at top level
                 if self.current_function.is_none() {
                     match callee.as_str() {
-                        "module_init" | "module_exit" | "late_initcall"
-                        | "subsys_initcall" | "device_initcall" => {
+                        "module_init" | "module_exit" | "late_initcall" | "subsys_initcall"
+                        | "device_initcall" => {
                             // Extract the argument (the registered function name)
                             if let Some(args) = node.child_by_field_name("arguments") {
                                 if let Some(arg) = args.named_child(0) {
@@ -167,8 +183,10 @@ impl<'a> CVisitor<'a> {
                             }
                             return;
                         }
-                        "EXPORT_SYMBOL" | "EXPORT_SYMBOL_GPL"
-                        | "EXPORT_SYMBOL_NS" | "EXPORT_SYMBOL_NS_GPL" => {
+                        "EXPORT_SYMBOL"
+                        | "EXPORT_SYMBOL_GPL"
+                        | "EXPORT_SYMBOL_NS"
+                        | "EXPORT_SYMBOL_NS_GPL" => {
                             if let Some(args) = node.child_by_field_name("arguments") {
                                 if let Some(arg) = args.named_child(0) {
                                     let func_name = self.node_text(arg);
@@ -228,9 +246,17 @@ impl<'a> CVisitor<'a> {
     fn is_common_identifier(name: &str) -> bool {
         matches!(
             name,
-            "NULL" | "null" | "true" | "false" | "TRUE" | "FALSE"
-                | "GFP_KERNEL" | "GFP_ATOMIC" | "IRQF_SHARED"
-                | "THIS_MODULE" | "ARRAY_SIZE"
+            "NULL"
+                | "null"
+                | "true"
+                | "false"
+                | "TRUE"
+                | "FALSE"
+                | "GFP_KERNEL"
+                | "GFP_ATOMIC"
+                | "IRQF_SHARED"
+                | "THIS_MODULE"
+                | "ARRAY_SIZE"
         )
     }
 
@@ -282,11 +308,13 @@ impl<'a> CVisitor<'a> {
             .to_string();
 
         let func = FunctionEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             signature,
             visibility: visibility.to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static,
@@ -487,10 +515,12 @@ impl<'a> CVisitor<'a> {
         let fields = self.extract_struct_fields(node);
 
         let struct_entity = ClassEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -520,10 +550,12 @@ impl<'a> CVisitor<'a> {
         let fields = self.extract_struct_fields(node);
 
         let union_entity = ClassEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -580,10 +612,12 @@ impl<'a> CVisitor<'a> {
         }
 
         let enum_entity = ClassEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -698,6 +732,7 @@ impl<'a> CVisitor<'a> {
                 } else {
                     None
                 },
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -921,6 +956,21 @@ mod tests {
         assert_eq!(visitor.functions[0].return_type, Some("int".to_string()));
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source = b"struct point { int x; };\nint greet(void) { return 0; }";
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_c::language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        let mut visitor = CVisitor::new(source);
+        visitor.set_file_path("src/greet.c");
+        visitor.visit_node(tree.root_node());
+
+        assert_eq!(visitor.functions[0].full_name(), "src/greet.c:greet");
+        assert_eq!(visitor.structs[0].full_name(), "src/greet.c:point");
+    }
+
     #[test]
     fn test_visitor_static_function() {
         let source = b"static void helper() {}";
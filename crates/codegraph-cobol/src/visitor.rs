@@ -112,9 +112,11 @@ impl<'a> CobolVisitor<'a> {
 
         let entity = ClassEntity {
             name,
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -184,11 +186,16 @@ impl<'a> CobolVisitor<'a> {
         self.current_paragraph = Some(name.clone());
 
         let func = FunctionEntity {
+            qualified_name: self
+                .current_program
+                .as_ref()
+                .map(|program| format!("{}.{}", program, name)),
             name,
             signature: full_text.trim().to_string(),
             visibility: "public".to_string(),
             line_start,
             line_end: line_start, // updated when next paragraph or program end is seen
+            span: None,
             is_async: false,
             is_test: false,
             is_static: false,
@@ -222,6 +229,7 @@ impl<'a> CobolVisitor<'a> {
                     symbols: Vec::new(),
                     is_wildcard: false,
                     alias: None,
+                    line: Some(node.start_position().row + 1),
                 });
                 return;
             }
@@ -388,6 +396,7 @@ mod tests {
         assert_eq!(visitor.paragraphs.len(), 1);
         assert_eq!(visitor.paragraphs[0].name, "MAIN-PARA");
         assert_eq!(visitor.paragraphs[0].parent_class, Some("TEST".to_string()));
+        assert_eq!(visitor.paragraphs[0].full_name(), "TEST.MAIN-PARA");
     }
 
     #[test]
@@ -162,6 +162,7 @@ impl<'a> CppVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: if is_struct {
                 "public".to_string()
             } else {
@@ -169,6 +170,7 @@ impl<'a> CppVisitor<'a> {
             },
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface: false,
             base_classes,
@@ -233,6 +235,8 @@ impl<'a> CppVisitor<'a> {
             decl_node.start_position().row + 1,
             decl_node.end_position().row + 1,
         )
+        .with_qualified_name(self.qualify_member(&name))
+        .with_span(decl_node.range().into())
         .with_visibility(&visibility)
         .with_signature(self.node_text(decl_node).lines().next().unwrap_or(""));
 
@@ -297,6 +301,8 @@ impl<'a> CppVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(&qualified_name)
+        .with_span(node.range().into())
         .with_visibility(self.extract_visibility(node))
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
@@ -353,6 +359,8 @@ impl<'a> CppVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(self.qualify_member(&name))
+        .with_span(node.range().into())
         .with_visibility(&visibility)
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
@@ -416,7 +424,9 @@ impl<'a> CppVisitor<'a> {
             caller,
             callee,
             call_site_line: node.start_position().row + 1,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         };
 
         self.calls.push(call);
@@ -482,10 +492,12 @@ impl<'a> CppVisitor<'a> {
         }
 
         let enum_entity = ClassEntity {
+            qualified_name: Some(qualified_name.clone()),
             name: qualified_name,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -535,6 +547,7 @@ impl<'a> CppVisitor<'a> {
                 } else {
                     None
                 },
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -906,6 +919,14 @@ impl<'a> CppVisitor<'a> {
         }
     }
 
+    /// Qualify a member as `namespace::Class::method`, or by namespace outside a class
+    fn qualify_member(&self, name: &str) -> String {
+        match &self.current_class {
+            Some(class) => format!("{}::{}", class, name),
+            None => self.qualify_name(name),
+        }
+    }
+
     fn calculate_complexity(&self, body: Node) -> ComplexityMetrics {
         let mut builder = ComplexityBuilder::new();
         self.visit_for_complexity(body, &mut builder);
@@ -1036,6 +1057,30 @@ mod tests {
         assert_eq!(visitor.classes[0].name, "myns::MyClass");
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source =
+            b"namespace app { class Server { public: void start() {} }; int run() { return 0; } }";
+        let visitor = parse_and_visit(source);
+
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("app::Server")
+        );
+        let start = visitor
+            .functions
+            .iter()
+            .find(|f| f.name == "start")
+            .unwrap();
+        assert_eq!(start.full_name(), "app::Server::start");
+        let run = visitor
+            .functions
+            .iter()
+            .find(|f| f.name == "app::run")
+            .unwrap();
+        assert_eq!(run.full_name(), "app::run");
+    }
+
     #[test]
     fn test_visitor_function_extraction() {
         let source = b"int add(int a, int b) { return a + b; }";
@@ -113,10 +113,7 @@ impl CodeParser for CSharpParser {
         graph: &mut CodeGraph,
     ) -> Result<FileInfo, ParserError> {
         // Route .aspx/.ascx/.master files to the directive extractor
-        let ext = file_path
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
+        let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
         if matches!(ext, "aspx" | "ascx" | "master") {
             let start = Instant::now();
             let mut file_info = crate::aspx::parse_aspx(source, file_path, graph)?;
@@ -276,7 +273,10 @@ mod tests {
     #[test]
     fn test_file_extensions() {
         let parser = CSharpParser::new();
-        assert_eq!(parser.file_extensions(), &[".cs", ".aspx", ".ascx", ".master"]);
+        assert_eq!(
+            parser.file_extensions(),
+            &[".cs", ".aspx", ".ascx", ".master"]
+        );
     }
 
     #[test]
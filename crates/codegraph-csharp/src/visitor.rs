@@ -4,7 +4,7 @@
 //! AST visitor for extracting C# entities
 
 use codegraph_parser_api::{
-    CallRelation, ClassEntity, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
+    naming, CallRelation, ClassEntity, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
     ImplementationRelation, ImportRelation, InheritanceRelation, Parameter, TraitEntity,
 };
 use tree_sitter::Node;
@@ -126,6 +126,7 @@ impl<'a> CSharpVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias: None,
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -190,9 +191,11 @@ impl<'a> CSharpVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface: false,
             base_classes,
@@ -255,13 +258,15 @@ impl<'a> CSharpVisitor<'a> {
         );
 
         // Extract required methods
-        let required_methods = self.extract_interface_methods(node);
+        let required_methods = self.extract_interface_methods(node, &qualified_name);
 
         let interface_entity = TraitEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits,
             doc_comment,
@@ -310,9 +315,11 @@ impl<'a> CSharpVisitor<'a> {
 
         let struct_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -350,10 +357,12 @@ impl<'a> CSharpVisitor<'a> {
         let doc_comment = self.extract_doc_comment(node);
 
         let enum_entity = ClassEntity {
+            qualified_name: Some(qualified_name.clone()),
             name: qualified_name,
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -391,9 +400,11 @@ impl<'a> CSharpVisitor<'a> {
 
         let record_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes,
@@ -454,10 +465,12 @@ impl<'a> CSharpVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_method(&name, &parameters)),
             signature: self.extract_method_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async,
             is_test: self.has_test_attribute(node),
             is_static,
@@ -525,7 +538,9 @@ impl<'a> CSharpVisitor<'a> {
             caller,
             callee,
             call_site_line,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         };
 
         self.calls.push(call);
@@ -642,7 +657,7 @@ impl<'a> CSharpVisitor<'a> {
         }
     }
 
-    fn extract_interface_methods(&self, node: Node) -> Vec<FunctionEntity> {
+    fn extract_interface_methods(&self, node: Node, interface: &str) -> Vec<FunctionEntity> {
         let mut methods = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
             let mut cursor = body.walk();
@@ -660,11 +675,18 @@ impl<'a> CSharpVisitor<'a> {
                     let parameters = self.extract_parameters(child);
 
                     let func = FunctionEntity {
+                        qualified_name: Some(format!(
+                            "{}#{}{}",
+                            interface,
+                            name,
+                            naming::parameter_types(&parameters)
+                        )),
                         name,
                         signature: self.extract_method_signature(child),
                         visibility,
                         line_start: child.start_position().row + 1,
                         line_end: child.end_position().row + 1,
+                        span: Some(child.range().into()),
                         is_async: false,
                         is_test: false,
                         is_static,
@@ -870,6 +892,12 @@ impl<'a> CSharpVisitor<'a> {
         }
     }
 
+    /// Qualify a method as `Namespace.Class#Method(ParamTypes)`
+    fn qualify_method(&self, name: &str, parameters: &[Parameter]) -> String {
+        let owner = self.current_class.as_deref().unwrap_or_default();
+        format!("{}#{}{}", owner, name, naming::parameter_types(parameters))
+    }
+
     fn calculate_complexity(&self, body: Node) -> ComplexityMetrics {
         let mut builder = ComplexityBuilder::new();
         self.visit_for_complexity(body, &mut builder);
@@ -1137,6 +1165,26 @@ mod tests {
         assert_eq!(visitor.classes[0].name, "MyApp.Models.User");
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source = b"namespace MyApp { public class Server { void Start(int port, string host) {} void Start() {} } interface IHandler { void Handle(Request r); } }";
+        let visitor = parse_and_visit(source);
+
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("MyApp.Server")
+        );
+        assert_eq!(
+            visitor.functions[0].full_name(),
+            "MyApp.Server#Start(int,string)"
+        );
+        assert_eq!(visitor.functions[1].full_name(), "MyApp.Server#Start()");
+        assert_eq!(
+            visitor.traits[0].required_methods[0].full_name(),
+            "MyApp.IHandler#Handle(Request)"
+        );
+    }
+
     #[test]
     fn test_visitor_abstract_class() {
         let source = b"public abstract class BaseController { public abstract void Handle(); }";
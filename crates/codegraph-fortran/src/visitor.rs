@@ -41,6 +41,14 @@ impl<'a> FortranVisitor<'a> {
         node.utf8_text(self.source).unwrap_or("").to_string()
     }
 
+    /// Qualify a procedure as `module::procedure`
+    fn qualify(&self, name: &str) -> String {
+        match &self.current_unit {
+            Some(unit) => format!("{}::{}", unit, name),
+            None => name.to_string(),
+        }
+    }
+
     /// Find the first `name` child node and return its text.
     fn find_name_child(&self, node: Node) -> Option<String> {
         let mut cursor = node.walk();
@@ -171,9 +179,11 @@ impl<'a> FortranVisitor<'a> {
 
         let entity = ClassEntity {
             name,
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface,
             base_classes: Vec::new(),
@@ -202,9 +212,11 @@ impl<'a> FortranVisitor<'a> {
 
         let entity = ClassEntity {
             name,
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -243,6 +255,7 @@ impl<'a> FortranVisitor<'a> {
         let complexity = self.calculate_complexity(node);
 
         let func = FunctionEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             signature: self
                 .node_text(node)
@@ -253,6 +266,7 @@ impl<'a> FortranVisitor<'a> {
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static: false,
@@ -293,6 +307,7 @@ impl<'a> FortranVisitor<'a> {
         let complexity = self.calculate_complexity(node);
 
         let func = FunctionEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             signature: self
                 .node_text(node)
@@ -303,6 +318,7 @@ impl<'a> FortranVisitor<'a> {
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static: false,
@@ -356,6 +372,7 @@ impl<'a> FortranVisitor<'a> {
                     symbols: Vec::new(),
                     is_wildcard,
                     alias: None,
+                    line: Some(node.start_position().row + 1),
                 });
             }
         }
@@ -454,6 +471,7 @@ impl<'a> FortranVisitor<'a> {
                     symbols: Vec::new(),
                     is_wildcard: true,
                     alias: None,
+                    line: Some(node.start_position().row + 1),
                 });
             }
         }
@@ -595,6 +613,23 @@ mod tests {
         assert_eq!(visitor.program_units[0].name.to_lowercase(), "mymod");
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        use tree_sitter::Parser;
+        let source = b"module mymod\ncontains\n  subroutine greet()\n  end subroutine greet\nend module mymod\n";
+        let mut parser = Parser::new();
+        parser.set_language(&crate::ts_fortran::language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        let mut visitor = FortranVisitor::new(source);
+        visitor.visit_node(tree.root_node());
+
+        assert_eq!(
+            visitor.functions[0].full_name().to_lowercase(),
+            "mymod::greet"
+        );
+    }
+
     #[test]
     fn test_visitor_subroutine_extraction() {
         use tree_sitter::Parser;
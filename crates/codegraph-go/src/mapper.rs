@@ -4,7 +4,7 @@
 //! Mapper for converting CodeIR to CodeGraph nodes and edges
 
 use codegraph::{CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
-use codegraph_parser_api::{CodeIR, FileInfo, ParserError};
+use codegraph_parser_api::{CodeIR, FileInfo, ParserError, SymbolTable};
 use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
@@ -14,7 +14,7 @@ pub fn ir_to_graph(
     graph: &mut CodeGraph,
     file_path: &Path,
 ) -> Result<FileInfo, ParserError> {
-    let mut node_map: HashMap<String, NodeId> = HashMap::new();
+    let mut symbols = SymbolTable::new();
     let mut function_ids = Vec::new();
     let mut class_ids = Vec::new();
     let mut trait_ids = Vec::new();
@@ -35,7 +35,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(module.name.clone(), id);
+        symbols.insert(&module.name, id);
         id
     } else {
         let name = file_path
@@ -51,7 +51,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(name, id);
+        symbols.insert(&name, id);
         id
     };
 
@@ -59,6 +59,7 @@ pub fn ir_to_graph(
     for func in &ir.functions {
         let mut props = PropertyMap::new()
             .with("name", func.name.clone())
+            .with("qualified_name", func.full_name())
             .with("path", file_path.display().to_string())
             .with("signature", func.signature.clone())
             .with("visibility", func.visibility.clone())
@@ -67,6 +68,10 @@ pub fn ir_to_graph(
             .with("is_async", func.is_async)
             .with("is_static", func.is_static);
 
+        if let Some(ref span) = func.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = func.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -95,7 +100,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Function, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(func.name.clone(), func_id);
+        symbols.insert_entity(
+            func.full_name(),
+            &func.name,
+            func_id,
+            func.line_start,
+            func.line_end,
+        );
         function_ids.push(func_id);
 
         // Link function to file
@@ -108,12 +119,17 @@ pub fn ir_to_graph(
     for class in &ir.classes {
         let mut props = PropertyMap::new()
             .with("name", class.name.clone())
+            .with("qualified_name", class.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", class.visibility.clone())
             .with("line_start", class.line_start as i64)
             .with("line_end", class.line_end as i64)
             .with("is_abstract", class.is_abstract);
 
+        if let Some(ref span) = class.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = class.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -122,7 +138,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Class, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(class.name.clone(), class_id);
+        symbols.insert_entity(
+            class.full_name(),
+            &class.name,
+            class_id,
+            class.line_start,
+            class.line_end,
+        );
         class_ids.push(class_id);
 
         // Link class to file
@@ -135,6 +157,7 @@ pub fn ir_to_graph(
             let method_name = format!("{}.{}", class.name, method.name);
             let mut method_props = PropertyMap::new()
                 .with("name", method_name.clone())
+                .with("qualified_name", method.full_name())
                 .with("path", file_path.display().to_string())
                 .with("signature", method.signature.clone())
                 .with("visibility", method.visibility.clone())
@@ -143,6 +166,10 @@ pub fn ir_to_graph(
                 .with("is_method", "true")
                 .with("parent_class", class.name.clone());
 
+            if let Some(ref span) = method.span {
+                method_props = span.apply_to(method_props);
+            }
+
             if let Some(ref doc) = method.doc_comment {
                 method_props = method_props.with("doc", doc.clone());
             }
@@ -151,7 +178,13 @@ pub fn ir_to_graph(
                 .add_node(NodeType::Function, method_props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-            node_map.insert(method_name, method_id);
+            symbols.insert_entity(
+                method.full_name(),
+                &method_name,
+                method_id,
+                method.line_start,
+                method.line_end,
+            );
             function_ids.push(method_id);
 
             // Link method to class
@@ -165,11 +198,16 @@ pub fn ir_to_graph(
     for interface in &ir.traits {
         let mut props = PropertyMap::new()
             .with("name", interface.name.clone())
+            .with("qualified_name", interface.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", interface.visibility.clone())
             .with("line_start", interface.line_start as i64)
             .with("line_end", interface.line_end as i64);
 
+        if let Some(ref span) = interface.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = interface.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -178,7 +216,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Interface, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(interface.name.clone(), trait_id);
+        symbols.insert_entity(
+            interface.full_name(),
+            &interface.name,
+            trait_id,
+            interface.line_start,
+            interface.line_end,
+        );
         trait_ids.push(trait_id);
 
         // Link interface to file
@@ -192,7 +236,7 @@ pub fn ir_to_graph(
         let imported_module = &import.imported;
 
         // Create or get import node
-        let import_id = if let Some(&existing_id) = node_map.get(imported_module) {
+        let import_id = if let Some(existing_id) = symbols.get(imported_module) {
             existing_id
         } else {
             let props = PropertyMap::new()
@@ -202,7 +246,7 @@ pub fn ir_to_graph(
             let id = graph
                 .add_node(NodeType::Module, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(imported_module.clone(), id);
+            symbols.insert(imported_module, id);
             id
         };
 
@@ -216,6 +260,9 @@ pub fn ir_to_graph(
         if import.is_wildcard {
             edge_props = edge_props.with("is_wildcard", "true");
         }
+        if let Some(line) = import.line {
+            edge_props = edge_props.with("line", line as i64);
+        }
         if !import.symbols.is_empty() {
             edge_props = edge_props.with("symbols", import.symbols.clone());
         }
@@ -226,11 +273,11 @@ pub fn ir_to_graph(
 
     // Add call relationships
     // Track unresolved calls per caller for cross-file resolution
-    let mut unresolved_calls: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unresolved_calls: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for call in &ir.calls {
-        if let Some(&caller_id) = node_map.get(&call.caller) {
-            if let Some(&callee_id) = node_map.get(&call.callee) {
+        if let Some(caller_id) = symbols.caller(&call.caller, call.call_site_line) {
+            if let Some(callee_id) = symbols.resolve(&call.callee, caller_id) {
                 // Both caller and callee are in this file - create direct edge
                 let edge_props = PropertyMap::new()
                     .with("call_site_line", call.call_site_line as i64)
@@ -242,7 +289,7 @@ pub fn ir_to_graph(
             } else {
                 // Callee not found in this file - store for cross-file resolution
                 unresolved_calls
-                    .entry(call.caller.clone())
+                    .entry(caller_id)
                     .or_default()
                     .push(call.callee.clone());
             }
@@ -250,33 +297,31 @@ pub fn ir_to_graph(
     }
 
     // Store unresolved calls on caller nodes for post-processing
-    for (caller_name, callees) in unresolved_calls {
-        if let Some(&caller_id) = node_map.get(&caller_name) {
-            if let Ok(node) = graph.get_node(caller_id) {
-                let mut all_callees: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_calls")
-                    .unwrap_or_default();
-                for callee in &callees {
-                    if !all_callees.iter().any(|c| c == callee) {
-                        all_callees.push(callee.clone());
-                    }
+    for (caller_id, callees) in unresolved_calls {
+        if let Ok(node) = graph.get_node(caller_id) {
+            let mut all_callees: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_calls")
+                .unwrap_or_default();
+            for callee in &callees {
+                if !all_callees.iter().any(|c| c == callee) {
+                    all_callees.push(callee.clone());
                 }
-                let new_props = node
-                    .properties
-                    .clone()
-                    .with("unresolved_calls", all_callees);
-                let _ = graph.update_node_properties(caller_id, new_props);
             }
+            let new_props = node
+                .properties
+                .clone()
+                .with("unresolved_calls", all_callees);
+            let _ = graph.update_node_properties(caller_id, new_props);
         }
     }
 
     // Add type reference relationships (creates References edges)
-    let mut unresolved_type_refs: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unresolved_type_refs: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for type_ref in &ir.type_references {
-        if let Some(&referrer_id) = node_map.get(&type_ref.referrer) {
-            if let Some(&type_id) = node_map.get(&type_ref.type_name) {
+        if let Some(referrer_id) = symbols.caller(&type_ref.referrer, type_ref.line_number) {
+            if let Some(type_id) = symbols.resolve(&type_ref.type_name, referrer_id) {
                 let _ = graph.add_edge(
                     referrer_id,
                     type_id,
@@ -285,37 +330,34 @@ pub fn ir_to_graph(
                 );
             } else {
                 unresolved_type_refs
-                    .entry(type_ref.referrer.clone())
+                    .entry(referrer_id)
                     .or_default()
                     .push(type_ref.type_name.clone());
             }
         }
     }
 
-    for (referrer_name, types) in unresolved_type_refs {
-        if let Some(&referrer_id) = node_map.get(&referrer_name) {
-            if let Ok(node) = graph.get_node(referrer_id) {
-                let mut all: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_type_refs")
-                    .unwrap_or_default();
-                for t in &types {
-                    if !all.iter().any(|existing| existing == t) {
-                        all.push(t.clone());
-                    }
+    for (referrer_id, types) in unresolved_type_refs {
+        if let Ok(node) = graph.get_node(referrer_id) {
+            let mut all: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_type_refs")
+                .unwrap_or_default();
+            for t in &types {
+                if !all.iter().any(|existing| existing == t) {
+                    all.push(t.clone());
                 }
-                let new_props = node.properties.clone().with("unresolved_type_refs", all);
-                let _ = graph.update_node_properties(referrer_id, new_props);
             }
+            let new_props = node.properties.clone().with("unresolved_type_refs", all);
+            let _ = graph.update_node_properties(referrer_id, new_props);
         }
     }
 
     // Add inheritance relationships
     for inheritance in &ir.inheritance {
-        if let (Some(&child_id), Some(&parent_id)) = (
-            node_map.get(&inheritance.child),
-            node_map.get(&inheritance.parent),
-        ) {
+        let child_id = symbols.get(&inheritance.child);
+        let parent_id = child_id.and_then(|id| symbols.resolve(&inheritance.parent, id));
+        if let (Some(child_id), Some(parent_id)) = (child_id, parent_id) {
             let edge_props = PropertyMap::new().with("order", inheritance.order as i64);
 
             graph
@@ -326,10 +368,9 @@ pub fn ir_to_graph(
 
     // Add implementation relationships (struct implements interface)
     for impl_rel in &ir.implementations {
-        if let (Some(&implementor_id), Some(&trait_id)) = (
-            node_map.get(&impl_rel.implementor),
-            node_map.get(&impl_rel.trait_name),
-        ) {
+        let implementor_id = symbols.get(&impl_rel.implementor);
+        let trait_id = implementor_id.and_then(|id| symbols.resolve(&impl_rel.trait_name, id));
+        if let (Some(implementor_id), Some(trait_id)) = (implementor_id, trait_id) {
             graph
                 .add_edge(
                     implementor_id,
@@ -42,6 +42,8 @@ pub struct GoVisitor<'a> {
     pub imports: Vec<ImportRelation>,
     pub calls: Vec<CallRelation>,
     pub type_references: Vec<TypeReference>,
+    /// Package named by the package clause
+    package: String,
     current_function: Option<String>,
 }
 
@@ -55,6 +57,7 @@ impl<'a> GoVisitor<'a> {
             imports: Vec::new(),
             calls: Vec::new(),
             type_references: Vec::new(),
+            package: String::new(),
             current_function: None,
         }
     }
@@ -75,6 +78,14 @@ impl<'a> GoVisitor<'a> {
                 // Don't recurse — visit_method handles body for call extraction
                 return;
             }
+            "package_clause" => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    if child.kind() == "package_identifier" {
+                        self.package = self.node_text(child);
+                    }
+                }
+            }
             "type_declaration" => self.visit_type_declaration(node),
             "import_declaration" => self.visit_import(node),
             "call_expression" => self.visit_call_expression(node),
@@ -87,6 +98,15 @@ impl<'a> GoVisitor<'a> {
         }
     }
 
+    /// Qualify a name with the package
+    fn qualify(&self, name: &str) -> String {
+        if self.package.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.package, name)
+        }
+    }
+
     fn visit_function(&mut self, node: Node) {
         let name = node
             .child_by_field_name("name")
@@ -112,8 +132,10 @@ impl<'a> GoVisitor<'a> {
         let is_test = name.starts_with("Test") || name.starts_with("Benchmark");
 
         self.extract_type_refs_from_signature(&name, node);
+        let qualified_name = self.qualify(&name);
 
         let func = FunctionEntity {
+            qualified_name: Some(qualified_name),
             name,
             signature: self
                 .node_text(node)
@@ -124,6 +146,7 @@ impl<'a> GoVisitor<'a> {
             visibility: visibility.to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test,
             is_static: false,
@@ -184,8 +207,16 @@ impl<'a> GoVisitor<'a> {
         let return_type = self.extract_return_type(node);
 
         self.extract_type_refs_from_signature(&name, node);
+        let qualified_name = match &parent_class {
+            Some(receiver) => {
+                let receiver = receiver.split('[').next().unwrap_or(receiver);
+                self.qualify(&format!("{}.{}", receiver, name))
+            }
+            None => self.qualify(&name),
+        };
 
         let func = FunctionEntity {
+            qualified_name: Some(qualified_name),
             name,
             signature: self
                 .node_text(node)
@@ -196,6 +227,7 @@ impl<'a> GoVisitor<'a> {
             visibility: visibility.to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static: false,
@@ -502,10 +534,12 @@ impl<'a> GoVisitor<'a> {
                     match type_node.kind() {
                         "struct_type" => {
                             let struct_entity = ClassEntity {
+                                qualified_name: Some(self.qualify(&name)),
                                 name,
                                 visibility: "public".to_string(),
                                 line_start: child.start_position().row + 1,
                                 line_end: child.end_position().row + 1,
+                                span: Some(child.range().into()),
                                 is_abstract: false,
                                 is_interface: false,
                                 base_classes: Vec::new(),
@@ -520,10 +554,12 @@ impl<'a> GoVisitor<'a> {
                         }
                         "interface_type" => {
                             let interface_entity = TraitEntity {
+                                qualified_name: Some(self.qualify(&name)),
                                 name,
                                 visibility: "public".to_string(),
                                 line_start: child.start_position().row + 1,
                                 line_end: child.end_position().row + 1,
+                                span: Some(child.range().into()),
                                 required_methods: Vec::new(),
                                 parent_traits: Vec::new(),
                                 doc_comment: None,
@@ -573,6 +609,7 @@ impl<'a> GoVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias: None,
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -630,6 +667,7 @@ impl<'a> GoVisitor<'a> {
             symbols: Vec::new(), // Go doesn't have named imports like TypeScript
             is_wildcard,
             alias,
+            line: Some(node.start_position().row + 1),
         };
         self.imports.push(import);
     }
@@ -988,6 +1026,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_qualified_names() {
+        use tree_sitter::Parser;
+
+        let source =
+            b"package store\ntype Cache struct{}\nfunc (c *Cache) Get() {}\nfunc Open() {}";
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_go::language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        let mut visitor = GoVisitor::new(source);
+        visitor.visit_node(tree.root_node());
+
+        assert_eq!(visitor.structs[0].full_name(), "store.Cache");
+        assert_eq!(visitor.functions[0].full_name(), "store.Cache.Get");
+        assert_eq!(visitor.functions[1].full_name(), "store.Open");
+    }
+
     #[test]
     fn test_complexity_simple_function() {
         use tree_sitter::Parser;
@@ -4,7 +4,7 @@
 //! AST visitor for extracting Java entities
 
 use codegraph_parser_api::{
-    CallRelation, ClassEntity, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
+    naming, CallRelation, ClassEntity, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
     ImplementationRelation, ImportRelation, InheritanceRelation, Parameter, TraitEntity,
 };
 use tree_sitter::Node;
@@ -134,6 +134,7 @@ impl<'a> JavaVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard,
                 alias: None,
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -177,9 +178,11 @@ impl<'a> JavaVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface: false,
             base_classes,
@@ -241,13 +244,15 @@ impl<'a> JavaVisitor<'a> {
         }
 
         // Extract required methods
-        let required_methods = self.extract_interface_methods(node);
+        let required_methods = self.extract_interface_methods(node, &qualified_name);
 
         let interface_entity = TraitEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits,
             doc_comment,
@@ -296,9 +301,11 @@ impl<'a> JavaVisitor<'a> {
 
         let enum_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -353,9 +360,11 @@ impl<'a> JavaVisitor<'a> {
 
         let record_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -419,10 +428,12 @@ impl<'a> JavaVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_method(&name, &parameters)),
             signature: self.extract_method_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: self.has_test_annotation(node),
             is_static,
@@ -469,7 +480,9 @@ impl<'a> JavaVisitor<'a> {
             caller,
             callee,
             call_site_line,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         };
 
         self.calls.push(call);
@@ -556,7 +569,7 @@ impl<'a> JavaVisitor<'a> {
         }
     }
 
-    fn extract_interface_methods(&self, node: Node) -> Vec<FunctionEntity> {
+    fn extract_interface_methods(&self, node: Node, interface: &str) -> Vec<FunctionEntity> {
         let mut methods = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
             let mut cursor = body.walk();
@@ -574,11 +587,18 @@ impl<'a> JavaVisitor<'a> {
                     let parameters = self.extract_parameters(child);
 
                     let func = FunctionEntity {
+                        qualified_name: Some(format!(
+                            "{}#{}{}",
+                            interface,
+                            name,
+                            naming::parameter_types(&parameters)
+                        )),
                         name,
                         signature: self.extract_method_signature(child),
                         visibility,
                         line_start: child.start_position().row + 1,
                         line_end: child.end_position().row + 1,
+                        span: Some(child.range().into()),
                         is_async: false,
                         is_test: false,
                         is_static,
@@ -864,6 +884,12 @@ impl<'a> JavaVisitor<'a> {
             name.to_string()
         }
     }
+
+    /// Qualify a method as `pkg.Class#method(ParamTypes)`
+    fn qualify_method(&self, name: &str, parameters: &[Parameter]) -> String {
+        let owner = self.current_class.as_deref().unwrap_or_default();
+        format!("{}#{}{}", owner, name, naming::parameter_types(parameters))
+    }
 }
 
 #[cfg(test)]
@@ -996,6 +1022,29 @@ mod tests {
         assert_eq!(visitor.classes[0].name, "com.example.app.App");
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source = b"package com.example;\npublic class Server {\n  void start(int port, String host) {}\n  void start() {}\n}\ninterface Handler { void handle(Request r); }";
+        let visitor = parse_and_visit(source);
+
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("com.example.Server")
+        );
+        assert_eq!(
+            visitor.functions[0].full_name(),
+            "com.example.Server#start(int,String)"
+        );
+        assert_eq!(
+            visitor.functions[1].full_name(),
+            "com.example.Server#start()"
+        );
+        assert_eq!(
+            visitor.traits[0].required_methods[0].full_name(),
+            "com.example.Handler#handle(Request)"
+        );
+    }
+
     #[test]
     fn test_visitor_abstract_class() {
         let source = b"public abstract class BaseController { public abstract void handle(); }";
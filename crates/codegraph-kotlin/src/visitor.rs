@@ -4,7 +4,7 @@
 //! AST visitor for extracting Kotlin entities
 
 use codegraph_parser_api::{
-    CallRelation, ClassEntity, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
+    naming, CallRelation, ClassEntity, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
     ImplementationRelation, ImportRelation, InheritanceRelation, Parameter, TraitEntity,
 };
 use tree_sitter::Node;
@@ -172,6 +172,7 @@ impl<'a> KotlinVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard,
                 alias,
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -213,9 +214,11 @@ impl<'a> KotlinVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface: false,
             base_classes,
@@ -267,9 +270,11 @@ impl<'a> KotlinVisitor<'a> {
 
         let object_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes,
@@ -311,13 +316,15 @@ impl<'a> KotlinVisitor<'a> {
         self.extract_supertypes(node, &qualified_name, &mut base_classes, &mut parent_traits);
 
         // Extract required methods
-        let required_methods = self.extract_interface_methods(node);
+        let required_methods = self.extract_interface_methods(node, &qualified_name);
 
         let interface_entity = TraitEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits,
             doc_comment,
@@ -360,9 +367,11 @@ impl<'a> KotlinVisitor<'a> {
 
         let enum_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes,
@@ -488,10 +497,12 @@ impl<'a> KotlinVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_method(&name, &parameters)),
             signature: self.extract_function_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: is_suspend,
             is_test: self.has_test_annotation(node),
             is_static: false, // Kotlin doesn't have static, uses companion objects
@@ -543,7 +554,9 @@ impl<'a> KotlinVisitor<'a> {
             caller,
             callee,
             call_site_line,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         };
 
         self.calls.push(call);
@@ -715,7 +728,7 @@ impl<'a> KotlinVisitor<'a> {
         false
     }
 
-    fn extract_interface_methods(&self, node: Node) -> Vec<FunctionEntity> {
+    fn extract_interface_methods(&self, node: Node, interface: &str) -> Vec<FunctionEntity> {
         let mut methods = Vec::new();
         if let Some(body) = node.child_by_field_name("class_body") {
             let mut cursor = body.walk();
@@ -733,11 +746,18 @@ impl<'a> KotlinVisitor<'a> {
                     let parameters = self.extract_parameters(child);
 
                     let func = FunctionEntity {
+                        qualified_name: Some(format!(
+                            "{}#{}{}",
+                            interface,
+                            name,
+                            naming::parameter_types(&parameters)
+                        )),
                         name,
                         signature: self.extract_function_signature(child),
                         visibility,
                         line_start: child.start_position().row + 1,
                         line_end: child.end_position().row + 1,
+                        span: Some(child.range().into()),
                         is_async: modifiers.contains(&"suspend".to_string()),
                         is_test: false,
                         is_static: false,
@@ -1052,6 +1072,17 @@ impl<'a> KotlinVisitor<'a> {
             name.to_string()
         }
     }
+
+    /// Qualify a function as `pkg.Class#method(ParamTypes)`, or `pkg#function(ParamTypes)`
+    /// at the top level
+    fn qualify_method(&self, name: &str, parameters: &[Parameter]) -> String {
+        let owner = self
+            .current_class
+            .as_deref()
+            .or(self.current_package.as_deref())
+            .unwrap_or_default();
+        format!("{}#{}{}", owner, name, naming::parameter_types(parameters))
+    }
 }
 
 #[cfg(test)]
@@ -1170,6 +1201,21 @@ mod tests {
         assert_eq!(visitor.classes[0].name, "com.example.app.App");
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source =
+            b"package com.example\nclass Server { fun start(port: Int) {} }\nfun main() {}";
+        let visitor = parse_and_visit(source);
+
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("com.example.Server")
+        );
+        let names: Vec<_> = visitor.functions.iter().map(|f| f.full_name()).collect();
+        assert!(names.contains(&"com.example.Server#start(Int)"));
+        assert!(names.contains(&"com.example#main()"));
+    }
+
     #[test]
     fn test_visitor_abstract_class() {
         let source = b"abstract class BaseController { abstract fun handle() }";
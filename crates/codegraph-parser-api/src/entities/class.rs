@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::function::FunctionEntity;
+use super::span::SourceSpan;
 use serde::{Deserialize, Serialize};
 
 /// Represents a class field/attribute
@@ -70,6 +71,9 @@ pub struct ClassEntity {
     /// Class name
     pub name: String,
 
+    /// Name qualified by its module and enclosing types, see [`crate::naming`]
+    pub qualified_name: Option<String>,
+
     /// Visibility: "public", "private", "internal"
     pub visibility: String,
 
@@ -79,6 +83,9 @@ pub struct ClassEntity {
     /// Ending line number (1-indexed)
     pub line_end: usize,
 
+    /// Full source span, when the parser records one
+    pub span: Option<SourceSpan>,
+
     /// Is this an abstract class?
     pub is_abstract: bool,
 
@@ -111,9 +118,11 @@ impl ClassEntity {
     pub fn new(name: impl Into<String>, line_start: usize, line_end: usize) -> Self {
         Self {
             name: name.into(),
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start,
             line_end,
+            span: None,
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -126,6 +135,11 @@ impl ClassEntity {
         }
     }
 
+    pub fn with_qualified_name(mut self, qualified_name: impl Into<String>) -> Self {
+        self.qualified_name = Some(qualified_name.into());
+        self
+    }
+
     pub fn with_visibility(mut self, vis: impl Into<String>) -> Self {
         self.visibility = vis.into();
         self
@@ -166,6 +180,11 @@ impl ClassEntity {
         self
     }
 
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     pub fn with_attributes(mut self, attrs: Vec<String>) -> Self {
         self.attributes = attrs;
         self
@@ -175,4 +194,9 @@ impl ClassEntity {
         self.type_parameters = type_params;
         self
     }
+
+    /// Qualified name if the parser recorded one, otherwise the bare name
+    pub fn full_name(&self) -> &str {
+        self.qualified_name.as_deref().unwrap_or(&self.name)
+    }
 }
@@ -1,6 +1,7 @@
 // Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
 // SPDX-License-Identifier: Apache-2.0
 
+use super::span::SourceSpan;
 use crate::complexity::ComplexityMetrics;
 use serde::{Deserialize, Serialize};
 
@@ -52,6 +53,9 @@ pub struct FunctionEntity {
     /// Function name
     pub name: String,
 
+    /// Name qualified by its module and enclosing types, see [`crate::naming`]
+    pub qualified_name: Option<String>,
+
     /// Full signature (including parameters and return type)
     pub signature: String,
 
@@ -64,6 +68,9 @@ pub struct FunctionEntity {
     /// Ending line number (1-indexed)
     pub line_end: usize,
 
+    /// Full source span, when the parser records one
+    pub span: Option<SourceSpan>,
+
     /// Is this an async/coroutine function?
     pub is_async: bool,
 
@@ -101,9 +108,11 @@ impl FunctionEntity {
         Self {
             signature: name.clone(),
             name,
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start,
             line_end,
+            span: None,
             is_async: false,
             is_test: false,
             is_static: false,
@@ -123,6 +132,11 @@ impl FunctionEntity {
         self
     }
 
+    pub fn with_qualified_name(mut self, qualified_name: impl Into<String>) -> Self {
+        self.qualified_name = Some(qualified_name.into());
+        self
+    }
+
     pub fn with_visibility(mut self, vis: impl Into<String>) -> Self {
         self.visibility = vis.into();
         self
@@ -163,6 +177,11 @@ impl FunctionEntity {
         self
     }
 
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     pub fn with_attributes(mut self, attrs: Vec<String>) -> Self {
         self.attributes = attrs;
         self
@@ -190,4 +209,9 @@ impl FunctionEntity {
     pub fn complexity_grade(&self) -> char {
         self.complexity.as_ref().map(|c| c.grade()).unwrap_or('A')
     }
+
+    /// Qualified name if the parser recorded one, otherwise the bare name
+    pub fn full_name(&self) -> &str {
+        self.qualified_name.as_deref().unwrap_or(&self.name)
+    }
 }
@@ -4,9 +4,11 @@
 pub mod class;
 pub mod function;
 pub mod module;
+pub mod span;
 pub mod trait_;
 
 pub use class::{ClassEntity, Field};
 pub use function::{FunctionEntity, Parameter};
 pub use module::ModuleEntity;
+pub use span::SourceSpan;
 pub use trait_::TraitEntity;
@@ -0,0 +1,76 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+use codegraph::PropertyMap;
+use serde::{Deserialize, Serialize};
+
+/// Full source extent of an entity.
+///
+/// Lines are 1-indexed like `line_start`/`line_end`; columns are 0-indexed byte
+/// offsets within their line. The end position and end byte are exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourceSpan {
+    /// Starting line number (1-indexed)
+    pub start_line: usize,
+
+    /// Starting column (0-indexed byte offset within the line)
+    pub start_column: usize,
+
+    /// Ending line number (1-indexed)
+    pub end_line: usize,
+
+    /// Ending column (0-indexed, exclusive)
+    pub end_column: usize,
+
+    /// Byte offset of the first byte in the file
+    pub start_byte: usize,
+
+    /// Byte offset one past the last byte in the file
+    pub end_byte: usize,
+}
+
+impl SourceSpan {
+    pub fn new(
+        start_line: usize,
+        start_column: usize,
+        end_line: usize,
+        end_column: usize,
+        start_byte: usize,
+        end_byte: usize,
+    ) -> Self {
+        Self {
+            start_line,
+            start_column,
+            end_line,
+            end_column,
+            start_byte,
+            end_byte,
+        }
+    }
+
+    /// Add the columns and byte offsets to a node's properties.
+    ///
+    /// Writes `col_start`, `col_end`, `byte_start` and `byte_end`; lines are
+    /// already recorded as `line_start`/`line_end` by every mapper.
+    pub fn apply_to(&self, props: PropertyMap) -> PropertyMap {
+        props
+            .with("col_start", self.start_column as i64)
+            .with("col_end", self.end_column as i64)
+            .with("byte_start", self.start_byte as i64)
+            .with("byte_end", self.end_byte as i64)
+    }
+}
+
+#[cfg(feature = "tree-sitter")]
+impl From<tree_sitter::Range> for SourceSpan {
+    fn from(range: tree_sitter::Range) -> Self {
+        Self {
+            start_line: range.start_point.row + 1,
+            start_column: range.start_point.column,
+            end_line: range.end_point.row + 1,
+            end_column: range.end_point.column,
+            start_byte: range.start_byte,
+            end_byte: range.end_byte,
+        }
+    }
+}
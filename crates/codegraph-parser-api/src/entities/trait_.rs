@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::function::FunctionEntity;
+use super::span::SourceSpan;
 use serde::{Deserialize, Serialize};
 
 /// Represents a trait/protocol/interface definition
@@ -10,6 +11,9 @@ pub struct TraitEntity {
     /// Trait name
     pub name: String,
 
+    /// Name qualified by its module and enclosing types, see [`crate::naming`]
+    pub qualified_name: Option<String>,
+
     /// Visibility
     pub visibility: String,
 
@@ -19,6 +23,9 @@ pub struct TraitEntity {
     /// Ending line number
     pub line_end: usize,
 
+    /// Full source span, when the parser records one
+    pub span: Option<SourceSpan>,
+
     /// Required methods
     pub required_methods: Vec<FunctionEntity>,
 
@@ -36,9 +43,11 @@ impl TraitEntity {
     pub fn new(name: impl Into<String>, line_start: usize, line_end: usize) -> Self {
         Self {
             name: name.into(),
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start,
             line_end,
+            span: None,
             required_methods: Vec::new(),
             parent_traits: Vec::new(),
             doc_comment: None,
@@ -46,6 +55,11 @@ impl TraitEntity {
         }
     }
 
+    pub fn with_qualified_name(mut self, qualified_name: impl Into<String>) -> Self {
+        self.qualified_name = Some(qualified_name.into());
+        self
+    }
+
     pub fn with_visibility(mut self, vis: impl Into<String>) -> Self {
         self.visibility = vis.into();
         self
@@ -66,8 +80,18 @@ impl TraitEntity {
         self
     }
 
+    pub fn with_span(mut self, span: SourceSpan) -> Self {
+        self.span = Some(span);
+        self
+    }
+
     pub fn with_attributes(mut self, attrs: Vec<String>) -> Self {
         self.attributes = attrs;
         self
     }
+
+    /// Qualified name if the parser recorded one, otherwise the bare name
+    pub fn full_name(&self) -> &str {
+        self.qualified_name.as_deref().unwrap_or(&self.name)
+    }
 }
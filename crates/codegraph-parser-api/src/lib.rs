@@ -13,6 +13,7 @@
 //! - **Relationship types**: Representations of code dependencies (calls, imports, etc.)
 //! - **Configuration**: Customizable parser behavior
 //! - **Metrics**: Performance and success tracking
+//! - **Naming**: Per-language qualified names and symbol lookup for mappers
 //! - **Error handling**: Comprehensive error types
 //!
 //! # Example
@@ -70,16 +71,20 @@ pub mod entities;
 pub mod errors;
 pub mod ir;
 pub mod metrics;
+pub mod naming;
 pub mod relationships;
 pub mod traits;
 
 // Re-export commonly used types
 pub use complexity::{ComplexityBuilder, ComplexityMetrics};
 pub use config::ParserConfig;
-pub use entities::{ClassEntity, Field, FunctionEntity, ModuleEntity, Parameter, TraitEntity};
+pub use entities::{
+    ClassEntity, Field, FunctionEntity, ModuleEntity, Parameter, SourceSpan, TraitEntity,
+};
 pub use errors::{ParserError, ParserResult};
 pub use ir::CodeIR;
 pub use metrics::ParserMetrics;
+pub use naming::SymbolTable;
 pub use relationships::{
     CallRelation, ImplementationRelation, ImportRelation, InheritanceRelation, TypeReference,
 };
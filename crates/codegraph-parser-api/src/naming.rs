@@ -0,0 +1,321 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Qualified names for entities
+//!
+//! Bare names collide: two `new` methods in different impls, or same-named
+//! functions in different namespaces. Every parser therefore records a
+//! `qualified_name` on functions, classes and traits following its language's
+//! scheme below. Mappers store it as the `qualified_name` node property and key
+//! their lookups on it through a [`SymbolTable`].
+//!
+//! | Language | Scheme | Example |
+//! |----------|--------|---------|
+//! | Rust | `crate::module::Type::method`, `<crate::module::Type as Trait>::method` | `crate::net::server::Server::new` |
+//! | Python | `package.module.Class.method` | `app.models.User.save` |
+//! | Java | `package.Class#method(ParamType,...)` | `com.example.Server#start(int,String)` |
+//! | Kotlin | `package.Class#method(ParamType,...)`, `package#function(ParamType,...)` | `com.example.Server#start(Int)` |
+//! | C# | `Namespace.Class#Method(ParamType,...)` | `App.Server#Start(int)` |
+//! | Go | `package.Type.Method`, `package.Func` | `server.Server.Start` |
+//! | TypeScript, JavaScript | `path/to/module:Class.method`, `path/to/module:function` | `src/server:Server.start` |
+//! | C | `path/to/file.c:function`, `path/to/file.c:struct` | `src/util.c:parse_args` |
+//! | C++ | `namespace::Class::method` | `net::Server::start` |
+//! | PHP | `Namespace\Class::method` | `App\Http\Server::start` |
+//! | Ruby | `Module::Class#method`, `Module::Class.singleton_method` | `Net::Server#start` |
+//! | Swift | `Type.method` | `Server.start` |
+//! | Tcl | `::namespace::proc` | `::net::start` |
+//! | Verilog | `module.task` | `uart_tx.send_byte` |
+//! | COBOL | `PROGRAM.PARAGRAPH` | `PAYROLL.COMPUTE-TAX` |
+//! | Fortran | `module::procedure` | `solver::step` |
+//!
+//! Paths are taken as given to the parser, with `/` separators. Java, Kotlin and
+//! C# include parameter types so that overloads stay distinct; parameters without
+//! a declared type contribute their name.
+
+use crate::entities::Parameter;
+use codegraph::NodeId;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+/// Path components of a file, without the root and `.` components.
+fn path_components(file_path: &Path) -> Vec<String> {
+    file_path
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+            Component::ParentDir => Some("..".to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Module components of a file: the components after the last `src` directory,
+/// or of a relative path; only the file for other absolute paths. The extension
+/// is dropped.
+fn module_components(file_path: &Path) -> Vec<String> {
+    let mut components = path_components(file_path);
+    if let Some(src) = components.iter().rposition(|c| c == "src") {
+        components.drain(..=src);
+    } else if file_path.is_absolute() {
+        components.drain(..components.len().saturating_sub(1));
+    }
+    if let Some(last) = components.last_mut() {
+        if let Some((stem, _)) = last.rsplit_once('.') {
+            *last = stem.to_string();
+        }
+    }
+    components
+}
+
+/// A file path as given, with `/` separators.
+pub fn file_path(file_path: &Path) -> String {
+    let prefix = if file_path.is_absolute() { "/" } else { "" };
+    format!("{prefix}{}", path_components(file_path).join("/"))
+}
+
+/// Module of a TypeScript or JavaScript file: its path without the extension.
+pub fn module_path(path: &Path) -> String {
+    let path_str = file_path(path);
+    match path_str.rsplit_once('.') {
+        Some((stem, ext)) if !ext.contains('/') => {
+            // `.d.ts` declarations name the same module as the source
+            stem.strip_suffix(".d").unwrap_or(stem).to_string()
+        }
+        _ => path_str,
+    }
+}
+
+/// Rust module path of a file: `crate` for `lib.rs` and `main.rs`, otherwise
+/// `crate::` followed by the directories under `src` and the file stem, with
+/// `mod.rs` naming its directory.
+pub fn rust_module_path(file_path: &Path) -> String {
+    let mut components = module_components(file_path);
+    if matches!(
+        components.last().map(String::as_str),
+        Some("mod" | "lib" | "main")
+    ) {
+        components.pop();
+    }
+    std::iter::once("crate".to_string())
+        .chain(components)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Python module path of a file: the dotted components under `src` (or of a
+/// relative path), with `__init__` naming its package.
+pub fn python_module_path(file_path: &Path) -> String {
+    let mut components = module_components(file_path);
+    if components.len() > 1 && components.last().is_some_and(|c| c == "__init__") {
+        components.pop();
+    }
+    components.join(".")
+}
+
+/// Parameter type list distinguishing overloads, e.g. `(int,String)`.
+pub fn parameter_types(parameters: &[Parameter]) -> String {
+    let types: Vec<&str> = parameters
+        .iter()
+        .map(|p| p.type_annotation.as_deref().unwrap_or(&p.name))
+        .collect();
+    format!("({})", types.join(","))
+}
+
+#[derive(Debug, Clone)]
+struct Symbol {
+    qualified_name: String,
+    node_id: NodeId,
+    lines: Option<(usize, usize)>,
+}
+
+/// Nodes of one file, looked up by qualified name or by bare name.
+///
+/// Relationships in the IR name their endpoints as written in the source, so a
+/// lookup first tries an exact qualified name and then every entity with that bare
+/// name. When a bare name is ambiguous, call sites resolve to the enclosing caller
+/// by line and references prefer the entity closest to where they were made.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolTable {
+    symbols: Vec<Symbol>,
+    /// Symbol indexes by qualified name and by bare name
+    by_name: HashMap<String, Vec<usize>>,
+    by_node: HashMap<NodeId, usize>,
+}
+
+impl SymbolTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a node known by a single name, such as a file or module.
+    pub fn insert(&mut self, name: &str, node_id: NodeId) {
+        self.push(name, name, node_id, None);
+    }
+
+    /// Register an entity under its qualified and bare names.
+    pub fn insert_entity(
+        &mut self,
+        qualified_name: &str,
+        name: &str,
+        node_id: NodeId,
+        line_start: usize,
+        line_end: usize,
+    ) {
+        self.push(qualified_name, name, node_id, Some((line_start, line_end)));
+    }
+
+    fn push(
+        &mut self,
+        qualified_name: &str,
+        name: &str,
+        node_id: NodeId,
+        lines: Option<(usize, usize)>,
+    ) {
+        let idx = self.symbols.len();
+        self.symbols.push(Symbol {
+            qualified_name: qualified_name.to_string(),
+            node_id,
+            lines,
+        });
+        self.by_name
+            .entry(qualified_name.to_string())
+            .or_default()
+            .push(idx);
+        if name != qualified_name {
+            self.by_name.entry(name.to_string()).or_default().push(idx);
+        }
+        self.by_node.entry(node_id).or_insert(idx);
+    }
+
+    /// Symbols named `name`: the exact qualified match if there is one, else every
+    /// entity with that bare name, in registration order.
+    fn candidates(&self, name: &str) -> Vec<&Symbol> {
+        let Some(indexes) = self.by_name.get(name) else {
+            return Vec::new();
+        };
+        let symbols: Vec<&Symbol> = indexes.iter().map(|&i| &self.symbols[i]).collect();
+        let exact: Vec<&Symbol> = symbols
+            .iter()
+            .copied()
+            .filter(|s| s.qualified_name == name)
+            .collect();
+        if exact.is_empty() {
+            symbols
+        } else {
+            exact
+        }
+    }
+
+    /// Check whether any node is registered under a name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.by_name.contains_key(name)
+    }
+
+    /// Look up a node by qualified name, or the first entity with a bare name.
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.candidates(name).first().map(|s| s.node_id)
+    }
+
+    /// Qualified name a node was registered under.
+    pub fn qualified_name(&self, node_id: NodeId) -> Option<&str> {
+        self.by_node
+            .get(&node_id)
+            .map(|&i| self.symbols[i].qualified_name.as_str())
+    }
+
+    /// Resolve the caller of a call site: among entities named `name`, the
+    /// innermost one whose lines contain `line`.
+    pub fn caller(&self, name: &str, line: usize) -> Option<NodeId> {
+        let candidates = self.candidates(name);
+        candidates
+            .iter()
+            .filter_map(|s| {
+                let (start, end) = s.lines?;
+                (start <= line && line <= end).then_some((end - start, s.node_id))
+            })
+            .min_by_key(|(size, _)| *size)
+            .map(|(_, node_id)| node_id)
+            .or_else(|| candidates.first().map(|s| s.node_id))
+    }
+
+    /// Resolve a name referenced from another node: among entities named `name`,
+    /// the one sharing the longest qualified-name prefix with `from`.
+    pub fn resolve(&self, name: &str, from: NodeId) -> Option<NodeId> {
+        let candidates = self.candidates(name);
+        if candidates.len() <= 1 {
+            return candidates.first().map(|s| s.node_id);
+        }
+        let context = self.qualified_name(from).unwrap_or("");
+        let shared = |s: &Symbol| {
+            s.qualified_name
+                .chars()
+                .zip(context.chars())
+                .take_while(|(a, b)| a == b)
+                .count()
+        };
+        // `max_by_key` keeps the last maximum; iterate in reverse to keep the first
+        candidates
+            .iter()
+            .rev()
+            .max_by_key(|s| shared(s))
+            .map(|s| s.node_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_paths() {
+        assert_eq!(rust_module_path(Path::new("src/lib.rs")), "crate");
+        assert_eq!(rust_module_path(Path::new("src/main.rs")), "crate");
+        assert_eq!(
+            rust_module_path(Path::new("/work/app/src/net/server.rs")),
+            "crate::net::server"
+        );
+        assert_eq!(rust_module_path(Path::new("src/net/mod.rs")), "crate::net");
+        assert_eq!(rust_module_path(Path::new("test.rs")), "crate::test");
+
+        assert_eq!(python_module_path(Path::new("app/models.py")), "app.models");
+        assert_eq!(python_module_path(Path::new("src/app/__init__.py")), "app");
+        assert_eq!(python_module_path(Path::new("/tmp/x/script.py")), "script");
+
+        assert_eq!(module_path(Path::new("./src/server.ts")), "src/server");
+        assert_eq!(module_path(Path::new("types/api.d.ts")), "types/api");
+        assert_eq!(file_path(Path::new("./src/util.c")), "src/util.c");
+    }
+
+    #[test]
+    fn test_parameter_types() {
+        let params = [
+            Parameter::new("port").with_type("int"),
+            Parameter::new("host").with_type("String"),
+        ];
+        assert_eq!(parameter_types(&params), "(int,String)");
+        assert_eq!(parameter_types(&[Parameter::new("x")]), "(x)");
+        assert_eq!(parameter_types(&[]), "()");
+    }
+
+    #[test]
+    fn test_symbol_table_disambiguates() {
+        let mut table = SymbolTable::new();
+        table.insert_entity("crate::a::Foo::new", "new", 1, 10, 20);
+        table.insert_entity("crate::b::Bar::new", "new", 2, 30, 40);
+        table.insert_entity("crate::a::Foo::build", "build", 3, 22, 28);
+        table.insert("std::fmt", 4);
+
+        assert_eq!(table.get("crate::b::Bar::new"), Some(2));
+        assert_eq!(table.get("new"), Some(1));
+        assert_eq!(table.get("std::fmt"), Some(4));
+        assert_eq!(table.get("missing"), None);
+
+        assert_eq!(table.caller("new", 35), Some(2));
+        assert_eq!(table.caller("new", 99), Some(1));
+
+        // `new` called from `Foo::build` is `Foo::new`
+        assert_eq!(table.resolve("new", 3), Some(1));
+        assert_eq!(table.resolve("build", 2), Some(3));
+        assert_eq!(table.qualified_name(2), Some("crate::b::Bar::new"));
+    }
+}
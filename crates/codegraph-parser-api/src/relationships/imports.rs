@@ -20,6 +20,9 @@ pub struct ImportRelation {
 
     /// Import alias (if any)
     pub alias: Option<String>,
+
+    /// Line number of the import statement (if known)
+    pub line: Option<usize>,
 }
 
 impl ImportRelation {
@@ -30,6 +33,7 @@ impl ImportRelation {
             symbols: Vec::new(),
             is_wildcard: false,
             alias: None,
+            line: None,
         }
     }
 
@@ -47,4 +51,9 @@ impl ImportRelation {
         self.alias = Some(alias.into());
         self
     }
+
+    pub fn with_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
 }
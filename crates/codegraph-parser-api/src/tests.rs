@@ -122,6 +122,37 @@ fn test_trait_entity_builder() {
     assert_eq!(trait_entity.doc_comment, Some("Trait docs".to_string()));
 }
 
+#[test]
+fn test_source_span() {
+    let span = SourceSpan::new(3, 4, 7, 1, 40, 120);
+    let func = FunctionEntity::new("f", 3, 7).with_span(span);
+    assert_eq!(func.span, Some(span));
+    assert_eq!(ClassEntity::new("C", 1, 2).span, None);
+
+    let props = span.apply_to(codegraph::PropertyMap::new());
+    assert_eq!(props.get_int("col_start"), Some(4));
+    assert_eq!(props.get_int("col_end"), Some(1));
+    assert_eq!(props.get_int("byte_start"), Some(40));
+    assert_eq!(props.get_int("byte_end"), Some(120));
+}
+
+#[test]
+fn test_qualified_name() {
+    let func = FunctionEntity::new("new", 1, 3);
+    assert_eq!(func.qualified_name, None);
+    assert_eq!(func.full_name(), "new");
+
+    let func = func.with_qualified_name("crate::server::Server::new");
+    assert_eq!(func.full_name(), "crate::server::Server::new");
+    assert_eq!(
+        ClassEntity::new("Server", 1, 9)
+            .with_qualified_name("app.Server")
+            .full_name(),
+        "app.Server"
+    );
+    assert_eq!(TraitEntity::new("Handler", 1, 2).full_name(), "Handler");
+}
+
 #[test]
 fn test_call_relation_builder() {
     let call = CallRelation::new("caller", "callee", 42);
@@ -140,13 +171,15 @@ fn test_call_relation_builder() {
 fn test_import_relation_builder() {
     let import = ImportRelation::new("my_module", "std::collections::HashMap")
         .with_alias("HMap")
-        .with_symbols(vec!["HashMap".to_string()]);
+        .with_symbols(vec!["HashMap".to_string()])
+        .with_line(3);
 
     assert_eq!(import.importer, "my_module");
     assert_eq!(import.imported, "std::collections::HashMap");
     assert_eq!(import.alias, Some("HMap".to_string()));
     assert_eq!(import.symbols.len(), 1);
     assert_eq!(import.symbols[0], "HashMap");
+    assert_eq!(import.line, Some(3));
 }
 
 #[test]
@@ -4,7 +4,7 @@
 //! Mapper for converting CodeIR to CodeGraph nodes and edges
 
 use codegraph::{CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
-use codegraph_parser_api::{CodeIR, FileInfo, ParserError};
+use codegraph_parser_api::{CodeIR, FileInfo, ParserError, SymbolTable};
 use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
@@ -14,7 +14,7 @@ pub fn ir_to_graph(
     graph: &mut CodeGraph,
     file_path: &Path,
 ) -> Result<FileInfo, ParserError> {
-    let mut node_map: HashMap<String, NodeId> = HashMap::new();
+    let mut symbols = SymbolTable::new();
     let mut function_ids = Vec::new();
     let mut class_ids = Vec::new();
     let mut trait_ids = Vec::new();
@@ -35,7 +35,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(module.name.clone(), id);
+        symbols.insert(&module.name, id);
         id
     } else {
         let name = file_path
@@ -51,7 +51,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(name, id);
+        symbols.insert(&name, id);
         id
     };
 
@@ -59,6 +59,7 @@ pub fn ir_to_graph(
     for func in &ir.functions {
         let mut props = PropertyMap::new()
             .with("name", func.name.clone())
+            .with("qualified_name", func.full_name())
             .with("path", file_path.display().to_string())
             .with("signature", func.signature.clone())
             .with("visibility", func.visibility.clone())
@@ -68,6 +69,10 @@ pub fn ir_to_graph(
             .with("is_static", func.is_static)
             .with("is_abstract", func.is_abstract);
 
+        if let Some(ref span) = func.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = func.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -103,12 +108,18 @@ pub fn ir_to_graph(
             .add_node(NodeType::Function, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(func.name.clone(), func_id);
+        symbols.insert_entity(
+            func.full_name(),
+            &func.name,
+            func_id,
+            func.line_start,
+            func.line_end,
+        );
         function_ids.push(func_id);
 
         // Link function to file or parent class
         if let Some(ref parent_class) = func.parent_class {
-            if let Some(&class_id) = node_map.get(parent_class) {
+            if let Some(class_id) = symbols.get(parent_class) {
                 graph
                     .add_edge(class_id, func_id, EdgeType::Contains, PropertyMap::new())
                     .map_err(|e| ParserError::GraphError(e.to_string()))?;
@@ -124,12 +135,17 @@ pub fn ir_to_graph(
     for class in &ir.classes {
         let mut props = PropertyMap::new()
             .with("name", class.name.clone())
+            .with("qualified_name", class.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", class.visibility.clone())
             .with("line_start", class.line_start as i64)
             .with("line_end", class.line_end as i64)
             .with("is_abstract", class.is_abstract);
 
+        if let Some(ref span) = class.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = class.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -141,7 +157,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Class, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(class.name.clone(), class_id);
+        symbols.insert_entity(
+            class.full_name(),
+            &class.name,
+            class_id,
+            class.line_start,
+            class.line_end,
+        );
         class_ids.push(class_id);
 
         // Link class to file
@@ -154,6 +176,7 @@ pub fn ir_to_graph(
             let method_name = format!("{}::{}", class.name, method.name);
             let mut method_props = PropertyMap::new()
                 .with("name", method_name.clone())
+                .with("qualified_name", method.full_name())
                 .with("path", file_path.display().to_string())
                 .with("signature", method.signature.clone())
                 .with("visibility", method.visibility.clone())
@@ -162,6 +185,10 @@ pub fn ir_to_graph(
                 .with("is_method", "true")
                 .with("parent_class", class.name.clone());
 
+            if let Some(ref span) = method.span {
+                method_props = span.apply_to(method_props);
+            }
+
             if let Some(ref doc) = method.doc_comment {
                 method_props = method_props.with("doc", doc.clone());
             }
@@ -170,7 +197,13 @@ pub fn ir_to_graph(
                 .add_node(NodeType::Function, method_props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-            node_map.insert(method_name, method_id);
+            symbols.insert_entity(
+                method.full_name(),
+                &method_name,
+                method_id,
+                method.line_start,
+                method.line_end,
+            );
             function_ids.push(method_id);
 
             // Link method to class
@@ -184,11 +217,16 @@ pub fn ir_to_graph(
     for trait_entity in &ir.traits {
         let mut props = PropertyMap::new()
             .with("name", trait_entity.name.clone())
+            .with("qualified_name", trait_entity.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", trait_entity.visibility.clone())
             .with("line_start", trait_entity.line_start as i64)
             .with("line_end", trait_entity.line_end as i64);
 
+        if let Some(ref span) = trait_entity.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = trait_entity.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -205,7 +243,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Interface, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(trait_entity.name.clone(), trait_id);
+        symbols.insert_entity(
+            trait_entity.full_name(),
+            &trait_entity.name,
+            trait_id,
+            trait_entity.line_start,
+            trait_entity.line_end,
+        );
         trait_ids.push(trait_id);
 
         // Link interface/trait to file
@@ -219,7 +263,7 @@ pub fn ir_to_graph(
         let imported_module = &import.imported;
 
         // Create or get import node
-        let import_id = if let Some(&existing_id) = node_map.get(imported_module) {
+        let import_id = if let Some(existing_id) = symbols.get(imported_module) {
             existing_id
         } else {
             let is_include = import.importer == "include_require";
@@ -241,7 +285,7 @@ pub fn ir_to_graph(
             let id = graph
                 .add_node(NodeType::Module, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(imported_module.clone(), id);
+            symbols.insert(imported_module, id);
             id
         };
 
@@ -255,6 +299,9 @@ pub fn ir_to_graph(
         if import.is_wildcard {
             edge_props = edge_props.with("is_wildcard", "true");
         }
+        if let Some(line) = import.line {
+            edge_props = edge_props.with("line", line as i64);
+        }
         if !import.symbols.is_empty() {
             edge_props = edge_props.with("symbols", import.symbols.clone());
         }
@@ -264,11 +311,11 @@ pub fn ir_to_graph(
     }
 
     // Add call relationships
-    let mut unresolved_calls: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unresolved_calls: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for call in &ir.calls {
-        if let Some(&caller_id) = node_map.get(&call.caller) {
-            if let Some(&callee_id) = node_map.get(&call.callee) {
+        if let Some(caller_id) = symbols.caller(&call.caller, call.call_site_line) {
+            if let Some(callee_id) = symbols.resolve(&call.callee, caller_id) {
                 let edge_props = PropertyMap::new()
                     .with("call_site_line", call.call_site_line as i64)
                     .with("is_direct", call.is_direct);
@@ -278,7 +325,7 @@ pub fn ir_to_graph(
                     .map_err(|e| ParserError::GraphError(e.to_string()))?;
             } else {
                 unresolved_calls
-                    .entry(call.caller.clone())
+                    .entry(caller_id)
                     .or_default()
                     .push(call.callee.clone());
             }
@@ -286,33 +333,30 @@ pub fn ir_to_graph(
     }
 
     // Store unresolved calls on caller nodes for post-processing
-    for (caller_name, callees) in unresolved_calls {
-        if let Some(&caller_id) = node_map.get(&caller_name) {
-            if let Ok(node) = graph.get_node(caller_id) {
-                let mut all_callees: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_calls")
-                    .unwrap_or_default();
-                for callee in &callees {
-                    if !all_callees.iter().any(|c| c == callee) {
-                        all_callees.push(callee.clone());
-                    }
+    for (caller_id, callees) in unresolved_calls {
+        if let Ok(node) = graph.get_node(caller_id) {
+            let mut all_callees: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_calls")
+                .unwrap_or_default();
+            for callee in &callees {
+                if !all_callees.iter().any(|c| c == callee) {
+                    all_callees.push(callee.clone());
                 }
-                let new_props = node
-                    .properties
-                    .clone()
-                    .with("unresolved_calls", all_callees);
-                let _ = graph.update_node_properties(caller_id, new_props);
             }
+            let new_props = node
+                .properties
+                .clone()
+                .with("unresolved_calls", all_callees);
+            let _ = graph.update_node_properties(caller_id, new_props);
         }
     }
 
     // Add inheritance relationships
     for inheritance in &ir.inheritance {
-        if let (Some(&child_id), Some(&parent_id)) = (
-            node_map.get(&inheritance.child),
-            node_map.get(&inheritance.parent),
-        ) {
+        let child_id = symbols.get(&inheritance.child);
+        let parent_id = child_id.and_then(|id| symbols.resolve(&inheritance.parent, id));
+        if let (Some(child_id), Some(parent_id)) = (child_id, parent_id) {
             let edge_props = PropertyMap::new().with("order", inheritance.order as i64);
 
             graph
@@ -323,10 +367,9 @@ pub fn ir_to_graph(
 
     // Add implementation relationships (class implements interface, uses trait)
     for impl_rel in &ir.implementations {
-        if let (Some(&implementor_id), Some(&trait_id)) = (
-            node_map.get(&impl_rel.implementor),
-            node_map.get(&impl_rel.trait_name),
-        ) {
+        let implementor_id = symbols.get(&impl_rel.implementor);
+        let trait_id = implementor_id.and_then(|id| symbols.resolve(&impl_rel.trait_name, id));
+        if let (Some(implementor_id), Some(trait_id)) = (implementor_id, trait_id) {
             graph
                 .add_edge(
                     implementor_id,
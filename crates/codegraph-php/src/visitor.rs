@@ -129,10 +129,12 @@ impl<'a> PhpVisitor<'a> {
 
         let func = FunctionEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             signature: self.extract_function_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static: false,
@@ -176,10 +178,12 @@ impl<'a> PhpVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_method(&name)),
             signature: self.extract_function_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static,
@@ -247,9 +251,11 @@ impl<'a> PhpVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface: false,
             base_classes,
@@ -321,13 +327,15 @@ impl<'a> PhpVisitor<'a> {
         }
 
         // Extract required methods
-        let required_methods = self.extract_interface_methods(node);
+        let required_methods = self.extract_interface_methods(node, &qualified_name);
 
         let interface_entity = TraitEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits,
             doc_comment,
@@ -364,9 +372,11 @@ impl<'a> PhpVisitor<'a> {
 
         let trait_entity = TraitEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods: Vec::new(),
             parent_traits: Vec::new(),
             doc_comment,
@@ -404,9 +414,11 @@ impl<'a> PhpVisitor<'a> {
         // PHP 8.1 enums are treated as classes
         let enum_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -463,12 +475,12 @@ impl<'a> PhpVisitor<'a> {
                 "string" | "encapsed_string" => {
                     let path = self.extract_string_content(child);
                     if !path.is_empty() {
-                        self.push_include_import(path, false, node.kind());
+                        self.push_include_import(path, false, node);
                     }
                     return;
                 }
                 "binary_expression" => {
-                    self.handle_concat_include(child, node.kind());
+                    self.handle_concat_include(child, node);
                     return;
                 }
                 "parenthesized_expression" => {
@@ -478,12 +490,12 @@ impl<'a> PhpVisitor<'a> {
                             "string" | "encapsed_string" => {
                                 let path = self.extract_string_content(inner);
                                 if !path.is_empty() {
-                                    self.push_include_import(path, false, node.kind());
+                                    self.push_include_import(path, false, node);
                                 }
                                 return;
                             }
                             "binary_expression" => {
-                                self.handle_concat_include(inner, node.kind());
+                                self.handle_concat_include(inner, node);
                                 return;
                             }
                             _ => {}
@@ -496,7 +508,7 @@ impl<'a> PhpVisitor<'a> {
     }
 
     /// Build the imported path from a concatenation expression and push an import.
-    fn handle_concat_include(&mut self, concat_node: Node, include_kind: &str) {
+    fn handle_concat_include(&mut self, concat_node: Node, include_node: Node) {
         let mut string_parts: Vec<String> = Vec::new();
         let mut has_dir_marker = false;
         let mut has_dynamic_parts = false;
@@ -520,10 +532,10 @@ impl<'a> PhpVisitor<'a> {
             } else {
                 format!("./{}", joined)
             };
-            self.push_include_import(path, false, include_kind);
+            self.push_include_import(path, false, include_node);
         } else if !joined.is_empty() {
             // Dynamic parts present — emit as suffix match
-            self.push_include_import(joined, true, include_kind);
+            self.push_include_import(joined, true, include_node);
         }
     }
 
@@ -623,13 +635,14 @@ impl<'a> PhpVisitor<'a> {
     }
 
     /// Helper to push an include/require import.
-    fn push_include_import(&mut self, path: String, is_suffix: bool, include_kind: &str) {
+    fn push_include_import(&mut self, path: String, is_suffix: bool, include_node: Node) {
         let import = ImportRelation {
             importer: "include_require".to_string(),
             imported: path,
             symbols: Vec::new(),
             is_wildcard: is_suffix,
-            alias: Some(include_kind.to_string()),
+            alias: Some(include_node.kind().to_string()),
+            line: Some(include_node.start_position().row + 1),
         };
         self.imports.push(import);
     }
@@ -673,6 +686,7 @@ impl<'a> PhpVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias,
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -699,7 +713,9 @@ impl<'a> PhpVisitor<'a> {
             caller,
             callee,
             call_site_line,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         };
 
         self.calls.push(call);
@@ -797,7 +813,7 @@ impl<'a> PhpVisitor<'a> {
         }
     }
 
-    fn extract_interface_methods(&self, node: Node) -> Vec<FunctionEntity> {
+    fn extract_interface_methods(&self, node: Node, interface: &str) -> Vec<FunctionEntity> {
         let mut methods = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
             let mut cursor = body.walk();
@@ -814,11 +830,13 @@ impl<'a> PhpVisitor<'a> {
                     let parameters = self.extract_parameters(child);
 
                     let func = FunctionEntity {
+                        qualified_name: Some(format!("{}::{}", interface, name)),
                         name,
                         signature: self.extract_function_signature(child),
                         visibility,
                         line_start: child.start_position().row + 1,
                         line_end: child.end_position().row + 1,
+                        span: Some(child.range().into()),
                         is_async: false,
                         is_test: false,
                         is_static,
@@ -1029,6 +1047,14 @@ impl<'a> PhpVisitor<'a> {
             name.to_string()
         }
     }
+
+    /// Qualify a method as `Namespace\Class::method`
+    fn qualify_method(&self, name: &str) -> String {
+        match &self.current_class {
+            Some(class) => format!("{}::{}", class, name),
+            None => name.to_string(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1170,6 +1196,23 @@ mod tests {
         assert_eq!(visitor.classes[0].name, "App\\Controllers\\HomeController");
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source = b"<?php\nnamespace App;\nfunction boot() {}\nclass Server { public function start() {} }\ninterface Handler { public function handle(); }";
+        let visitor = parse_and_visit(source);
+
+        assert_eq!(visitor.functions[0].full_name(), "App\\boot");
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("App\\Server")
+        );
+        assert_eq!(visitor.functions[1].full_name(), "App\\Server::start");
+        assert_eq!(
+            visitor.traits[0].required_methods[0].full_name(),
+            "App\\Handler::handle"
+        );
+    }
+
     #[test]
     fn test_visitor_abstract_class() {
         let source =
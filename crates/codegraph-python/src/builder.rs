@@ -2,8 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use codegraph::{helpers, CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
-use codegraph_parser_api::CodeIR;
-use std::collections::HashMap;
+use codegraph_parser_api::{CodeIR, SymbolTable};
 
 use crate::error::Result;
 
@@ -26,12 +25,13 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
         .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
 
     // Track entity name -> NodeId mappings for relationship building
-    let mut entity_map: HashMap<String, NodeId> = HashMap::new();
+    let mut symbols = SymbolTable::new();
 
     // Add all functions
     for func in &ir.functions {
         let mut props = PropertyMap::new()
             .with("name", func.name.clone())
+            .with("qualified_name", func.full_name())
             .with("signature", func.signature.clone())
             .with("line_start", func.line_start as i64)
             .with("line_end", func.line_end as i64)
@@ -41,6 +41,10 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
             .with("is_test", func.is_test)
             .with("attributes", func.attributes.clone());
 
+        if let Some(ref span) = func.span {
+            props = span.apply_to(props);
+        }
+
         // Add complexity metrics if available
         if let Some(ref complexity) = func.complexity {
             props = props
@@ -69,7 +73,13 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
             .add_edge(file_id, func_id, EdgeType::Contains, PropertyMap::new())
             .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
 
-        entity_map.insert(func.name.clone(), func_id);
+        symbols.insert_entity(
+            func.full_name(),
+            &func.name,
+            func_id,
+            func.line_start,
+            func.line_end,
+        );
     }
 
     // Add all classes
@@ -82,8 +92,20 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
             class.line_end as i64,
         )
         .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
+        graph
+            .update_node_properties(
+                class_id,
+                PropertyMap::new().with("qualified_name", class.full_name()),
+            )
+            .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
 
-        entity_map.insert(class.name.clone(), class_id);
+        symbols.insert_entity(
+            class.full_name(),
+            &class.name,
+            class_id,
+            class.line_start,
+            class.line_end,
+        );
 
         // Add methods as functions linked to the class
         for method in &class.methods {
@@ -95,18 +117,29 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
                 method.line_end as i64,
             )
             .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
+            graph
+                .update_node_properties(
+                    method_id,
+                    PropertyMap::new().with("qualified_name", method.full_name()),
+                )
+                .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
 
-            // Track methods with qualified name for call relationships
-            let qualified = format!("{}.{}", class.name, method.name);
-            entity_map.insert(qualified, method_id);
+            // Track methods as `Class.method` for call relationships
+            symbols.insert_entity(
+                method.full_name(),
+                &format!("{}.{}", class.name, method.name),
+                method_id,
+                method.line_start,
+                method.line_end,
+            );
         }
     }
 
     // Add call relationships
     for call in &ir.calls {
-        if let (Some(&caller_id), Some(&callee_id)) =
-            (entity_map.get(&call.caller), entity_map.get(&call.callee))
-        {
+        let caller_id = symbols.caller(&call.caller, call.call_site_line);
+        let callee_id = caller_id.and_then(|id| symbols.resolve(&call.callee, id));
+        if let (Some(caller_id), Some(callee_id)) = (caller_id, callee_id) {
             helpers::add_call(graph, caller_id, callee_id, call.call_site_line as i64)
                 .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
         }
@@ -116,7 +149,7 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
     for import in &ir.imports {
         let imported_module = &import.imported;
 
-        let import_id = if let Some(&existing_id) = entity_map.get(imported_module) {
+        let import_id = if let Some(existing_id) = symbols.get(imported_module) {
             existing_id
         } else {
             let is_external = !imported_module.starts_with('.');
@@ -127,7 +160,7 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
             let id = graph
                 .add_node(NodeType::Module, props)
                 .map_err(|e| crate::error::ParseError::GraphError(e.to_string()))?;
-            entity_map.insert(imported_module.clone(), id);
+            symbols.insert(imported_module, id);
             id
         };
 
@@ -138,6 +171,9 @@ pub fn build_graph(graph: &mut CodeGraph, ir: &CodeIR, file_path: &str) -> Resul
         if import.is_wildcard {
             edge_props = edge_props.with("is_wildcard", "true");
         }
+        if let Some(line) = import.line {
+            edge_props = edge_props.with("line", line as i64);
+        }
         if !import.symbols.is_empty() {
             edge_props = edge_props.with("symbols", import.symbols.clone());
         }
@@ -9,8 +9,8 @@
 use crate::config::ParserConfig;
 use crate::visitor::{extract_decorators, extract_docstring};
 use codegraph_parser_api::{
-    CallRelation, ClassEntity, CodeIR, ComplexityBuilder, ComplexityMetrics, FunctionEntity,
-    ImportRelation, InheritanceRelation, ModuleEntity, Parameter, TraitEntity,
+    naming, CallRelation, ClassEntity, CodeIR, ComplexityBuilder, ComplexityMetrics,
+    FunctionEntity, ImportRelation, InheritanceRelation, ModuleEntity, Parameter, TraitEntity,
 };
 use std::path::Path;
 use tree_sitter::{Node, Parser};
@@ -88,15 +88,24 @@ pub fn extract(source: &str, file_path: &Path, config: &ParserConfig) -> Result<
     .with_line_count(line_count);
     ir.set_module(module);
 
+    // Qualified names are prefixed with the dotted module path
+    let module_path = naming::python_module_path(file_path);
+
     // Walk through top-level statements
     let mut cursor = root_node.walk();
     for child in root_node.children(&mut cursor) {
         match child.kind() {
             "function_definition" => {
-                if let Some(func) = extract_function(source_bytes, child, config, None) {
+                if let Some(func) =
+                    extract_function(source_bytes, child, config, &module_path, None)
+                {
                     // Extract calls from function body
-                    let calls =
-                        extract_calls_from_node(source_bytes, child, &func.name, func.line_start);
+                    let calls = extract_calls_from_node(
+                        source_bytes,
+                        child,
+                        func.full_name(),
+                        func.line_start,
+                    );
                     for call in calls {
                         ir.add_call(call);
                     }
@@ -108,13 +117,17 @@ pub fn extract(source: &str, file_path: &Path, config: &ParserConfig) -> Result<
                 if let Some(definition) = find_definition_in_decorated(child) {
                     match definition.kind() {
                         "function_definition" => {
-                            if let Some(func) =
-                                extract_function(source_bytes, definition, config, None)
-                            {
+                            if let Some(func) = extract_function(
+                                source_bytes,
+                                definition,
+                                config,
+                                &module_path,
+                                None,
+                            ) {
                                 let calls = extract_calls_from_node(
                                     source_bytes,
                                     definition,
-                                    &func.name,
+                                    func.full_name(),
                                     func.line_start,
                                 );
                                 for call in calls {
@@ -126,7 +139,7 @@ pub fn extract(source: &str, file_path: &Path, config: &ParserConfig) -> Result<
                         "class_definition" => {
                             apply_class_extraction(
                                 &mut ir,
-                                extract_class(source_bytes, definition, config),
+                                extract_class(source_bytes, definition, config, &module_path),
                             );
                         }
                         _ => {}
@@ -134,7 +147,10 @@ pub fn extract(source: &str, file_path: &Path, config: &ParserConfig) -> Result<
                 }
             }
             "class_definition" => {
-                apply_class_extraction(&mut ir, extract_class(source_bytes, child, config));
+                apply_class_extraction(
+                    &mut ir,
+                    extract_class(source_bytes, child, config, &module_path),
+                );
             }
             "import_statement" => {
                 let imports = extract_import(source_bytes, child, &module_name);
@@ -167,11 +183,12 @@ fn find_definition_in_decorated(node: Node) -> Option<Node> {
     None
 }
 
-/// Extract a function entity from a function_definition node
+/// Extract a function entity from a function_definition node, qualified by `scope`
 fn extract_function(
     source: &[u8],
     node: Node,
     config: &ParserConfig,
+    scope: &str,
     parent_class: Option<&str>,
 ) -> Option<FunctionEntity> {
     let name = node
@@ -235,7 +252,9 @@ fn extract_function(
         .child_by_field_name("body")
         .map(|body| calculate_complexity_from_node(source, body));
 
-    let mut func = FunctionEntity::new(&name, line_start, line_end);
+    let mut func = FunctionEntity::new(&name, line_start, line_end)
+        .with_qualified_name(format!("{scope}.{name}"))
+        .with_span(node.range().into());
     func.visibility = python_visibility(&name);
     func.parameters = parameters;
     func.return_type = return_type;
@@ -384,10 +403,16 @@ const ENUM_BASES: &[&str] = &["Enum", "IntEnum", "Flag", "IntFlag", "StrEnum", "
 const ABC_BASES: &[&str] = &["ABC", "ABCMeta", "Protocol"];
 
 /// Extract a class entity with its methods
-fn extract_class(source: &[u8], node: Node, config: &ParserConfig) -> Option<ClassExtraction> {
+fn extract_class(
+    source: &[u8],
+    node: Node,
+    config: &ParserConfig,
+    module_path: &str,
+) -> Option<ClassExtraction> {
     let name = node
         .child_by_field_name("name")
         .map(|n| n.utf8_text(source).unwrap_or("Class").to_string())?;
+    let qualified_name = format!("{module_path}.{name}");
 
     let line_start = node.start_position().row + 1;
     let line_end = node.end_position().row + 1;
@@ -430,12 +455,13 @@ fn extract_class(source: &[u8], node: Node, config: &ParserConfig) -> Option<Cla
         for child in body.children(&mut cursor) {
             match child.kind() {
                 "function_definition" => {
-                    if let Some(method) = extract_function(source, child, config, Some(&name)) {
-                        let method_qualified_name = format!("{}.{}", name, method.name);
+                    if let Some(method) =
+                        extract_function(source, child, config, &qualified_name, Some(&name))
+                    {
                         let method_calls = extract_calls_from_node(
                             source,
                             child,
-                            &method_qualified_name,
+                            method.full_name(),
                             method.line_start,
                         );
                         calls.extend(method_calls);
@@ -445,14 +471,17 @@ fn extract_class(source: &[u8], node: Node, config: &ParserConfig) -> Option<Cla
                 "decorated_definition" => {
                     if let Some(definition) = find_definition_in_decorated(child) {
                         if definition.kind() == "function_definition" {
-                            if let Some(method) =
-                                extract_function(source, definition, config, Some(&name))
-                            {
-                                let method_qualified_name = format!("{}.{}", name, method.name);
+                            if let Some(method) = extract_function(
+                                source,
+                                definition,
+                                config,
+                                &qualified_name,
+                                Some(&name),
+                            ) {
                                 let method_calls = extract_calls_from_node(
                                     source,
                                     definition,
-                                    &method_qualified_name,
+                                    method.full_name(),
                                     method.line_start,
                                 );
                                 calls.extend(method_calls);
@@ -485,9 +514,11 @@ fn extract_class(source: &[u8], node: Node, config: &ParserConfig) -> Option<Cla
 
         let trait_entity = TraitEntity {
             name,
+            qualified_name: Some(qualified_name),
             visibility,
             line_start,
             line_end,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits,
             doc_comment,
@@ -503,7 +534,9 @@ fn extract_class(source: &[u8], node: Node, config: &ParserConfig) -> Option<Cla
         });
     }
 
-    let mut class = ClassEntity::new(&name, line_start, line_end);
+    let mut class = ClassEntity::new(&name, line_start, line_end)
+        .with_qualified_name(qualified_name)
+        .with_span(node.range().into());
     class.doc_comment = doc_comment;
     class.methods = methods.clone();
     class.visibility = visibility;
@@ -600,6 +633,7 @@ fn extract_callee_name(source: &[u8], node: Node) -> String {
 
 /// Extract import statement
 fn extract_import(source: &[u8], node: Node, importer: &str) -> Vec<ImportRelation> {
+    let line = node.start_position().row + 1;
     let mut imports = Vec::new();
     let mut cursor = node.walk();
 
@@ -622,7 +656,7 @@ fn extract_import(source: &[u8], node: Node, importer: &str) -> Vec<ImportRelati
             };
 
             if let Some(module) = module_name {
-                let mut import_rel = ImportRelation::new(importer, &module);
+                let mut import_rel = ImportRelation::new(importer, &module).with_line(line);
                 if let Some(a) = alias {
                     import_rel = import_rel.with_alias(&a);
                 }
@@ -669,12 +703,14 @@ fn extract_import_from(source: &[u8], node: Node, importer: &str) -> Vec<ImportR
         }
     }
 
+    let import =
+        ImportRelation::new(importer, &from_module).with_line(node.start_position().row + 1);
     if is_wildcard {
-        vec![ImportRelation::new(importer, &from_module).wildcard()]
+        vec![import.wildcard()]
     } else if !symbols.is_empty() {
-        vec![ImportRelation::new(importer, &from_module).with_symbols(symbols)]
+        vec![import.with_symbols(symbols)]
     } else {
-        vec![ImportRelation::new(importer, &from_module)]
+        vec![import]
     }
 }
 
@@ -943,6 +979,8 @@ def main():
             ir.imports.len() >= 4,
             "Should find at least 4 import statements"
         );
+        let typing = ir.imports.iter().find(|i| i.imported == "typing").unwrap();
+        assert_eq!(typing.line, Some(5));
     }
 
     #[test]
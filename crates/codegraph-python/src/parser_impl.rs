@@ -8,7 +8,9 @@
 //! with the unified parser API.
 
 use codegraph::{CodeGraph, NodeId};
-use codegraph_parser_api::{CodeParser, FileInfo, ParserConfig, ParserError, ParserMetrics};
+use codegraph_parser_api::{
+    CodeParser, FileInfo, ParserConfig, ParserError, ParserMetrics, SymbolTable,
+};
 use std::path::Path;
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
@@ -66,7 +68,7 @@ impl PythonParser {
         use codegraph::{EdgeType, NodeType, PropertyMap};
         use std::collections::HashMap;
 
-        let mut node_map: HashMap<String, NodeId> = HashMap::new();
+        let mut symbols = SymbolTable::new();
         let mut function_ids = Vec::new();
         let mut class_ids = Vec::new();
         let mut trait_ids = Vec::new();
@@ -87,7 +89,7 @@ impl PythonParser {
             let id = graph
                 .add_node(NodeType::CodeFile, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(module.name.clone(), id);
+            symbols.insert(&module.name, id);
             id
         } else {
             // Create a default file node
@@ -104,7 +106,7 @@ impl PythonParser {
             let id = graph
                 .add_node(NodeType::CodeFile, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(file_name, id);
+            symbols.insert(&file_name, id);
             id
         };
 
@@ -112,6 +114,7 @@ impl PythonParser {
         for func in &ir.functions {
             let mut props = PropertyMap::new()
                 .with("name", func.name.clone())
+                .with("qualified_name", func.full_name())
                 .with("path", file_path.display().to_string())
                 .with("signature", func.signature.clone())
                 .with("visibility", func.visibility.clone())
@@ -122,6 +125,10 @@ impl PythonParser {
                 .with("is_test", func.is_test)
                 .with("attributes", func.attributes.clone());
 
+            if let Some(ref span) = func.span {
+                props = span.apply_to(props);
+            }
+
             if let Some(ref doc) = func.doc_comment {
                 props = props.with("doc", doc.clone());
             }
@@ -133,7 +140,13 @@ impl PythonParser {
                 .add_node(NodeType::Function, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-            node_map.insert(func.name.clone(), func_id);
+            symbols.insert_entity(
+                func.full_name(),
+                &func.name,
+                func_id,
+                func.line_start,
+                func.line_end,
+            );
             function_ids.push(func_id);
 
             // Link function to file
@@ -146,12 +159,17 @@ impl PythonParser {
         for class in &ir.classes {
             let mut props = PropertyMap::new()
                 .with("name", class.name.clone())
+                .with("qualified_name", class.full_name())
                 .with("path", file_path.display().to_string())
                 .with("visibility", class.visibility.clone())
                 .with("line_start", class.line_start as i64)
                 .with("line_end", class.line_end as i64)
                 .with("is_abstract", class.is_abstract.to_string());
 
+            if let Some(ref span) = class.span {
+                props = span.apply_to(props);
+            }
+
             if let Some(ref doc) = class.doc_comment {
                 props = props.with("doc", doc.clone());
             }
@@ -160,7 +178,13 @@ impl PythonParser {
                 .add_node(NodeType::Class, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-            node_map.insert(class.name.clone(), class_id);
+            symbols.insert_entity(
+                class.full_name(),
+                &class.name,
+                class_id,
+                class.line_start,
+                class.line_end,
+            );
             class_ids.push(class_id);
 
             // Link class to file
@@ -171,8 +195,7 @@ impl PythonParser {
             // Methods are already added via ir.functions with parent_class set
             // Just create edges from class to its methods
             for method in &class.methods {
-                let method_name = method.name.clone();
-                if let Some(&method_id) = node_map.get(&method_name) {
+                if let Some(method_id) = symbols.get(method.full_name()) {
                     // Link method to class
                     graph
                         .add_edge(class_id, method_id, EdgeType::Contains, PropertyMap::new())
@@ -185,11 +208,16 @@ impl PythonParser {
         for trait_entity in &ir.traits {
             let mut props = PropertyMap::new()
                 .with("name", trait_entity.name.clone())
+                .with("qualified_name", trait_entity.full_name())
                 .with("path", file_path.display().to_string())
                 .with("visibility", trait_entity.visibility.clone())
                 .with("line_start", trait_entity.line_start as i64)
                 .with("line_end", trait_entity.line_end as i64);
 
+            if let Some(ref span) = trait_entity.span {
+                props = span.apply_to(props);
+            }
+
             if let Some(ref doc) = trait_entity.doc_comment {
                 props = props.with("doc", doc.clone());
             }
@@ -198,7 +226,13 @@ impl PythonParser {
                 .add_node(NodeType::Interface, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-            node_map.insert(trait_entity.name.clone(), trait_id);
+            symbols.insert_entity(
+                trait_entity.full_name(),
+                &trait_entity.name,
+                trait_id,
+                trait_entity.line_start,
+                trait_entity.line_end,
+            );
             trait_ids.push(trait_id);
 
             // Link trait to file
@@ -212,7 +246,7 @@ impl PythonParser {
             let imported_module = &import.imported;
 
             // Create or get import node
-            let import_id = if let Some(&existing_id) = node_map.get(imported_module) {
+            let import_id = if let Some(existing_id) = symbols.get(imported_module) {
                 existing_id
             } else {
                 // Relative imports (from .foo, from ..bar) are internal
@@ -224,7 +258,7 @@ impl PythonParser {
                 let id = graph
                     .add_node(NodeType::Module, props)
                     .map_err(|e| ParserError::GraphError(e.to_string()))?;
-                node_map.insert(imported_module.clone(), id);
+                symbols.insert(imported_module, id);
                 id
             };
 
@@ -248,12 +282,11 @@ impl PythonParser {
 
         // Add call relationships
         // Track unresolved calls per caller for cross-file resolution
-        let mut unresolved_calls: std::collections::HashMap<String, Vec<String>> =
-            std::collections::HashMap::new();
+        let mut unresolved_calls: HashMap<NodeId, Vec<String>> = HashMap::new();
 
         for call in &ir.calls {
-            if let Some(&caller_id) = node_map.get(&call.caller) {
-                if let Some(&callee_id) = node_map.get(&call.callee) {
+            if let Some(caller_id) = symbols.caller(&call.caller, call.call_site_line) {
+                if let Some(callee_id) = symbols.resolve(&call.callee, caller_id) {
                     // Both caller and callee are in this file - create direct edge
                     let edge_props = PropertyMap::new()
                         .with("call_site_line", call.call_site_line.to_string())
@@ -265,7 +298,7 @@ impl PythonParser {
                 } else {
                     // Callee not found in this file - store for cross-file resolution
                     unresolved_calls
-                        .entry(call.caller.clone())
+                        .entry(caller_id)
                         .or_default()
                         .push(call.callee.clone());
                 }
@@ -273,33 +306,30 @@ impl PythonParser {
         }
 
         // Store unresolved calls on caller nodes for post-processing
-        for (caller_name, callees) in unresolved_calls {
-            if let Some(&caller_id) = node_map.get(&caller_name) {
-                if let Ok(node) = graph.get_node(caller_id) {
-                    let mut all_callees: Vec<String> = node
-                        .properties
-                        .get_string_list_compat("unresolved_calls")
-                        .unwrap_or_default();
-                    for callee in &callees {
-                        if !all_callees.iter().any(|c| c == callee) {
-                            all_callees.push(callee.clone());
-                        }
+        for (caller_id, callees) in unresolved_calls {
+            if let Ok(node) = graph.get_node(caller_id) {
+                let mut all_callees: Vec<String> = node
+                    .properties
+                    .get_string_list_compat("unresolved_calls")
+                    .unwrap_or_default();
+                for callee in &callees {
+                    if !all_callees.iter().any(|c| c == callee) {
+                        all_callees.push(callee.clone());
                     }
-                    let new_props = node
-                        .properties
-                        .clone()
-                        .with("unresolved_calls", all_callees);
-                    let _ = graph.update_node_properties(caller_id, new_props);
                 }
+                let new_props = node
+                    .properties
+                    .clone()
+                    .with("unresolved_calls", all_callees);
+                let _ = graph.update_node_properties(caller_id, new_props);
             }
         }
 
         // Add inheritance relationships
         for inheritance in &ir.inheritance {
-            if let (Some(&child_id), Some(&parent_id)) = (
-                node_map.get(&inheritance.child),
-                node_map.get(&inheritance.parent),
-            ) {
+            let child_id = symbols.get(&inheritance.child);
+            let parent_id = child_id.and_then(|id| symbols.resolve(&inheritance.parent, id));
+            if let (Some(child_id), Some(parent_id)) = (child_id, parent_id) {
                 let edge_props = PropertyMap::new().with("order", inheritance.order.to_string());
 
                 graph
@@ -310,10 +340,9 @@ impl PythonParser {
 
         // Add implementation relationships (class implements protocol/interface)
         for impl_rel in &ir.implementations {
-            if let (Some(&implementor_id), Some(&trait_id)) = (
-                node_map.get(&impl_rel.implementor),
-                node_map.get(&impl_rel.trait_name),
-            ) {
+            let implementor_id = symbols.get(&impl_rel.implementor);
+            let trait_id = implementor_id.and_then(|id| symbols.resolve(&impl_rel.trait_name, id));
+            if let (Some(implementor_id), Some(trait_id)) = (implementor_id, trait_id) {
                 graph
                     .add_edge(
                         implementor_id,
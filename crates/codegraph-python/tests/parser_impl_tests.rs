@@ -4,7 +4,7 @@
 //! Unit tests specifically for parser_impl.rs
 //! Testing the PythonParser implementation details
 
-use codegraph::CodeGraph;
+use codegraph::{CodeGraph, Direction};
 use codegraph_parser_api::{CodeParser, ParserConfig, ParserError};
 use codegraph_python::PythonParser;
 use std::path::Path;
@@ -301,6 +301,77 @@ class MyClass:
     assert!(!file_info.functions.is_empty());
 }
 
+#[test]
+fn test_method_position_lookup() {
+    let parser = PythonParser::new();
+    let mut graph = CodeGraph::in_memory().unwrap();
+
+    let source = "class Person:\n    def greet(self):\n        return 'hi'\n";
+
+    let file_info = parser
+        .parse_source(source, Path::new("person.py"), &mut graph)
+        .unwrap();
+    let class = file_info.classes[0];
+    let method = file_info.functions[0];
+
+    let props = &graph.get_node(method).unwrap().properties;
+    assert_eq!(props.get_int("col_start"), Some(4));
+    assert_eq!(props.get_int("byte_start"), Some(18));
+
+    assert_eq!(graph.entity_at("person.py", 3, 10), vec![method, class]);
+    assert_eq!(graph.entity_at("person.py", 1, 0), vec![class]);
+    assert!(graph.entity_at("person.py", 4, 0).is_empty());
+}
+
+#[test]
+fn test_same_named_methods_do_not_collide() {
+    let parser = PythonParser::new();
+    let mut graph = CodeGraph::in_memory().unwrap();
+
+    let source = "def flush():\n    pass\n\nclass Reader:\n    def close(self):\n        flush()\n\nclass Writer:\n    def close(self):\n        flush()\n";
+
+    let file_info = parser
+        .parse_source(source, Path::new("app/io.py"), &mut graph)
+        .unwrap();
+    let qualified = |id| {
+        graph
+            .get_node(id)
+            .unwrap()
+            .properties
+            .get_string("qualified_name")
+            .unwrap()
+            .to_string()
+    };
+    let names: Vec<String> = file_info
+        .functions
+        .iter()
+        .map(|&id| qualified(id))
+        .collect();
+    assert_eq!(
+        names,
+        vec!["app.io.flush", "app.io.Reader.close", "app.io.Writer.close"]
+    );
+    assert_eq!(qualified(file_info.classes[1]), "app.io.Writer");
+
+    // Each class contains its own `close`, and both call `flush`
+    let [flush, reader_close, writer_close] = file_info.functions[..] else {
+        panic!("expected three functions");
+    };
+    for (class, method) in [
+        (file_info.classes[0], reader_close),
+        (file_info.classes[1], writer_close),
+    ] {
+        assert_eq!(
+            graph.get_neighbors(class, Direction::Outgoing).unwrap(),
+            vec![method]
+        );
+        assert_eq!(
+            graph.get_neighbors(method, Direction::Outgoing).unwrap(),
+            vec![flush]
+        );
+    }
+}
+
 // ====================
 // Error Handling Tests
 // ====================
@@ -108,10 +108,12 @@ impl<'a> RubyVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_method(&name, "#")),
             signature: self.extract_method_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: self.has_test_annotation(&name),
             is_static: false,
@@ -167,10 +169,12 @@ impl<'a> RubyVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_method(&name, ".")),
             signature: self.extract_method_signature(node),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: self.has_test_annotation(&name),
             is_static: true, // Singleton methods are class methods
@@ -231,9 +235,11 @@ impl<'a> RubyVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes,
@@ -335,9 +341,11 @@ impl<'a> RubyVisitor<'a> {
 
         let trait_entity = TraitEntity {
             name: qualified_name.clone(),
+            qualified_name: Some(qualified_name.clone()),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits: Vec::new(),
             doc_comment,
@@ -390,7 +398,9 @@ impl<'a> RubyVisitor<'a> {
                     caller: caller.clone(),
                     callee,
                     call_site_line: node.start_position().row + 1,
-                    is_direct: true, struct_type: None, field_name: None,
+                    is_direct: true,
+                    struct_type: None,
+                    field_name: None,
                 };
                 self.calls.push(call);
             }
@@ -419,6 +429,7 @@ impl<'a> RubyVisitor<'a> {
                         } else {
                             None
                         },
+                        line: Some(node.start_position().row + 1),
                     };
 
                     self.imports.push(import);
@@ -464,7 +475,9 @@ impl<'a> RubyVisitor<'a> {
                                 caller: caller.clone(),
                                 callee,
                                 call_site_line: child.start_position().row + 1,
-                                is_direct: true, struct_type: None, field_name: None,
+                                is_direct: true,
+                                struct_type: None,
+                                field_name: None,
                             };
                             self.calls.push(call);
                         }
@@ -641,6 +654,15 @@ impl<'a> RubyVisitor<'a> {
             name.to_string()
         }
     }
+
+    /// Qualify a method as `Module::Class#method`, or `Module::Class.method`
+    /// for singleton methods
+    fn qualify_method(&self, name: &str, separator: &str) -> String {
+        match &self.current_class {
+            Some(class) => format!("{}{}{}", class, separator, name),
+            None => name.to_string(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -838,6 +860,19 @@ end
         assert!(visitor.traits.iter().any(|t| t.name == "Outer::Inner"));
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source = b"module App\n  class Server\n    def start\n    end\n    def self.build\n    end\n  end\nend";
+        let visitor = parse_and_visit(source);
+
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("App::Server")
+        );
+        assert_eq!(visitor.functions[0].full_name(), "App::Server#start");
+        assert_eq!(visitor.functions[1].full_name(), "App::Server.build");
+    }
+
     #[test]
     fn test_complexity_simple_method() {
         // A method with no control flow has CC=1
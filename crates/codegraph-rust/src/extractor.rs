@@ -6,7 +6,7 @@
 //! This module parses Rust source code and extracts entities and relationships
 //! into a CodeIR representation.
 
-use codegraph_parser_api::{CodeIR, ModuleEntity, ParserConfig, ParserError};
+use codegraph_parser_api::{naming, CodeIR, ModuleEntity, ParserConfig, ParserError};
 use std::path::Path;
 use tree_sitter::Parser;
 
@@ -83,6 +83,7 @@ pub fn extract(
 
     // Create visitor and walk the AST
     let mut visitor = RustVisitor::new(source.as_bytes(), config.clone());
+    visitor.module_path = naming::rust_module_path(file_path);
     visitor.visit_node(root_node);
 
     // Transfer extracted entities to IR
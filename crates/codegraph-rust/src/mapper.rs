@@ -7,7 +7,7 @@
 //! into actual graph nodes and edges in the CodeGraph database.
 
 use codegraph::{CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
-use codegraph_parser_api::{CodeIR, FileInfo, ParserError};
+use codegraph_parser_api::{CodeIR, FileInfo, ParserError, SymbolTable};
 use std::collections::HashMap;
 use std::path::Path;
 use std::time::Duration;
@@ -18,7 +18,7 @@ pub fn ir_to_graph(
     graph: &mut CodeGraph,
     file_path: &Path,
 ) -> Result<FileInfo, ParserError> {
-    let mut node_map: HashMap<String, NodeId> = HashMap::new();
+    let mut symbols = SymbolTable::new();
     let mut function_ids = Vec::new();
     let mut class_ids = Vec::new();
     let mut trait_ids = Vec::new();
@@ -39,7 +39,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(module.name.clone(), id);
+        symbols.insert(&module.name, id);
         id
     } else {
         // Create a default file node
@@ -56,7 +56,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(file_name, id);
+        symbols.insert(&file_name, id);
         id
     };
 
@@ -64,6 +64,7 @@ pub fn ir_to_graph(
     for func in &ir.functions {
         let mut props = PropertyMap::new()
             .with("name", func.name.clone())
+            .with("qualified_name", func.full_name())
             .with("path", file_path.display().to_string())
             .with("signature", func.signature.clone())
             .with("visibility", func.visibility.clone())
@@ -73,6 +74,10 @@ pub fn ir_to_graph(
             .with("is_static", func.is_static)
             .with("is_abstract", func.is_abstract);
 
+        if let Some(ref span) = func.span {
+            props = span.apply_to(props);
+        }
+
         // Add complexity metrics if available
         if let Some(ref complexity) = func.complexity {
             props = props
@@ -103,13 +108,19 @@ pub fn ir_to_graph(
             .add_node(NodeType::Function, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(func.name.clone(), func_id);
+        symbols.insert_entity(
+            func.full_name(),
+            &func.name,
+            func_id,
+            func.line_start,
+            func.line_end,
+        );
         function_ids.push(func_id);
 
         // Link function to file or parent class
         if let Some(ref parent_class) = func.parent_class {
             // This is a method - link to class if it exists
-            if let Some(&class_id) = node_map.get(parent_class) {
+            if let Some(class_id) = symbols.get(parent_class) {
                 graph
                     .add_edge(class_id, func_id, EdgeType::Contains, PropertyMap::new())
                     .map_err(|e| ParserError::GraphError(e.to_string()))?;
@@ -131,6 +142,7 @@ pub fn ir_to_graph(
     for class in &ir.classes {
         let mut props = PropertyMap::new()
             .with("name", class.name.clone())
+            .with("qualified_name", class.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", class.visibility.clone())
             .with("line_start", class.line_start as i64)
@@ -138,6 +150,10 @@ pub fn ir_to_graph(
             .with("is_abstract", class.is_abstract)
             .with("is_interface", class.is_interface);
 
+        if let Some(ref span) = class.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = class.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -150,7 +166,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Class, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(class.name.clone(), class_id);
+        symbols.insert_entity(
+            class.full_name(),
+            &class.name,
+            class_id,
+            class.line_start,
+            class.line_end,
+        );
         class_ids.push(class_id);
 
         // Link class to file
@@ -163,11 +185,16 @@ pub fn ir_to_graph(
     for trait_entity in &ir.traits {
         let mut props = PropertyMap::new()
             .with("name", trait_entity.name.clone())
+            .with("qualified_name", trait_entity.full_name())
             .with("path", file_path.display().to_string())
             .with("visibility", trait_entity.visibility.clone())
             .with("line_start", trait_entity.line_start as i64)
             .with("line_end", trait_entity.line_end as i64);
 
+        if let Some(ref span) = trait_entity.span {
+            props = span.apply_to(props);
+        }
+
         if let Some(ref doc) = trait_entity.doc_comment {
             props = props.with("doc", doc.clone());
         }
@@ -176,7 +203,13 @@ pub fn ir_to_graph(
             .add_node(NodeType::Interface, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(trait_entity.name.clone(), trait_id);
+        symbols.insert_entity(
+            trait_entity.full_name(),
+            &trait_entity.name,
+            trait_id,
+            trait_entity.line_start,
+            trait_entity.line_end,
+        );
         trait_ids.push(trait_id);
 
         // Link trait to file
@@ -186,7 +219,7 @@ pub fn ir_to_graph(
 
         // Add parent trait relationships
         for parent in &trait_entity.parent_traits {
-            if let Some(&parent_id) = node_map.get(parent) {
+            if let Some(parent_id) = symbols.resolve(parent, trait_id) {
                 graph
                     .add_edge(trait_id, parent_id, EdgeType::Extends, PropertyMap::new())
                     .map_err(|e| ParserError::GraphError(e.to_string()))?;
@@ -200,7 +233,7 @@ pub fn ir_to_graph(
         let is_mod = import.importer == "mod_declaration";
 
         // Create or get module node
-        let import_id = if let Some(&existing_id) = node_map.get(imported_module) {
+        let import_id = if let Some(existing_id) = symbols.get(imported_module) {
             existing_id
         } else {
             // Determine if this is an external or internal module
@@ -221,7 +254,7 @@ pub fn ir_to_graph(
             let id = graph
                 .add_node(NodeType::Module, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(imported_module.clone(), id);
+            symbols.insert(imported_module, id);
             id
         };
 
@@ -235,6 +268,9 @@ pub fn ir_to_graph(
         if import.is_wildcard {
             edge_props = edge_props.with("is_wildcard", "true");
         }
+        if let Some(line) = import.line {
+            edge_props = edge_props.with("line", line as i64);
+        }
         if !import.symbols.is_empty() {
             edge_props = edge_props.with("symbols", import.symbols.clone());
         }
@@ -246,11 +282,11 @@ pub fn ir_to_graph(
 
     // Add call relationships
     // Track unresolved calls per caller for cross-file resolution
-    let mut unresolved_calls: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unresolved_calls: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for call in &ir.calls {
-        if let Some(&caller_id) = node_map.get(&call.caller) {
-            if let Some(&callee_id) = node_map.get(&call.callee) {
+        if let Some(caller_id) = symbols.caller(&call.caller, call.call_site_line) {
+            if let Some(callee_id) = symbols.resolve(&call.callee, caller_id) {
                 // Both caller and callee are in this file - create direct edge
                 graph
                     .add_edge(caller_id, callee_id, EdgeType::Calls, PropertyMap::new())
@@ -258,7 +294,7 @@ pub fn ir_to_graph(
             } else {
                 // Callee not found in this file - store for cross-file resolution
                 unresolved_calls
-                    .entry(call.caller.clone())
+                    .entry(caller_id)
                     .or_default()
                     .push(call.callee.clone());
             }
@@ -266,33 +302,31 @@ pub fn ir_to_graph(
     }
 
     // Store unresolved calls on caller nodes for post-processing
-    for (caller_name, callees) in unresolved_calls {
-        if let Some(&caller_id) = node_map.get(&caller_name) {
-            if let Ok(node) = graph.get_node(caller_id) {
-                let mut all_callees: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_calls")
-                    .unwrap_or_default();
-                for callee in &callees {
-                    if !all_callees.iter().any(|c| c == callee) {
-                        all_callees.push(callee.clone());
-                    }
+    for (caller_id, callees) in unresolved_calls {
+        if let Ok(node) = graph.get_node(caller_id) {
+            let mut all_callees: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_calls")
+                .unwrap_or_default();
+            for callee in &callees {
+                if !all_callees.iter().any(|c| c == callee) {
+                    all_callees.push(callee.clone());
                 }
-                let new_props = node
-                    .properties
-                    .clone()
-                    .with("unresolved_calls", all_callees);
-                let _ = graph.update_node_properties(caller_id, new_props);
             }
+            let new_props = node
+                .properties
+                .clone()
+                .with("unresolved_calls", all_callees);
+            let _ = graph.update_node_properties(caller_id, new_props);
         }
     }
 
     // Add type reference relationships (creates References edges)
-    let mut unresolved_type_refs: HashMap<String, Vec<String>> = HashMap::new();
+    let mut unresolved_type_refs: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for type_ref in &ir.type_references {
-        if let Some(&referrer_id) = node_map.get(&type_ref.referrer) {
-            if let Some(&type_id) = node_map.get(&type_ref.type_name) {
+        if let Some(referrer_id) = symbols.caller(&type_ref.referrer, type_ref.line_number) {
+            if let Some(type_id) = symbols.resolve(&type_ref.type_name, referrer_id) {
                 let _ = graph.add_edge(
                     referrer_id,
                     type_id,
@@ -301,37 +335,34 @@ pub fn ir_to_graph(
                 );
             } else {
                 unresolved_type_refs
-                    .entry(type_ref.referrer.clone())
+                    .entry(referrer_id)
                     .or_default()
                     .push(type_ref.type_name.clone());
             }
         }
     }
 
-    for (referrer_name, types) in unresolved_type_refs {
-        if let Some(&referrer_id) = node_map.get(&referrer_name) {
-            if let Ok(node) = graph.get_node(referrer_id) {
-                let mut all: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_type_refs")
-                    .unwrap_or_default();
-                for t in &types {
-                    if !all.iter().any(|existing| existing == t) {
-                        all.push(t.clone());
-                    }
+    for (referrer_id, types) in unresolved_type_refs {
+        if let Ok(node) = graph.get_node(referrer_id) {
+            let mut all: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_type_refs")
+                .unwrap_or_default();
+            for t in &types {
+                if !all.iter().any(|existing| existing == t) {
+                    all.push(t.clone());
                 }
-                let new_props = node.properties.clone().with("unresolved_type_refs", all);
-                let _ = graph.update_node_properties(referrer_id, new_props);
             }
+            let new_props = node.properties.clone().with("unresolved_type_refs", all);
+            let _ = graph.update_node_properties(referrer_id, new_props);
         }
     }
 
     // Add implementation relationships
     for impl_rel in &ir.implementations {
-        if let (Some(&implementor_id), Some(&trait_id)) = (
-            node_map.get(&impl_rel.implementor),
-            node_map.get(&impl_rel.trait_name),
-        ) {
+        let implementor_id = symbols.get(&impl_rel.implementor);
+        let trait_id = implementor_id.and_then(|id| symbols.resolve(&impl_rel.trait_name, id));
+        if let (Some(implementor_id), Some(trait_id)) = (implementor_id, trait_id) {
             graph
                 .add_edge(
                     implementor_id,
@@ -345,10 +376,9 @@ pub fn ir_to_graph(
 
     // Add inheritance relationships
     for inheritance in &ir.inheritance {
-        if let (Some(&child_id), Some(&parent_id)) = (
-            node_map.get(&inheritance.child),
-            node_map.get(&inheritance.parent),
-        ) {
+        let child_id = symbols.get(&inheritance.child);
+        let parent_id = child_id.and_then(|id| symbols.resolve(&inheritance.parent, id));
+        if let (Some(child_id), Some(parent_id)) = (child_id, parent_id) {
             graph
                 .add_edge(child_id, parent_id, EdgeType::Extends, PropertyMap::new())
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
@@ -389,10 +419,12 @@ mod tests {
 
         ir.functions.push(FunctionEntity {
             name: "hello".to_string(),
+            qualified_name: None,
             signature: "fn hello()".to_string(),
             visibility: "public".to_string(),
             line_start: 1,
             line_end: 3,
+            span: None,
             is_async: false,
             is_test: false,
             is_static: false,
@@ -485,14 +517,24 @@ mod tests {
             "test",
             "std::collections",
         ));
-        ir.add_import(codegraph_parser_api::ImportRelation::new("test", "std::io"));
+        ir.add_import(codegraph_parser_api::ImportRelation::new("test", "std::io").with_line(4));
 
         let mut graph = CodeGraph::in_memory().unwrap();
-        let result = ir_to_graph(&ir, &mut graph, Path::new("test.rs"));
-
-        assert!(result.is_ok());
-        // Note: Import processing not yet implemented in mapper
-        // When implemented, this should be: assert_eq!(file_info.imports.len(), 2);
+        let file_info = ir_to_graph(&ir, &mut graph, Path::new("test.rs")).unwrap();
+        assert_eq!(file_info.imports.len(), 2);
+
+        // The import line is kept on the edge
+        let lines: Vec<Option<i64>> = file_info
+            .imports
+            .iter()
+            .map(|&import_id| {
+                let edges = graph
+                    .get_edges_between(file_info.file_id, import_id)
+                    .unwrap();
+                graph.get_edge(edges[0]).unwrap().properties.get_int("line")
+            })
+            .collect();
+        assert_eq!(lines, vec![None, Some(4)]);
     }
 
     #[test]
@@ -33,6 +33,8 @@ pub struct RustVisitor<'a> {
     pub implementations: Vec<ImplementationRelation>,
     pub inheritance: Vec<InheritanceRelation>,
     pub type_references: Vec<TypeReference>,
+    /// Module path of the file, e.g. `crate::net::server`
+    pub module_path: String,
     current_class: Option<String>,
     current_function: Option<String>,
 }
@@ -50,6 +52,7 @@ impl<'a> RustVisitor<'a> {
             implementations: Vec::new(),
             inheritance: Vec::new(),
             type_references: Vec::new(),
+            module_path: "crate".to_string(),
             current_class: None,
             current_function: None,
         }
@@ -84,7 +87,13 @@ impl<'a> RustVisitor<'a> {
                 return;
             }
             "use_declaration" => self.visit_use(node),
-            "mod_item" => self.visit_mod(node),
+            "mod_item" => {
+                if let Some(body) = node.child_by_field_name("body") {
+                    self.visit_inline_mod(node, body);
+                    return;
+                }
+                self.visit_mod(node);
+            }
             "call_expression" => self.visit_call_expression(node),
             _ => {}
         }
@@ -96,6 +105,11 @@ impl<'a> RustVisitor<'a> {
         }
     }
 
+    /// Qualify an item name with the current module path
+    fn qualify(&self, name: &str) -> String {
+        format!("{}::{}", self.module_path, name)
+    }
+
     /// Extract visibility from a visibility_modifier node
     fn extract_visibility(&self, node: Node) -> String {
         // Look for visibility_modifier child
@@ -306,10 +320,12 @@ impl<'a> RustVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify(&name)),
             signature: self.extract_signature(node),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: self.is_async(node),
             is_test,
             is_static: false,
@@ -390,9 +406,11 @@ impl<'a> RustVisitor<'a> {
 
         let class = ClassEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify(&name)),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -424,9 +442,11 @@ impl<'a> RustVisitor<'a> {
         // Treat enums as classes with an "enum" attribute
         let class = ClassEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify(&name)),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -456,6 +476,7 @@ impl<'a> RustVisitor<'a> {
         }
 
         // Extract required methods from the trait body
+        let qualified_trait = self.qualify(&name);
         let mut required_methods = Vec::new();
         if let Some(body) = node.child_by_field_name("body") {
             let mut cursor = body.walk();
@@ -467,11 +488,13 @@ impl<'a> RustVisitor<'a> {
                         .unwrap_or_else(|| "method".to_string());
 
                     let func = FunctionEntity {
+                        qualified_name: Some(format!("{}::{}", qualified_trait, method_name)),
                         name: method_name,
                         signature: self.extract_signature(child),
                         visibility: "public".to_string(),
                         line_start: child.start_position().row + 1,
                         line_end: child.end_position().row + 1,
+                        span: Some(child.range().into()),
                         is_async: self.is_async(child),
                         is_test: false,
                         is_static: false,
@@ -502,9 +525,11 @@ impl<'a> RustVisitor<'a> {
 
         let trait_entity = TraitEntity {
             name: name.clone(),
+            qualified_name: Some(qualified_trait),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods,
             parent_traits,
             doc_comment: self.extract_doc_comment(node),
@@ -526,6 +551,9 @@ impl<'a> RustVisitor<'a> {
             })
             .unwrap_or_else(|| "unknown".to_string());
 
+        // Methods are qualified by the type, and by the trait for trait impls
+        let mut scope = self.qualify(&implementor);
+
         // Check if this is a trait implementation
         if let Some(trait_node) = node.child_by_field_name("trait") {
             let trait_name = self.node_text(trait_node);
@@ -536,6 +564,7 @@ impl<'a> RustVisitor<'a> {
                 .trim()
                 .to_string();
 
+            scope = format!("<{} as {}>", scope, trait_name);
             let impl_rel = ImplementationRelation {
                 implementor: implementor.clone(),
                 trait_name,
@@ -571,10 +600,12 @@ impl<'a> RustVisitor<'a> {
 
                     let func = FunctionEntity {
                         name: method_name.clone(),
+                        qualified_name: Some(format!("{}::{}", scope, method_name)),
                         signature: self.extract_signature(child),
                         visibility,
                         line_start: child.start_position().row + 1,
                         line_end: child.end_position().row + 1,
+                        span: Some(child.range().into()),
                         is_async: self.is_async(child),
                         is_test: false,
                         is_static,
@@ -619,6 +650,7 @@ impl<'a> RustVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias: None,
+                line: Some(node.start_position().row + 1),
             };
 
             self.imports.push(import);
@@ -1080,6 +1112,20 @@ impl<'a> RustVisitor<'a> {
         )
     }
 
+    /// Visit an inline mod (`mod foo { ... }`), qualifying its items with the mod name
+    fn visit_inline_mod(&mut self, node: Node, body: Node) {
+        let Some(name_node) = node.child_by_field_name("name") else {
+            return;
+        };
+        let previous_module = self.module_path.clone();
+        self.module_path = self.qualify(&self.node_text(name_node));
+        let mut cursor = body.walk();
+        for child in body.children(&mut cursor) {
+            self.visit_node(child);
+        }
+        self.module_path = previous_module;
+    }
+
     /// Visit a mod declaration (`mod foo;`)
     ///
     /// Skips inline mods (`mod foo { ... }`) since they don't reference external files.
@@ -1102,6 +1148,7 @@ impl<'a> RustVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias: None,
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -1205,6 +1252,44 @@ impl MyStruct {
         assert_eq!(impl_methods.len(), 2);
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source = r#"
+struct Server;
+impl Server {
+    fn new() -> Self { Server }
+}
+impl Default for Server {
+    fn default() -> Self { Server::new() }
+}
+trait Handler {
+    fn handle(&self);
+}
+mod client {
+    pub struct Client;
+    impl Client {
+        fn new() -> Self { Client }
+    }
+}
+"#;
+        let visitor = parse_and_visit(source);
+        let names: Vec<_> = visitor.functions.iter().map(|f| f.full_name()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "crate::Server::new",
+                "<crate::Server as Default>::default",
+                "crate::client::Client::new",
+            ]
+        );
+        assert_eq!(visitor.classes[1].full_name(), "crate::client::Client");
+        assert_eq!(visitor.traits[0].full_name(), "crate::Handler");
+        assert_eq!(
+            visitor.traits[0].required_methods[0].full_name(),
+            "crate::Handler::handle"
+        );
+    }
+
     #[test]
     fn test_visitor_async_function() {
         let source = r#"
@@ -1225,6 +1310,8 @@ use std::io::{self, Read};
 "#;
         let visitor = parse_and_visit(source);
         assert_eq!(visitor.imports.len(), 2);
+        assert_eq!(visitor.imports[0].line, Some(2));
+        assert_eq!(visitor.imports[1].line, Some(3));
     }
 
     #[test]
@@ -3,7 +3,7 @@
 
 //! Integration tests for codegraph-rust parser
 
-use codegraph::{CodeGraph, EdgeType};
+use codegraph::{CodeGraph, Direction, EdgeType};
 use codegraph_parser_api::CodeParser;
 use codegraph_rust::RustParser;
 use std::path::Path;
@@ -681,3 +681,155 @@ fn handle(req: HttpRequest) -> HttpResponse {
         unresolved
     );
 }
+
+#[test]
+fn test_source_spans_and_position_lookup() {
+    let source = r#"struct Config {
+    port: u16,
+}
+
+fn load() -> Config {
+    let port = 8080;
+    Config { port }
+}
+"#;
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let parser = RustParser::new();
+    let info = parser
+        .parse_source(source, Path::new("src/config.rs"), &mut graph)
+        .unwrap();
+
+    let load = info.functions[0];
+    let props = &graph.get_node(load).unwrap().properties;
+    assert_eq!(props.get_int("line_start"), Some(5));
+    assert_eq!(props.get_int("col_start"), Some(0));
+    assert_eq!(props.get_int("col_end"), Some(1));
+    let byte_start = source.find("fn load").unwrap() as i64;
+    assert_eq!(props.get_int("byte_start"), Some(byte_start));
+    assert_eq!(props.get_int("byte_end"), Some(source.len() as i64 - 1));
+
+    // Cursor on `port` inside the body
+    assert_eq!(graph.entity_at("src/config.rs", 6, 8), vec![load]);
+    assert_eq!(
+        graph.entity_at("src/config.rs", 1, 10),
+        vec![info.classes[0]]
+    );
+    assert!(graph.entity_at("src/config.rs", 4, 0).is_empty());
+}
+
+#[test]
+fn test_same_named_methods_do_not_collide() {
+    let source = r#"struct Reader;
+struct Writer;
+
+impl Reader {
+    fn new() -> Self {
+        Reader
+    }
+    fn open() -> Self {
+        Self::new()
+    }
+}
+
+impl Writer {
+    fn new() -> Self {
+        Writer
+    }
+    fn open() -> Self {
+        Self::new()
+    }
+}
+"#;
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let parser = RustParser::new();
+    let info = parser
+        .parse_source(source, Path::new("src/io/mod.rs"), &mut graph)
+        .unwrap();
+    assert_eq!(info.functions.len(), 4);
+
+    let qualified = |id| {
+        graph
+            .get_node(id)
+            .unwrap()
+            .properties
+            .get_string("qualified_name")
+            .unwrap()
+            .to_string()
+    };
+    let names: Vec<String> = info.functions.iter().map(|&id| qualified(id)).collect();
+    assert_eq!(
+        names,
+        vec![
+            "crate::io::Reader::new",
+            "crate::io::Reader::open",
+            "crate::io::Writer::new",
+            "crate::io::Writer::open",
+        ]
+    );
+
+    // Each `open` calls the `new` of its own type
+    let [reader_new, reader_open, writer_new, writer_open] = info.functions[..] else {
+        panic!("expected four methods");
+    };
+    assert_eq!(
+        graph
+            .get_neighbors(reader_open, Direction::Outgoing)
+            .unwrap(),
+        vec![reader_new]
+    );
+    assert_eq!(
+        graph
+            .get_neighbors(writer_open, Direction::Outgoing)
+            .unwrap(),
+        vec![writer_new]
+    );
+}
+
+#[test]
+fn test_lsif_references_from_imports() {
+    let source = r#"use std::collections::HashMap;
+use std::io;
+
+fn load() -> HashMap<String, String> {
+    HashMap::new()
+}
+"#;
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let parser = RustParser::new();
+    parser
+        .parse_source(source, Path::new("src/config.rs"), &mut graph)
+        .unwrap();
+
+    let mut dump = Vec::new();
+    codegraph::export::export_lsif_to(&graph, Path::new("/work/repo"), &mut dump).unwrap();
+    let elements: Vec<serde_json::Value> = String::from_utf8(dump)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let find = |label: &'static str| elements.iter().filter(move |e| e["label"] == label);
+
+    // Each import statement is a reference to its module, on its own line
+    for (line, module) in [(0, "std::collections::HashMap"), (1, "std::io")] {
+        let range = find("range")
+            .find(|r| r["start"]["line"] == line && r["end"]["line"] == line + 1)
+            .unwrap_or_else(|| panic!("no range on line {line}"));
+        let result_set = find("next").find(|e| e["outV"] == range["id"]).unwrap()["inV"].clone();
+        let hover = find("textDocument/hover")
+            .find(|e| e["outV"] == result_set)
+            .unwrap()["inV"]
+            .clone();
+        let hover = elements.iter().find(|e| e["id"] == hover).unwrap();
+        assert!(hover["result"]["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .contains(module));
+        assert!(find("item").any(|item| {
+            item["property"] == "references"
+                && item["inVs"].as_array().unwrap().contains(&range["id"])
+        }));
+    }
+}
@@ -130,9 +130,11 @@ impl<'a> SwiftVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: name.clone(),
+            qualified_name: None,
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes,
@@ -181,9 +183,11 @@ impl<'a> SwiftVisitor<'a> {
 
         let class_entity = ClassEntity {
             name: name.clone(),
+            qualified_name: None,
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes,
@@ -230,7 +234,8 @@ impl<'a> SwiftVisitor<'a> {
                         || body_child.kind() == "function_declaration"
                     {
                         if let Some(method) = self.extract_function_signature(body_child) {
-                            required_methods.push(method);
+                            let qualified_name = format!("{}.{}", name, method.name);
+                            required_methods.push(method.with_qualified_name(qualified_name));
                         }
                     }
                 }
@@ -239,9 +244,11 @@ impl<'a> SwiftVisitor<'a> {
 
         let trait_entity = TraitEntity {
             name,
+            qualified_name: None,
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             doc_comment,
             required_methods,
             parent_traits: Vec::new(),
@@ -289,6 +296,8 @@ impl<'a> SwiftVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(self.qualify_member(&name))
+        .with_span(node.range().into())
         .with_visibility(&visibility)
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
@@ -339,6 +348,8 @@ impl<'a> SwiftVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(self.qualify_member(&name))
+        .with_span(node.range().into())
         .with_visibility(&visibility)
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
@@ -389,6 +400,8 @@ impl<'a> SwiftVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(self.qualify_member(&name))
+        .with_span(node.range().into())
         .with_visibility(&visibility)
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
@@ -431,6 +444,8 @@ impl<'a> SwiftVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(self.qualify_member(&name))
+        .with_span(node.range().into())
         .with_visibility("internal")
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
@@ -471,7 +486,9 @@ impl<'a> SwiftVisitor<'a> {
             caller,
             callee,
             call_site_line: node.start_position().row + 1,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         };
 
         self.calls.push(call);
@@ -522,9 +539,11 @@ impl<'a> SwiftVisitor<'a> {
 
         let class_entity = ClassEntity {
             name,
+            qualified_name: None,
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -575,6 +594,7 @@ impl<'a> SwiftVisitor<'a> {
                     symbols: Vec::new(),
                     is_wildcard: true,
                     alias: None,
+                    line: Some(node.start_position().row + 1),
                 };
                 self.imports.push(import);
                 return;
@@ -625,6 +645,7 @@ impl<'a> SwiftVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_span(node.range().into())
         .with_signature(self.node_text(node).lines().next().unwrap_or(""));
 
         func.parameters = params;
@@ -938,6 +959,14 @@ impl<'a> SwiftVisitor<'a> {
         }
     }
 
+    /// Qualify a member as `Type.method`
+    fn qualify_member(&self, name: &str) -> String {
+        match &self.current_class {
+            Some(class) => format!("{}.{}", class, name),
+            None => name.to_string(),
+        }
+    }
+
     fn extract_doc_comment(&self, node: Node) -> Option<String> {
         if let Some(prev) = node.prev_sibling() {
             if prev.kind() == "comment" || prev.kind() == "multiline_comment" {
@@ -996,6 +1025,26 @@ mod tests {
             .contains(&"struct".to_string()));
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        let source =
+            b"class Server { func start() {} }\nprotocol Handler { func handle() }\nfunc main() {}";
+        let visitor = parse_and_visit(source);
+
+        let start = visitor
+            .functions
+            .iter()
+            .find(|f| f.name == "start")
+            .unwrap();
+        assert_eq!(start.full_name(), "Server.start");
+        let main = visitor.functions.iter().find(|f| f.name == "main").unwrap();
+        assert_eq!(main.full_name(), "main");
+        assert_eq!(
+            visitor.traits[0].required_methods[0].full_name(),
+            "Handler.handle"
+        );
+    }
+
     #[test]
     fn test_visitor_protocol_extraction() {
         let source = b"protocol Drawable { func draw() }";
@@ -10,8 +10,9 @@
 
 use codegraph_parser_api::{
     CallRelation, ClassEntity, ComplexityMetrics, FunctionEntity, ImportRelation, Parameter,
+    SourceSpan,
 };
-use tree_sitter::Node;
+use tree_sitter::{Node, Range};
 
 use crate::eda::{self, EdaCommand, EdaData};
 use crate::sdc::{self, SdcData};
@@ -106,6 +107,17 @@ fn resolve_kind(node: Node<'_>) -> &str {
     }
 }
 
+/// Span from the start of one node to the end of another, for procs that the
+/// grammar splits across sibling nodes.
+fn span_between(start: Node, end: Node) -> SourceSpan {
+    SourceSpan::from(Range {
+        start_byte: start.start_byte(),
+        end_byte: end.end_byte(),
+        start_point: start.start_position(),
+        end_point: end.end_position(),
+    })
+}
+
 pub struct TclVisitor<'a> {
     pub source: &'a [u8],
 
@@ -158,6 +170,11 @@ impl<'a> TclVisitor<'a> {
         }
     }
 
+    /// Fully qualified Tcl name with the leading `::`, e.g. `::utils::add`
+    fn fully_qualified(name: &str) -> String {
+        format!("::{}", name.trim_start_matches("::"))
+    }
+
     // ── Main dispatch ───────────────────────────────────────────────────
 
     pub fn visit_node(&mut self, node: Node) {
@@ -243,6 +260,7 @@ impl<'a> TclVisitor<'a> {
                         symbols: Vec::new(),
                         is_wildcard: true,
                         alias: None,
+                        line: Some(cmd_node.start_position().row + 1),
                     });
                 }
             }
@@ -259,6 +277,7 @@ impl<'a> TclVisitor<'a> {
                                 symbols: Vec::new(),
                                 is_wildcard: false,
                                 alias: None,
+                                line: Some(cmd_node.start_position().row + 1),
                             });
                         }
                     }
@@ -295,6 +314,7 @@ impl<'a> TclVisitor<'a> {
                                 symbols: Vec::new(),
                                 is_wildcard: false,
                                 alias: None,
+                                line: Some(node.start_position().row + 1),
                             });
                         }
                         self.eda_data.design_reads.push((file_type, path));
@@ -457,6 +477,8 @@ impl<'a> TclVisitor<'a> {
             error_node.start_position().row + 1,
             cmd_node.end_position().row + 1,
         )
+        .with_qualified_name(Self::fully_qualified(&qualified))
+        .with_span(span_between(error_node, cmd_node))
         .with_visibility("public")
         .with_signature(&signature);
 
@@ -524,10 +546,12 @@ impl<'a> TclVisitor<'a> {
         let full_ns = self.current_namespace().unwrap_or_default();
 
         let class = ClassEntity {
+            qualified_name: Some(Self::fully_qualified(&full_ns)),
             name: full_ns,
             visibility: "public".to_string(),
             line_start: cmd_node.start_position().row + 1,
             line_end: cmd_node.end_position().row + 1,
+            span: Some(cmd_node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -651,6 +675,8 @@ impl<'a> TclVisitor<'a> {
             node.start_position().row + 1,
             node.end_position().row + 1,
         )
+        .with_qualified_name(Self::fully_qualified(&qualified))
+        .with_span(node.range().into())
         .with_visibility("public")
         .with_signature(&signature);
 
@@ -824,10 +850,12 @@ impl<'a> TclVisitor<'a> {
         let doc_comment = self.extract_preceding_comment(node);
 
         let class = ClassEntity {
+            qualified_name: Some(Self::fully_qualified(&full_ns)),
             name: full_ns,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -1034,6 +1062,7 @@ impl<'a> TclVisitor<'a> {
                                 symbols: Vec::new(),
                                 is_wildcard: false,
                                 alias: None,
+                                line: Some(node.start_position().row + 1),
                             });
                         }
                         self.eda_data.design_reads.push((file_type, path));
@@ -1124,6 +1153,7 @@ impl<'a> TclVisitor<'a> {
                     symbols: Vec::new(),
                     is_wildcard: true,
                     alias: None,
+                    line: Some(node.start_position().row + 1),
                 });
             }
         }
@@ -1144,6 +1174,7 @@ impl<'a> TclVisitor<'a> {
                     symbols: Vec::new(),
                     is_wildcard: false,
                     alias: None,
+                    line: Some(node.start_position().row + 1),
                 });
             }
         }
@@ -1336,15 +1367,14 @@ impl<'a> TclVisitor<'a> {
         let signature = format!("proc {} {{{}}} {{...}}", name_str, param_str);
 
         let proc_node = tokens[proc_idx];
+        let end_node = tokens.get(body_end).copied().unwrap_or(proc_node);
         let mut func = FunctionEntity::new(
             &qualified,
             proc_node.start_position().row + 1,
-            tokens
-                .get(body_end)
-                .map_or(proc_node.end_position().row + 1, |n| {
-                    n.end_position().row + 1
-                }),
+            end_node.end_position().row + 1,
         )
+        .with_qualified_name(Self::fully_qualified(&qualified))
+        .with_span(span_between(proc_node, end_node))
         .with_visibility("public")
         .with_signature(&signature);
 
@@ -1456,7 +1486,9 @@ impl<'a> TclVisitor<'a> {
             caller,
             callee: callee.to_string(),
             call_site_line: node.start_position().row + 1,
-            is_direct: true, struct_type: None, field_name: None,
+            is_direct: true,
+            struct_type: None,
+            field_name: None,
         });
     }
 
@@ -1929,6 +1961,23 @@ source lib/utils.tcl
         }
     }
 
+    #[test]
+    fn test_qualified_names() {
+        let source = b"namespace eval utils {\n    proc add {a b} {\n        puts hello\n    }\n}";
+        let visitor = parse_and_visit(source);
+        let add = visitor
+            .functions
+            .iter()
+            .find(|f| f.name == "utils::add")
+            .unwrap();
+        assert_eq!(add.full_name(), "::utils::add");
+        assert_eq!(visitor.classes[0].full_name(), "::utils");
+
+        let source = b"proc main {} {\n    puts hello\n}";
+        let visitor = parse_and_visit(source);
+        assert_eq!(visitor.functions[0].full_name(), "::main");
+    }
+
     #[test]
     fn test_proc_with_control_flow() {
         let source = b"proc check {x} {\n    if {$x > 0} {\n        puts positive\n    }\n}";
@@ -3,7 +3,7 @@
 
 //! AST extraction for TypeScript/JavaScript source code
 
-use codegraph_parser_api::{CodeIR, ModuleEntity, ParserConfig, ParserError};
+use codegraph_parser_api::{naming, CodeIR, ModuleEntity, ParserConfig, ParserError};
 use std::path::Path;
 use tree_sitter::Parser;
 
@@ -74,6 +74,7 @@ pub fn extract(
 
     // Create visitor and walk the AST
     let mut visitor = TypeScriptVisitor::new(source.as_bytes());
+    visitor.module_path = naming::module_path(file_path);
     visitor.visit_node(root_node);
 
     // Transfer extracted entities to IR
@@ -4,7 +4,7 @@
 //! Mapper for converting CodeIR to CodeGraph nodes and edges
 
 use codegraph::{CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
-use codegraph_parser_api::{CodeIR, FileInfo, ParserError};
+use codegraph_parser_api::{CodeIR, FileInfo, ParserError, SymbolTable};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -67,7 +67,7 @@ pub fn ir_to_graph(
     graph: &mut CodeGraph,
     file_path: &Path,
 ) -> Result<FileInfo, ParserError> {
-    let mut node_map: HashMap<String, NodeId> = HashMap::new();
+    let mut symbols = SymbolTable::new();
     let mut function_ids = Vec::new();
     let mut class_ids = Vec::new();
     let mut trait_ids = Vec::new();
@@ -88,7 +88,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(module.name.clone(), id);
+        symbols.insert(&module.name, id);
         id
     } else {
         let file_name = file_path
@@ -104,7 +104,7 @@ pub fn ir_to_graph(
         let id = graph
             .add_node(NodeType::CodeFile, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
-        node_map.insert(file_name, id);
+        symbols.insert(&file_name, id);
         id
     };
 
@@ -112,6 +112,7 @@ pub fn ir_to_graph(
     for func in &ir.functions {
         let mut props = PropertyMap::new()
             .with("name", func.name.clone())
+            .with("qualified_name", func.full_name())
             .with("path", file_path.display().to_string())
             .with("signature", func.signature.clone())
             .with("line_start", func.line_start as i64)
@@ -119,6 +120,10 @@ pub fn ir_to_graph(
             .with("is_async", func.is_async)
             .with("visibility", func.visibility.clone());
 
+        if let Some(ref span) = func.span {
+            props = span.apply_to(props);
+        }
+
         // Add complexity metrics if available
         if let Some(ref complexity) = func.complexity {
             props = props
@@ -142,12 +147,18 @@ pub fn ir_to_graph(
             .add_node(NodeType::Function, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(func.name.clone(), func_id);
+        symbols.insert_entity(
+            func.full_name(),
+            &func.name,
+            func_id,
+            func.line_start,
+            func.line_end,
+        );
         function_ids.push(func_id);
 
         // Link to file or parent class
         if let Some(ref parent_class) = func.parent_class {
-            if let Some(&class_id) = node_map.get(parent_class) {
+            if let Some(class_id) = symbols.get(parent_class) {
                 graph
                     .add_edge(class_id, func_id, EdgeType::Contains, PropertyMap::new())
                     .map_err(|e| ParserError::GraphError(e.to_string()))?;
@@ -165,18 +176,29 @@ pub fn ir_to_graph(
 
     // Add classes
     for class in &ir.classes {
-        let props = PropertyMap::new()
+        let mut props = PropertyMap::new()
             .with("name", class.name.clone())
+            .with("qualified_name", class.full_name())
             .with("path", file_path.display().to_string())
             .with("line_start", class.line_start as i64)
             .with("line_end", class.line_end as i64)
             .with("visibility", class.visibility.clone());
 
+        if let Some(ref span) = class.span {
+            props = span.apply_to(props);
+        }
+
         let class_id = graph
             .add_node(NodeType::Class, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(class.name.clone(), class_id);
+        symbols.insert_entity(
+            class.full_name(),
+            &class.name,
+            class_id,
+            class.line_start,
+            class.line_end,
+        );
         class_ids.push(class_id);
 
         graph
@@ -186,18 +208,29 @@ pub fn ir_to_graph(
 
     // Add interfaces
     for interface in &ir.traits {
-        let props = PropertyMap::new()
+        let mut props = PropertyMap::new()
             .with("name", interface.name.clone())
+            .with("qualified_name", interface.full_name())
             .with("path", file_path.display().to_string())
             .with("line_start", interface.line_start as i64)
             .with("line_end", interface.line_end as i64)
             .with("visibility", interface.visibility.clone());
 
+        if let Some(ref span) = interface.span {
+            props = span.apply_to(props);
+        }
+
         let trait_id = graph
             .add_node(NodeType::Interface, props)
             .map_err(|e| ParserError::GraphError(e.to_string()))?;
 
-        node_map.insert(interface.name.clone(), trait_id);
+        symbols.insert_entity(
+            interface.full_name(),
+            &interface.name,
+            trait_id,
+            interface.line_start,
+            interface.line_end,
+        );
         trait_ids.push(trait_id);
 
         graph
@@ -228,7 +261,7 @@ pub fn ir_to_graph(
                 .map(|s| s.to_string());
 
             if let Some(ref stem) = import_file_stem {
-                target_node_id = node_map.get(stem).copied();
+                target_node_id = symbols.get(stem);
             }
         }
 
@@ -240,6 +273,9 @@ pub fn ir_to_graph(
         if import.is_wildcard {
             edge_props = edge_props.with("is_wildcard", "true");
         }
+        if let Some(line) = import.line {
+            edge_props = edge_props.with("line", line as i64);
+        }
         if !import.symbols.is_empty() {
             edge_props = edge_props.with("symbols", import.symbols.clone());
         }
@@ -255,7 +291,7 @@ pub fn ir_to_graph(
         if !import.symbols.is_empty() {
             for symbol in &import.symbols {
                 // Check if this symbol exists in our node_map
-                if let Some(&symbol_id) = node_map.get(symbol) {
+                if let Some(symbol_id) = symbols.get(symbol) {
                     // Create an import edge directly to the symbol
                     let symbol_edge_props = edge_props
                         .clone()
@@ -273,7 +309,7 @@ pub fn ir_to_graph(
         // Also create edge to the module/file node (for module-level tracking)
         let import_id = if let Some(id) = target_node_id {
             id
-        } else if let Some(&existing_id) = node_map.get(imported_module) {
+        } else if let Some(existing_id) = symbols.get(imported_module) {
             existing_id
         } else {
             // Create a placeholder module node for unresolved/external imports
@@ -285,7 +321,7 @@ pub fn ir_to_graph(
             let id = graph
                 .add_node(NodeType::Module, props)
                 .map_err(|e| ParserError::GraphError(e.to_string()))?;
-            node_map.insert(imported_module.clone(), id);
+            symbols.insert(imported_module, id);
             id
         };
 
@@ -302,12 +338,11 @@ pub fn ir_to_graph(
 
     // Add call relationships
     // Track unresolved calls per caller for cross-file resolution
-    let mut unresolved_calls: std::collections::HashMap<String, Vec<String>> =
-        std::collections::HashMap::new();
+    let mut unresolved_calls: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for call in &ir.calls {
-        if let Some(&caller_id) = node_map.get(&call.caller) {
-            if let Some(&callee_id) = node_map.get(&call.callee) {
+        if let Some(caller_id) = symbols.caller(&call.caller, call.call_site_line) {
+            if let Some(callee_id) = symbols.resolve(&call.callee, caller_id) {
                 // Both caller and callee are in this file - create direct edge
                 let edge_props = PropertyMap::new()
                     .with("call_site_line", call.call_site_line as i64)
@@ -319,7 +354,7 @@ pub fn ir_to_graph(
             } else {
                 // Callee not found in this file - store for cross-file resolution
                 unresolved_calls
-                    .entry(call.caller.clone())
+                    .entry(caller_id)
                     .or_default()
                     .push(call.callee.clone());
             }
@@ -327,33 +362,30 @@ pub fn ir_to_graph(
     }
 
     // Store unresolved calls on caller nodes for post-processing
-    for (caller_name, callees) in unresolved_calls {
-        if let Some(&caller_id) = node_map.get(&caller_name) {
-            if let Ok(node) = graph.get_node(caller_id) {
-                let mut all_callees: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_calls")
-                    .unwrap_or_default();
-                for callee in &callees {
-                    if !all_callees.iter().any(|c| c == callee) {
-                        all_callees.push(callee.clone());
-                    }
+    for (caller_id, callees) in unresolved_calls {
+        if let Ok(node) = graph.get_node(caller_id) {
+            let mut all_callees: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_calls")
+                .unwrap_or_default();
+            for callee in &callees {
+                if !all_callees.iter().any(|c| c == callee) {
+                    all_callees.push(callee.clone());
                 }
-                let new_props = node
-                    .properties
-                    .clone()
-                    .with("unresolved_calls", all_callees);
-                let _ = graph.update_node_properties(caller_id, new_props);
             }
+            let new_props = node
+                .properties
+                .clone()
+                .with("unresolved_calls", all_callees);
+            let _ = graph.update_node_properties(caller_id, new_props);
         }
     }
 
     // Add inheritance relationships
     for inheritance in &ir.inheritance {
-        if let (Some(&child_id), Some(&parent_id)) = (
-            node_map.get(&inheritance.child),
-            node_map.get(&inheritance.parent),
-        ) {
+        let child_id = symbols.get(&inheritance.child);
+        let parent_id = child_id.and_then(|id| symbols.resolve(&inheritance.parent, id));
+        if let (Some(child_id), Some(parent_id)) = (child_id, parent_id) {
             let edge_props = PropertyMap::new().with("order", inheritance.order as i64);
 
             graph
@@ -364,12 +396,11 @@ pub fn ir_to_graph(
 
     // Add type reference relationships (creates References edges)
     // Track unresolved type refs per referrer for cross-file resolution
-    let mut unresolved_type_refs: std::collections::HashMap<String, Vec<String>> =
-        std::collections::HashMap::new();
+    let mut unresolved_type_refs: HashMap<NodeId, Vec<String>> = HashMap::new();
 
     for type_ref in &ir.type_references {
-        if let Some(&referrer_id) = node_map.get(&type_ref.referrer) {
-            if let Some(&type_id) = node_map.get(&type_ref.type_name) {
+        if let Some(referrer_id) = symbols.caller(&type_ref.referrer, type_ref.line_number) {
+            if let Some(type_id) = symbols.resolve(&type_ref.type_name, referrer_id) {
                 // Both referrer and type are in this file — create direct edge
                 let _ = graph.add_edge(
                     referrer_id,
@@ -380,7 +411,7 @@ pub fn ir_to_graph(
             } else {
                 // Type not in this file — store for cross-file resolution
                 unresolved_type_refs
-                    .entry(type_ref.referrer.clone())
+                    .entry(referrer_id)
                     .or_default()
                     .push(type_ref.type_name.clone());
             }
@@ -388,30 +419,27 @@ pub fn ir_to_graph(
     }
 
     // Store unresolved type refs on referrer nodes for post-processing
-    for (referrer_name, types) in unresolved_type_refs {
-        if let Some(&referrer_id) = node_map.get(&referrer_name) {
-            if let Ok(node) = graph.get_node(referrer_id) {
-                let mut all: Vec<String> = node
-                    .properties
-                    .get_string_list_compat("unresolved_type_refs")
-                    .unwrap_or_default();
-                for t in &types {
-                    if !all.iter().any(|existing| existing == t) {
-                        all.push(t.clone());
-                    }
+    for (referrer_id, types) in unresolved_type_refs {
+        if let Ok(node) = graph.get_node(referrer_id) {
+            let mut all: Vec<String> = node
+                .properties
+                .get_string_list_compat("unresolved_type_refs")
+                .unwrap_or_default();
+            for t in &types {
+                if !all.iter().any(|existing| existing == t) {
+                    all.push(t.clone());
                 }
-                let new_props = node.properties.clone().with("unresolved_type_refs", all);
-                let _ = graph.update_node_properties(referrer_id, new_props);
             }
+            let new_props = node.properties.clone().with("unresolved_type_refs", all);
+            let _ = graph.update_node_properties(referrer_id, new_props);
         }
     }
 
     // Add implementation relationships
     for impl_rel in &ir.implementations {
-        if let (Some(&implementor_id), Some(&trait_id)) = (
-            node_map.get(&impl_rel.implementor),
-            node_map.get(&impl_rel.trait_name),
-        ) {
+        let implementor_id = symbols.get(&impl_rel.implementor);
+        let trait_id = implementor_id.and_then(|id| symbols.resolve(&impl_rel.trait_name, id));
+        if let (Some(implementor_id), Some(trait_id)) = (implementor_id, trait_id) {
             graph
                 .add_edge(
                     implementor_id,
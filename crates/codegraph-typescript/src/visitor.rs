@@ -21,6 +21,8 @@ pub struct TypeScriptVisitor<'a> {
     pub implementations: Vec<ImplementationRelation>,
     pub inheritance: Vec<InheritanceRelation>,
     pub type_references: Vec<TypeReference>,
+    /// Module path of the file, e.g. `src/net/server`
+    pub module_path: String,
     current_class: Option<String>,
     current_function: Option<String>,
 }
@@ -37,6 +39,7 @@ impl<'a> TypeScriptVisitor<'a> {
             implementations: Vec::new(),
             inheritance: Vec::new(),
             type_references: Vec::new(),
+            module_path: String::new(),
             current_class: None,
             current_function: None,
         }
@@ -47,6 +50,23 @@ impl<'a> TypeScriptVisitor<'a> {
         node.utf8_text(self.source).unwrap_or("").to_string()
     }
 
+    /// Qualify a top-level name as `module:Name`
+    fn qualify(&self, name: &str) -> String {
+        if self.module_path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}:{}", self.module_path, name)
+        }
+    }
+
+    /// Qualify a function as `module:Class.method`, or `module:function` outside a class
+    fn qualify_member(&self, name: &str) -> String {
+        match &self.current_class {
+            Some(class) => self.qualify(&format!("{}.{}", class, name)),
+            None => self.qualify(name),
+        }
+    }
+
     /// Visit a tree-sitter node
     pub fn visit_node(&mut self, node: Node) {
         match node.kind() {
@@ -139,6 +159,7 @@ impl<'a> TypeScriptVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_member(&name)),
             signature: self
                 .node_text(node)
                 .lines()
@@ -148,6 +169,7 @@ impl<'a> TypeScriptVisitor<'a> {
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async,
             is_test: false,
             is_static: false,
@@ -212,10 +234,12 @@ impl<'a> TypeScriptVisitor<'a> {
 
         let func = FunctionEntity {
             name: "arrow_function".to_string(),
+            qualified_name: None,
             signature: "() => {}".to_string(),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async,
             is_test: false,
             is_static: false,
@@ -273,10 +297,12 @@ impl<'a> TypeScriptVisitor<'a> {
 
         let func = FunctionEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify_member(&name)),
             signature: node_text.lines().next().unwrap_or("").to_string(),
             visibility,
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async,
             is_test: false,
             is_static,
@@ -321,9 +347,11 @@ impl<'a> TypeScriptVisitor<'a> {
 
         let class = ClassEntity {
             name: name.clone(),
+            qualified_name: Some(self.qualify(&name)),
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -429,10 +457,12 @@ impl<'a> TypeScriptVisitor<'a> {
         }
 
         let interface = TraitEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             visibility: "public".to_string(),
             line_start: line,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             required_methods: Vec::new(),
             parent_traits: Vec::new(),
             doc_comment: None,
@@ -486,10 +516,12 @@ impl<'a> TypeScriptVisitor<'a> {
         }
 
         let class = ClassEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract: false,
             is_interface: false,
             base_classes: Vec::new(),
@@ -558,6 +590,7 @@ impl<'a> TypeScriptVisitor<'a> {
             symbols,
             is_wildcard,
             alias,
+            line: Some(node.start_position().row + 1),
         };
 
         self.imports.push(import);
@@ -580,6 +613,7 @@ impl<'a> TypeScriptVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias: Some("reference".to_string()),
+                line: Some(node.start_position().row + 1),
             };
             self.imports.push(import);
         }
@@ -786,7 +820,9 @@ impl<'a> TypeScriptVisitor<'a> {
                 caller: caller.clone(),
                 callee,
                 call_site_line,
-                is_direct: true, struct_type: None, field_name: None,
+                is_direct: true,
+                struct_type: None,
+                field_name: None,
             };
 
             self.calls.push(call);
@@ -823,7 +859,9 @@ impl<'a> TypeScriptVisitor<'a> {
                     caller: caller.clone(),
                     callee: class_name,
                     call_site_line: node.start_position().row + 1,
-                    is_direct: true, struct_type: None, field_name: None,
+                    is_direct: true,
+                    struct_type: None,
+                    field_name: None,
                 });
             }
         }
@@ -1125,6 +1163,35 @@ mod tests {
         // Visitor would need to match "method_definition" node type
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        use tree_sitter::Parser;
+
+        let source = b"class Server { start() {} }\nfunction main() {}";
+        let mut parser = Parser::new();
+        parser
+            .set_language(&tree_sitter_typescript::language_typescript())
+            .unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        let mut visitor = TypeScriptVisitor::new(source);
+        visitor.module_path = "src/server".to_string();
+        visitor.visit_node(tree.root_node());
+
+        assert_eq!(
+            visitor.classes[0].qualified_name.as_deref(),
+            Some("src/server:Server")
+        );
+        let start = visitor
+            .functions
+            .iter()
+            .find(|f| f.name == "start")
+            .unwrap();
+        assert_eq!(start.full_name(), "src/server:Server.start");
+        let main = visitor.functions.iter().find(|f| f.name == "main").unwrap();
+        assert_eq!(main.full_name(), "src/server:main");
+    }
+
     #[test]
     fn test_visitor_interface_extraction() {
         use tree_sitter::Parser;
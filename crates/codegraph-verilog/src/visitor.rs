@@ -40,6 +40,14 @@ impl<'a> VerilogVisitor<'a> {
         node.utf8_text(self.source).unwrap_or("").to_string()
     }
 
+    /// Qualify a function or task as `module.name`
+    fn qualify(&self, name: &str) -> String {
+        match &self.current_module {
+            Some(module) => format!("{}.{}", module, name),
+            None => name.to_string(),
+        }
+    }
+
     /// Extract parameters from a function_body_declaration or task_body_declaration.
     /// AST: body_declaration → tf_port_list → tf_port_item1 → port_identifier → simple_identifier
     fn extract_tf_parameters(&self, body_node: Node) -> Vec<Parameter> {
@@ -268,9 +276,11 @@ impl<'a> VerilogVisitor<'a> {
 
         let entity = ClassEntity {
             name,
+            qualified_name: None,
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_abstract,
             is_interface,
             base_classes: Vec::new(),
@@ -320,6 +330,7 @@ impl<'a> VerilogVisitor<'a> {
         let complexity = self.calculate_complexity(node);
 
         let func = FunctionEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             signature: self
                 .node_text(node)
@@ -330,6 +341,7 @@ impl<'a> VerilogVisitor<'a> {
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static: false,
@@ -372,6 +384,7 @@ impl<'a> VerilogVisitor<'a> {
         let complexity = self.calculate_complexity(node);
 
         let func = FunctionEntity {
+            qualified_name: Some(self.qualify(&name)),
             name,
             signature: self
                 .node_text(node)
@@ -382,6 +395,7 @@ impl<'a> VerilogVisitor<'a> {
             visibility: "public".to_string(),
             line_start: node.start_position().row + 1,
             line_end: node.end_position().row + 1,
+            span: Some(node.range().into()),
             is_async: false,
             is_test: false,
             is_static: false,
@@ -422,6 +436,7 @@ impl<'a> VerilogVisitor<'a> {
                 symbols: Vec::new(),
                 is_wildcard: false,
                 alias: None,
+                line: Some(node.start_position().row + 1),
             });
         }
     }
@@ -468,6 +483,7 @@ impl<'a> VerilogVisitor<'a> {
                         symbols: Vec::new(),
                         is_wildcard,
                         alias: None,
+                        line: Some(node.start_position().row + 1),
                     });
                 }
             }
@@ -648,6 +664,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_visitor_qualified_names() {
+        use tree_sitter::Parser;
+        let source = b"module top(); task reset; begin end endtask endmodule";
+        let mut parser = Parser::new();
+        parser.set_language(&crate::ts_verilog::language()).unwrap();
+        let tree = parser.parse(source, None).unwrap();
+
+        let mut visitor = VerilogVisitor::new(source);
+        visitor.visit_node(tree.root_node());
+
+        assert_eq!(visitor.functions[0].full_name(), "top.reset");
+    }
+
     #[test]
     fn test_function_parameter_extraction() {
         use tree_sitter::Parser;
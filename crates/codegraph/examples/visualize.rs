@@ -158,6 +158,7 @@ fn main() -> codegraph::Result<()> {
         node_shapes,
         rankdir: "TB".to_string(),
         show_properties: vec!["visibility".to_string(), "is_test".to_string()],
+        ..DotOptions::default()
     };
 
     let styled_dot = graph.export_dot_styled(options)?;
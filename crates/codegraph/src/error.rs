@@ -85,6 +85,17 @@ pub enum GraphError {
         /// Actual type found
         actual: String,
     },
+
+    /// Malformed or invalid textual graph query
+    #[error("Query error at line {line}, column {column}: {message}")]
+    QuerySyntax {
+        /// Description of what went wrong
+        message: String,
+        /// 1-based line of the offending token
+        line: usize,
+        /// 1-based column of the offending token
+        column: usize,
+    },
 }
 
 impl GraphError {
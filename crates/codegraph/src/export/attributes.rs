@@ -0,0 +1,229 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Typed attribute declarations shared by the XML exporters.
+//!
+//! GraphML and GEXF declare every attribute with a type before the graph data.
+//! The declarations are derived from the [`PropertyValue`] variants actually
+//! present: a key holding both integers and floats is declared as a double, a
+//! key holding lists of any kind as a list, and any other mix as a string.
+
+use crate::{Node, PropertyMap, PropertyValue};
+use std::collections::BTreeMap;
+
+/// Declared type of an exported attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AttributeType {
+    Boolean,
+    Long,
+    Double,
+    String,
+    List,
+}
+
+impl AttributeType {
+    fn of(value: &PropertyValue) -> Option<Self> {
+        Some(match value {
+            PropertyValue::Bool(_) => Self::Boolean,
+            PropertyValue::Int(_) => Self::Long,
+            PropertyValue::Float(_) => Self::Double,
+            PropertyValue::String(_) => Self::String,
+            PropertyValue::StringList(_) | PropertyValue::IntList(_) | PropertyValue::Vector(_) => {
+                Self::List
+            }
+            PropertyValue::Null => return None,
+        })
+    }
+
+    fn widen(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Long, Self::Double) | (Self::Double, Self::Long) => Self::Double,
+            _ => Self::String,
+        }
+    }
+}
+
+/// An attribute declaration: a property key, its declared name and its type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Attribute {
+    pub(super) name: String,
+    /// The key, prefixed with `prop_` if it collides with a built-in attribute
+    pub(super) title: String,
+    pub(super) attr_type: AttributeType,
+}
+
+/// Declare one attribute per property key, sorted by key.
+///
+/// Keys that only ever hold `Null` are declared as strings. Keys named like one
+/// of the exporter's `reserved` attributes are declared as `prop_{key}`.
+pub(super) fn declare<'a>(
+    properties: impl Iterator<Item = &'a PropertyMap>,
+    reserved: &[&str],
+) -> Vec<Attribute> {
+    let mut types: BTreeMap<&str, Option<AttributeType>> = BTreeMap::new();
+    for props in properties {
+        for (key, value) in props.iter() {
+            let declared = types.entry(key.as_str()).or_default();
+            if let Some(value_type) = AttributeType::of(value) {
+                *declared = Some(declared.map_or(value_type, |t| t.widen(value_type)));
+            }
+        }
+    }
+    types
+        .iter()
+        .map(|(&name, attr_type)| {
+            let mut title = name.to_string();
+            if reserved.contains(&name) {
+                title = format!("prop_{title}");
+                while types.contains_key(title.as_str()) || reserved.contains(&title.as_str()) {
+                    title = format!("prop_{title}");
+                }
+            }
+            Attribute {
+                name: name.to_string(),
+                title,
+                attr_type: attr_type.unwrap_or(AttributeType::String),
+            }
+        })
+        .collect()
+}
+
+/// Format a scalar value as XML Schema text, joining lists with `separator`.
+///
+/// Returns `None` for `Null`, which is exported as a missing value.
+pub(super) fn format_value(value: &PropertyValue, separator: &str) -> Option<String> {
+    Some(match value {
+        PropertyValue::String(s) => s.clone(),
+        PropertyValue::Int(i) => i.to_string(),
+        PropertyValue::Float(f) => format_double(*f),
+        PropertyValue::Bool(b) => b.to_string(),
+        PropertyValue::StringList(v) => v.join(separator),
+        PropertyValue::IntList(v) => join(v.iter(), separator),
+        PropertyValue::Vector(v) => join(v.iter().map(|&x| format_double(x as f64)), separator),
+        PropertyValue::Null => return None,
+    })
+}
+
+fn join<T: ToString>(items: impl Iterator<Item = T>, separator: &str) -> String {
+    items
+        .map(|item| item.to_string())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Format a double using the XML Schema spelling of non-finite values
+fn format_double(f: f64) -> String {
+    if f.is_nan() {
+        "NaN".to_string()
+    } else if f.is_infinite() {
+        if f > 0.0 { "INF" } else { "-INF" }.to_string()
+    } else {
+        f.to_string()
+    }
+}
+
+/// Display label of a node: its name, else its path, else its ID
+pub(super) fn node_label(node_id: u64, node: &Node) -> String {
+    node.properties
+        .get_string("name")
+        .or_else(|| node.properties.get_string("path"))
+        .map_or_else(|| format!("n{node_id}"), str::to_string)
+}
+
+/// Escape text for use in XML content and attribute values.
+///
+/// Control characters that XML 1.0 cannot represent are dropped.
+pub(super) fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declare_widens_types() {
+        let a = PropertyMap::new()
+            .with("count", 1i64)
+            .with("score", 1i64)
+            .with("tags", vec!["x".to_string()])
+            .with("mixed", true)
+            .with("missing", PropertyValue::Null);
+        let b = PropertyMap::new()
+            .with("score", 0.5f64)
+            .with("tags", vec![1i64])
+            .with("mixed", "yes");
+
+        let declared = declare([&a, &b].into_iter(), &[]);
+        let types: Vec<_> = declared
+            .iter()
+            .map(|attr| (attr.name.as_str(), attr.attr_type))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("count", AttributeType::Long),
+                ("missing", AttributeType::String),
+                ("mixed", AttributeType::String),
+                ("score", AttributeType::Double),
+                ("tags", AttributeType::List),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_declare_renames_reserved_keys() {
+        let props = PropertyMap::new()
+            .with("label", "x")
+            .with("prop_label", "y")
+            .with("name", "z");
+        let declared = declare([&props].into_iter(), &["label"]);
+        let titles: Vec<_> = declared
+            .iter()
+            .map(|attr| (attr.name.as_str(), attr.title.as_str()))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![
+                ("label", "prop_prop_label"),
+                ("name", "name"),
+                ("prop_label", "prop_label"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(
+            format_value(&PropertyValue::Float(f64::NEG_INFINITY), "|"),
+            Some("-INF".to_string())
+        );
+        assert_eq!(
+            format_value(&PropertyValue::IntList(vec![1, 2]), "|"),
+            Some("1|2".to_string())
+        );
+        assert_eq!(format_value(&PropertyValue::Null, "|"), None);
+    }
+
+    #[test]
+    fn test_escape_xml() {
+        assert_eq!(
+            escape_xml("a<b> & \"c\"\u{1}"),
+            "a&lt;b&gt; &amp; &quot;c&quot;"
+        );
+    }
+}
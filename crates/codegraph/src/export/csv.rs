@@ -5,153 +5,108 @@
 //!
 //! Generates separate CSV files for nodes and edges with auto-detected columns.
 
-use crate::{CodeGraph, Result};
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::write_error;
+use crate::{CodeGraph, PropertyMap, Result};
 use std::collections::HashSet;
 use std::fs::File;
-use std::io::Write;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
 /// Export nodes to CSV file
 pub fn export_csv_nodes(graph: &CodeGraph, path: &Path) -> Result<()> {
-    let mut file = File::create(path).map_err(|e| crate::GraphError::Storage {
-        message: format!("Failed to create CSV file: {}", path.display()),
-        source: Some(Box::new(e)),
-    })?;
+    let mut file = create_csv_file(path)?;
+    export_csv_nodes_to(graph, &mut file)?;
+    file.flush().map_err(write_error)
+}
 
-    // Collect all property keys used in the graph
-    let mut all_keys = HashSet::new();
-    for node_id in 0..graph.node_count() as u64 {
-        if let Ok(node) = graph.get_node(node_id) {
-            for (key, _) in node.properties.iter() {
-                all_keys.insert(key.clone());
-            }
-        }
-    }
+/// Export edges to CSV file
+pub fn export_csv_edges(graph: &CodeGraph, path: &Path) -> Result<()> {
+    let mut file = create_csv_file(path)?;
+    export_csv_edges_to(graph, &mut file)?;
+    file.flush().map_err(write_error)
+}
 
-    let mut keys_vec: Vec<String> = all_keys.into_iter().collect();
-    keys_vec.sort();
+/// Stream nodes as CSV to a writer
+pub fn export_csv_nodes_to<W: Write>(graph: &CodeGraph, mut writer: W) -> Result<()> {
+    let nodes = sorted_nodes(graph);
+    let keys = property_keys(nodes.iter().map(|(_, node)| &node.properties));
 
     // Write header
-    write!(file, "id,type").map_err(|e| crate::GraphError::Storage {
-        message: "Failed to write CSV header".to_string(),
-        source: Some(Box::new(e)),
-    })?;
-    for key in &keys_vec {
-        write!(file, ",{key}").map_err(|e| crate::GraphError::Storage {
-            message: "Failed to write CSV header".to_string(),
-            source: Some(Box::new(e)),
-        })?;
+    write!(writer, "id,type").map_err(write_error)?;
+    write_header(&mut writer, &keys)?;
+
+    // Write rows
+    for (node_id, node) in nodes {
+        write!(writer, "{},{:?}", node_id, node.node_type).map_err(write_error)?;
+        write_row(&mut writer, &keys, &node.properties)?;
     }
-    writeln!(file).map_err(|e| crate::GraphError::Storage {
-        message: "Failed to write CSV header".to_string(),
-        source: Some(Box::new(e)),
-    })?;
+
+    Ok(())
+}
+
+/// Stream edges as CSV to a writer
+pub fn export_csv_edges_to<W: Write>(graph: &CodeGraph, mut writer: W) -> Result<()> {
+    let edges = sorted_edges(graph);
+    let keys = property_keys(edges.iter().map(|(_, edge)| &edge.properties));
+
+    // Write header
+    write!(writer, "id,source,target,type").map_err(write_error)?;
+    write_header(&mut writer, &keys)?;
 
     // Write rows
-    for node_id in 0..graph.node_count() as u64 {
-        if let Ok(node) = graph.get_node(node_id) {
-            write!(file, "{},{:?}", node_id, node.node_type).map_err(|e| {
-                crate::GraphError::Storage {
-                    message: "Failed to write CSV row".to_string(),
-                    source: Some(Box::new(e)),
-                }
-            })?;
-
-            for key in &keys_vec {
-                write!(file, ",").map_err(|e| crate::GraphError::Storage {
-                    message: "Failed to write CSV row".to_string(),
-                    source: Some(Box::new(e)),
-                })?;
-                if let Some(value) = node.properties.get(key) {
-                    write!(file, "{}", escape_csv(&format_property_value(value))).map_err(|e| {
-                        crate::GraphError::Storage {
-                            message: "Failed to write CSV row".to_string(),
-                            source: Some(Box::new(e)),
-                        }
-                    })?;
-                }
-            }
-            writeln!(file).map_err(|e| crate::GraphError::Storage {
-                message: "Failed to write CSV row".to_string(),
-                source: Some(Box::new(e)),
-            })?;
-        }
+    for (edge_id, edge) in edges {
+        write!(
+            writer,
+            "{},{},{},{:?}",
+            edge_id, edge.source_id, edge.target_id, edge.edge_type
+        )
+        .map_err(write_error)?;
+        write_row(&mut writer, &keys, &edge.properties)?;
     }
 
     Ok(())
 }
 
-/// Export edges to CSV file
-pub fn export_csv_edges(graph: &CodeGraph, path: &Path) -> Result<()> {
-    let mut file = File::create(path).map_err(|e| crate::GraphError::Storage {
+pub(super) fn create_csv_file(path: &Path) -> Result<BufWriter<File>> {
+    let file = File::create(path).map_err(|e| crate::GraphError::Storage {
         message: format!("Failed to create CSV file: {}", path.display()),
         source: Some(Box::new(e)),
     })?;
+    Ok(BufWriter::new(file))
+}
 
-    // Collect all property keys used in edges
+/// Collect all property keys used, sorted
+fn property_keys<'a>(properties: impl Iterator<Item = &'a PropertyMap>) -> Vec<String> {
     let mut all_keys = HashSet::new();
-    for edge_id in 0..graph.edge_count() as u64 {
-        if let Ok(edge) = graph.get_edge(edge_id) {
-            for (key, _) in edge.properties.iter() {
-                all_keys.insert(key.clone());
-            }
+    for props in properties {
+        for (key, _) in props.iter() {
+            all_keys.insert(key.clone());
         }
     }
 
     let mut keys_vec: Vec<String> = all_keys.into_iter().collect();
     keys_vec.sort();
+    keys_vec
+}
 
-    // Write header
-    write!(file, "id,source,target,type").map_err(|e| crate::GraphError::Storage {
-        message: "Failed to write CSV header".to_string(),
-        source: Some(Box::new(e)),
-    })?;
-    for key in &keys_vec {
-        write!(file, ",{key}").map_err(|e| crate::GraphError::Storage {
-            message: "Failed to write CSV header".to_string(),
-            source: Some(Box::new(e)),
-        })?;
+/// Finish a header line with one column per property key
+fn write_header<W: Write>(writer: &mut W, keys: &[String]) -> Result<()> {
+    for key in keys {
+        write!(writer, ",{}", escape_csv(key)).map_err(write_error)?;
     }
-    writeln!(file).map_err(|e| crate::GraphError::Storage {
-        message: "Failed to write CSV header".to_string(),
-        source: Some(Box::new(e)),
-    })?;
+    writeln!(writer).map_err(write_error)
+}
 
-    // Write rows
-    for edge_id in 0..graph.edge_count() as u64 {
-        if let Ok(edge) = graph.get_edge(edge_id) {
-            write!(
-                file,
-                "{},{},{},{:?}",
-                edge_id, edge.source_id, edge.target_id, edge.edge_type
-            )
-            .map_err(|e| crate::GraphError::Storage {
-                message: "Failed to write CSV row".to_string(),
-                source: Some(Box::new(e)),
-            })?;
-
-            for key in &keys_vec {
-                write!(file, ",").map_err(|e| crate::GraphError::Storage {
-                    message: "Failed to write CSV row".to_string(),
-                    source: Some(Box::new(e)),
-                })?;
-                if let Some(value) = edge.properties.get(key) {
-                    write!(file, "{}", escape_csv(&format_property_value(value))).map_err(|e| {
-                        crate::GraphError::Storage {
-                            message: "Failed to write CSV row".to_string(),
-                            source: Some(Box::new(e)),
-                        }
-                    })?;
-                }
-            }
-            writeln!(file).map_err(|e| crate::GraphError::Storage {
-                message: "Failed to write CSV row".to_string(),
-                source: Some(Box::new(e)),
-            })?;
+/// Finish a row with the value of each property key
+fn write_row<W: Write>(writer: &mut W, keys: &[String], properties: &PropertyMap) -> Result<()> {
+    for key in keys {
+        write!(writer, ",").map_err(write_error)?;
+        if let Some(value) = properties.get(key) {
+            write!(writer, "{}", escape_csv(&format_property_value(value))).map_err(write_error)?;
         }
     }
-
-    Ok(())
+    writeln!(writer).map_err(write_error)
 }
 
 /// Export both nodes and edges to separate CSV files (convenience method)
@@ -174,13 +129,18 @@ fn format_property_value(value: &crate::PropertyValue) -> String {
             .map(|i| i.to_string())
             .collect::<Vec<_>>()
             .join(";"),
+        crate::PropertyValue::Vector(v) => v
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(";"),
         crate::PropertyValue::Null => String::new(),
     }
 }
 
 /// Escape CSV value (add quotes if contains comma, quote, or newline)
-fn escape_csv(s: &str) -> String {
-    if s.contains(',') || s.contains('"') || s.contains('\n') {
+pub(super) fn escape_csv(s: &str) -> String {
+    if s.contains(',') || s.contains('"') || s.contains('\n') || s.contains('\r') {
         format!("\"{}\"", s.replace('"', "\"\""))
     } else {
         s.to_string()
@@ -196,5 +156,6 @@ mod tests {
         assert_eq!(escape_csv("hello"), "hello");
         assert_eq!(escape_csv("hello,world"), "\"hello,world\"");
         assert_eq!(escape_csv("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv("line\r\nbreak"), "\"line\r\nbreak\"");
     }
 }
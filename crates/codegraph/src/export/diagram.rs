@@ -0,0 +1,470 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Diagram models shared by the Mermaid and PlantUML exporters.
+//!
+//! Diagrams are meant to be pasted into Markdown documents and reviews, so they
+//! are kept small: class members beyond [`DiagramOptions::max_members`] are
+//! collapsed into a count, flowcharts over [`DiagramOptions::max_nodes`] are
+//! collapsed to one node per file, and whatever still exceeds the limit is
+//! trimmed to the best connected nodes, with a comment saying how many were
+//! left out. To diagram part of a graph, pass a subgraph from
+//! [`CodeGraph::extract_subgraph`] or restrict types with an [`ExportFilter`].
+
+use super::attributes::node_label;
+use super::filter::{ExportFilter, Selection};
+use crate::graph::{Edge, NodeId};
+use crate::{helpers, CodeGraph, EdgeType, Node, NodeType, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Options for Mermaid and PlantUML diagrams
+#[derive(Debug, Clone)]
+pub struct DiagramOptions {
+    /// Node and edge types to include
+    pub filter: ExportFilter,
+    /// Maximum number of boxes before collapsing and trimming
+    pub max_nodes: usize,
+    /// Maximum number of fields and methods listed per class
+    pub max_members: usize,
+    /// Group entities by their containing file (class diagrams and flowcharts)
+    /// or directory (component diagrams)
+    pub group_by_module: bool,
+    /// Flowchart layout direction: LR, TB, RL, BT
+    pub direction: String,
+}
+
+impl Default for DiagramOptions {
+    fn default() -> Self {
+        DiagramOptions {
+            filter: ExportFilter::new(),
+            max_nodes: 40,
+            max_members: 8,
+            group_by_module: true,
+            direction: "LR".to_string(),
+        }
+    }
+}
+
+/// Visibility marker of a class member, shared by Mermaid and PlantUML
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Visibility {
+    Public,
+    Private,
+    Protected,
+    Package,
+    Unknown,
+}
+
+impl Visibility {
+    fn of(node: &Node) -> Self {
+        match node.properties.get_string("visibility") {
+            Some("public" | "pub" | "export" | "exported") => Self::Public,
+            Some("private") => Self::Private,
+            Some("protected") => Self::Protected,
+            Some("internal" | "package" | "pub(crate)" | "pub(super)") => Self::Package,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub(super) fn marker(self) -> &'static str {
+        match self {
+            Self::Public => "+",
+            Self::Private => "-",
+            Self::Protected => "#",
+            Self::Package => "~",
+            Self::Unknown => "",
+        }
+    }
+}
+
+/// A field or method listed in a class box
+#[derive(Debug, Clone)]
+pub(super) struct Member {
+    pub(super) name: String,
+    /// Field type or method return type
+    pub(super) type_name: Option<String>,
+    pub(super) visibility: Visibility,
+    pub(super) is_static: bool,
+    pub(super) is_abstract: bool,
+}
+
+impl Member {
+    fn of(node: &Node) -> Self {
+        let type_name = ["type_annotation", "return_type"]
+            .iter()
+            .filter_map(|key| node.properties.get_string(key))
+            .find(|t| !t.is_empty())
+            .map(str::to_string);
+        Member {
+            name: node
+                .properties
+                .get_string("name")
+                .unwrap_or("?")
+                .to_string(),
+            type_name,
+            visibility: Visibility::of(node),
+            is_static: node.properties.get_bool("is_static").unwrap_or(false),
+            is_abstract: node.properties.get_bool("is_abstract").unwrap_or(false),
+        }
+    }
+}
+
+/// Kind of class box
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum ClassKind {
+    Class,
+    Abstract,
+    Interface,
+}
+
+/// A class or interface with its members
+#[derive(Debug, Clone)]
+pub(super) struct ClassBox {
+    pub(super) id: NodeId,
+    pub(super) name: String,
+    pub(super) kind: ClassKind,
+    pub(super) fields: Vec<Member>,
+    pub(super) methods: Vec<Member>,
+    /// Members left out because of [`DiagramOptions::max_members`]
+    pub(super) hidden_members: usize,
+}
+
+/// An `Extends` or `Implements` arrow from subtype to supertype
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Inheritance {
+    pub(super) source: NodeId,
+    pub(super) target: NodeId,
+    pub(super) edge_type: EdgeType,
+}
+
+/// Class diagram: class boxes by group, and the inheritance between them
+#[derive(Debug, Clone, Default)]
+pub(super) struct ClassModel {
+    /// Classes keyed by group label; ungrouped classes under `None`
+    pub(super) groups: BTreeMap<Option<String>, Vec<ClassBox>>,
+    pub(super) relations: Vec<Inheritance>,
+    /// Classes trimmed because of [`DiagramOptions::max_nodes`]
+    pub(super) omitted: usize,
+}
+
+/// A box in a flowchart or component diagram
+#[derive(Debug, Clone)]
+pub(super) struct FlowNode {
+    pub(super) id: NodeId,
+    pub(super) label: String,
+    pub(super) node_type: NodeType,
+}
+
+/// A labelled arrow in a flowchart or component diagram
+#[derive(Debug, Clone)]
+pub(super) struct FlowEdge {
+    pub(super) source: NodeId,
+    pub(super) target: NodeId,
+    pub(super) label: String,
+}
+
+/// Flowchart or component diagram
+#[derive(Debug, Clone, Default)]
+pub(super) struct FlowModel {
+    /// Nodes keyed by group; ungrouped nodes under `None`. A group is the
+    /// file node containing its members, or a directory name.
+    pub(super) groups: BTreeMap<Option<FlowGroup>, Vec<FlowNode>>,
+    pub(super) edges: Vec<FlowEdge>,
+    /// Whether the graph was collapsed to one node per file
+    pub(super) collapsed: bool,
+    /// Nodes trimmed because of [`DiagramOptions::max_nodes`]
+    pub(super) omitted: usize,
+}
+
+/// A group of flowchart nodes
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) struct FlowGroup {
+    pub(super) label: String,
+    /// The file node the group stands for, which edges may point at
+    pub(super) node: Option<NodeId>,
+}
+
+/// Build the class diagram model.
+pub(super) fn class_model(graph: &CodeGraph, options: &DiagramOptions) -> Result<ClassModel> {
+    let Selection { nodes, edges } = options.filter.select(graph);
+    let classes: Vec<(NodeId, &Node)> = nodes
+        .into_iter()
+        .filter(|(_, node)| matches!(node.node_type, NodeType::Class | NodeType::Interface))
+        .collect();
+    let class_ids: HashSet<NodeId> = classes.iter().map(|(id, _)| *id).collect();
+    let mut relations: Vec<Inheritance> = edges
+        .iter()
+        .filter(|(_, edge)| matches!(edge.edge_type, EdgeType::Extends | EdgeType::Implements))
+        .filter(|(_, edge)| {
+            class_ids.contains(&edge.source_id) && class_ids.contains(&edge.target_id)
+        })
+        .map(|(_, edge)| Inheritance {
+            source: edge.source_id,
+            target: edge.target_id,
+            edge_type: edge.edge_type,
+        })
+        .collect();
+
+    let (kept, omitted) = trim(
+        classes.iter().map(|(id, _)| *id),
+        relations.iter().map(|r| (r.source, r.target)),
+        options.max_nodes,
+    );
+    relations.retain(|r| kept.contains(&r.source) && kept.contains(&r.target));
+
+    let mut model = ClassModel {
+        relations,
+        omitted,
+        ..ClassModel::default()
+    };
+    for (id, node) in classes {
+        if !kept.contains(&id) {
+            continue;
+        }
+        let group = if options.group_by_module {
+            file_label(graph, id)?
+        } else {
+            None
+        };
+        model
+            .groups
+            .entry(group)
+            .or_default()
+            .push(class_box(graph, id, node, options));
+    }
+    Ok(model)
+}
+
+fn class_box(graph: &CodeGraph, id: NodeId, node: &Node, options: &DiagramOptions) -> ClassBox {
+    let is_interface = node.node_type == NodeType::Interface
+        || node.properties.get_bool("is_interface").unwrap_or(false);
+    let kind = if is_interface {
+        ClassKind::Interface
+    } else if node.properties.get_bool("is_abstract").unwrap_or(false) {
+        ClassKind::Abstract
+    } else {
+        ClassKind::Class
+    };
+
+    let mut members: Vec<&Node> = graph
+        .outgoing_edges(id)
+        .filter(|edge| edge.edge_type == EdgeType::Contains)
+        .filter_map(|edge| graph.get_node(edge.target_id).ok())
+        .filter(|member| matches!(member.node_type, NodeType::Variable | NodeType::Function))
+        .filter(|member| options.filter.includes_node(member))
+        .collect();
+    // Source order; members without a position go last
+    members.sort_by_key(|member| {
+        let line = member.properties.get_int("line_start");
+        (line.is_none(), line, member.id)
+    });
+    let hidden_members = members.len().saturating_sub(options.max_members);
+    members.truncate(options.max_members);
+
+    let (fields, methods): (Vec<&Node>, Vec<&Node>) = members
+        .into_iter()
+        .partition(|member| member.node_type == NodeType::Variable);
+    ClassBox {
+        id,
+        name: node_label(id, node),
+        kind,
+        fields: fields.into_iter().map(Member::of).collect(),
+        methods: methods.into_iter().map(Member::of).collect(),
+        hidden_members,
+    }
+}
+
+/// Build the flowchart model, collapsing it to files when it is too large.
+pub(super) fn flow_model(graph: &CodeGraph, options: &DiagramOptions) -> Result<FlowModel> {
+    let Selection { nodes, edges } = options.filter.select(graph);
+    if nodes.len() > options.max_nodes {
+        return file_model(graph, options, false);
+    }
+
+    let mut groups: HashMap<NodeId, Option<NodeId>> = HashMap::new();
+    for (id, node) in &nodes {
+        let file = if options.group_by_module && node.node_type != NodeType::CodeFile {
+            helpers::containing_file(graph, *id)?
+        } else {
+            None
+        };
+        groups.insert(*id, file);
+    }
+    // Files that group other nodes become the group itself
+    let group_files: HashSet<NodeId> = groups.values().flatten().copied().collect();
+
+    let mut model = FlowModel::default();
+    for (id, node) in &nodes {
+        if group_files.contains(id) {
+            continue;
+        }
+        let group = match groups[id] {
+            Some(file) => Some(FlowGroup {
+                label: path_label(file, graph.get_node(file)?),
+                node: groups.contains_key(&file).then_some(file),
+            }),
+            None => None,
+        };
+        model
+            .groups
+            .entry(group)
+            .or_default()
+            .push(flow_node(*id, node));
+    }
+    model.edges = edges
+        .into_iter()
+        .filter(|(_, edge)| !is_grouping(edge, &groups))
+        .map(|(_, edge)| FlowEdge {
+            source: edge.source_id,
+            target: edge.target_id,
+            label: edge.edge_type.to_string(),
+        })
+        .collect();
+    Ok(model)
+}
+
+/// Build the component diagram model: one node per file or module.
+pub(super) fn component_model(graph: &CodeGraph, options: &DiagramOptions) -> Result<FlowModel> {
+    file_model(graph, options, true)
+}
+
+/// Collapse the selection to files and modules, aggregating the edges between
+/// their contents; trim to `max_nodes` by connectedness.
+fn file_model(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+    by_directory: bool,
+) -> Result<FlowModel> {
+    let Selection { nodes, edges } = options.filter.select(graph);
+    let mut owner: HashMap<NodeId, NodeId> = HashMap::new();
+    for (id, node) in &nodes {
+        let container = match node.node_type {
+            NodeType::CodeFile | NodeType::Module => Some(*id),
+            _ => helpers::containing_file(graph, *id)?,
+        };
+        if let Some(container) = container {
+            owner.insert(*id, container);
+        }
+    }
+
+    let mut counts: BTreeMap<(NodeId, NodeId, String), usize> = BTreeMap::new();
+    for (_, edge) in &edges {
+        if edge.edge_type == EdgeType::Contains {
+            continue;
+        }
+        if let (Some(&source), Some(&target)) =
+            (owner.get(&edge.source_id), owner.get(&edge.target_id))
+        {
+            if source != target {
+                let key = (source, target, edge.edge_type.to_string());
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+    }
+
+    let mut units: Vec<NodeId> = owner.values().copied().collect();
+    units.sort_unstable();
+    units.dedup();
+    let (kept, omitted) = trim(
+        units.iter().copied(),
+        counts.keys().map(|&(source, target, _)| (source, target)),
+        options.max_nodes,
+    );
+
+    let mut model = FlowModel {
+        collapsed: true,
+        omitted,
+        ..FlowModel::default()
+    };
+    for id in units {
+        if !kept.contains(&id) {
+            continue;
+        }
+        let node = graph.get_node(id)?;
+        let group = (options.group_by_module && by_directory)
+            .then(|| directory(node))
+            .flatten()
+            .map(|label| FlowGroup { label, node: None });
+        model
+            .groups
+            .entry(group)
+            .or_default()
+            .push(flow_node(id, node));
+    }
+    model.edges = counts
+        .into_iter()
+        .filter(|((source, target, _), _)| kept.contains(source) && kept.contains(target))
+        .map(|((source, target, edge_type), count)| FlowEdge {
+            source,
+            target,
+            label: if count > 1 {
+                format!("{edge_type} ({count})")
+            } else {
+                edge_type
+            },
+        })
+        .collect();
+    Ok(model)
+}
+
+fn flow_node(id: NodeId, node: &Node) -> FlowNode {
+    FlowNode {
+        id,
+        label: node_label(id, node),
+        node_type: node.node_type,
+    }
+}
+
+/// `Contains` edges from a file to the nodes drawn inside its group
+fn is_grouping(edge: &Edge, groups: &HashMap<NodeId, Option<NodeId>>) -> bool {
+    edge.edge_type == EdgeType::Contains
+        && groups.get(&edge.target_id) == Some(&Some(edge.source_id))
+}
+
+/// Label of the file containing a node
+fn file_label(graph: &CodeGraph, id: NodeId) -> Result<Option<String>> {
+    Ok(match helpers::containing_file(graph, id)? {
+        Some(file) => Some(path_label(file, graph.get_node(file)?)),
+        None => None,
+    })
+}
+
+/// Path of a file node, falling back to its label
+pub(super) fn path_label(id: NodeId, node: &Node) -> String {
+    node.properties
+        .get_string("path")
+        .map_or_else(|| node_label(id, node), str::to_string)
+}
+
+/// Parent directory of a file node's path
+fn directory(node: &Node) -> Option<String> {
+    let path = node.properties.get_string("path")?;
+    let (dir, _) = path.rsplit_once('/')?;
+    Some(dir.to_string())
+}
+
+/// Keep at most `limit` nodes, preferring the best connected; ties keep the lower ID.
+///
+/// Returns the kept nodes and the number trimmed.
+pub(super) fn trim(
+    nodes: impl Iterator<Item = NodeId>,
+    edges: impl Iterator<Item = (NodeId, NodeId)>,
+    limit: usize,
+) -> (HashSet<NodeId>, usize) {
+    let mut degree: HashMap<NodeId, usize> = nodes.map(|id| (id, 0)).collect();
+    for (source, target) in edges {
+        for id in [source, target] {
+            if let Some(d) = degree.get_mut(&id) {
+                *d += 1;
+            }
+        }
+    }
+    let total = degree.len();
+    if total <= limit {
+        return (degree.into_keys().collect(), 0);
+    }
+    let mut ranked: Vec<(NodeId, usize)> = degree.into_iter().collect();
+    ranked.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    let kept = ranked.into_iter().take(limit).map(|(id, _)| id).collect();
+    (kept, total - limit)
+}
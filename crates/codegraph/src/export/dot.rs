@@ -4,11 +4,22 @@
 //! DOT format export for Graphviz visualization.
 //!
 //! Generates Graphviz DOT format for rendering graphs as images or interactive visualizations.
+//! Large graphs can be filtered, clustered by file, trimmed to a node budget
+//! and drawn with aggregated edges; see [`DotOptions`].
 
-use crate::{CodeGraph, EdgeType, NodeType, Result};
-use std::collections::HashMap;
+use super::diagram::{path_label, trim};
+use super::filter::{ExportFilter, Selection};
+use super::stream::{export_to_string, write_error};
+use crate::graph::NodeId;
+use crate::{CodeGraph, EdgeType, Node, NodeType, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
 
 /// Options for styling DOT export
+///
+/// Large graphs stay readable by combining the layout options: a type
+/// `filter`, `cluster_by_file`, a `max_nodes` budget and `aggregate_edges`,
+/// or `lift_to_files` for a file-to-file view.
 #[derive(Debug, Clone)]
 pub struct DotOptions {
     /// Node colors by type (hex color codes)
@@ -21,6 +32,19 @@ pub struct DotOptions {
     pub rankdir: String,
     /// Property names to show in node labels
     pub show_properties: Vec<String>,
+    /// Node and edge types to include
+    pub filter: ExportFilter,
+    /// Draw each file or module with its contents (found through `Contains`
+    /// edges) as a `subgraph cluster_*`
+    pub cluster_by_file: bool,
+    /// Maximum number of nodes drawn; beyond it the least connected nodes are
+    /// collapsed into their file or module, and their edges moved with them
+    pub max_nodes: Option<usize>,
+    /// Draw parallel edges of the same type as one edge labelled with the count
+    pub aggregate_edges: bool,
+    /// Draw only files and modules: every other node is collapsed into its file
+    /// or module, its edges are lifted there, and parallel edges are counted
+    pub lift_to_files: bool,
 }
 
 impl Default for DotOptions {
@@ -47,6 +71,33 @@ impl Default for DotOptions {
             node_shapes,
             rankdir: "LR".to_string(),
             show_properties: vec![],
+            filter: ExportFilter::new(),
+            cluster_by_file: false,
+            max_nodes: None,
+            aggregate_edges: false,
+            lift_to_files: false,
+        }
+    }
+}
+
+impl DotOptions {
+    /// Options for large graphs: clustered by file, at most `max_nodes` nodes
+    /// and aggregated edges.
+    pub fn compact(max_nodes: usize) -> Self {
+        DotOptions {
+            cluster_by_file: true,
+            max_nodes: Some(max_nodes),
+            aggregate_edges: true,
+            ..DotOptions::default()
+        }
+    }
+
+    /// Options for a file dependency view: symbols collapsed into their files
+    /// or modules, with the edges between them counted.
+    pub fn file_level() -> Self {
+        DotOptions {
+            lift_to_files: true,
+            ..DotOptions::default()
         }
     }
 }
@@ -58,78 +109,305 @@ pub fn export_dot(graph: &CodeGraph) -> Result<String> {
 
 /// Export graph to Graphviz DOT format with custom styling
 pub fn export_dot_styled(graph: &CodeGraph, options: DotOptions) -> Result<String> {
-    let mut output = String::new();
+    export_to_string(|buffer| export_dot_styled_to(graph, &options, buffer))
+}
+
+/// Stream graph in Graphviz DOT format to a writer
+pub fn export_dot_to<W: Write>(graph: &CodeGraph, writer: W) -> Result<()> {
+    export_dot_styled_to(graph, &DotOptions::default(), writer)
+}
+
+/// Stream graph in Graphviz DOT format with custom styling to a writer
+pub fn export_dot_styled_to<W: Write>(
+    graph: &CodeGraph,
+    options: &DotOptions,
+    mut writer: W,
+) -> Result<()> {
+    let layout = layout(graph, options)?;
 
     // Header
-    output.push_str("digraph code_graph {\n");
-    output.push_str(&format!("    rankdir={};\n", options.rankdir));
-    output.push_str("    node [style=filled];\n\n");
-
-    // Export nodes - iterate through all node IDs
-    for node_id in 0..graph.node_count() as u64 {
-        if let Ok(node) = graph.get_node(node_id) {
-            // Build label
-            let mut label = if let Some(name) = node.properties.get_string("name") {
-                escape_dot_label(name)
-            } else if let Some(path) = node.properties.get_string("path") {
-                escape_dot_label(path)
-            } else {
-                format!("n{node_id}")
-            };
-
-            // Add properties to label if requested
-            for prop_name in &options.show_properties {
-                if let Some(value) = node.properties.get(prop_name) {
-                    label.push_str(&format!(
-                        "\\n{}:{}",
-                        prop_name,
-                        format_property_value(value)
-                    ));
-                }
+    writeln!(writer, "digraph code_graph {{").map_err(write_error)?;
+    writeln!(writer, "    rankdir={};", options.rankdir).map_err(write_error)?;
+    writeln!(writer, "    node [style=filled];").map_err(write_error)?;
+    let collapsed: usize = layout.collapsed.values().sum();
+    if collapsed > 0 {
+        writeln!(
+            writer,
+            "    // {collapsed} nodes collapsed into their files"
+        )
+        .map_err(write_error)?;
+    }
+    if layout.omitted > 0 {
+        writeln!(writer, "    // {} nodes omitted", layout.omitted).map_err(write_error)?;
+    }
+    writeln!(writer).map_err(write_error)?;
+
+    // Export nodes in ID order, clusters after unclustered nodes
+    for (cluster, nodes) in &layout.clusters {
+        let indent = match cluster {
+            Some(id) => {
+                let label = escape_dot_label(&path_label(*id, graph.get_node(*id)?));
+                writeln!(writer, "    subgraph cluster_n{id} {{").map_err(write_error)?;
+                writeln!(writer, "        label=\"{label}\";").map_err(write_error)?;
+                "        "
             }
+            None => "    ",
+        };
+        for (node_id, node) in nodes {
+            let collapsed = layout.collapsed.get(node_id).copied().unwrap_or(0);
+            let line = node_line(*node_id, node, collapsed, options);
+            writeln!(writer, "{indent}{line}").map_err(write_error)?;
+        }
+        if cluster.is_some() {
+            writeln!(writer, "    }}").map_err(write_error)?;
+        }
+    }
+
+    writeln!(writer).map_err(write_error)?;
+
+    // Export edges in ID order
+    for edge in &layout.edges {
+        let edge_label = if edge.count > 1 {
+            format!("{:?} ({})", edge.edge_type, edge.count)
+        } else {
+            format!("{:?}", edge.edge_type)
+        };
+
+        let color = options
+            .edge_colors
+            .get(&edge.edge_type)
+            .map(|c| format!(", color=\"{c}\""))
+            .unwrap_or_default();
 
-            // Get styling
-            let color = options
-                .node_colors
-                .get(&node.node_type)
-                .map(|s| s.as_str())
-                .unwrap_or("#FFFFFF");
-
-            let shape = options
-                .node_shapes
-                .get(&node.node_type)
-                .map(|s| s.as_str())
-                .unwrap_or("box");
-
-            output.push_str(&format!(
-                "    n{node_id} [label=\"{label}\", shape={shape}, fillcolor=\"{color}\"];\n"
+        writeln!(
+            writer,
+            "    n{} -> n{} [label=\"{}\"{}];",
+            edge.source, edge.target, edge_label, color
+        )
+        .map_err(write_error)?;
+    }
+
+    writeln!(writer, "}}").map_err(write_error)?;
+
+    Ok(())
+}
+
+/// Node statement with its label and styling
+fn node_line(node_id: NodeId, node: &Node, collapsed: usize, options: &DotOptions) -> String {
+    // Build label
+    let mut label = if let Some(name) = node.properties.get_string("name") {
+        escape_dot_label(name)
+    } else if let Some(path) = node.properties.get_string("path") {
+        escape_dot_label(path)
+    } else {
+        format!("n{node_id}")
+    };
+
+    // Add properties to label if requested
+    for prop_name in &options.show_properties {
+        if let Some(value) = node.properties.get(prop_name) {
+            label.push_str(&format!(
+                "\\n{}:{}",
+                prop_name,
+                format_property_value(value)
             ));
         }
     }
+    if collapsed > 0 {
+        label.push_str(&format!("\\n(+{collapsed} collapsed)"));
+    }
+
+    // Get styling
+    let color = options
+        .node_colors
+        .get(&node.node_type)
+        .map(|s| s.as_str())
+        .unwrap_or("#FFFFFF");
 
-    output.push('\n');
+    let shape = options
+        .node_shapes
+        .get(&node.node_type)
+        .map(|s| s.as_str())
+        .unwrap_or("box");
 
-    // Export edges - iterate through all edge IDs
-    for edge_id in 0..graph.edge_count() as u64 {
-        if let Ok(edge) = graph.get_edge(edge_id) {
-            let edge_label = format!("{:?}", edge.edge_type);
+    format!("n{node_id} [label=\"{label}\", shape={shape}, fillcolor=\"{color}\"];")
+}
 
-            let color = options
-                .edge_colors
-                .get(&edge.edge_type)
-                .map(|c| format!(", color=\"{c}\""))
-                .unwrap_or_default();
+/// An edge between drawn nodes, standing for `count` graph edges
+struct DotEdge {
+    source: NodeId,
+    target: NodeId,
+    edge_type: EdgeType,
+    count: usize,
+}
 
-            output.push_str(&format!(
-                "    n{} -> n{} [label=\"{}\"{}];\n",
-                edge.source_id, edge.target_id, edge_label, color
-            ));
+/// Nodes to draw by cluster, and the edges between them
+struct Layout<'a> {
+    clusters: BTreeMap<Option<NodeId>, Vec<(NodeId, &'a Node)>>,
+    edges: Vec<DotEdge>,
+    /// Number of nodes collapsed into each file or module
+    collapsed: HashMap<NodeId, usize>,
+    omitted: usize,
+}
+
+fn layout<'a>(graph: &'a CodeGraph, options: &DotOptions) -> Result<Layout<'a>> {
+    let Selection { nodes, edges } = options.filter.select(graph);
+    let over_budget = options.max_nodes.is_some_and(|max| nodes.len() > max);
+
+    // Nearest selected file or module of each node
+    let mut containers: HashMap<NodeId, NodeId> = HashMap::new();
+    if options.cluster_by_file || options.lift_to_files || over_budget {
+        let selected: HashSet<NodeId> = nodes.iter().map(|(id, _)| *id).collect();
+        for (id, _) in &nodes {
+            if let Some(container) = container(graph, *id)? {
+                if selected.contains(&container) {
+                    containers.insert(*id, container);
+                }
+            }
         }
     }
+    let units: HashSet<NodeId> = containers.values().copied().collect();
 
-    output.push_str("}\n");
+    // Each drawn node stands for itself; collapsed nodes for their container
+    let mut representative: HashMap<NodeId, NodeId> =
+        nodes.iter().map(|(id, _)| (*id, *id)).collect();
+    let mut collapsed: HashMap<NodeId, usize> = HashMap::new();
+    let mut omitted = 0;
+    if let (true, Some(max)) = (over_budget, options.max_nodes) {
+        let links = || {
+            edges
+                .iter()
+                .filter(|(_, edge)| edge.edge_type != EdgeType::Contains)
+                .map(|(_, edge)| (edge.source_id, edge.target_id))
+        };
+        let (kept_units, _) = trim(units.iter().copied(), links(), max);
+        let others = nodes
+            .iter()
+            .map(|(id, _)| *id)
+            .filter(|id| !units.contains(id));
+        let (kept_others, _) = trim(others, links(), max - kept_units.len());
+
+        for (id, _) in &nodes {
+            if kept_units.contains(id) || kept_others.contains(id) {
+                continue;
+            }
+            match containers.get(id).filter(|c| kept_units.contains(*c)) {
+                Some(&container) if !units.contains(id) => {
+                    representative.insert(*id, container);
+                    *collapsed.entry(container).or_default() += 1;
+                }
+                _ => {
+                    representative.remove(id);
+                    omitted += 1;
+                }
+            }
+        }
+    }
+    if options.lift_to_files {
+        for (id, _) in &nodes {
+            if units.contains(id) || representative.get(id) != Some(id) {
+                continue;
+            }
+            if let Some(&container) = containers.get(id) {
+                if representative.get(&container) == Some(&container) {
+                    representative.insert(*id, container);
+                    *collapsed.entry(container).or_default() += 1;
+                }
+            }
+        }
+    }
+    let drawn = |id: &NodeId| representative.get(id) == Some(id);
 
-    Ok(output)
+    let mut clusters: BTreeMap<Option<NodeId>, Vec<(NodeId, &Node)>> = BTreeMap::new();
+    for (id, node) in &nodes {
+        if !drawn(id) {
+            continue;
+        }
+        let cluster = if !options.cluster_by_file {
+            None
+        } else if units.contains(id) {
+            Some(*id)
+        } else {
+            containers.get(id).copied().filter(|c| drawn(c))
+        };
+        clusters.entry(cluster).or_default().push((*id, *node));
+    }
+    // A cluster holding only its own file is drawn as a plain node
+    let lone: Vec<NodeId> = clusters
+        .iter()
+        .filter(|(cluster, members)| cluster.is_some() && members.len() == 1)
+        .filter_map(|(cluster, _)| *cluster)
+        .collect();
+    for id in lone {
+        if let Some(members) = clusters.remove(&Some(id)) {
+            clusters.entry(None).or_default().extend(members);
+        }
+    }
+    if let Some(unclustered) = clusters.get_mut(&None) {
+        unclustered.sort_unstable_by_key(|(id, _)| *id);
+    }
+
+    let mut dot_edges: Vec<DotEdge> = Vec::new();
+    let mut merged: HashMap<(NodeId, NodeId, EdgeType), usize> = HashMap::new();
+    for (_, edge) in &edges {
+        let (Some(&source), Some(&target)) = (
+            representative.get(&edge.source_id),
+            representative.get(&edge.target_id),
+        ) else {
+            continue;
+        };
+        // Edges inside a collapsed node, and Contains edges shown by a cluster
+        let clustered = edge.edge_type == EdgeType::Contains
+            && clusters.contains_key(&Some(source))
+            && containers.get(&target) == Some(&source);
+        if source == target || clustered {
+            continue;
+        }
+        if options.aggregate_edges || options.lift_to_files {
+            if let Some(&i) = merged.get(&(source, target, edge.edge_type)) {
+                dot_edges[i].count += 1;
+                continue;
+            }
+            merged.insert((source, target, edge.edge_type), dot_edges.len());
+        }
+        dot_edges.push(DotEdge {
+            source,
+            target,
+            edge_type: edge.edge_type,
+            count: 1,
+        });
+    }
+
+    Ok(Layout {
+        clusters,
+        edges: dot_edges,
+        collapsed,
+        omitted,
+    })
+}
+
+/// Nearest file or module containing a node through `Contains` edges
+fn container(graph: &CodeGraph, node_id: NodeId) -> Result<Option<NodeId>> {
+    let mut current = node_id;
+    let mut visited = HashSet::new();
+    while visited.insert(current) {
+        let parent = graph
+            .incoming_edges(current)
+            .filter(|edge| edge.edge_type == EdgeType::Contains)
+            .map(|edge| edge.source_id)
+            .min();
+        let Some(parent) = parent else {
+            return Ok(None);
+        };
+        if matches!(
+            graph.get_node(parent)?.node_type,
+            NodeType::CodeFile | NodeType::Module
+        ) {
+            return Ok(Some(parent));
+        }
+        current = parent;
+    }
+    Ok(None)
 }
 
 /// Escape special characters for DOT labels
@@ -152,6 +430,11 @@ fn format_property_value(value: &crate::PropertyValue) -> String {
             .map(|i| i.to_string())
             .collect::<Vec<_>>()
             .join(","),
+        crate::PropertyValue::Vector(v) => v
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(","),
         crate::PropertyValue::Null => "null".to_string(),
     }
 }
@@ -0,0 +1,103 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Node and edge type filters for exporters.
+//!
+//! An [`ExportFilter`] restricts an export to chosen node and edge types. Edges
+//! are only exported when both endpoints are, so a filtered export is always a
+//! self-contained graph.
+
+use crate::graph::{Edge, EdgeId, NodeId};
+use crate::{CodeGraph, EdgeType, Node, NodeType};
+use std::collections::HashSet;
+
+/// Restricts an export to chosen node and edge types.
+///
+/// The default filter exports everything.
+///
+/// # Example
+///
+/// ```
+/// use codegraph::export::ExportFilter;
+/// use codegraph::{EdgeType, NodeType};
+///
+/// // Call graph between functions only
+/// let filter = ExportFilter::new()
+///     .node_types(&[NodeType::Function])
+///     .edge_types(&[EdgeType::Calls]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExportFilter {
+    node_types: Option<Vec<NodeType>>,
+    edge_types: Option<Vec<EdgeType>>,
+}
+
+impl ExportFilter {
+    /// Export every node and edge.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only export nodes of the given types.
+    pub fn node_types(mut self, node_types: &[NodeType]) -> Self {
+        self.node_types = Some(node_types.to_vec());
+        self
+    }
+
+    /// Only export edges of the given types.
+    pub fn edge_types(mut self, edge_types: &[EdgeType]) -> Self {
+        self.edge_types = Some(edge_types.to_vec());
+        self
+    }
+
+    /// Check whether a node passes the filter.
+    pub fn includes_node(&self, node: &Node) -> bool {
+        self.node_types
+            .as_ref()
+            .map_or(true, |types| types.contains(&node.node_type))
+    }
+
+    /// Check whether an edge's type passes the filter.
+    ///
+    /// Exporters additionally drop edges whose endpoints were filtered out.
+    pub fn includes_edge(&self, edge: &Edge) -> bool {
+        self.edge_types
+            .as_ref()
+            .map_or(true, |types| types.contains(&edge.edge_type))
+    }
+
+    /// Nodes and edges passing the filter, in ascending ID order.
+    pub(crate) fn select<'a>(&self, graph: &'a CodeGraph) -> Selection<'a> {
+        let mut nodes = sorted_nodes(graph);
+        nodes.retain(|(_, node)| self.includes_node(node));
+        let selected: HashSet<NodeId> = nodes.iter().map(|(id, _)| *id).collect();
+
+        let mut edges = sorted_edges(graph);
+        edges.retain(|(_, edge)| {
+            self.includes_edge(edge)
+                && selected.contains(&edge.source_id)
+                && selected.contains(&edge.target_id)
+        });
+        Selection { nodes, edges }
+    }
+}
+
+/// Nodes and edges chosen by an [`ExportFilter`]
+pub(crate) struct Selection<'a> {
+    pub(crate) nodes: Vec<(NodeId, &'a Node)>,
+    pub(crate) edges: Vec<(EdgeId, &'a Edge)>,
+}
+
+/// All nodes in ascending ID order
+pub(crate) fn sorted_nodes(graph: &CodeGraph) -> Vec<(NodeId, &Node)> {
+    let mut nodes: Vec<_> = graph.iter_nodes().collect();
+    nodes.sort_unstable_by_key(|(id, _)| *id);
+    nodes
+}
+
+/// All edges in ascending ID order
+pub(crate) fn sorted_edges(graph: &CodeGraph) -> Vec<(EdgeId, &Edge)> {
+    let mut edges: Vec<_> = graph.iter_edges().collect();
+    edges.sort_unstable_by_key(|(id, _)| *id);
+    edges
+}
@@ -0,0 +1,146 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! GEXF 1.2 format export for Gephi.
+//!
+//! Every property key becomes an `<attribute>` declaration typed after the
+//! values it holds (`boolean`, `long`, `double`, `string` or `liststring`), so
+//! metrics can drive Gephi's filters and ranking. Node and edge types are
+//! exported as the `node_type` and `edge_type` attributes, and properties with
+//! those names as `prop_node_type` and `prop_edge_type`; edges are also
+//! labelled with their type. List values are separated with `|`.
+
+use super::attributes::{declare, escape_xml, format_value, node_label, Attribute, AttributeType};
+use super::filter::{ExportFilter, Selection};
+use super::stream::{export_to_string, write_chunk};
+use crate::{CodeGraph, PropertyMap, Result};
+use std::io::Write;
+
+/// Export graph to GEXF format
+pub fn export_gexf(graph: &CodeGraph) -> Result<String> {
+    export_gexf_filtered(graph, &ExportFilter::new())
+}
+
+/// Export the nodes and edges passing `filter` to GEXF format
+pub fn export_gexf_filtered(graph: &CodeGraph, filter: &ExportFilter) -> Result<String> {
+    export_to_string(|buffer| export_gexf_filtered_to(graph, filter, buffer))
+}
+
+/// Stream graph in GEXF format to a writer
+pub fn export_gexf_to<W: Write>(graph: &CodeGraph, writer: W) -> Result<()> {
+    export_gexf_filtered_to(graph, &ExportFilter::new(), writer)
+}
+
+/// Stream the nodes and edges passing `filter` in GEXF format to a writer
+pub fn export_gexf_filtered_to<W: Write>(
+    graph: &CodeGraph,
+    filter: &ExportFilter,
+    mut writer: W,
+) -> Result<()> {
+    let Selection { nodes, edges } = filter.select(graph);
+    let node_attributes = declare(
+        nodes.iter().map(|(_, node)| &node.properties),
+        &["node_type"],
+    );
+    let edge_attributes = declare(
+        edges.iter().map(|(_, edge)| &edge.properties),
+        &["edge_type"],
+    );
+
+    let mut output = String::new();
+    output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str("<gexf xmlns=\"http://www.gexf.net/1.2draft\" version=\"1.2\">\n");
+    output.push_str("  <meta>\n    <creator>codegraph</creator>\n  </meta>\n");
+    output.push_str("  <graph mode=\"static\" defaultedgetype=\"directed\">\n");
+
+    // Attribute 0 is the element type, property attributes follow
+    push_attributes(&mut output, "node", "node_type", &node_attributes);
+    push_attributes(&mut output, "edge", "edge_type", &edge_attributes);
+
+    output.push_str("    <nodes>\n");
+    for (node_id, node) in &nodes {
+        output.push_str(&format!(
+            "      <node id=\"{}\" label=\"{}\">\n",
+            node_id,
+            escape_xml(&node_label(*node_id, node))
+        ));
+        push_values(
+            &mut output,
+            &format!("{:?}", node.node_type),
+            &node_attributes,
+            &node.properties,
+        );
+        output.push_str("      </node>\n");
+        write_chunk(&mut writer, &mut output)?;
+    }
+    output.push_str("    </nodes>\n");
+
+    output.push_str("    <edges>\n");
+    for (edge_id, edge) in &edges {
+        let edge_type = format!("{:?}", edge.edge_type);
+        output.push_str(&format!(
+            "      <edge id=\"{}\" source=\"{}\" target=\"{}\" label=\"{}\">\n",
+            edge_id, edge.source_id, edge.target_id, edge_type
+        ));
+        push_values(&mut output, &edge_type, &edge_attributes, &edge.properties);
+        output.push_str("      </edge>\n");
+        write_chunk(&mut writer, &mut output)?;
+    }
+    output.push_str("    </edges>\n");
+
+    output.push_str("  </graph>\n");
+    output.push_str("</gexf>\n");
+    write_chunk(&mut writer, &mut output)
+}
+
+fn type_name(attr_type: AttributeType) -> &'static str {
+    match attr_type {
+        AttributeType::Boolean => "boolean",
+        AttributeType::Long => "long",
+        AttributeType::Double => "double",
+        AttributeType::String => "string",
+        AttributeType::List => "liststring",
+    }
+}
+
+fn push_attributes(output: &mut String, class: &str, type_title: &str, attributes: &[Attribute]) {
+    output.push_str(&format!("    <attributes class=\"{class}\">\n"));
+    output.push_str(&format!(
+        "      <attribute id=\"0\" title=\"{type_title}\" type=\"string\"/>\n"
+    ));
+    for (i, attr) in attributes.iter().enumerate() {
+        output.push_str(&format!(
+            "      <attribute id=\"{}\" title=\"{}\" type=\"{}\"/>\n",
+            i + 1,
+            escape_xml(&attr.title),
+            type_name(attr.attr_type)
+        ));
+    }
+    output.push_str("    </attributes>\n");
+}
+
+/// Write the element type and every non-null property as `<attvalue>`s
+fn push_values(
+    output: &mut String,
+    element_type: &str,
+    attributes: &[Attribute],
+    properties: &PropertyMap,
+) {
+    output.push_str("        <attvalues>\n");
+    output.push_str(&format!(
+        "          <attvalue for=\"0\" value=\"{element_type}\"/>\n"
+    ));
+    for (i, attr) in attributes.iter().enumerate() {
+        if let Some(value) = properties
+            .get(&attr.name)
+            .and_then(|v| format_value(v, "|"))
+        {
+            output.push_str(&format!(
+                "          <attvalue for=\"{}\" value=\"{}\"/>\n",
+                i + 1,
+                escape_xml(&value)
+            ));
+        }
+    }
+    output.push_str("        </attvalues>\n");
+}
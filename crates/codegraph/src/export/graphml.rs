@@ -0,0 +1,160 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! GraphML format export for yEd, Cytoscape and Gephi.
+//!
+//! Every property key becomes a `<key>` declaration typed after the values it
+//! holds (`boolean`, `long`, `double` or `string`), so tools can filter and size
+//! by metrics. Node and edge types are exported as the `node_type` and
+//! `edge_type` attributes and node names as `label`; properties with those names
+//! are declared as `prop_label` and so on. GraphML has no list type; list
+//! properties are written as JSON arrays.
+
+use super::attributes::{declare, escape_xml, format_value, node_label, Attribute, AttributeType};
+use super::filter::{ExportFilter, Selection};
+use super::stream::{export_to_string, write_chunk};
+use crate::{CodeGraph, PropertyMap, PropertyValue, Result};
+use std::io::Write;
+
+/// Export graph to GraphML format
+pub fn export_graphml(graph: &CodeGraph) -> Result<String> {
+    export_graphml_filtered(graph, &ExportFilter::new())
+}
+
+/// Export the nodes and edges passing `filter` to GraphML format
+pub fn export_graphml_filtered(graph: &CodeGraph, filter: &ExportFilter) -> Result<String> {
+    export_to_string(|buffer| export_graphml_filtered_to(graph, filter, buffer))
+}
+
+/// Stream graph in GraphML format to a writer
+pub fn export_graphml_to<W: Write>(graph: &CodeGraph, writer: W) -> Result<()> {
+    export_graphml_filtered_to(graph, &ExportFilter::new(), writer)
+}
+
+/// Stream the nodes and edges passing `filter` in GraphML format to a writer
+pub fn export_graphml_filtered_to<W: Write>(
+    graph: &CodeGraph,
+    filter: &ExportFilter,
+    mut writer: W,
+) -> Result<()> {
+    let Selection { nodes, edges } = filter.select(graph);
+    let node_attributes = declare(
+        nodes.iter().map(|(_, node)| &node.properties),
+        &["label", "node_type"],
+    );
+    let edge_attributes = declare(
+        edges.iter().map(|(_, edge)| &edge.properties),
+        &["edge_type"],
+    );
+
+    let mut output = String::new();
+    output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    output.push_str(
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+         xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+         http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+    );
+
+    // Key declarations: built-in attributes first, then one per property key
+    push_key(&mut output, "label", "node", "label", "string");
+    push_key(&mut output, "node_type", "node", "node_type", "string");
+    for (i, attr) in node_attributes.iter().enumerate() {
+        let id = format!("n{i}");
+        push_key(
+            &mut output,
+            &id,
+            "node",
+            &attr.title,
+            type_name(attr.attr_type),
+        );
+    }
+    push_key(&mut output, "edge_type", "edge", "edge_type", "string");
+    for (i, attr) in edge_attributes.iter().enumerate() {
+        let id = format!("e{i}");
+        push_key(
+            &mut output,
+            &id,
+            "edge",
+            &attr.title,
+            type_name(attr.attr_type),
+        );
+    }
+
+    output.push_str("  <graph id=\"codegraph\" edgedefault=\"directed\">\n");
+
+    for (node_id, node) in &nodes {
+        output.push_str(&format!("    <node id=\"n{node_id}\">\n"));
+        push_data(&mut output, "label", &node_label(*node_id, node));
+        push_data(&mut output, "node_type", &format!("{:?}", node.node_type));
+        push_properties(&mut output, "n", &node_attributes, &node.properties);
+        output.push_str("    </node>\n");
+        write_chunk(&mut writer, &mut output)?;
+    }
+
+    for (edge_id, edge) in &edges {
+        output.push_str(&format!(
+            "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">\n",
+            edge_id, edge.source_id, edge.target_id
+        ));
+        push_data(&mut output, "edge_type", &format!("{:?}", edge.edge_type));
+        push_properties(&mut output, "e", &edge_attributes, &edge.properties);
+        output.push_str("    </edge>\n");
+        write_chunk(&mut writer, &mut output)?;
+    }
+
+    output.push_str("  </graph>\n");
+    output.push_str("</graphml>\n");
+    write_chunk(&mut writer, &mut output)
+}
+
+fn type_name(attr_type: AttributeType) -> &'static str {
+    match attr_type {
+        AttributeType::Boolean => "boolean",
+        AttributeType::Long => "long",
+        AttributeType::Double => "double",
+        AttributeType::String | AttributeType::List => "string",
+    }
+}
+
+fn push_key(output: &mut String, id: &str, domain: &str, name: &str, attr_type: &str) {
+    output.push_str(&format!(
+        "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+        id,
+        domain,
+        escape_xml(name),
+        attr_type
+    ));
+}
+
+fn push_data(output: &mut String, key: &str, value: &str) {
+    output.push_str(&format!(
+        "      <data key=\"{}\">{}</data>\n",
+        key,
+        escape_xml(value)
+    ));
+}
+
+/// Write one `<data>` element per non-null property, keyed `{prefix}{index}`
+fn push_properties(
+    output: &mut String,
+    prefix: &str,
+    attributes: &[Attribute],
+    properties: &PropertyMap,
+) {
+    for (i, attr) in attributes.iter().enumerate() {
+        if let Some(value) = properties.get(&attr.name).and_then(format_graphml_value) {
+            push_data(output, &format!("{prefix}{i}"), &value);
+        }
+    }
+}
+
+/// Format a property value, writing lists as JSON arrays
+fn format_graphml_value(value: &PropertyValue) -> Option<String> {
+    match value {
+        PropertyValue::StringList(v) => serde_json::to_string(v).ok(),
+        PropertyValue::IntList(v) => serde_json::to_string(v).ok(),
+        PropertyValue::Vector(v) => serde_json::to_string(v).ok(),
+        _ => format_value(value, ","),
+    }
+}
@@ -0,0 +1,243 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-contained interactive HTML report.
+//!
+//! Produces a single HTML file with the report data embedded as JSON and a
+//! small renderer, so it opens offline in any browser:
+//! - a searchable list of symbols
+//! - per-file summaries: symbol counts, complexity and dependencies
+//! - a zoomable dependency graph between files (see [`component_model`])
+//! - the complexity hotspots table
+//!
+//! Complexity comes from the `complexity` and `complexity_grade` properties
+//! written by the parsers; graphs without them simply have no hotspots.
+
+use super::attributes::{escape_xml, node_label};
+use super::diagram::{component_model, path_label, DiagramOptions};
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::write_error;
+use crate::graph::NodeId;
+use crate::{helpers, CodeGraph, EdgeType, NodeType, Result};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Write;
+
+const TEMPLATE: &str = include_str!("html_report.html");
+
+/// Options for the HTML report
+#[derive(Debug, Clone)]
+pub struct HtmlOptions {
+    /// Report title
+    pub title: String,
+    /// Maximum number of files in the dependency graph; the least connected
+    /// are left out
+    pub max_graph_nodes: usize,
+    /// Number of functions in the hotspots table
+    pub max_hotspots: usize,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            title: "Code Graph Report".to_string(),
+            max_graph_nodes: 150,
+            max_hotspots: 25,
+        }
+    }
+}
+
+/// Export graph as a self-contained HTML report
+pub fn export_html(graph: &CodeGraph, options: &HtmlOptions) -> Result<String> {
+    let data = report_data(graph, options)?;
+    let json = serde_json::to_string(&data)
+        .map_err(|e| crate::GraphError::serialization("Failed to serialize report", Some(e)))?;
+    // `<` never appears literally, so the data cannot close its script element
+    let json = json.replace('<', "\\u003c");
+
+    let (head, tail) = TEMPLATE
+        .split_once("{{DATA}}")
+        .expect("report template has a data placeholder");
+    let head = head.replace("{{TITLE}}", &escape_xml(&options.title));
+    Ok(format!("{head}{json}{tail}"))
+}
+
+/// Write an HTML report to a writer
+///
+/// The embedded data is built in memory before being written.
+pub fn export_html_to<W: Write>(
+    graph: &CodeGraph,
+    options: &HtmlOptions,
+    mut writer: W,
+) -> Result<()> {
+    let report = export_html(graph, options)?;
+    writer.write_all(report.as_bytes()).map_err(write_error)
+}
+
+#[derive(Default)]
+struct FileSummary {
+    path: String,
+    language: String,
+    lines: Option<i64>,
+    symbols: usize,
+    functions: usize,
+    classes: usize,
+    complexity: i64,
+    max_complexity: i64,
+    depends_on: BTreeSet<NodeId>,
+    depended_on_by: BTreeSet<NodeId>,
+}
+
+fn report_data(graph: &CodeGraph, options: &HtmlOptions) -> Result<Value> {
+    let nodes = sorted_nodes(graph);
+    let mut files: BTreeMap<NodeId, FileSummary> = BTreeMap::new();
+    for (id, node) in &nodes {
+        if node.node_type == NodeType::CodeFile {
+            let summary = FileSummary {
+                path: path_label(*id, node),
+                language: node
+                    .properties
+                    .get_string("language")
+                    .unwrap_or_default()
+                    .to_string(),
+                lines: node.properties.get_int("line_count"),
+                ..FileSummary::default()
+            };
+            files.insert(*id, summary);
+        }
+    }
+
+    let mut owner = BTreeMap::new();
+    let mut symbols = Vec::new();
+    let mut hotspots = Vec::new();
+    for (id, node) in &nodes {
+        let file = match node.node_type {
+            NodeType::CodeFile => Some(*id),
+            _ => helpers::containing_file(graph, *id)?,
+        };
+        if let Some(file) = file {
+            owner.insert(*id, file);
+        }
+        if node.node_type == NodeType::CodeFile {
+            continue;
+        }
+
+        let path = file
+            .and_then(|f| files.get(&f))
+            .map(|f| f.path.clone())
+            .unwrap_or_default();
+        let line = node.properties.get_int("line_start");
+        let complexity = node.properties.get_int("complexity");
+        symbols.push(json!({
+            "name": node_label(*id, node),
+            "type": node.node_type.to_string(),
+            "file": path,
+            "line": line,
+        }));
+
+        if let Some(summary) = file.and_then(|f| files.get_mut(&f)) {
+            summary.symbols += 1;
+            match node.node_type {
+                NodeType::Function => summary.functions += 1,
+                NodeType::Class | NodeType::Interface => summary.classes += 1,
+                _ => {}
+            }
+            if let Some(complexity) = complexity {
+                summary.complexity += complexity;
+                summary.max_complexity = summary.max_complexity.max(complexity);
+            }
+        }
+        if let (NodeType::Function, Some(complexity)) = (node.node_type, complexity) {
+            hotspots.push((complexity, *id, path, line, node));
+        }
+    }
+
+    for (_, edge) in sorted_edges(graph) {
+        if edge.edge_type == EdgeType::Contains {
+            continue;
+        }
+        if let (Some(&source), Some(&target)) =
+            (owner.get(&edge.source_id), owner.get(&edge.target_id))
+        {
+            if source != target {
+                if let Some(file) = files.get_mut(&source) {
+                    file.depends_on.insert(target);
+                }
+                if let Some(file) = files.get_mut(&target) {
+                    file.depended_on_by.insert(source);
+                }
+            }
+        }
+    }
+
+    hotspots.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    let hotspots: Vec<Value> = hotspots
+        .into_iter()
+        .take(options.max_hotspots)
+        .map(|(complexity, id, file, line, node)| {
+            json!({
+                "name": node_label(id, node),
+                "file": file,
+                "line": line,
+                "complexity": complexity,
+                "grade": node.properties.get_string("complexity_grade"),
+            })
+        })
+        .collect();
+
+    let files: Vec<Value> = files
+        .values()
+        .map(|file| {
+            json!({
+                "path": file.path,
+                "language": file.language,
+                "lines": file.lines,
+                "symbols": file.symbols,
+                "functions": file.functions,
+                "classes": file.classes,
+                "complexity": file.complexity,
+                "maxComplexity": file.max_complexity,
+                "dependsOn": file.depends_on.len(),
+                "dependedOnBy": file.depended_on_by.len(),
+            })
+        })
+        .collect();
+
+    let model = component_model(
+        graph,
+        &DiagramOptions {
+            max_nodes: options.max_graph_nodes,
+            ..DiagramOptions::default()
+        },
+    )?;
+    let graph_nodes: Vec<Value> = model
+        .groups
+        .iter()
+        .flat_map(|(group, nodes)| {
+            let group = group.as_ref().map(|g| g.label.clone()).unwrap_or_default();
+            nodes.iter().map(move |node| {
+                json!({
+                    "id": node.id,
+                    "label": node.label,
+                    "group": group,
+                    "type": node.node_type.to_string(),
+                })
+            })
+        })
+        .collect();
+    let graph_edges: Vec<Value> = model
+        .edges
+        .iter()
+        .map(|edge| json!({"source": edge.source, "target": edge.target, "label": edge.label}))
+        .collect();
+
+    Ok(json!({
+        "title": options.title,
+        "version": crate::metadata::VERSION,
+        "stats": {"nodes": graph.node_count(), "edges": graph.edge_count()},
+        "symbols": symbols,
+        "files": files,
+        "graph": {"nodes": graph_nodes, "edges": graph_edges, "omitted": model.omitted},
+        "hotspots": hotspots,
+    }))
+}
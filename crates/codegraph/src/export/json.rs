@@ -4,43 +4,24 @@
 //! JSON format export for D3.js and web visualization tools.
 //!
 //! Generates JSON with "nodes" and "links" arrays compatible with D3.js force-directed layouts.
+//! The same format reads back with [`import_json`], so it doubles as a portable backup
+//! and interchange format. Property types follow from the JSON values (integers, floats,
+//! strings, booleans, null, and arrays of strings, integers or floats); empty lists, whose
+//! type the values can't tell, are recorded in a `property_types` object next to
+//! `properties`. Non-finite floats are written as `null`; inside a vector they read
+//! back as NaN.
 
-use crate::{CodeGraph, Node, Result};
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::{export_to_string, write_error};
+use crate::graph::{Edge, NodeType};
+use crate::{CodeGraph, EdgeType, GraphError, Node, PropertyMap, PropertyValue, Result};
 use serde_json::{json, Value};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
 
 /// Export graph to D3.js-compatible JSON format
 pub fn export_json(graph: &CodeGraph) -> Result<String> {
-    let mut nodes_array = Vec::new();
-    let mut links_array = Vec::new();
-
-    // Export all nodes
-    for node_id in 0..graph.node_count() as u64 {
-        if let Ok(node) = graph.get_node(node_id) {
-            nodes_array.push(node_to_json(node_id, node));
-        }
-    }
-
-    // Export all edges
-    for edge_id in 0..graph.edge_count() as u64 {
-        if let Ok(edge) = graph.get_edge(edge_id) {
-            links_array.push(json!({
-                "id": edge_id,
-                "source": edge.source_id,
-                "target": edge.target_id,
-                "type": format!("{:?}", edge.edge_type),
-                "properties": properties_to_json(&edge.properties),
-            }));
-        }
-    }
-
-    let result = json!({
-        "nodes": nodes_array,
-        "links": links_array,
-    });
-
-    // serde_json::to_string_pretty should never fail for our data structures
-    Ok(serde_json::to_string_pretty(&result).expect("Failed to serialize JSON"))
+    export_to_string(|buffer| export_json_to(graph, buffer))
 }
 
 /// Export filtered subset of graph to JSON
@@ -49,55 +30,101 @@ pub fn export_json_filtered(
     node_filter: impl Fn(&Node) -> bool,
     include_edges: bool,
 ) -> Result<String> {
-    let mut nodes_array = Vec::new();
+    export_to_string(|buffer| export_json_filtered_to(graph, node_filter, include_edges, buffer))
+}
+
+/// Stream graph in D3.js-compatible JSON format to a writer, one node or link per line
+pub fn export_json_to<W: Write>(graph: &CodeGraph, writer: W) -> Result<()> {
+    export_json_filtered_to(graph, |_| true, true, writer)
+}
+
+/// Stream filtered subset of graph as JSON to a writer
+pub fn export_json_filtered_to<W: Write>(
+    graph: &CodeGraph,
+    node_filter: impl Fn(&Node) -> bool,
+    include_edges: bool,
+    mut writer: W,
+) -> Result<()> {
     let mut filtered_ids = HashSet::new();
 
     // Export filtered nodes
-    for node_id in 0..graph.node_count() as u64 {
-        if let Ok(node) = graph.get_node(node_id) {
-            if node_filter(node) {
-                nodes_array.push(node_to_json(node_id, node));
-                filtered_ids.insert(node_id);
-            }
+    writer
+        .write_all(b"{\n  \"nodes\": [")
+        .map_err(write_error)?;
+    let mut first = true;
+    for (node_id, node) in sorted_nodes(graph) {
+        if node_filter(node) {
+            write_element(&mut writer, &node_to_json(node_id, node), &mut first)?;
+            filtered_ids.insert(node_id);
         }
     }
 
     // Export edges if requested
-    let mut links_array = Vec::new();
+    writer
+        .write_all(b"\n  ],\n  \"links\": [")
+        .map_err(write_error)?;
     if include_edges {
-        for edge_id in 0..graph.edge_count() as u64 {
-            if let Ok(edge) = graph.get_edge(edge_id) {
-                // Only include edges between filtered nodes
-                if filtered_ids.contains(&edge.source_id) && filtered_ids.contains(&edge.target_id)
-                {
-                    links_array.push(json!({
-                        "id": edge_id,
-                        "source": edge.source_id,
-                        "target": edge.target_id,
-                        "type": format!("{:?}", edge.edge_type),
-                        "properties": properties_to_json(&edge.properties),
-                    }));
-                }
+        let mut first = true;
+        for (edge_id, edge) in sorted_edges(graph) {
+            // Only include edges between filtered nodes
+            if filtered_ids.contains(&edge.source_id) && filtered_ids.contains(&edge.target_id) {
+                write_element(&mut writer, &edge_to_json(edge_id, edge), &mut first)?;
             }
         }
     }
+    writer.write_all(b"\n  ]\n}\n").map_err(write_error)?;
 
-    let result = json!({
-        "nodes": nodes_array,
-        "links": links_array,
-    });
+    Ok(())
+}
 
-    // serde_json::to_string_pretty should never fail for our data structures
-    Ok(serde_json::to_string_pretty(&result).expect("Failed to serialize JSON"))
+/// Write one array element on its own line
+fn write_element<W: Write>(writer: &mut W, value: &Value, first: &mut bool) -> Result<()> {
+    let separator: &[u8] = if *first { b"\n    " } else { b",\n    " };
+    *first = false;
+    writer.write_all(separator).map_err(write_error)?;
+    serde_json::to_writer(&mut *writer, value)
+        .map_err(|e| GraphError::serialization("Failed to write JSON export", Some(e)))
 }
 
 /// Convert node to JSON object
 fn node_to_json(node_id: u64, node: &Node) -> Value {
-    json!({
+    let mut value = json!({
         "id": node_id,
         "type": format!("{:?}", node.node_type),
         "properties": properties_to_json(&node.properties),
-    })
+    });
+    add_property_types(&mut value, &node.properties);
+    value
+}
+
+/// Convert edge to JSON link object
+fn edge_to_json(edge_id: u64, edge: &Edge) -> Value {
+    let mut value = json!({
+        "id": edge_id,
+        "source": edge.source_id,
+        "target": edge.target_id,
+        "type": format!("{:?}", edge.edge_type),
+        "properties": properties_to_json(&edge.properties),
+    });
+    add_property_types(&mut value, &edge.properties);
+    value
+}
+
+/// Record the type of empty list properties, which the JSON values can't carry
+fn add_property_types(value: &mut Value, props: &PropertyMap) {
+    let mut types = serde_json::Map::new();
+    for (key, prop) in props.iter() {
+        let name = match prop {
+            PropertyValue::StringList(v) if v.is_empty() => "StringList",
+            PropertyValue::IntList(v) if v.is_empty() => "IntList",
+            PropertyValue::Vector(v) if v.is_empty() => "Vector",
+            _ => continue,
+        };
+        types.insert(key.clone(), json!(name));
+    }
+    if !types.is_empty() {
+        value["property_types"] = Value::Object(types);
+    }
 }
 
 /// Convert PropertyMap to JSON object
@@ -112,6 +139,7 @@ fn properties_to_json(props: &crate::PropertyMap) -> Value {
             crate::PropertyValue::Bool(b) => json!(b),
             crate::PropertyValue::StringList(v) => json!(v),
             crate::PropertyValue::IntList(v) => json!(v),
+            crate::PropertyValue::Vector(v) => json!(v),
             crate::PropertyValue::Null => json!(null),
         };
         obj.insert(key.clone(), json_value);
@@ -120,6 +148,181 @@ fn properties_to_json(props: &crate::PropertyMap) -> Value {
     Value::Object(obj)
 }
 
+/// Import a graph from the JSON written by [`export_json`].
+///
+/// Node and edge IDs are preserved, and nodes added to the imported graph later
+/// are numbered after the highest imported ID. To import into an existing graph
+/// with fresh IDs, use [`CodeGraph::import_json_into`].
+///
+/// # Errors
+///
+/// Returns [`GraphError::Serialization`] if the input is not valid JSON or does not
+/// follow the schema: unknown node or edge types, missing or duplicate IDs, links
+/// to missing nodes, or property values that don't map to a [`PropertyValue`].
+pub fn import_json(json: &str) -> Result<CodeGraph> {
+    let value = serde_json::from_str(json)
+        .map_err(|e| GraphError::serialization("Invalid JSON graph", Some(e)))?;
+    graph_from_json(&value)
+}
+
+/// Import a graph from a reader yielding the JSON written by [`export_json`].
+///
+/// See [`import_json`] for ID handling and errors.
+pub fn import_json_reader<R: Read>(reader: R) -> Result<CodeGraph> {
+    let value = serde_json::from_reader(reader)
+        .map_err(|e| GraphError::serialization("Invalid JSON graph", Some(e)))?;
+    graph_from_json(&value)
+}
+
+fn graph_from_json(value: &Value) -> Result<CodeGraph> {
+    let root = value
+        .as_object()
+        .ok_or_else(|| schema_error("top level", "expected an object"))?;
+    let node_values = root
+        .get("nodes")
+        .and_then(Value::as_array)
+        .ok_or_else(|| schema_error("top level", "expected a \"nodes\" array"))?;
+    let link_values = match root.get("links") {
+        Some(links) => links
+            .as_array()
+            .ok_or_else(|| schema_error("top level", "\"links\" must be an array"))?
+            .as_slice(),
+        None => &[],
+    };
+
+    let mut nodes = Vec::with_capacity(node_values.len());
+    let mut node_ids = HashSet::with_capacity(node_values.len());
+    for (i, value) in node_values.iter().enumerate() {
+        let at = format!("nodes[{i}]");
+        let id = require_id(value, "id", &at)?;
+        let node_type: NodeType = require_str(value, "type", &at)?
+            .parse()
+            .map_err(|_| schema_error(&at, "unknown node type"))?;
+        if !node_ids.insert(id) {
+            return Err(schema_error(&at, &format!("duplicate node id {id}")));
+        }
+        nodes.push(Node::new(id, node_type, properties_from_json(value, &at)?));
+    }
+
+    let mut edges = Vec::with_capacity(link_values.len());
+    let mut edge_ids = HashSet::with_capacity(link_values.len());
+    for (i, value) in link_values.iter().enumerate() {
+        let at = format!("links[{i}]");
+        let id = require_id(value, "id", &at)?;
+        let source = require_id(value, "source", &at)?;
+        let target = require_id(value, "target", &at)?;
+        let edge_type: EdgeType = require_str(value, "type", &at)?
+            .parse()
+            .map_err(|_| schema_error(&at, "unknown edge type"))?;
+        for endpoint in [source, target] {
+            if !node_ids.contains(&endpoint) {
+                return Err(schema_error(&at, &format!("links missing node {endpoint}")));
+            }
+        }
+        if !edge_ids.insert(id) {
+            return Err(schema_error(&at, &format!("duplicate link id {id}")));
+        }
+        let properties = properties_from_json(value, &at)?;
+        edges.push(Edge::new(id, source, target, edge_type, properties));
+    }
+
+    let next_node = node_ids.iter().max().map_or(0, |&id| id + 1);
+    let next_edge = edge_ids.iter().max().map_or(0, |&id| id + 1);
+    CodeGraph::from_parts(nodes, edges, (next_node, next_edge))
+}
+
+fn schema_error(at: &str, message: &str) -> GraphError {
+    GraphError::Serialization {
+        message: format!("Invalid JSON graph at {at}: {message}"),
+        source: None,
+    }
+}
+
+fn require_id(value: &Value, key: &str, at: &str) -> Result<u64> {
+    value
+        .get(key)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| schema_error(at, &format!("expected a non-negative integer \"{key}\"")))
+}
+
+fn require_str<'a>(value: &'a Value, key: &str, at: &str) -> Result<&'a str> {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| schema_error(at, &format!("expected a string \"{key}\"")))
+}
+
+/// Read the `properties` object of a node or link, typed by `property_types`
+fn properties_from_json(value: &Value, at: &str) -> Result<PropertyMap> {
+    let Some(properties) = value.get("properties") else {
+        return Ok(PropertyMap::new());
+    };
+    let properties = properties
+        .as_object()
+        .ok_or_else(|| schema_error(at, "\"properties\" must be an object"))?;
+    let types: HashMap<&str, &str> = match value.get("property_types") {
+        Some(types) => types
+            .as_object()
+            .ok_or_else(|| schema_error(at, "\"property_types\" must be an object"))?
+            .iter()
+            .filter_map(|(key, name)| Some((key.as_str(), name.as_str()?)))
+            .collect(),
+        None => HashMap::new(),
+    };
+
+    let mut props = PropertyMap::new();
+    for (key, value) in properties {
+        let prop = property_from_json(value, types.get(key.as_str()).copied())
+            .ok_or_else(|| schema_error(at, &format!("unsupported value for property '{key}'")))?;
+        props.insert(key.clone(), prop);
+    }
+    Ok(props)
+}
+
+/// Convert a JSON value back to the property value it was exported from
+fn property_from_json(value: &Value, type_hint: Option<&str>) -> Option<PropertyValue> {
+    Some(match value {
+        Value::Null => PropertyValue::Null,
+        Value::Bool(b) => PropertyValue::Bool(*b),
+        Value::String(s) => PropertyValue::String(s.clone()),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => PropertyValue::Int(i),
+            None => PropertyValue::Float(n.as_f64()?),
+        },
+        Value::Array(items) => match type_hint {
+            Some("StringList") => PropertyValue::StringList(string_list(items)?),
+            Some("IntList") => PropertyValue::IntList(int_list(items)?),
+            Some("Vector") => PropertyValue::Vector(vector(items)?),
+            Some(_) => return None,
+            None if items.iter().all(Value::is_string) => {
+                PropertyValue::StringList(string_list(items)?)
+            }
+            None if items.iter().all(Value::is_i64) => PropertyValue::IntList(int_list(items)?),
+            None => PropertyValue::Vector(vector(items)?),
+        },
+        Value::Object(_) => return None,
+    })
+}
+
+fn string_list(items: &[Value]) -> Option<Vec<String>> {
+    items.iter().map(|v| v.as_str().map(String::from)).collect()
+}
+
+fn int_list(items: &[Value]) -> Option<Vec<i64>> {
+    items.iter().map(Value::as_i64).collect()
+}
+
+/// Floats of a vector; `null` items were non-finite and become NaN
+fn vector(items: &[Value]) -> Option<Vec<f32>> {
+    items
+        .iter()
+        .map(|v| match v {
+            Value::Null => Some(f32::NAN),
+            v => v.as_f64().map(|f| f as f32),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -136,4 +339,35 @@ mod tests {
         assert_eq!(json["name"], "test");
         assert_eq!(json["count"], 42);
     }
+
+    #[test]
+    fn test_property_from_json() {
+        assert_eq!(
+            property_from_json(&json!(3), None),
+            Some(PropertyValue::Int(3))
+        );
+        assert_eq!(
+            property_from_json(&json!(3.0), None),
+            Some(PropertyValue::Float(3.0))
+        );
+        assert_eq!(
+            property_from_json(&json!([1, 2]), None),
+            Some(PropertyValue::IntList(vec![1, 2]))
+        );
+        assert_eq!(
+            property_from_json(&json!([1.0, 0.5]), None),
+            Some(PropertyValue::Vector(vec![1.0, 0.5]))
+        );
+        let Some(PropertyValue::Vector(v)) = property_from_json(&json!([null, 0.5]), None) else {
+            panic!("expected a vector");
+        };
+        assert!(v[0].is_nan());
+        assert_eq!(v[1], 0.5);
+        assert_eq!(
+            property_from_json(&json!([]), Some("IntList")),
+            Some(PropertyValue::IntList(vec![]))
+        );
+        assert_eq!(property_from_json(&json!(["a", 1]), None), None);
+        assert_eq!(property_from_json(&json!({"a": 1}), None), None);
+    }
 }
@@ -0,0 +1,461 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! LSIF code intelligence index export.
+//!
+//! Writes an [LSIF 0.5](https://microsoft.github.io/language-server-protocol/specifications/lsif/0.5.0/specification/)
+//! dump (one JSON vertex or edge per line) that code-navigation tools load to
+//! answer go-to-definition, find-references and hover without a language
+//! server:
+//! - a document per file path
+//! - a definition per node with a `line_start`, covering its `name` from
+//!   `col_start` (column 0 when unknown); files are defined at their first
+//!   character
+//! - references from `Calls`, `References` and `Imports` edges, placed on the
+//!   whole line given by the edge's `call_site_line` or `line` property (the
+//!   parser mappers write `line` on `Imports` edges); edges without a line have
+//!   no location and are skipped, and references on the same line share one
+//!   range, which resolves to the first symbol
+//! - imported nodes without a location, such as the module nodes parsers add
+//!   for external packages, as symbols with references but no definition
+//! - hovers showing the `signature` (or name) and the `doc` property
+//!
+//! Lines are 1-indexed in the graph; nodes and edges on lines below 1 are
+//! skipped.
+//!
+//! Columns are taken as-is from the graph, which stores byte offsets; LSIF
+//! expects UTF-16 code units, so positions after non-ASCII text on the same
+//! line are approximate.
+
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::write_error;
+use crate::graph::NodeId;
+use crate::{helpers, CodeGraph, EdgeType, Node, NodeType, Result};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Export a graph as an LSIF dump file
+///
+/// Relative `path` properties are resolved against `project_root` to form
+/// document URIs.
+pub fn export_lsif(graph: &CodeGraph, project_root: &Path, path: &Path) -> Result<()> {
+    let file = File::create(path).map_err(|e| crate::GraphError::Storage {
+        message: format!("Failed to create LSIF file: {}", path.display()),
+        source: Some(Box::new(e)),
+    })?;
+    let mut writer = BufWriter::new(file);
+    export_lsif_to(graph, project_root, &mut writer)?;
+    writer.flush().map_err(write_error)
+}
+
+/// Stream a graph as an LSIF dump to a writer
+pub fn export_lsif_to<W: Write>(graph: &CodeGraph, project_root: &Path, writer: W) -> Result<()> {
+    let index = Index::build(graph)?;
+    let root = project_root.to_string_lossy();
+    let root = root.trim_end_matches('/');
+    let mut out = Emitter { writer, next_id: 0 };
+
+    out.vertex(
+        "metaData",
+        json!({
+            "version": "0.5.0",
+            "projectRoot": file_uri(root, ""),
+            "positionEncoding": "utf-16",
+            "toolInfo": {"name": "codegraph", "version": crate::metadata::VERSION},
+        }),
+    )?;
+    let project = out.vertex("project", json!({"kind": index.project_kind()}))?;
+    out.event("begin", "project", project)?;
+
+    // Result sets are project-wide; ranges attach to them from their documents
+    let mut results = BTreeMap::new();
+    for (node_id, symbol) in &index.symbols {
+        let result_set = out.vertex("resultSet", json!({}))?;
+        let definitions = out.vertex("definitionResult", json!({}))?;
+        out.edge(
+            "textDocument/definition",
+            result_set,
+            definitions,
+            json!({}),
+        )?;
+        let references = out.vertex("referenceResult", json!({}))?;
+        out.edge("textDocument/references", result_set, references, json!({}))?;
+        if let Some(hover) = &symbol.hover {
+            let hover = out.vertex(
+                "hoverResult",
+                json!({"result": {"contents": {"kind": "markdown", "value": hover}}}),
+            )?;
+            out.edge("textDocument/hover", result_set, hover, json!({}))?;
+        }
+        results.insert(
+            *node_id,
+            Results {
+                result_set,
+                definitions,
+                references,
+            },
+        );
+    }
+
+    let mut documents = Vec::new();
+    for (path, document) in &index.documents {
+        let id = out.vertex(
+            "document",
+            json!({"uri": file_uri(root, path), "languageId": document.language}),
+        )?;
+        out.event("begin", "document", id)?;
+        documents.push(id);
+
+        let mut ranges = Vec::new();
+        let mut reference_ranges: BTreeMap<Range, u64> = BTreeMap::new();
+        let mut definitions: BTreeMap<NodeId, Vec<u64>> = BTreeMap::new();
+        let mut references: BTreeMap<NodeId, Vec<u64>> = BTreeMap::new();
+        for (symbol, range, is_definition) in &document.ranges {
+            // Edges carry no column, so references on one line share a range
+            let range_id = match reference_ranges.get(range) {
+                Some(&range_id) if !is_definition => range_id,
+                _ => {
+                    let range_id = out.vertex("range", range.to_json())?;
+                    out.edge("next", range_id, results[symbol].result_set, json!({}))?;
+                    ranges.push(range_id);
+                    if !is_definition {
+                        reference_ranges.insert(*range, range_id);
+                    }
+                    range_id
+                }
+            };
+            let target = if *is_definition {
+                &mut definitions
+            } else {
+                &mut references
+            };
+            target.entry(*symbol).or_default().push(range_id);
+        }
+
+        for (symbol, range_ids) in &definitions {
+            let result = &results[symbol];
+            out.items(result.definitions, range_ids, id, None)?;
+            out.items(result.references, range_ids, id, Some("definitions"))?;
+        }
+        for (symbol, range_ids) in &references {
+            out.items(
+                results[symbol].references,
+                range_ids,
+                id,
+                Some("references"),
+            )?;
+        }
+        if !ranges.is_empty() {
+            out.edges("contains", id, &ranges)?;
+        }
+        out.event("end", "document", id)?;
+    }
+
+    if !documents.is_empty() {
+        out.edges("contains", project, &documents)?;
+    }
+    out.event("end", "project", project)?;
+    Ok(())
+}
+
+/// Zero-based, end-exclusive LSP range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Range {
+    start: (i64, i64),
+    end: (i64, i64),
+}
+
+impl Range {
+    /// The rest of a 1-indexed line from `column`
+    fn line(line: i64, column: i64) -> Self {
+        Self {
+            start: (line - 1, column),
+            end: (line, 0),
+        }
+    }
+
+    /// `text` on a 1-indexed line, starting at `column`
+    fn text(line: i64, column: i64, text: &str) -> Self {
+        let len = text.encode_utf16().count() as i64;
+        Self {
+            start: (line - 1, column),
+            end: (line - 1, column + len),
+        }
+    }
+
+    fn to_json(self) -> Value {
+        json!({
+            "start": {"line": self.start.0, "character": self.start.1},
+            "end": {"line": self.end.0, "character": self.end.1},
+        })
+    }
+}
+
+struct Symbol {
+    hover: Option<String>,
+}
+
+#[derive(Default)]
+struct Document {
+    language: String,
+    /// Symbol, range and whether it is the definition, sorted by position
+    ranges: Vec<(NodeId, Range, bool)>,
+}
+
+struct Results {
+    result_set: u64,
+    definitions: u64,
+    references: u64,
+}
+
+/// Definitions and references grouped by document
+struct Index {
+    symbols: BTreeMap<NodeId, Symbol>,
+    documents: BTreeMap<String, Document>,
+}
+
+impl Index {
+    fn build(graph: &CodeGraph) -> Result<Self> {
+        let mut index = Self {
+            symbols: BTreeMap::new(),
+            documents: BTreeMap::new(),
+        };
+        let mut paths = BTreeMap::new();
+
+        for (_, node) in sorted_nodes(graph) {
+            if node.node_type == NodeType::CodeFile {
+                if let Some(path) = node.properties.get_string("path") {
+                    let language = node
+                        .properties
+                        .get_string("language")
+                        .unwrap_or("plaintext");
+                    index.document(path).language = language.to_lowercase();
+                }
+            }
+        }
+
+        for (node_id, node) in sorted_nodes(graph) {
+            let Some(path) = document_path(graph, node_id, node)? else {
+                continue;
+            };
+            let range = if node.node_type == NodeType::CodeFile {
+                Range {
+                    start: (0, 0),
+                    end: (0, 0),
+                }
+            } else if let Some(line) = node.properties.get_int("line_start").filter(|&l| l >= 1) {
+                let column = node.properties.get_int("col_start").unwrap_or(0).max(0);
+                match node.properties.get_string("name") {
+                    Some(name) => Range::text(line, column, name),
+                    None => Range::line(line, column),
+                }
+            } else {
+                continue;
+            };
+            index.document(&path).ranges.push((node_id, range, true));
+            index.symbols.insert(node_id, Symbol { hover: hover(node) });
+            paths.insert(node_id, path);
+        }
+
+        for (_, edge) in sorted_edges(graph) {
+            let referencing = matches!(
+                edge.edge_type,
+                EdgeType::Calls | EdgeType::References | EdgeType::Imports
+            );
+            let located = index.symbols.contains_key(&edge.target_id);
+            if !referencing || !(located || edge.edge_type == EdgeType::Imports) {
+                continue;
+            }
+            let line = edge
+                .properties
+                .get_int("call_site_line")
+                .or_else(|| edge.properties.get_int("line"))
+                .filter(|&line| line >= 1);
+            let (Some(line), Some(path)) = (line, paths.get(&edge.source_id)) else {
+                continue;
+            };
+            let path = path.clone();
+            if !located {
+                let target = graph.get_node(edge.target_id)?;
+                index.symbols.insert(
+                    edge.target_id,
+                    Symbol {
+                        hover: hover(target),
+                    },
+                );
+            }
+            index
+                .document(&path)
+                .ranges
+                .push((edge.target_id, Range::line(line, 0), false));
+        }
+
+        for document in index.documents.values_mut() {
+            document
+                .ranges
+                .sort_by_key(|(symbol, range, _)| (*range, *symbol));
+            document.ranges.dedup();
+            if document.language.is_empty() {
+                document.language = "plaintext".to_string();
+            }
+        }
+        Ok(index)
+    }
+
+    fn document(&mut self, path: &str) -> &mut Document {
+        self.documents.entry(path.to_string()).or_default()
+    }
+
+    /// The language shared by every document, if there is one
+    fn project_kind(&self) -> &str {
+        let mut languages = self.documents.values().map(|d| d.language.as_str());
+        match languages.next() {
+            Some(first) if languages.all(|l| l == first) => first,
+            _ => "polyglot",
+        }
+    }
+}
+
+/// A node's own `path`, else the path of its containing file
+fn document_path(graph: &CodeGraph, node_id: NodeId, node: &Node) -> Result<Option<String>> {
+    if let Some(path) = node.properties.get_string("path") {
+        return Ok(Some(path.to_string()));
+    }
+    Ok(match helpers::containing_file(graph, node_id)? {
+        Some(file) => graph
+            .get_node(file)?
+            .properties
+            .get_string("path")
+            .map(str::to_string),
+        None => None,
+    })
+}
+
+/// Markdown hover: the signature or name as code, then the doc comment
+fn hover(node: &Node) -> Option<String> {
+    let declaration = node
+        .properties
+        .get_string("signature")
+        .or_else(|| node.properties.get_string("name"))?;
+    let mut hover = format!("```\n{declaration}\n```");
+    if let Some(doc) = node.properties.get_string("doc") {
+        hover.push_str("\n\n---\n\n");
+        hover.push_str(doc);
+    }
+    Some(hover)
+}
+
+/// `file://` URI for a path, relative paths resolved against `root`
+fn file_uri(root: &str, path: &str) -> String {
+    let absolute = if path.starts_with('/') {
+        path.to_string()
+    } else if path.is_empty() {
+        format!("{root}/")
+    } else {
+        format!("{root}/{path}")
+    };
+    let mut uri = String::from("file://");
+    if !absolute.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in absolute.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    uri
+}
+
+/// Writes numbered LSIF vertices and edges, one per line
+struct Emitter<W> {
+    writer: W,
+    next_id: u64,
+}
+
+impl<W: Write> Emitter<W> {
+    fn emit(&mut self, kind: &str, label: &str, fields: Value) -> Result<u64> {
+        self.next_id += 1;
+        let mut element = json!({"id": self.next_id, "type": kind, "label": label});
+        if let (Value::Object(element), Value::Object(fields)) = (&mut element, fields) {
+            element.extend(fields);
+        }
+        let line = serde_json::to_string(&element)
+            .map_err(|e| crate::GraphError::serialization("Failed to serialize LSIF", Some(e)))?;
+        writeln!(self.writer, "{line}").map_err(write_error)?;
+        Ok(self.next_id)
+    }
+
+    fn vertex(&mut self, label: &str, fields: Value) -> Result<u64> {
+        self.emit("vertex", label, fields)
+    }
+
+    fn event(&mut self, kind: &str, scope: &str, data: u64) -> Result<u64> {
+        self.vertex(
+            "$event",
+            json!({"kind": kind, "scope": scope, "data": data}),
+        )
+    }
+
+    /// One-to-one edge
+    fn edge(&mut self, label: &str, out_v: u64, in_v: u64, fields: Value) -> Result<u64> {
+        let mut fields = fields;
+        fields["outV"] = json!(out_v);
+        fields["inV"] = json!(in_v);
+        self.emit("edge", label, fields)
+    }
+
+    /// One-to-many edge
+    fn edges(&mut self, label: &str, out_v: u64, in_vs: &[u64]) -> Result<u64> {
+        self.emit("edge", label, json!({"outV": out_v, "inVs": in_vs}))
+    }
+
+    /// `item` edge from a result to ranges in a document
+    fn items(
+        &mut self,
+        result: u64,
+        ranges: &[u64],
+        document: u64,
+        property: Option<&str>,
+    ) -> Result<u64> {
+        let mut fields = json!({"outV": result, "inVs": ranges, "document": document});
+        if let Some(property) = property {
+            fields["property"] = json!(property);
+        }
+        self.emit("edge", "item", fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_uri() {
+        assert_eq!(
+            file_uri("/home/me/repo", "src/my file.rs"),
+            "file:///home/me/repo/src/my%20file.rs"
+        );
+        assert_eq!(
+            file_uri("/home/me/repo", "/abs/lib.rs"),
+            "file:///abs/lib.rs"
+        );
+        assert_eq!(file_uri("/home/me/repo", ""), "file:///home/me/repo/");
+    }
+
+    #[test]
+    fn test_line_range() {
+        assert_eq!(
+            Range::line(3, 4).to_json(),
+            json!({"start": {"line": 2, "character": 4}, "end": {"line": 3, "character": 0}})
+        );
+        assert_eq!(
+            Range::text(3, 4, "naïve").to_json(),
+            json!({"start": {"line": 2, "character": 4}, "end": {"line": 2, "character": 9}})
+        );
+    }
+}
@@ -0,0 +1,320 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Mermaid diagram export for Markdown documents.
+//!
+//! Generates `flowchart` and `classDiagram` blocks that GitHub, GitLab and most
+//! Markdown renderers display inline. See [`super::diagram`] for how large
+//! graphs are kept readable.
+
+use super::diagram::{
+    class_model, flow_model, ClassBox, ClassKind, DiagramOptions, FlowNode, Member,
+};
+use super::sequence::{sequence_model, SequenceOptions, Step};
+use super::stream::write_error;
+use crate::graph::NodeId;
+use crate::{CodeGraph, EdgeType, NodeType, Result};
+use std::io::Write;
+
+/// Export graph to a Mermaid `flowchart`
+///
+/// Nodes are grouped into a `subgraph` per containing file, and shaped by type.
+pub fn export_mermaid_flowchart(graph: &CodeGraph, options: &DiagramOptions) -> Result<String> {
+    let model = flow_model(graph, options)?;
+    let mut output = format!("flowchart {}\n", options.direction);
+    if model.collapsed {
+        output.push_str("    %% Collapsed to one node per file\n");
+    }
+    if model.omitted > 0 {
+        output.push_str(&format!("    %% {} nodes omitted\n", model.omitted));
+    }
+
+    for (i, (group, nodes)) in model.groups.iter().enumerate() {
+        match group {
+            Some(group) => {
+                let id = group
+                    .node
+                    .map_or_else(|| format!("g{i}"), |n| format!("n{n}"));
+                output.push_str(&format!(
+                    "    subgraph {}[\"{}\"]\n",
+                    id,
+                    escape_mermaid(&group.label)
+                ));
+                for node in nodes {
+                    output.push_str(&format!("        {}\n", flow_node(node)));
+                }
+                output.push_str("    end\n");
+            }
+            None => {
+                for node in nodes {
+                    output.push_str(&format!("    {}\n", flow_node(node)));
+                }
+            }
+        }
+    }
+
+    for edge in &model.edges {
+        output.push_str(&format!(
+            "    n{} -->|\"{}\"| n{}\n",
+            edge.source,
+            escape_mermaid(&edge.label),
+            edge.target
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Export the classes and interfaces of a graph to a Mermaid `classDiagram`
+///
+/// Classes list their fields and methods (from `Contains` edges) and are
+/// grouped into a `namespace` per containing file.
+pub fn export_mermaid_class_diagram(graph: &CodeGraph, options: &DiagramOptions) -> Result<String> {
+    let model = class_model(graph, options)?;
+    let mut output = String::from("classDiagram\n");
+    if model.omitted > 0 {
+        output.push_str(&format!("    %% {} classes omitted\n", model.omitted));
+    }
+
+    for (group, classes) in &model.groups {
+        match group {
+            Some(label) => {
+                output.push_str(&format!("    namespace {} {{\n", namespace_id(label)));
+                for class in classes {
+                    push_class(&mut output, class, "        ");
+                }
+                output.push_str("    }\n");
+            }
+            None => {
+                for class in classes {
+                    push_class(&mut output, class, "    ");
+                }
+            }
+        }
+    }
+
+    // Annotations are declared outside namespaces, which don't accept them
+    for class in model.groups.values().flatten() {
+        let annotation = match class.kind {
+            ClassKind::Interface => "interface",
+            ClassKind::Abstract => "abstract",
+            ClassKind::Class => continue,
+        };
+        output.push_str(&format!("    <<{annotation}>> n{}\n", class.id));
+    }
+
+    for relation in &model.relations {
+        let arrow = match relation.edge_type {
+            EdgeType::Implements => "<|..",
+            _ => "<|--",
+        };
+        output.push_str(&format!(
+            "    n{} {} n{}\n",
+            relation.target, arrow, relation.source
+        ));
+    }
+
+    Ok(output)
+}
+
+/// Export the call chain starting at `entry` to a Mermaid `sequenceDiagram`
+///
+/// See [`super::sequence`] for how calls are followed.
+///
+/// # Errors
+///
+/// Returns [`crate::GraphError::InvalidOperation`] if `entry` is not a function.
+pub fn export_mermaid_sequence(
+    graph: &CodeGraph,
+    entry: NodeId,
+    options: &SequenceOptions,
+) -> Result<String> {
+    let model = sequence_model(graph, entry, options)?;
+    let mut output = String::from("sequenceDiagram\n");
+    for (i, label) in model.participants.iter().enumerate() {
+        output.push_str(&format!(
+            "    participant p{i} as {}\n",
+            escape_sequence(label)
+        ));
+    }
+
+    output.push_str(&format!("    activate p{}\n", model.entry));
+    // Calls activate their target only when a return deactivates it
+    let (activate, deactivate) = if options.show_returns {
+        ("+", "-")
+    } else {
+        ("", "")
+    };
+    for step in &model.steps {
+        match step {
+            Step::Call { from, to, label } => output.push_str(&format!(
+                "    p{from}->>{activate}p{to}: {}\n",
+                escape_sequence(label)
+            )),
+            Step::Return { from, to } => {
+                output.push_str(&format!("    p{from}-->>{deactivate}p{to}: return\n"))
+            }
+        }
+    }
+    if model.truncated {
+        output.push_str(&format!(
+            "    Note over p{}: Truncated after {} calls\n",
+            model.entry, options.max_messages
+        ));
+    }
+    output.push_str(&format!("    deactivate p{}\n", model.entry));
+
+    Ok(output)
+}
+
+/// Write a Mermaid `flowchart` to a writer
+///
+/// Diagrams are bounded by their size limits, so they are built in memory
+/// before being written.
+pub fn export_mermaid_flowchart_to<W: Write>(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+    mut writer: W,
+) -> Result<()> {
+    let diagram = export_mermaid_flowchart(graph, options)?;
+    writer.write_all(diagram.as_bytes()).map_err(write_error)
+}
+
+/// Write a Mermaid `classDiagram` to a writer
+///
+/// Diagrams are bounded by their size limits, so they are built in memory
+/// before being written.
+pub fn export_mermaid_class_diagram_to<W: Write>(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+    mut writer: W,
+) -> Result<()> {
+    let diagram = export_mermaid_class_diagram(graph, options)?;
+    writer.write_all(diagram.as_bytes()).map_err(write_error)
+}
+
+/// Write a Mermaid `sequenceDiagram` to a writer
+///
+/// Diagrams are bounded by their size limits, so they are built in memory
+/// before being written.
+pub fn export_mermaid_sequence_to<W: Write>(
+    graph: &CodeGraph,
+    entry: NodeId,
+    options: &SequenceOptions,
+    mut writer: W,
+) -> Result<()> {
+    let diagram = export_mermaid_sequence(graph, entry, options)?;
+    writer.write_all(diagram.as_bytes()).map_err(write_error)
+}
+
+/// Node declaration shaped by type
+fn flow_node(node: &FlowNode) -> String {
+    let label = escape_mermaid(&node.label);
+    let (open, close) = match node.node_type {
+        NodeType::CodeFile => ("[/\"", "\"/]"),
+        NodeType::Module => ("[(\"", "\")]"),
+        NodeType::Class => ("[[\"", "\"]]"),
+        NodeType::Interface => ("{{\"", "\"}}"),
+        NodeType::Variable => ("([\"", "\"])"),
+        _ => ("[\"", "\"]"),
+    };
+    format!("n{}{open}{label}{close}", node.id)
+}
+
+fn push_class(output: &mut String, class: &ClassBox, indent: &str) {
+    output.push_str(&format!(
+        "{indent}class n{}[\"{}\"] {{\n",
+        class.id,
+        escape_mermaid(&class.name)
+    ));
+    for field in &class.fields {
+        output.push_str(&format!("{indent}    {}\n", field_line(field)));
+    }
+    for method in &class.methods {
+        output.push_str(&format!("{indent}    {}\n", method_line(method)));
+    }
+    if class.hidden_members > 0 {
+        output.push_str(&format!("{indent}    ...{} more\n", class.hidden_members));
+    }
+    output.push_str(&format!("{indent}}}\n"));
+}
+
+/// `+Type name$`
+fn field_line(field: &Member) -> String {
+    let type_name = field
+        .type_name
+        .as_ref()
+        .map(|t| format!("{} ", escape_member(t)))
+        .unwrap_or_default();
+    format!(
+        "{}{}{}{}",
+        field.visibility.marker(),
+        type_name,
+        escape_member(&field.name),
+        classifier(field)
+    )
+}
+
+/// `+name()* ReturnType`
+fn method_line(method: &Member) -> String {
+    let return_type = method
+        .type_name
+        .as_ref()
+        .map(|t| format!(" {}", escape_member(t)))
+        .unwrap_or_default();
+    format!(
+        "{}{}(){}{}",
+        method.visibility.marker(),
+        escape_member(&method.name),
+        classifier(method),
+        return_type
+    )
+}
+
+fn classifier(member: &Member) -> &'static str {
+    if member.is_abstract {
+        "*"
+    } else if member.is_static {
+        "$"
+    } else {
+        ""
+    }
+}
+
+/// Namespace names must be identifiers
+fn namespace_id(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+/// Escape text inside quoted Mermaid labels
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;").replace('\n', " ")
+}
+
+/// Sequence diagram text ends at `;` and `#` starts an entity code
+fn escape_sequence(s: &str) -> String {
+    s.replace([';', '#', '\n'], " ")
+}
+
+/// Mermaid reads `~T~` as generics and braces as block delimiters in members
+fn escape_member(s: &str) -> String {
+    s.replace(['{', '}'], "").replace(['<', '>'], "~")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_namespace_id() {
+        assert_eq!(namespace_id("src/net/server.rs"), "src_net_server_rs");
+    }
+
+    #[test]
+    fn test_escape_member() {
+        assert_eq!(escape_member("Vec<String>"), "Vec~String~");
+    }
+}
@@ -5,16 +5,72 @@
 //!
 //! Supports multiple industry-standard formats:
 //! - **DOT**: Graphviz visualization
-//! - **JSON**: D3.js and web-based tools
+//! - **JSON**: D3.js and web-based tools (also importable)
 //! - **CSV**: Data analysis in spreadsheets/pandas
-//! - **RDF Triples**: Semantic web and SPARQL queries
+//! - **Neo4j CSV** and **Cypher**: Bulk import into Neo4j and Memgraph
+//! - **GraphML**: yEd, Cytoscape and Gephi
+//! - **GEXF**: Gephi
+//! - **HTML**: Self-contained interactive report for sharing
+//! - **LSIF**: Code intelligence indexes for code-navigation tools
+//! - **Mermaid** and **PlantUML**: class, component and sequence diagrams for Markdown documents
+//! - **RDF**: N-Triples and Turtle with the codegraph ontology, for SPARQL stores
+//! - **Triples**: Simple triples with placeholder IRIs
+//!
+//! Each exporter returning a `String` has an `*_to` counterpart that streams to
+//! any [`std::io::Write`]; see [`stream`].
 
+mod attributes;
 pub mod csv;
+pub mod diagram;
 pub mod dot;
+pub mod filter;
+pub mod gexf;
+pub mod graphml;
+pub mod html;
 pub mod json;
+pub mod lsif;
+pub mod mermaid;
+pub mod neo4j;
+pub mod plantuml;
+pub mod rdf;
+pub mod sequence;
+pub mod stream;
 pub mod triples;
 
-pub use csv::{export_csv, export_csv_edges, export_csv_nodes};
-pub use dot::{export_dot, export_dot_styled, DotOptions};
-pub use json::{export_json, export_json_filtered};
-pub use triples::export_triples;
+pub use csv::{
+    export_csv, export_csv_edges, export_csv_edges_to, export_csv_nodes, export_csv_nodes_to,
+};
+pub use diagram::DiagramOptions;
+pub use dot::{export_dot, export_dot_styled, export_dot_styled_to, export_dot_to, DotOptions};
+pub use filter::ExportFilter;
+pub use gexf::{export_gexf, export_gexf_filtered, export_gexf_filtered_to, export_gexf_to};
+pub use graphml::{
+    export_graphml, export_graphml_filtered, export_graphml_filtered_to, export_graphml_to,
+};
+pub use html::{export_html, export_html_to, HtmlOptions};
+pub use json::{
+    export_json, export_json_filtered, export_json_filtered_to, export_json_to, import_json,
+    import_json_reader,
+};
+pub use lsif::{export_lsif, export_lsif_to};
+pub use mermaid::{
+    export_mermaid_class_diagram, export_mermaid_class_diagram_to, export_mermaid_flowchart,
+    export_mermaid_flowchart_to, export_mermaid_sequence, export_mermaid_sequence_to,
+};
+pub use neo4j::{
+    export_cypher, export_cypher_to, export_neo4j_csv, export_neo4j_nodes_to,
+    export_neo4j_relationships_to, CypherDialect, CypherOptions,
+};
+pub use plantuml::{
+    export_plantuml_class_diagram, export_plantuml_class_diagram_to,
+    export_plantuml_component_diagram, export_plantuml_component_diagram_to,
+    export_plantuml_sequence, export_plantuml_sequence_to,
+};
+pub use rdf::{
+    export_rdf, export_rdf_ontology, export_rdf_ontology_to, export_rdf_to, EdgePropertyStyle,
+    RdfFormat, RdfOptions,
+};
+pub use sequence::SequenceOptions;
+#[cfg(feature = "gzip")]
+pub use stream::GzipWriter;
+pub use triples::{export_triples, export_triples_to};
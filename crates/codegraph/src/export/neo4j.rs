@@ -0,0 +1,439 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Bulk-import export for Neo4j and Memgraph.
+//!
+//! Two routes into a property graph database:
+//! - **CSV** files in the `neo4j-admin database import` layout, with typed
+//!   headers (`id:ID`, `:LABEL`, `name:string`, `complexity:int`, `:START_ID`,
+//!   `:END_ID`, `:TYPE`) and `;` as the array delimiter
+//! - **Cypher** scripts of `MERGE` statements that can be re-run against the
+//!   same database without duplicating nodes or relationships
+//!
+//! Every node gets the [`NODE_LABEL`] label plus its node type, and keeps its
+//! graph ID in an `id` property. Relationship types are the edge types in
+//! upper snake case (`IMPORTS_FROM`), with the edge ID in an `id` property.
+
+use super::csv::{create_csv_file, escape_csv};
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::{export_to_string, write_error};
+use crate::{CodeGraph, EdgeType, GraphError, PropertyMap, PropertyValue, Result};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+/// Label shared by every exported node, carrying the unique `id`
+pub const NODE_LABEL: &str = "CodeNode";
+
+/// Database flavour of a Cypher script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CypherDialect {
+    /// Neo4j 5 constraint syntax
+    #[default]
+    Neo4j,
+    /// Memgraph constraint syntax
+    Memgraph,
+}
+
+/// Options for Cypher script export
+#[derive(Debug, Clone)]
+pub struct CypherOptions {
+    /// Database flavour
+    pub dialect: CypherDialect,
+    /// Start with a uniqueness constraint on `CodeNode.id`, which also
+    /// indexes the `MERGE` lookups
+    pub create_constraint: bool,
+}
+
+impl Default for CypherOptions {
+    fn default() -> Self {
+        Self {
+            dialect: CypherDialect::Neo4j,
+            create_constraint: true,
+        }
+    }
+}
+
+/// Column type in a `neo4j-admin` CSV header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Boolean,
+    Int,
+    Long,
+    Double,
+    String,
+    StringArray,
+    LongArray,
+    FloatArray,
+}
+
+impl ColumnType {
+    fn of(value: &PropertyValue) -> Option<Self> {
+        Some(match value {
+            PropertyValue::Bool(_) => Self::Boolean,
+            PropertyValue::Int(i) if i32::try_from(*i).is_ok() => Self::Int,
+            PropertyValue::Int(_) => Self::Long,
+            PropertyValue::Float(_) => Self::Double,
+            PropertyValue::String(_) => Self::String,
+            PropertyValue::StringList(_) => Self::StringArray,
+            PropertyValue::IntList(_) => Self::LongArray,
+            PropertyValue::Vector(_) => Self::FloatArray,
+            PropertyValue::Null => return None,
+        })
+    }
+
+    fn widen(self, other: Self) -> Self {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Self::Int, Self::Long) | (Self::Long, Self::Int) => Self::Long,
+            (Self::Int | Self::Long, Self::Double) | (Self::Double, Self::Int | Self::Long) => {
+                Self::Double
+            }
+            _ => Self::String,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Int => "int",
+            Self::Long => "long",
+            Self::Double => "double",
+            Self::String => "string",
+            Self::StringArray => "string[]",
+            Self::LongArray => "long[]",
+            Self::FloatArray => "float[]",
+        }
+    }
+}
+
+/// Export nodes and relationships to `neo4j-admin` import CSV files
+///
+/// Import them with
+///
+/// ```text
+/// neo4j-admin database import full --id-type=INTEGER --multiline-fields=true \
+///     --nodes=nodes.csv --relationships=relationships.csv <database>
+/// ```
+///
+/// `--id-type=INTEGER` stores the `id` property as an integer, as the Cypher
+/// script does, and `--multiline-fields=true` accepts quoted values spanning
+/// several lines, such as doc comments.
+///
+/// # Errors
+///
+/// Returns [`GraphError::InvalidOperation`] if a string list item contains the
+/// `;` array delimiter, which the importer cannot escape.
+pub fn export_neo4j_csv(
+    graph: &CodeGraph,
+    nodes_path: &Path,
+    relationships_path: &Path,
+) -> Result<()> {
+    let mut nodes = create_csv_file(nodes_path)?;
+    export_neo4j_nodes_to(graph, &mut nodes)?;
+    nodes.flush().map_err(write_error)?;
+
+    let mut relationships = create_csv_file(relationships_path)?;
+    export_neo4j_relationships_to(graph, &mut relationships)?;
+    relationships.flush().map_err(write_error)
+}
+
+/// Stream nodes as `neo4j-admin` import CSV to a writer
+pub fn export_neo4j_nodes_to<W: Write>(graph: &CodeGraph, mut writer: W) -> Result<()> {
+    let nodes = sorted_nodes(graph);
+    let columns = declare(nodes.iter().map(|(_, node)| &node.properties));
+
+    write!(writer, "id:ID,:LABEL").map_err(write_error)?;
+    write_header(&mut writer, &columns)?;
+    for (node_id, node) in nodes {
+        write!(writer, "{node_id},{NODE_LABEL};{}", node.node_type).map_err(write_error)?;
+        write_row(&mut writer, &columns, &node.properties)?;
+    }
+    Ok(())
+}
+
+/// Stream relationships as `neo4j-admin` import CSV to a writer
+pub fn export_neo4j_relationships_to<W: Write>(graph: &CodeGraph, mut writer: W) -> Result<()> {
+    let edges = sorted_edges(graph);
+    let columns = declare(edges.iter().map(|(_, edge)| &edge.properties));
+
+    write!(writer, ":START_ID,:END_ID,:TYPE,id:long").map_err(write_error)?;
+    write_header(&mut writer, &columns)?;
+    for (edge_id, edge) in edges {
+        write!(
+            writer,
+            "{},{},{},{edge_id}",
+            edge.source_id,
+            edge.target_id,
+            relationship_type(edge.edge_type)
+        )
+        .map_err(write_error)?;
+        write_row(&mut writer, &columns, &edge.properties)?;
+    }
+    Ok(())
+}
+
+/// Export graph as a Cypher script of idempotent `MERGE` statements
+///
+/// Nodes are merged on `CodeNode.id` and relationships on their type and `id`;
+/// their properties are replaced, so re-running the script after the graph
+/// changes updates the database in place. Deleted nodes are not removed.
+pub fn export_cypher(graph: &CodeGraph, options: &CypherOptions) -> Result<String> {
+    export_to_string(|buffer| export_cypher_to(graph, options, buffer))
+}
+
+/// Stream graph as a Cypher `MERGE` script to a writer
+pub fn export_cypher_to<W: Write>(
+    graph: &CodeGraph,
+    options: &CypherOptions,
+    mut writer: W,
+) -> Result<()> {
+    if options.create_constraint {
+        let constraint = match options.dialect {
+            CypherDialect::Neo4j => format!(
+                "CREATE CONSTRAINT code_node_id IF NOT EXISTS FOR (n:{NODE_LABEL}) REQUIRE n.id IS UNIQUE;"
+            ),
+            CypherDialect::Memgraph => {
+                format!("CREATE CONSTRAINT ON (n:{NODE_LABEL}) ASSERT n.id IS UNIQUE;")
+            }
+        };
+        writeln!(writer, "{constraint}").map_err(write_error)?;
+    }
+
+    for (node_id, node) in sorted_nodes(graph) {
+        writeln!(
+            writer,
+            "MERGE (n:{NODE_LABEL} {{id: {node_id}}}) SET n = {}, n:{};",
+            cypher_map(node_id, &node.properties),
+            node.node_type
+        )
+        .map_err(write_error)?;
+    }
+
+    for (edge_id, edge) in sorted_edges(graph) {
+        writeln!(
+            writer,
+            "MATCH (a:{NODE_LABEL} {{id: {}}}), (b:{NODE_LABEL} {{id: {}}}) \
+             MERGE (a)-[r:{} {{id: {edge_id}}}]->(b) SET r = {};",
+            edge.source_id,
+            edge.target_id,
+            relationship_type(edge.edge_type),
+            cypher_map(edge_id, &edge.properties)
+        )
+        .map_err(write_error)?;
+    }
+
+    Ok(())
+}
+
+/// `ImportsFrom` -> `IMPORTS_FROM`
+fn relationship_type(edge_type: EdgeType) -> String {
+    let mut result = String::new();
+    for (i, c) in edge_type.to_string().chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            result.push('_');
+        }
+        result.push(c.to_ascii_uppercase());
+    }
+    result
+}
+
+/// Declare a typed column per property key, sorted by key
+///
+/// `id` holds the graph ID, so a property of that name is not exported.
+fn declare<'a>(properties: impl Iterator<Item = &'a PropertyMap>) -> Vec<(String, ColumnType)> {
+    let mut types: BTreeMap<&str, Option<ColumnType>> = BTreeMap::new();
+    for props in properties {
+        for (key, value) in props.iter().filter(|(key, _)| *key != "id") {
+            let declared = types.entry(key.as_str()).or_default();
+            if let Some(value_type) = ColumnType::of(value) {
+                *declared = Some(declared.map_or(value_type, |t| t.widen(value_type)));
+            }
+        }
+    }
+    types
+        .into_iter()
+        .map(|(key, column)| (key.to_string(), column.unwrap_or(ColumnType::String)))
+        .collect()
+}
+
+fn write_header<W: Write>(writer: &mut W, columns: &[(String, ColumnType)]) -> Result<()> {
+    for (key, column) in columns {
+        let header = format!("{key}:{}", column.name());
+        write!(writer, ",{}", escape_csv(&header)).map_err(write_error)?;
+    }
+    writeln!(writer).map_err(write_error)
+}
+
+/// Finish a row; missing and null values are empty cells, which import as absent
+fn write_row<W: Write>(
+    writer: &mut W,
+    columns: &[(String, ColumnType)],
+    properties: &PropertyMap,
+) -> Result<()> {
+    for (key, column) in columns {
+        write!(writer, ",").map_err(write_error)?;
+        if let Some(value) = properties.get(key) {
+            if let Some(value) = csv_value(key, value, *column)? {
+                write!(writer, "{}", escape_csv(&value)).map_err(write_error)?;
+            }
+        }
+    }
+    writeln!(writer).map_err(write_error)
+}
+
+fn csv_value(key: &str, value: &PropertyValue, column: ColumnType) -> Result<Option<String>> {
+    Ok(Some(match value {
+        PropertyValue::String(s) => s.clone(),
+        PropertyValue::Int(i) => i.to_string(),
+        PropertyValue::Float(f) if column == ColumnType::Double => double(*f),
+        PropertyValue::Float(f) => f.to_string(),
+        PropertyValue::Bool(b) => b.to_string(),
+        PropertyValue::StringList(v) => {
+            // neo4j-admin has no escape for the array delimiter
+            if let Some(item) = v.iter().find(|item| item.contains(';')) {
+                return Err(GraphError::InvalidOperation {
+                    message: format!(
+                        "Cannot export '{key}' to Neo4j CSV: list item '{item}' contains the ';' array delimiter"
+                    ),
+                });
+            }
+            v.join(";")
+        }
+        PropertyValue::IntList(v) => join(v.iter().map(i64::to_string), ";"),
+        PropertyValue::Vector(v) => join(v.iter().map(|x| double(*x as f64)), ";"),
+        PropertyValue::Null => return Ok(None),
+    }))
+}
+
+/// Neo4j parses `NaN` and `Infinity` in numeric columns
+fn double(f: f64) -> String {
+    if f.is_infinite() {
+        if f > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else {
+        f.to_string()
+    }
+}
+
+/// Cypher map literal of `id` and the properties, with keys sorted
+///
+/// Nulls and non-finite floats have no Cypher literal and are left out.
+fn cypher_map(id: u64, properties: &PropertyMap) -> String {
+    let mut entries: Vec<_> = properties
+        .iter()
+        .filter(|(key, _)| *key != "id")
+        .filter_map(|(key, value)| Some((key, cypher_value(value)?)))
+        .collect();
+    entries.sort();
+
+    let mut map = format!("{{id: {id}");
+    for (key, value) in entries {
+        map.push_str(&format!(", {}: {value}", cypher_key(key)));
+    }
+    map.push('}');
+    map
+}
+
+fn cypher_value(value: &PropertyValue) -> Option<String> {
+    Some(match value {
+        PropertyValue::String(s) => cypher_string(s),
+        PropertyValue::Int(i) => i.to_string(),
+        PropertyValue::Float(f) => cypher_float(*f)?,
+        PropertyValue::Bool(b) => b.to_string(),
+        PropertyValue::StringList(v) => {
+            format!("[{}]", join(v.iter().map(|s| cypher_string(s)), ", "))
+        }
+        PropertyValue::IntList(v) => format!("[{}]", join(v.iter().map(i64::to_string), ", ")),
+        PropertyValue::Vector(v) => {
+            let items: Option<Vec<_>> = v.iter().map(|x| cypher_float(*x as f64)).collect();
+            format!("[{}]", items?.join(", "))
+        }
+        PropertyValue::Null => return None,
+    })
+}
+
+/// Floats always carry a decimal point so Cypher doesn't read them as integers
+fn cypher_float(f: f64) -> Option<String> {
+    if !f.is_finite() {
+        return None;
+    }
+    let text = f.to_string();
+    Some(if text.contains(['.', 'e']) {
+        text
+    } else {
+        format!("{text}.0")
+    })
+}
+
+fn cypher_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('\'');
+    for c in s.chars() {
+        match c {
+            '\'' => result.push_str("\\'"),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+    result.push('\'');
+    result
+}
+
+/// Property keys that aren't plain identifiers are quoted with backticks
+fn cypher_key(key: &str) -> String {
+    let plain = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        key.to_string()
+    } else {
+        format!("`{}`", key.replace('`', "``"))
+    }
+}
+
+fn join(items: impl Iterator<Item = String>, separator: &str) -> String {
+    items.collect::<Vec<_>>().join(separator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_types_widen() {
+        let a = PropertyMap::new()
+            .with("complexity", 3i64)
+            .with("size", 1i64)
+            .with("score", 1i64);
+        let b = PropertyMap::new()
+            .with("complexity", 5i64)
+            .with("size", i64::MAX)
+            .with("score", 0.5f64);
+        let columns = declare([&a, &b].into_iter());
+        assert_eq!(
+            columns,
+            vec![
+                ("complexity".to_string(), ColumnType::Int),
+                ("score".to_string(), ColumnType::Double),
+                ("size".to_string(), ColumnType::Long),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relationship_type() {
+        assert_eq!(relationship_type(EdgeType::ImportsFrom), "IMPORTS_FROM");
+        assert_eq!(relationship_type(EdgeType::Calls), "CALLS");
+    }
+
+    #[test]
+    fn test_cypher_literals() {
+        assert_eq!(cypher_string("it's\n"), r"'it\'s\n'");
+        assert_eq!(cypher_key("line_start"), "line_start");
+        assert_eq!(cypher_key("odd key"), "`odd key`");
+        assert_eq!(cypher_float(2.0), Some("2.0".to_string()));
+        assert_eq!(cypher_float(f64::NAN), None);
+    }
+}
@@ -0,0 +1,261 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! PlantUML diagram export.
+//!
+//! Generates class diagrams, with fields, methods and inheritance grouped into
+//! a `package` per file, and component diagrams of files and modules with the
+//! dependencies between them grouped by directory. See [`super::diagram`] for
+//! how large graphs are kept readable.
+
+use super::diagram::{
+    class_model, component_model, ClassBox, ClassKind, DiagramOptions, FlowNode, Member,
+};
+use super::sequence::{sequence_model, SequenceOptions, Step};
+use super::stream::write_error;
+use crate::graph::NodeId;
+use crate::{CodeGraph, EdgeType, NodeType, Result};
+use std::io::Write;
+
+/// Export the classes and interfaces of a graph to a PlantUML class diagram
+pub fn export_plantuml_class_diagram(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+) -> Result<String> {
+    let model = class_model(graph, options)?;
+    let mut output = String::from("@startuml\n");
+    if model.omitted > 0 {
+        output.push_str(&format!("' {} classes omitted\n", model.omitted));
+    }
+
+    for (group, classes) in &model.groups {
+        match group {
+            Some(label) => {
+                output.push_str(&format!("package \"{}\" {{\n", escape_plantuml(label)));
+                for class in classes {
+                    push_class(&mut output, class, "  ");
+                }
+                output.push_str("}\n");
+            }
+            None => {
+                for class in classes {
+                    push_class(&mut output, class, "");
+                }
+            }
+        }
+    }
+
+    for relation in &model.relations {
+        let arrow = match relation.edge_type {
+            EdgeType::Implements => "<|..",
+            _ => "<|--",
+        };
+        output.push_str(&format!(
+            "n{} {} n{}\n",
+            relation.target, arrow, relation.source
+        ));
+    }
+
+    output.push_str("@enduml\n");
+    Ok(output)
+}
+
+/// Export the files and modules of a graph to a PlantUML component diagram
+///
+/// Edges between the entities of two files are aggregated into one labelled
+/// dependency per edge type.
+pub fn export_plantuml_component_diagram(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+) -> Result<String> {
+    let model = component_model(graph, options)?;
+    let mut output = String::from("@startuml\n");
+    if model.omitted > 0 {
+        output.push_str(&format!("' {} components omitted\n", model.omitted));
+    }
+
+    for (group, nodes) in &model.groups {
+        match group {
+            Some(group) => {
+                output.push_str(&format!(
+                    "package \"{}\" {{\n",
+                    escape_plantuml(&group.label)
+                ));
+                for node in nodes {
+                    output.push_str(&format!("  {}\n", component(node)));
+                }
+                output.push_str("}\n");
+            }
+            None => {
+                for node in nodes {
+                    output.push_str(&format!("{}\n", component(node)));
+                }
+            }
+        }
+    }
+
+    for edge in &model.edges {
+        output.push_str(&format!(
+            "n{} --> n{} : {}\n",
+            edge.source,
+            edge.target,
+            escape_plantuml(&edge.label)
+        ));
+    }
+
+    output.push_str("@enduml\n");
+    Ok(output)
+}
+
+/// Export the call chain starting at `entry` to a PlantUML sequence diagram
+///
+/// See [`super::sequence`] for how calls are followed.
+///
+/// # Errors
+///
+/// Returns [`crate::GraphError::InvalidOperation`] if `entry` is not a function.
+pub fn export_plantuml_sequence(
+    graph: &CodeGraph,
+    entry: NodeId,
+    options: &SequenceOptions,
+) -> Result<String> {
+    let model = sequence_model(graph, entry, options)?;
+    let mut output = String::from("@startuml\n");
+    for (i, label) in model.participants.iter().enumerate() {
+        output.push_str(&format!(
+            "participant \"{}\" as p{i}\n",
+            escape_plantuml(label)
+        ));
+    }
+
+    output.push_str(&format!("activate p{}\n", model.entry));
+    for step in &model.steps {
+        match step {
+            Step::Call { from, to, label } => {
+                output.push_str(&format!("p{from} -> p{to} : {}\n", escape_plantuml(label)));
+                if options.show_returns {
+                    output.push_str(&format!("activate p{to}\n"));
+                }
+            }
+            Step::Return { from, to } => {
+                output.push_str(&format!("p{from} --> p{to}\n"));
+                output.push_str(&format!("deactivate p{from}\n"));
+            }
+        }
+    }
+    if model.truncated {
+        output.push_str(&format!(
+            "note over p{} : Truncated after {} calls\n",
+            model.entry, options.max_messages
+        ));
+    }
+    output.push_str(&format!("deactivate p{}\n", model.entry));
+
+    output.push_str("@enduml\n");
+    Ok(output)
+}
+
+/// Write a PlantUML class diagram to a writer
+///
+/// Diagrams are bounded by their size limits, so they are built in memory
+/// before being written.
+pub fn export_plantuml_class_diagram_to<W: Write>(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+    mut writer: W,
+) -> Result<()> {
+    let diagram = export_plantuml_class_diagram(graph, options)?;
+    writer.write_all(diagram.as_bytes()).map_err(write_error)
+}
+
+/// Write a PlantUML component diagram to a writer
+///
+/// Diagrams are bounded by their size limits, so they are built in memory
+/// before being written.
+pub fn export_plantuml_component_diagram_to<W: Write>(
+    graph: &CodeGraph,
+    options: &DiagramOptions,
+    mut writer: W,
+) -> Result<()> {
+    let diagram = export_plantuml_component_diagram(graph, options)?;
+    writer.write_all(diagram.as_bytes()).map_err(write_error)
+}
+
+/// Write a PlantUML sequence diagram to a writer
+///
+/// Diagrams are bounded by their size limits, so they are built in memory
+/// before being written.
+pub fn export_plantuml_sequence_to<W: Write>(
+    graph: &CodeGraph,
+    entry: NodeId,
+    options: &SequenceOptions,
+    mut writer: W,
+) -> Result<()> {
+    let diagram = export_plantuml_sequence(graph, entry, options)?;
+    writer.write_all(diagram.as_bytes()).map_err(write_error)
+}
+
+fn component(node: &FlowNode) -> String {
+    let stereotype = match node.node_type {
+        NodeType::Module => " <<module>>",
+        _ => "",
+    };
+    format!(
+        "component \"{}\" as n{}{}",
+        escape_plantuml(&node.label),
+        node.id,
+        stereotype
+    )
+}
+
+fn push_class(output: &mut String, class: &ClassBox, indent: &str) {
+    let keyword = match class.kind {
+        ClassKind::Class => "class",
+        ClassKind::Abstract => "abstract class",
+        ClassKind::Interface => "interface",
+    };
+    output.push_str(&format!(
+        "{indent}{keyword} \"{}\" as n{} {{\n",
+        escape_plantuml(&class.name),
+        class.id
+    ));
+    for field in &class.fields {
+        output.push_str(&format!("{indent}  {}\n", member_line(field, "")));
+    }
+    for method in &class.methods {
+        output.push_str(&format!("{indent}  {}\n", member_line(method, "()")));
+    }
+    if class.hidden_members > 0 {
+        output.push_str(&format!("{indent}  .. {} more ..\n", class.hidden_members));
+    }
+    output.push_str(&format!("{indent}}}\n"));
+}
+
+/// `{static} +name() : Type`
+fn member_line(member: &Member, parens: &str) -> String {
+    let modifier = if member.is_abstract {
+        "{abstract} "
+    } else if member.is_static {
+        "{static} "
+    } else {
+        ""
+    };
+    let type_name = member
+        .type_name
+        .as_ref()
+        .map(|t| format!(" : {t}"))
+        .unwrap_or_default();
+    format!(
+        "{}{}{}{}{}",
+        modifier,
+        member.visibility.marker(),
+        member.name,
+        parens,
+        type_name
+    )
+}
+
+/// Escape text inside quoted PlantUML names
+fn escape_plantuml(s: &str) -> String {
+    s.replace('"', "'").replace('\n', " ")
+}
@@ -0,0 +1,459 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Standards-compliant RDF export in N-Triples and Turtle.
+//!
+//! Nodes and edges get absolute IRIs under a configurable base
+//! (`{base}node/{id}`, `{base}edge/{id}`). Node types, edge types and
+//! well-known properties use the codegraph vocabulary at [`VOCABULARY`],
+//! published by [`export_rdf_ontology`]; other properties are minted under
+//! `{base}property/`. Ints, floats and bools become `xsd` typed literals.
+//!
+//! Edge properties are attached either to an `rdf:Statement` reifying the edge
+//! (the default, readable by every RDF 1.1 tool) or as RDF-star annotations on
+//! the quoted edge triple.
+
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::{export_to_string, write_error};
+use crate::{CodeGraph, EdgeType, GraphError, NodeType, PropertyMap, PropertyValue, Result};
+use std::io::Write;
+
+/// IRI of the codegraph ontology
+pub const ONTOLOGY: &str = "https://github.com/anvanster/codegraph/ontology";
+
+/// Namespace of the codegraph vocabulary (classes and properties)
+pub const VOCABULARY: &str = "https://github.com/anvanster/codegraph/ontology#";
+
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
+const OWL: &str = "http://www.w3.org/2002/07/owl#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// Property keys with a term in the vocabulary, and the datatype of their values
+const WELL_KNOWN_PROPERTIES: [(&str, &str); 20] = [
+    ("name", "string"),
+    ("qualified_name", "string"),
+    ("path", "string"),
+    ("language", "string"),
+    ("visibility", "string"),
+    ("signature", "string"),
+    ("return_type", "string"),
+    ("doc", "string"),
+    ("line_start", "integer"),
+    ("line_end", "integer"),
+    ("col_start", "integer"),
+    ("col_end", "integer"),
+    ("line_count", "integer"),
+    ("complexity", "integer"),
+    ("is_async", "boolean"),
+    ("is_static", "boolean"),
+    ("is_abstract", "boolean"),
+    ("is_test", "boolean"),
+    ("call_site_line", "integer"),
+    ("line", "integer"),
+];
+
+/// RDF serialization syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RdfFormat {
+    /// One absolute-IRI triple per line
+    #[default]
+    NTriples,
+    /// Turtle with prefixed names
+    Turtle,
+}
+
+/// How edge properties are expressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EdgePropertyStyle {
+    /// An `rdf:Statement` per edge with properties, identified by the edge IRI
+    #[default]
+    Reification,
+    /// RDF-star annotations on the quoted edge triple
+    ///
+    /// Parallel edges of the same type share one quoted triple, so their
+    /// properties are merged.
+    RdfStar,
+}
+
+/// Options for RDF export
+#[derive(Debug, Clone)]
+pub struct RdfOptions {
+    /// Absolute IRI that node, edge and property IRIs are minted under
+    pub base_iri: String,
+    /// Serialization syntax
+    pub format: RdfFormat,
+    /// How edge properties are expressed
+    pub edge_properties: EdgePropertyStyle,
+}
+
+impl Default for RdfOptions {
+    fn default() -> Self {
+        Self {
+            base_iri: "urn:codegraph:".to_string(),
+            format: RdfFormat::NTriples,
+            edge_properties: EdgePropertyStyle::Reification,
+        }
+    }
+}
+
+/// Export graph as RDF
+///
+/// # Errors
+///
+/// Returns [`GraphError::InvalidOperation`] if `base_iri` is not an absolute IRI.
+pub fn export_rdf(graph: &CodeGraph, options: &RdfOptions) -> Result<String> {
+    export_to_string(|buffer| export_rdf_to(graph, options, buffer))
+}
+
+/// Stream graph as RDF to a writer
+pub fn export_rdf_to<W: Write>(graph: &CodeGraph, options: &RdfOptions, writer: W) -> Result<()> {
+    validate_base_iri(&options.base_iri)?;
+    let base = &options.base_iri;
+    let mut out = Serializer::new(
+        writer,
+        options.format,
+        vec![
+            ("rdf", RDF.to_string()),
+            ("xsd", XSD.to_string()),
+            ("cg", VOCABULARY.to_string()),
+            ("n", format!("{base}node/")),
+            ("e", format!("{base}edge/")),
+            ("p", format!("{base}property/")),
+        ],
+    )?;
+
+    for (node_id, node) in sorted_nodes(graph) {
+        let subject = out.iri(&format!("{base}node/{node_id}"));
+        out.triple(
+            &subject,
+            &out.rdf_type(),
+            &out.iri(&class_iri(node.node_type)),
+        )?;
+        write_properties(&mut out, &subject, &node.properties, base)?;
+    }
+
+    for (edge_id, edge) in sorted_edges(graph) {
+        let source = out.iri(&format!("{base}node/{}", edge.source_id));
+        let target = out.iri(&format!("{base}node/{}", edge.target_id));
+        let predicate = out.iri(&edge_iri(edge.edge_type));
+        out.triple(&source, &predicate, &target)?;
+
+        if edge.properties.is_empty() {
+            continue;
+        }
+        let subject = match options.edge_properties {
+            EdgePropertyStyle::Reification => {
+                let statement = out.iri(&format!("{base}edge/{edge_id}"));
+                let rdf_type = out.rdf_type();
+                out.triple(&statement, &rdf_type, &out.iri(&format!("{RDF}Statement")))?;
+                out.triple(&statement, &out.iri(&format!("{RDF}subject")), &source)?;
+                out.triple(&statement, &out.iri(&format!("{RDF}predicate")), &predicate)?;
+                out.triple(&statement, &out.iri(&format!("{RDF}object")), &target)?;
+                statement
+            }
+            EdgePropertyStyle::RdfStar => format!("<< {source} {predicate} {target} >>"),
+        };
+        write_properties(&mut out, &subject, &edge.properties, base)?;
+    }
+
+    Ok(())
+}
+
+/// Export the codegraph vocabulary as an OWL ontology
+///
+/// Declares a class per [`NodeType`] (subclasses of `cg:Node`), an object
+/// property per [`EdgeType`] and a datatype property per well-known property.
+pub fn export_rdf_ontology(format: RdfFormat) -> Result<String> {
+    export_to_string(|buffer| export_rdf_ontology_to(format, buffer))
+}
+
+/// Stream the codegraph ontology to a writer
+pub fn export_rdf_ontology_to<W: Write>(format: RdfFormat, writer: W) -> Result<()> {
+    let mut out = Serializer::new(
+        writer,
+        format,
+        vec![
+            ("rdf", RDF.to_string()),
+            ("rdfs", RDFS.to_string()),
+            ("owl", OWL.to_string()),
+            ("xsd", XSD.to_string()),
+            ("cg", VOCABULARY.to_string()),
+        ],
+    )?;
+    let rdf_type = out.rdf_type();
+    let label = out.iri(&format!("{RDFS}label"));
+    let sub_class_of = out.iri(&format!("{RDFS}subClassOf"));
+    let domain = out.iri(&format!("{RDFS}domain"));
+    let range = out.iri(&format!("{RDFS}range"));
+    let owl_class = out.iri(&format!("{OWL}Class"));
+    let object_property = out.iri(&format!("{OWL}ObjectProperty"));
+    let datatype_property = out.iri(&format!("{OWL}DatatypeProperty"));
+
+    let ontology = out.iri(ONTOLOGY);
+    out.triple(&ontology, &rdf_type, &out.iri(&format!("{OWL}Ontology")))?;
+    out.triple(&ontology, &label, &literal("codegraph", None))?;
+
+    let node = out.iri(&format!("{VOCABULARY}Node"));
+    out.triple(&node, &rdf_type, &owl_class)?;
+    out.triple(&node, &label, &literal("Node", None))?;
+
+    for node_type in NodeType::ALL {
+        let class = out.iri(&class_iri(node_type));
+        out.triple(&class, &rdf_type, &owl_class)?;
+        out.triple(&class, &sub_class_of, &node)?;
+        out.triple(&class, &label, &literal(&node_type.to_string(), None))?;
+    }
+
+    for edge_type in EdgeType::ALL {
+        let property = out.iri(&edge_iri(edge_type));
+        out.triple(&property, &rdf_type, &object_property)?;
+        out.triple(&property, &domain, &node)?;
+        out.triple(&property, &range, &node)?;
+        out.triple(&property, &label, &literal(&edge_type.to_string(), None))?;
+    }
+
+    for (key, datatype) in WELL_KNOWN_PROPERTIES {
+        let property = out.iri(&format!("{VOCABULARY}{}", camel_case(key)));
+        out.triple(&property, &rdf_type, &datatype_property)?;
+        out.triple(&property, &range, &out.iri(&format!("{XSD}{datatype}")))?;
+        out.triple(&property, &label, &literal(key, None))?;
+    }
+
+    Ok(())
+}
+
+/// Writes triples, abbreviating IRIs to prefixed names in Turtle
+struct Serializer<W> {
+    writer: W,
+    format: RdfFormat,
+    prefixes: Vec<(&'static str, String)>,
+}
+
+impl<W: Write> Serializer<W> {
+    fn new(
+        mut writer: W,
+        format: RdfFormat,
+        prefixes: Vec<(&'static str, String)>,
+    ) -> Result<Self> {
+        if format == RdfFormat::Turtle {
+            for (prefix, namespace) in &prefixes {
+                writeln!(writer, "@prefix {prefix}: <{namespace}> .").map_err(write_error)?;
+            }
+            writeln!(writer).map_err(write_error)?;
+        }
+        Ok(Self {
+            writer,
+            format,
+            prefixes,
+        })
+    }
+
+    /// Render an IRI as `<iri>`, or `prefix:local` in Turtle
+    fn iri(&self, iri: &str) -> String {
+        if self.format == RdfFormat::Turtle {
+            for (prefix, namespace) in &self.prefixes {
+                if let Some(local) = iri.strip_prefix(namespace.as_str()) {
+                    if is_local_name(local) {
+                        return format!("{prefix}:{local}");
+                    }
+                }
+            }
+        }
+        format!("<{iri}>")
+    }
+
+    fn rdf_type(&self) -> String {
+        match self.format {
+            RdfFormat::Turtle => "a".to_string(),
+            RdfFormat::NTriples => format!("<{RDF}type>"),
+        }
+    }
+
+    /// Render a typed literal with its datatype abbreviated like other IRIs
+    fn typed(&self, lexical: &str, datatype: &str) -> String {
+        literal(lexical, Some(&self.iri(datatype)))
+    }
+
+    fn triple(&mut self, subject: &str, predicate: &str, object: &str) -> Result<()> {
+        writeln!(self.writer, "{subject} {predicate} {object} .").map_err(write_error)
+    }
+}
+
+fn write_properties<W: Write>(
+    out: &mut Serializer<W>,
+    subject: &str,
+    properties: &PropertyMap,
+    base: &str,
+) -> Result<()> {
+    let mut entries: Vec<_> = properties.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, value) in entries {
+        let predicate = out.iri(&property_iri(key, base));
+        for object in objects(out, value) {
+            out.triple(subject, &predicate, &object)?;
+        }
+    }
+    Ok(())
+}
+
+/// RDF objects for a property value
+///
+/// String lists become one triple per item; ordered numeric lists are kept
+/// whole as `rdf:JSON` literals, and nulls are omitted.
+fn objects<W: Write>(out: &Serializer<W>, value: &PropertyValue) -> Vec<String> {
+    match value {
+        PropertyValue::String(s) => vec![literal(s, None)],
+        PropertyValue::Int(i) => vec![out.typed(&i.to_string(), &format!("{XSD}integer"))],
+        PropertyValue::Float(f) => vec![out.typed(&double_lexical(*f), &format!("{XSD}double"))],
+        PropertyValue::Bool(b) => vec![out.typed(&b.to_string(), &format!("{XSD}boolean"))],
+        PropertyValue::StringList(items) => items.iter().map(|s| literal(s, None)).collect(),
+        PropertyValue::IntList(items) => {
+            let json = serde_json::to_string(items).unwrap_or_default();
+            vec![out.typed(&json, &format!("{RDF}JSON"))]
+        }
+        PropertyValue::Vector(items) => {
+            let json = serde_json::to_string(items).unwrap_or_default();
+            vec![out.typed(&json, &format!("{RDF}JSON"))]
+        }
+        PropertyValue::Null => Vec::new(),
+    }
+}
+
+fn class_iri(node_type: NodeType) -> String {
+    format!("{VOCABULARY}{node_type}")
+}
+
+fn edge_iri(edge_type: EdgeType) -> String {
+    let name = edge_type.to_string();
+    let mut chars = name.chars();
+    let first = chars.next().map(|c| c.to_ascii_lowercase());
+    format!(
+        "{VOCABULARY}{}{}",
+        first.into_iter().collect::<String>(),
+        chars.as_str()
+    )
+}
+
+fn property_iri(key: &str, base: &str) -> String {
+    if WELL_KNOWN_PROPERTIES.iter().any(|(known, _)| *known == key) {
+        format!("{VOCABULARY}{}", camel_case(key))
+    } else {
+        format!("{base}property/{}", percent_encode(key))
+    }
+}
+
+/// `line_start` -> `lineStart`
+fn camel_case(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    let mut upper = false;
+    for c in key.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            result.push(c.to_ascii_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Percent-encode everything but unreserved IRI characters
+fn percent_encode(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            result.push(byte as char);
+        } else {
+            result.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    result
+}
+
+/// Local names that need no escaping in a Turtle prefixed name
+fn is_local_name(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// `xsd:double` spells infinities `INF` and `-INF`
+fn double_lexical(f: f64) -> String {
+    if f.is_infinite() {
+        if f > 0.0 { "INF" } else { "-INF" }.to_string()
+    } else {
+        f.to_string()
+    }
+}
+
+fn literal(s: &str, datatype: Option<&str>) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04X}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    if let Some(datatype) = datatype {
+        result.push_str("^^");
+        result.push_str(datatype);
+    }
+    result
+}
+
+/// IRIs need a scheme and may not contain spaces or `<>"{}|^\``
+fn validate_base_iri(base: &str) -> Result<()> {
+    let has_scheme = base.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+    let has_invalid = base
+        .chars()
+        .any(|c| c.is_whitespace() || c.is_control() || "<>\"{}|^`\\".contains(c));
+    if !has_scheme || has_invalid {
+        return Err(GraphError::InvalidOperation {
+            message: format!("RDF base IRI must be an absolute IRI, got '{base}'"),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vocabulary_names() {
+        assert_eq!(
+            edge_iri(EdgeType::ImportsFrom),
+            format!("{VOCABULARY}importsFrom")
+        );
+        assert_eq!(camel_case("call_site_line"), "callSiteLine");
+        assert_eq!(percent_encode("a b/c"), "a%20b%2Fc");
+    }
+
+    #[test]
+    fn test_literal_escaping() {
+        assert_eq!(literal("say \"hi\"\n", None), r#""say \"hi\"\n""#);
+        assert_eq!(literal("\u{7}", None), r#""\u0007""#);
+        assert_eq!(double_lexical(f64::NEG_INFINITY), "-INF");
+    }
+
+    #[test]
+    fn test_validate_base_iri() {
+        assert!(validate_base_iri("https://example.org/repo/").is_ok());
+        assert!(validate_base_iri("urn:codegraph:").is_ok());
+        assert!(validate_base_iri("node/").is_err());
+        assert!(validate_base_iri("http://example.org/a b").is_err());
+    }
+}
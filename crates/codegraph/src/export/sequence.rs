@@ -0,0 +1,209 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Sequence diagrams from call chains.
+//!
+//! Starting at an entry-point function, `Calls` edges are followed depth-first
+//! in call-site order (the `call_site_line` edge property, or `line` for edges
+//! added with [`helpers::add_call`](crate::helpers::add_call)), producing the
+//! messages of a sequence diagram. Participants are the classes containing the
+//! called functions, or their files for free functions. Recursive calls are
+//! shown once and not followed.
+
+use super::attributes::node_label;
+use super::diagram::path_label;
+use crate::graph::NodeId;
+use crate::{CodeGraph, Edge, EdgeType, GraphError, NodeType, Result};
+use std::collections::HashMap;
+
+/// Options for sequence diagrams
+#[derive(Debug, Clone)]
+pub struct SequenceOptions {
+    /// Maximum call depth followed from the entry point
+    pub max_depth: usize,
+    /// Maximum number of call messages before the diagram is cut off
+    pub max_messages: usize,
+    /// Show return messages after each call
+    pub show_returns: bool,
+}
+
+impl Default for SequenceOptions {
+    fn default() -> Self {
+        SequenceOptions {
+            max_depth: 4,
+            max_messages: 60,
+            show_returns: true,
+        }
+    }
+}
+
+/// One step of a sequence diagram, between participant indexes
+#[derive(Debug, Clone)]
+pub(super) enum Step {
+    Call {
+        from: usize,
+        to: usize,
+        label: String,
+    },
+    Return {
+        from: usize,
+        to: usize,
+    },
+}
+
+/// Participants in order of appearance and the steps between them
+#[derive(Debug, Clone, Default)]
+pub(super) struct SequenceModel {
+    /// Labels of the classes and files taking part
+    pub(super) participants: Vec<String>,
+    pub(super) steps: Vec<Step>,
+    /// Participant of the entry point, which starts active
+    pub(super) entry: usize,
+    /// Whether `max_messages` cut the diagram short
+    pub(super) truncated: bool,
+}
+
+/// Build the sequence model by walking calls from `entry`.
+pub(super) fn sequence_model(
+    graph: &CodeGraph,
+    entry: NodeId,
+    options: &SequenceOptions,
+) -> Result<SequenceModel> {
+    let node = graph.get_node(entry)?;
+    if node.node_type != NodeType::Function {
+        return Err(GraphError::InvalidOperation {
+            message: format!(
+                "Sequence diagrams start from a Function node, node {entry} is a {}",
+                node.node_type
+            ),
+        });
+    }
+
+    let mut walker = Walker {
+        graph,
+        options,
+        model: SequenceModel::default(),
+        participant_of: HashMap::new(),
+        participant_index: HashMap::new(),
+        stack: vec![entry],
+        messages: 0,
+    };
+    walker.model.entry = walker.participant(entry)?;
+    walker.walk(entry, 0)?;
+    Ok(walker.model)
+}
+
+struct Walker<'a> {
+    graph: &'a CodeGraph,
+    options: &'a SequenceOptions,
+    model: SequenceModel,
+    /// Participant node of each function seen
+    participant_of: HashMap<NodeId, NodeId>,
+    /// Index of each participant node in the model
+    participant_index: HashMap<NodeId, usize>,
+    stack: Vec<NodeId>,
+    messages: usize,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, function: NodeId, depth: usize) -> Result<()> {
+        if depth >= self.options.max_depth {
+            return Ok(());
+        }
+        let from = self.participant(function)?;
+        for edge in calls_in_order(self.graph, function) {
+            if self.messages >= self.options.max_messages {
+                self.model.truncated = true;
+                return Ok(());
+            }
+            self.messages += 1;
+
+            let callee = edge.target_id;
+            let to = self.participant(callee)?;
+            let recursive = self.stack.contains(&callee);
+            let name = node_label(callee, self.graph.get_node(callee)?);
+            let label = if recursive {
+                format!("{name}() (recursive)")
+            } else {
+                format!("{name}()")
+            };
+            self.model.steps.push(Step::Call { from, to, label });
+
+            if !recursive {
+                self.stack.push(callee);
+                self.walk(callee, depth + 1)?;
+                self.stack.pop();
+            }
+            if self.options.show_returns {
+                self.model.steps.push(Step::Return { from: to, to: from });
+            }
+        }
+        Ok(())
+    }
+
+    /// Participant index of a function, registering it on first appearance
+    fn participant(&mut self, function: NodeId) -> Result<usize> {
+        let owner = match self.participant_of.get(&function) {
+            Some(&owner) => owner,
+            None => {
+                let owner = owner(self.graph, function)?;
+                self.participant_of.insert(function, owner);
+                owner
+            }
+        };
+        if let Some(&index) = self.participant_index.get(&owner) {
+            return Ok(index);
+        }
+        let node = self.graph.get_node(owner)?;
+        let label = match node.node_type {
+            NodeType::CodeFile => path_label(owner, node),
+            _ => node_label(owner, node),
+        };
+        self.model.participants.push(label);
+        let index = self.model.participants.len() - 1;
+        self.participant_index.insert(owner, index);
+        Ok(index)
+    }
+}
+
+/// Outgoing `Calls` edges ordered by call-site line, then edge ID
+fn calls_in_order(graph: &CodeGraph, function: NodeId) -> Vec<&Edge> {
+    let mut calls: Vec<&Edge> = graph
+        .outgoing_edges(function)
+        .filter(|edge| edge.edge_type == EdgeType::Calls)
+        .collect();
+    calls.sort_by_key(|edge| {
+        let line = edge
+            .properties
+            .get_int("call_site_line")
+            .or_else(|| edge.properties.get_int("line"));
+        (line.is_none(), line, edge.id)
+    });
+    calls
+}
+
+/// Nearest containing class or interface, else file, else the function itself
+fn owner(graph: &CodeGraph, function: NodeId) -> Result<NodeId> {
+    let mut current = function;
+    let mut file = None;
+    // Bounded walk up the `Contains` hierarchy; containment cycles end it early
+    for _ in 0..graph.node_count() {
+        let Some(parent) = graph
+            .incoming_edges(current)
+            .filter(|edge| edge.edge_type == EdgeType::Contains)
+            .map(|edge| edge.source_id)
+            .min()
+        else {
+            break;
+        };
+        match graph.get_node(parent)?.node_type {
+            NodeType::Class | NodeType::Interface => return Ok(parent),
+            NodeType::CodeFile => {
+                file = Some(parent);
+                break;
+            }
+            _ => current = parent,
+        }
+    }
+    Ok(file.unwrap_or(function))
+}
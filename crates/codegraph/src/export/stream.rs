@@ -0,0 +1,110 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for streaming exporters.
+//!
+//! Every exporter has an `*_to` variant writing to any [`std::io::Write`] as it
+//! walks the graph, so exports of large graphs never hold the whole output in
+//! memory. Pass a [`std::io::BufWriter`] around files and sockets; with the
+//! `gzip` feature, [`GzipWriter`] compresses the output on the way.
+
+use crate::{GraphError, Result};
+use std::io::Write;
+
+/// Collect the output of a streaming exporter into a `String`.
+pub(crate) fn export_to_string(export: impl FnOnce(&mut Vec<u8>) -> Result<()>) -> Result<String> {
+    let mut buffer = Vec::new();
+    export(&mut buffer)?;
+    String::from_utf8(buffer)
+        .map_err(|e| GraphError::serialization("Export produced invalid UTF-8", Some(e)))
+}
+
+/// Write a chunk of output built in memory and clear it for reuse.
+///
+/// Exporters that format an element at a time call this after each node and
+/// edge, so memory use is bounded by the largest element.
+pub(crate) fn write_chunk<W: Write>(writer: &mut W, chunk: &mut String) -> Result<()> {
+    writer.write_all(chunk.as_bytes()).map_err(write_error)?;
+    chunk.clear();
+    Ok(())
+}
+
+/// Convert a write failure into a storage error.
+pub(crate) fn write_error(e: std::io::Error) -> GraphError {
+    GraphError::storage("Failed to write export", Some(e))
+}
+
+/// Gzip-compressing writer for exporters.
+///
+/// Call [`finish`](Self::finish) when the export is done to write the gzip
+/// trailer and get the inner writer back; dropping the writer instead finishes
+/// the stream but ignores errors.
+///
+/// # Example
+///
+/// ```no_run
+/// use codegraph::export::{export_json_to, GzipWriter};
+/// use codegraph::CodeGraph;
+/// use std::fs::File;
+/// use std::io::BufWriter;
+///
+/// # fn example(graph: &CodeGraph) -> codegraph::Result<()> {
+/// let file = BufWriter::new(File::create("graph.json.gz").unwrap());
+/// let mut gzip = GzipWriter::new(file);
+/// export_json_to(graph, &mut gzip)?;
+/// gzip.finish()?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "gzip")]
+pub struct GzipWriter<W: Write> {
+    encoder: flate2::write::GzEncoder<W>,
+}
+
+#[cfg(feature = "gzip")]
+impl<W: Write> GzipWriter<W> {
+    /// Compress into `writer` at the default compression level.
+    pub fn new(writer: W) -> Self {
+        Self {
+            encoder: flate2::write::GzEncoder::new(writer, flate2::Compression::default()),
+        }
+    }
+
+    /// Write the gzip trailer and return the inner writer.
+    pub fn finish(self) -> Result<W> {
+        let mut writer = self.encoder.finish().map_err(write_error)?;
+        writer.flush().map_err(write_error)?;
+        Ok(writer)
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl<W: Write> Write for GzipWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.encoder.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.encoder.flush()
+    }
+}
+
+#[cfg(all(test, feature = "gzip"))]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_gzip_writer_round_trip() {
+        let mut gzip = GzipWriter::new(Vec::new());
+        gzip.write_all(b"digraph code_graph {}\n").unwrap();
+        let compressed = gzip.finish().unwrap();
+        assert_eq!(&compressed[..2], &[0x1f, 0x8b]);
+
+        let mut text = String::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text, "digraph code_graph {}\n");
+    }
+}
@@ -4,47 +4,56 @@
 //! RDF Triples format export for semantic web and SPARQL queries.
 //!
 //! Generates N-Triples format where each line is a triple: (subject, predicate, object).
+//!
+//! Subjects and predicates are placeholder IRIs such as `<node:1>`, which RDF
+//! tools reject; use [`super::rdf`] to load graphs into a triple store.
 
+use super::filter::{sorted_edges, sorted_nodes};
+use super::stream::{export_to_string, write_error};
 use crate::{CodeGraph, Result};
+use std::io::Write;
 
 /// Export graph as RDF triples in N-Triples format
 pub fn export_triples(graph: &CodeGraph) -> Result<String> {
-    let mut output = String::new();
+    export_to_string(|buffer| export_triples_to(graph, buffer))
+}
 
+/// Stream graph as RDF triples in N-Triples format to a writer
+pub fn export_triples_to<W: Write>(graph: &CodeGraph, mut writer: W) -> Result<()> {
     // Export node types
-    for node_id in 0..graph.node_count() as u64 {
-        if let Ok(node) = graph.get_node(node_id) {
-            // Node type triple
-            output.push_str(&format!(
-                "<node:{}> <rdf:type> <type:{:?}> .\n",
-                node_id, node.node_type
-            ));
+    for (node_id, node) in sorted_nodes(graph) {
+        // Node type triple
+        writeln!(
+            writer,
+            "<node:{}> <rdf:type> <type:{:?}> .",
+            node_id, node.node_type
+        )
+        .map_err(write_error)?;
 
-            // Property triples
-            for (key, value) in node.properties.iter() {
-                let object = format_triple_object(value);
-                output.push_str(&format!("<node:{node_id}> <prop:{key}> {object} .\n"));
-            }
+        // Property triples
+        for (key, value) in node.properties.iter() {
+            let object = format_triple_object(value);
+            writeln!(writer, "<node:{node_id}> <prop:{key}> {object} .").map_err(write_error)?;
         }
     }
 
     // Export edges as triples
-    for edge_id in 0..graph.edge_count() as u64 {
-        if let Ok(edge) = graph.get_edge(edge_id) {
-            output.push_str(&format!(
-                "<node:{}> <edge:{:?}> <node:{}> .\n",
-                edge.source_id, edge.edge_type, edge.target_id
-            ));
+    for (edge_id, edge) in sorted_edges(graph) {
+        writeln!(
+            writer,
+            "<node:{}> <edge:{:?}> <node:{}> .",
+            edge.source_id, edge.edge_type, edge.target_id
+        )
+        .map_err(write_error)?;
 
-            // Edge properties as triples about the edge
-            for (key, value) in edge.properties.iter() {
-                let object = format_triple_object(value);
-                output.push_str(&format!("<edge:{edge_id}> <prop:{key}> {object} .\n"));
-            }
+        // Edge properties as triples about the edge
+        for (key, value) in edge.properties.iter() {
+            let object = format_triple_object(value);
+            writeln!(writer, "<edge:{edge_id}> <prop:{key}> {object} .").map_err(write_error)?;
         }
     }
 
-    Ok(output)
+    Ok(())
 }
 
 /// Format property value as RDF triple object (with type annotations)
@@ -77,6 +86,14 @@ fn format_triple_object(value: &crate::PropertyValue) -> String {
                 .join(",");
             format!("\"[{joined}]\"^^<xsd:array>")
         }
+        crate::PropertyValue::Vector(v) => {
+            let joined = v
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("\"[{joined}]\"^^<xsd:array>")
+        }
         crate::PropertyValue::Null => "\"null\"".to_string(),
     }
 }
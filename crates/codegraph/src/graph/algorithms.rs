@@ -3,12 +3,13 @@
 
 //! Graph traversal and analysis algorithms.
 //!
-//! Provides BFS, DFS, cycle detection (Tarjan's SCC), and path finding algorithms
-//! optimized for code dependency analysis.
+//! Provides BFS, DFS, cycle detection (Tarjan's SCC), cycle breaking (feedback
+//! edge sets), and path finding algorithms optimized for code dependency analysis.
 
 use crate::error::Result;
-use crate::graph::{CodeGraph, Direction, NodeId};
-use std::collections::{HashMap, HashSet, VecDeque};
+use crate::graph::{CodeGraph, Direction, Edge, EdgeId, EdgeType, NodeId};
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 
 /// Breadth-First Search traversal from a starting node.
 ///
@@ -281,6 +282,274 @@ fn find_paths_recursive(
     Ok(())
 }
 
+/// Find strongly connected components, following only edges of the given types.
+///
+/// Unlike [`find_strongly_connected_components`], this includes single-node
+/// components and uses an iterative Tarjan traversal, so it is safe on deep graphs.
+/// Components are returned in reverse topological order (a component only has
+/// edges into components that appear before it).
+///
+/// # Parameters
+/// - `graph`: The graph to analyze
+/// - `edge_types`: Edge types to follow (e.g. `[EdgeType::Imports]`)
+///
+/// # Returns
+/// Vec of SCCs, where each SCC is a Vec of node IDs sorted ascending
+pub fn strongly_connected_components_by_type(
+    graph: &CodeGraph,
+    edge_types: &[EdgeType],
+) -> Vec<Vec<NodeId>> {
+    let successors = |v: NodeId| -> Vec<NodeId> {
+        let mut targets: Vec<NodeId> = graph
+            .outgoing_edges(v)
+            .filter(|edge| edge_types.contains(&edge.edge_type))
+            .map(|edge| edge.target_id)
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        targets
+    };
+
+    let mut node_ids: Vec<NodeId> = graph.iter_nodes().map(|(id, _)| id).collect();
+    node_ids.sort_unstable();
+
+    let mut next_index = 0;
+    let mut indices: HashMap<NodeId, usize> = HashMap::new();
+    let mut lowlinks: HashMap<NodeId, usize> = HashMap::new();
+    let mut stack: Vec<NodeId> = Vec::new();
+    let mut on_stack: HashSet<NodeId> = HashSet::new();
+    let mut sccs = Vec::new();
+
+    for root in node_ids {
+        if indices.contains_key(&root) {
+            continue;
+        }
+
+        // Explicit call stack of (node, successors, next successor position)
+        let mut call_stack: Vec<(NodeId, Vec<NodeId>, usize)> = Vec::new();
+        indices.insert(root, next_index);
+        lowlinks.insert(root, next_index);
+        next_index += 1;
+        stack.push(root);
+        on_stack.insert(root);
+        call_stack.push((root, successors(root), 0));
+
+        while let Some((v, succ, pos)) = call_stack.last_mut() {
+            let v = *v;
+            if *pos < succ.len() {
+                let w = succ[*pos];
+                *pos += 1;
+                if let Entry::Vacant(entry) = indices.entry(w) {
+                    entry.insert(next_index);
+                    lowlinks.insert(w, next_index);
+                    next_index += 1;
+                    stack.push(w);
+                    on_stack.insert(w);
+                    call_stack.push((w, successors(w), 0));
+                } else if on_stack.contains(&w) {
+                    let low = lowlinks[&v].min(indices[&w]);
+                    lowlinks.insert(v, low);
+                }
+                continue;
+            }
+
+            call_stack.pop();
+            if let Some((parent, _, _)) = call_stack.last() {
+                let low = lowlinks[parent].min(lowlinks[&v]);
+                lowlinks.insert(*parent, low);
+            }
+
+            if lowlinks[&v] == indices[&v] {
+                let mut scc = Vec::new();
+                while let Some(w) = stack.pop() {
+                    on_stack.remove(&w);
+                    scc.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                scc.sort_unstable();
+                sccs.push(scc);
+            }
+        }
+    }
+
+    sccs
+}
+
+/// Propose a small set of edges whose removal makes the graph acyclic.
+///
+/// Computes a heuristic minimum-weight feedback edge set, restricted to edges of
+/// the given types. Each strongly connected component is linearized with the
+/// greedy Eades-Lin-Smyth ordering (weighted by `weight`), edges pointing
+/// backwards in that order are selected, and selected edges that can be kept
+/// without reintroducing a cycle are dropped again. Self-loops are always selected.
+///
+/// # Parameters
+/// - `graph`: The graph to analyze
+/// - `edge_types`: Edge types that form the dependency relation
+/// - `weight`: Cost of removing an edge (e.g. number of imported symbols)
+///
+/// # Returns
+/// IDs of the edges to remove, sorted ascending
+pub fn minimum_feedback_edges(
+    graph: &CodeGraph,
+    edge_types: &[EdgeType],
+    weight: impl Fn(&Edge) -> f64,
+) -> Result<Vec<EdgeId>> {
+    let mut removed = Vec::new();
+
+    for scc in strongly_connected_components_by_type(graph, edge_types) {
+        let members: HashSet<NodeId> = scc.iter().copied().collect();
+        let mut internal: Vec<&Edge> = Vec::new();
+        for &node_id in &scc {
+            for edge in graph.outgoing_edges(node_id) {
+                if edge_types.contains(&edge.edge_type) && members.contains(&edge.target_id) {
+                    internal.push(edge);
+                }
+            }
+        }
+        internal.sort_unstable_by_key(|edge| edge.id);
+
+        // Self-loops can only be broken by removing them
+        let (self_loops, internal): (Vec<&Edge>, Vec<&Edge>) = internal
+            .into_iter()
+            .partition(|edge| edge.source_id == edge.target_id);
+        removed.extend(self_loops.iter().map(|edge| edge.id));
+
+        if scc.len() < 2 {
+            continue;
+        }
+
+        let order = eades_ordering(&scc, &internal, &weight);
+        let position: HashMap<NodeId, usize> =
+            order.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+
+        let (mut backward, forward): (Vec<&Edge>, Vec<&Edge>) = internal
+            .into_iter()
+            .partition(|edge| position[&edge.source_id] > position[&edge.target_id]);
+
+        // Forward edges form a DAG; try to restore the most valuable backward edges
+        let mut kept: HashMap<NodeId, Vec<NodeId>> = HashMap::new();
+        for edge in &forward {
+            kept.entry(edge.source_id).or_default().push(edge.target_id);
+        }
+        backward.sort_by(|a, b| {
+            weight(b)
+                .partial_cmp(&weight(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.id.cmp(&b.id))
+        });
+        for edge in backward {
+            if reaches(&kept, edge.target_id, edge.source_id) {
+                removed.push(edge.id);
+            } else {
+                kept.entry(edge.source_id).or_default().push(edge.target_id);
+            }
+        }
+    }
+
+    removed.sort_unstable();
+    Ok(removed)
+}
+
+/// Greedy Eades-Lin-Smyth linear ordering of the nodes of one component.
+fn eades_ordering(
+    nodes: &[NodeId],
+    edges: &[&Edge],
+    weight: &impl Fn(&Edge) -> f64,
+) -> Vec<NodeId> {
+    let mut out_w: BTreeMap<NodeId, BTreeMap<NodeId, f64>> = BTreeMap::new();
+    let mut in_w: BTreeMap<NodeId, BTreeMap<NodeId, f64>> = BTreeMap::new();
+    for &node in nodes {
+        out_w.insert(node, BTreeMap::new());
+        in_w.insert(node, BTreeMap::new());
+    }
+    for edge in edges {
+        let w = weight(edge);
+        *out_w
+            .get_mut(&edge.source_id)
+            .unwrap()
+            .entry(edge.target_id)
+            .or_insert(0.0) += w;
+        *in_w
+            .get_mut(&edge.target_id)
+            .unwrap()
+            .entry(edge.source_id)
+            .or_insert(0.0) += w;
+    }
+
+    let mut remaining: BTreeSet<NodeId> = nodes.iter().copied().collect();
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+
+    let detach = |node: NodeId,
+                  out_w: &mut BTreeMap<NodeId, BTreeMap<NodeId, f64>>,
+                  in_w: &mut BTreeMap<NodeId, BTreeMap<NodeId, f64>>| {
+        for target in out_w.remove(&node).unwrap_or_default().into_keys() {
+            if let Some(incoming) = in_w.get_mut(&target) {
+                incoming.remove(&node);
+            }
+        }
+        for source in in_w.remove(&node).unwrap_or_default().into_keys() {
+            if let Some(outgoing) = out_w.get_mut(&source) {
+                outgoing.remove(&node);
+            }
+        }
+    };
+
+    while !remaining.is_empty() {
+        let sink = remaining.iter().copied().find(|n| out_w[n].is_empty());
+        if let Some(node) = sink {
+            tail.push(node);
+            remaining.remove(&node);
+            detach(node, &mut out_w, &mut in_w);
+            continue;
+        }
+
+        let source = remaining.iter().copied().find(|n| in_w[n].is_empty());
+        let node = source.unwrap_or_else(|| {
+            // Pick the node whose outgoing weight exceeds its incoming weight the most
+            let delta = |n: &NodeId| out_w[n].values().sum::<f64>() - in_w[n].values().sum::<f64>();
+            remaining
+                .iter()
+                .copied()
+                .fold(None::<(NodeId, f64)>, |best, n| {
+                    let d = delta(&n);
+                    match best {
+                        Some((_, best_d)) if best_d >= d => best,
+                        _ => Some((n, d)),
+                    }
+                })
+                .map(|(n, _)| n)
+                .expect("remaining is not empty")
+        });
+        head.push(node);
+        remaining.remove(&node);
+        detach(node, &mut out_w, &mut in_w);
+    }
+
+    head.extend(tail.into_iter().rev());
+    head
+}
+
+/// Check whether `to` is reachable from `from` in a plain adjacency map.
+fn reaches(adjacency: &HashMap<NodeId, Vec<NodeId>>, from: NodeId, to: NodeId) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![from];
+    while let Some(current) = stack.pop() {
+        if current == to {
+            return true;
+        }
+        if visited.insert(current) {
+            if let Some(next) = adjacency.get(&current) {
+                stack.extend(next.iter().copied());
+            }
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,9 +4,16 @@
 //! Main CodeGraph interface for graph operations.
 
 use super::property::PropertyMap;
+use super::reachability::ReachabilityIndex;
+use super::spans::SpanIndex;
+use super::subgraph::{MergeMapping, MergeOptions, SubgraphSpec};
 use super::types::{Direction, Edge, EdgeId, EdgeType, Node, NodeId, NodeType};
 use crate::error::{GraphError, Result};
-use crate::storage::StorageBackend;
+use crate::search::{
+    SearchHit, SymbolFilter, SymbolIndex, SymbolMatch, TextIndex, TextIndexConfig, VectorIndex,
+    VectorIndexConfig, VectorMatch, VECTOR_HEADER_KEY, VECTOR_RECORD_PREFIX,
+};
+use crate::storage::{BatchOperation, StorageBackend};
 use log::{debug, info, trace};
 use std::collections::{HashMap, HashSet};
 #[cfg(feature = "rocksdb-backend")]
@@ -27,6 +34,16 @@ pub struct CodeGraph {
     // Adjacency indexes for O(1) neighbor lookups
     adjacency_out: HashMap<NodeId, HashSet<EdgeId>>,
     adjacency_in: HashMap<NodeId, HashSet<EdgeId>>,
+    // Per-file index of node source spans for position lookups
+    spans: SpanIndex,
+    // Optional reachability index, kept in sync with mutations
+    reachability: Option<ReachabilityIndex>,
+    // Optional full-text search index, kept in sync with node mutations
+    search: Option<TextIndex>,
+    // Optional fuzzy symbol index, kept in sync with node mutations
+    symbols: Option<SymbolIndex>,
+    // Optional vector similarity index, kept in sync with node mutations and persisted
+    vectors: Option<VectorIndex>,
 }
 
 impl CodeGraph {
@@ -46,6 +63,11 @@ impl CodeGraph {
             edges: HashMap::new(),
             adjacency_out: HashMap::new(),
             adjacency_in: HashMap::new(),
+            spans: SpanIndex::default(),
+            reachability: None,
+            search: None,
+            symbols: None,
+            vectors: None,
         };
 
         // Load graph state from storage
@@ -101,21 +123,41 @@ impl CodeGraph {
     ///
     /// # Errors
     ///
-    /// Returns [`GraphError::Storage`] if persistence fails.
+    /// Returns [`GraphError::Storage`] if persistence fails, leaving the graph
+    /// unchanged.
     pub fn add_node(&mut self, node_type: NodeType, properties: PropertyMap) -> Result<NodeId> {
-        let node_id = self.next_node_id();
+        // The ID is only taken once the node is stored
+        let node_id = self.node_counter;
         debug!("Adding node: id={node_id}, type={node_type}");
         let node = Node::new(node_id, node_type, properties);
 
-        // Serialize and store
+        // Serialize
         let key = format!("node:{node_id}");
         let value = serde_json::to_vec(&node)
             .map_err(|e| GraphError::serialization("Failed to serialize node", Some(e)))?;
 
-        self.storage.put(key.as_bytes(), &value)?;
+        // Store the node together with its vector index links
+        if let Some(index) = self.vectors.as_mut() {
+            index.on_node_changed(node_id, &node);
+        }
+        let operations = vec![BatchOperation::Put {
+            key: key.into_bytes(),
+            value,
+        }];
+        if let Err(e) = self.write_with_vector_changes(operations) {
+            if let Some(index) = self.vectors.as_mut() {
+                index.on_node_removed(node_id);
+            }
+            return Err(e);
+        }
 
         // Update in-memory cache
+        self.node_counter += 1;
         self.nodes.insert(node_id, node);
+        if let Some(index) = self.reachability.as_mut() {
+            index.on_node_added(node_id);
+        }
+        self.update_node_indexes(node_id);
         trace!("Node {node_id} added successfully");
 
         Ok(node_id)
@@ -134,6 +176,10 @@ impl CodeGraph {
 
     /// Get a mutable reference to a node by ID.
     ///
+    /// Changes made through this reference are not seen by the position, search,
+    /// symbol and vector indexes; use [`update_node_properties`](Self::update_node_properties) or
+    /// rebuild the indexes afterwards.
+    ///
     /// # Errors
     ///
     /// Returns [`GraphError::NodeNotFound`] if the node doesn't exist.
@@ -151,9 +197,10 @@ impl CodeGraph {
     ///
     /// # Errors
     ///
-    /// Returns error if node not found or persistence fails.
+    /// Returns error if node not found or persistence fails; the node is then
+    /// left unchanged.
     pub fn update_node_properties(&mut self, id: NodeId, properties: PropertyMap) -> Result<()> {
-        let node = self.get_node_mut(id)?;
+        let mut node = self.get_node(id)?.clone();
 
         // Merge properties
         for (key, value) in properties.iter() {
@@ -162,10 +209,25 @@ impl CodeGraph {
 
         // Persist updated node
         let key = format!("node:{id}");
-        let value = serde_json::to_vec(node)
+        let value = serde_json::to_vec(&node)
             .map_err(|e| GraphError::serialization("Failed to serialize node", Some(e)))?;
 
-        self.storage.put(key.as_bytes(), &value)?;
+        if let Some(index) = self.vectors.as_mut() {
+            index.on_node_changed(id, &node);
+        }
+        let operations = vec![BatchOperation::Put {
+            key: key.into_bytes(),
+            value,
+        }];
+        if let Err(e) = self.write_with_vector_changes(operations) {
+            if let Some(index) = self.vectors.as_mut() {
+                index.on_node_changed(id, &self.nodes[&id]);
+            }
+            return Err(e);
+        }
+
+        self.nodes.insert(id, node);
+        self.update_node_indexes(id);
 
         Ok(())
     }
@@ -204,12 +266,27 @@ impl CodeGraph {
             self.delete_edge(edge_id)?;
         }
 
-        // Delete node from storage
+        // Delete node from storage, along with its vector index links
+        if let Some(index) = self.vectors.as_mut() {
+            index.on_node_removed(id);
+        }
         let key = format!("node:{id}");
-        self.storage.delete(key.as_bytes())?;
+        let operations = vec![BatchOperation::Delete {
+            key: key.into_bytes(),
+        }];
+        if let Err(e) = self.write_with_vector_changes(operations) {
+            if let Some(index) = self.vectors.as_mut() {
+                index.on_node_changed(id, &self.nodes[&id]);
+            }
+            return Err(e);
+        }
 
         // Remove from cache
         self.nodes.remove(&id);
+        if let Some(index) = self.reachability.as_mut() {
+            index.on_node_removed(id);
+        }
+        self.update_node_indexes(id);
 
         Ok(())
     }
@@ -265,6 +342,7 @@ impl CodeGraph {
 
         // Update in-memory cache
         self.edges.insert(edge_id, edge);
+        self.update_reachability(|index, graph| index.on_edge_added(graph, &graph.edges[&edge_id]));
 
         Ok(edge_id)
     }
@@ -311,7 +389,9 @@ impl CodeGraph {
         }
 
         // Remove from cache
-        self.edges.remove(&id);
+        if let Some(edge) = self.edges.remove(&id) {
+            self.update_reachability(|index, graph| index.on_edge_removed(graph, &edge));
+        }
 
         Ok(())
     }
@@ -398,6 +478,28 @@ impl CodeGraph {
         Ok(edges)
     }
 
+    /// Iterate over the outgoing edges of a node.
+    ///
+    /// Yields nothing if the node doesn't exist or has no outgoing edges.
+    pub fn outgoing_edges(&self, node_id: NodeId) -> impl Iterator<Item = &Edge> {
+        self.adjacency_out
+            .get(&node_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|edge_id| self.edges.get(edge_id))
+    }
+
+    /// Iterate over the incoming edges of a node.
+    ///
+    /// Yields nothing if the node doesn't exist or has no incoming edges.
+    pub fn incoming_edges(&self, node_id: NodeId) -> impl Iterator<Item = &Edge> {
+        self.adjacency_in
+            .get(&node_id)
+            .into_iter()
+            .flatten()
+            .filter_map(|edge_id| self.edges.get(edge_id))
+    }
+
     /// Add multiple nodes in an atomic batch operation.
     ///
     /// Either all nodes are added or none are.
@@ -407,11 +509,11 @@ impl CodeGraph {
     /// Vector of assigned node IDs in the same order as input.
     pub fn add_nodes_batch(&mut self, nodes: Vec<(NodeType, PropertyMap)>) -> Result<Vec<NodeId>> {
         debug!("Adding batch of {} nodes", nodes.len());
-        let mut node_ids = Vec::with_capacity(nodes.len());
+        let mut batch = Vec::with_capacity(nodes.len());
         let mut operations = Vec::with_capacity(nodes.len());
 
-        for (node_type, properties) in nodes {
-            let node_id = self.next_node_id();
+        // IDs are only taken once the nodes are stored
+        for ((node_type, properties), node_id) in nodes.into_iter().zip(self.node_counter..) {
             let node = Node::new(node_id, node_type, properties);
 
             let key = format!("node:{node_id}");
@@ -422,12 +524,35 @@ impl CodeGraph {
                 key: key.into_bytes(),
                 value,
             });
+            batch.push(node);
+        }
 
-            self.nodes.insert(node_id, node);
-            node_ids.push(node_id);
+        if let Some(index) = self.vectors.as_mut() {
+            for node in &batch {
+                index.on_node_changed(node.id, node);
+            }
+        }
+        if let Err(e) = self.write_with_vector_changes(operations) {
+            if let Some(index) = self.vectors.as_mut() {
+                for node in &batch {
+                    index.on_node_removed(node.id);
+                }
+            }
+            return Err(e);
         }
 
-        self.storage.write_batch(operations)?;
+        let node_ids: Vec<NodeId> = batch.iter().map(|node| node.id).collect();
+        self.node_counter += batch.len() as NodeId;
+        self.nodes
+            .extend(batch.into_iter().map(|node| (node.id, node)));
+        if let Some(index) = self.reachability.as_mut() {
+            for &node_id in &node_ids {
+                index.on_node_added(node_id);
+            }
+        }
+        for &node_id in &node_ids {
+            self.update_node_indexes(node_id);
+        }
         trace!("Batch of {} nodes added successfully", node_ids.len());
 
         Ok(node_ids)
@@ -483,6 +608,11 @@ impl CodeGraph {
         }
 
         self.storage.write_batch(operations)?;
+        self.update_reachability(|index, graph| {
+            for edge_id in &edge_ids {
+                index.on_edge_added(graph, &graph.edges[edge_id]);
+            }
+        });
 
         Ok(edge_ids)
     }
@@ -539,6 +669,20 @@ impl CodeGraph {
         self.nodes.clear();
         self.adjacency_out.clear();
         self.adjacency_in.clear();
+        self.spans.clear();
+        self.update_reachability(|index, graph| {
+            *index = ReachabilityIndex::build(graph, index.edge_types());
+        });
+        if let Some(index) = self.search.as_mut() {
+            index.clear();
+        }
+        if let Some(index) = self.symbols.as_mut() {
+            index.clear();
+        }
+        if let Some(index) = self.vectors.as_mut() {
+            index.clear();
+        }
+        self.save_vector_index()?;
 
         // Reset counters
         self.node_counter = 0;
@@ -576,6 +720,49 @@ impl CodeGraph {
         crate::query::QueryBuilder::new(self)
     }
 
+    /// Create a new edge query builder.
+    ///
+    /// See [`crate::query::EdgeQueryBuilder`] for the available filters.
+    pub fn edge_query<'a>(&'a self) -> crate::query::EdgeQueryBuilder<'a> {
+        crate::query::EdgeQueryBuilder::new(self)
+    }
+
+    /// Find all matches of a subgraph pattern.
+    ///
+    /// See [`crate::query::Pattern`] for how patterns are built.
+    pub fn match_pattern(
+        &self,
+        pattern: &crate::query::Pattern,
+    ) -> Result<Vec<crate::query::PatternMatch>> {
+        pattern.execute(self)
+    }
+
+    /// Parse and run a textual query (see [`crate::query::language`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use codegraph::{helpers, CodeGraph, PropertyValue};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// let file = helpers::add_file(&mut graph, "src/main.rs", "rust")?;
+    /// let main = helpers::add_function(&mut graph, file, "main", 1, 10)?;
+    /// let exec = helpers::add_function(&mut graph, file, "exec", 12, 20)?;
+    /// helpers::add_call(&mut graph, main, exec, 5)?;
+    ///
+    /// let result = graph.execute_query(
+    ///     "MATCH (f:Function)-[:Calls*1..3]->(g:Function {name: 'exec'}) RETURN f.name",
+    /// )?;
+    /// assert_eq!(result.rows, vec![vec![PropertyValue::from("main")]]);
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    pub fn execute_query(&self, query: &str) -> Result<crate::query::QueryResult> {
+        crate::query::GraphQuery::parse(query)?.execute(self)
+    }
+
     /// Explicitly flush any buffered writes to disk.
     ///
     /// Most operations are durable immediately, but this ensures WAL is synced.
@@ -610,8 +797,8 @@ impl CodeGraph {
 
     /// Persist all in-memory data to a storage backend.
     ///
-    /// Opens the given backend, writes all nodes, edges, and counters,
-    /// then drops the backend (releasing locks). The graph continues
+    /// Opens the given backend, writes all nodes, edges, counters and the
+    /// vector index, then drops the backend (releasing locks). The graph continues
     /// operating with its current (in-memory) backend.
     pub fn persist_to(&self, mut backend: Box<dyn StorageBackend>) -> Result<()> {
         info!(
@@ -620,7 +807,88 @@ impl CodeGraph {
             self.edges.len()
         );
 
-        // Build batch of all nodes and edges
+        backend.write_batch(self.record_operations()?)?;
+        backend.flush()?;
+
+        info!("Persist complete");
+        Ok(())
+    }
+
+    // Private helper methods
+
+    /// Run an update against the reachability index, if one is enabled.
+    fn update_reachability(&mut self, update: impl FnOnce(&mut ReachabilityIndex, &CodeGraph)) {
+        if let Some(mut index) = self.reachability.take() {
+            update(&mut index, self);
+            self.reachability = Some(index);
+        }
+    }
+
+    /// Bring the position, search and symbol indexes up to date with a node's
+    /// current state (added, changed or removed).
+    fn update_node_indexes(&mut self, node_id: NodeId) {
+        let node = self.nodes.get(&node_id);
+        match node {
+            Some(node) => self.spans.on_node_changed(node_id, node),
+            None => self.spans.on_node_removed(node_id),
+        }
+        if let Some(index) = self.search.as_mut() {
+            match node {
+                Some(node) => index.on_node_changed(node_id, node),
+                None => index.on_node_removed(node_id),
+            }
+        }
+        if let Some(index) = self.symbols.as_mut() {
+            match node {
+                Some(node) => index.on_node_changed(node_id, node),
+                None => index.on_node_removed(node_id),
+            }
+        }
+    }
+
+    /// Write a batch together with the pending vector index changes. The changes
+    /// stay pending if the write fails, so a later write still persists them.
+    fn write_with_vector_changes(&mut self, mut operations: Vec<BatchOperation>) -> Result<()> {
+        if let Some(index) = self.vectors.as_ref() {
+            operations.extend(index.changes()?);
+        }
+        if operations.is_empty() {
+            return Ok(());
+        }
+        self.storage.write_batch(operations)?;
+        if let Some(index) = self.vectors.as_mut() {
+            index.changes_written();
+        }
+        Ok(())
+    }
+
+    /// Write the vector index changes to storage.
+    fn save_vector_index(&mut self) -> Result<()> {
+        self.write_with_vector_changes(Vec::new())
+    }
+
+    /// Storage deletes for every persisted vector index key.
+    fn stored_vector_index_deletes(&self) -> Result<Vec<BatchOperation>> {
+        let mut operations = vec![BatchOperation::Delete {
+            key: VECTOR_HEADER_KEY.to_vec(),
+        }];
+        for (key, _) in self.storage.scan_prefix(VECTOR_RECORD_PREFIX)? {
+            operations.push(BatchOperation::Delete { key });
+        }
+        Ok(operations)
+    }
+
+    fn load_vector_index(&mut self) -> Result<()> {
+        let Some(header) = self.storage.get(VECTOR_HEADER_KEY)? else {
+            return Ok(());
+        };
+        let records = self.storage.scan_prefix(VECTOR_RECORD_PREFIX)?;
+        self.vectors = Some(VectorIndex::restore(self, &header, records)?);
+        self.save_vector_index()
+    }
+
+    /// Storage writes for every node and edge, the ID counters and the vector index.
+    fn record_operations(&self) -> Result<Vec<BatchOperation>> {
         let mut operations = Vec::with_capacity(self.nodes.len() + self.edges.len() + 1);
 
         for (&id, node) in &self.nodes {
@@ -655,19 +923,49 @@ impl CodeGraph {
             value: counter_value,
         });
 
-        backend.write_batch(operations)?;
-        backend.flush()?;
+        if let Some(index) = self.vectors.as_ref() {
+            operations.extend(index.snapshot()?);
+        }
 
-        info!("Persist complete");
-        Ok(())
+        Ok(operations)
     }
 
-    // Private helper methods
+    /// Build an in-memory graph from copies of nodes and edges, keeping their IDs.
+    ///
+    /// New IDs continue from the given counters, which must exceed every copied ID.
+    pub(crate) fn from_parts(
+        nodes: Vec<Node>,
+        edges: Vec<Edge>,
+        (node_counter, edge_counter): (NodeId, EdgeId),
+    ) -> Result<Self> {
+        let mut graph = Self::in_memory()?;
+        graph.node_counter = node_counter;
+        graph.edge_counter = edge_counter;
+        for node in nodes {
+            graph.nodes.insert(node.id, node);
+        }
+        for edge in edges {
+            graph
+                .adjacency_out
+                .entry(edge.source_id)
+                .or_default()
+                .insert(edge.id);
+            graph
+                .adjacency_in
+                .entry(edge.target_id)
+                .or_default()
+                .insert(edge.id);
+            graph.edges.insert(edge.id, edge);
+        }
+        graph.spans = SpanIndex::build(graph.iter_nodes());
+        let operations = graph.record_operations()?;
+        graph.storage.write_batch(operations)?;
+        Ok(graph)
+    }
 
-    fn next_node_id(&mut self) -> NodeId {
-        let id = self.node_counter;
-        self.node_counter += 1;
-        id
+    /// Next node and edge IDs to be assigned.
+    pub(super) fn id_counters(&self) -> (NodeId, EdgeId) {
+        (self.node_counter, self.edge_counter)
     }
 
     fn next_edge_id(&mut self) -> EdgeId {
@@ -739,6 +1037,11 @@ impl CodeGraph {
             self.edges.insert(edge.id, edge);
         }
 
+        self.spans = SpanIndex::build(self.iter_nodes());
+
+        // Restore the persisted vector index, if any
+        self.load_vector_index()?;
+
         Ok(())
     }
 
@@ -812,6 +1115,467 @@ impl CodeGraph {
         super::algorithms::find_all_paths(self, start, end, max_depth)
     }
 
+    // ===== Reachability Index =====
+
+    /// Build (or replace) the reachability index over the given edge types.
+    ///
+    /// Once built, the index is kept up to date as nodes and edges are added.
+    /// Removing an indexed edge, directly or by deleting one of its nodes, marks
+    /// it stale unless a parallel edge keeps the endpoints connected;
+    /// [`reaches`](Self::reaches) then falls back to a traversal, so it never
+    /// reports a removed path, until
+    /// [`rebuild_reachability_index`](Self::rebuild_reachability_index) is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codegraph::{helpers, CodeGraph, EdgeType};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// let a = helpers::add_file(&mut graph, "a.rs", "rust")?;
+    /// let b = helpers::add_file(&mut graph, "b.rs", "rust")?;
+    /// helpers::add_import(&mut graph, a, b, vec![])?;
+    ///
+    /// graph.build_reachability_index(&[EdgeType::Imports, EdgeType::ImportsFrom]);
+    /// assert!(graph.reaches(a, b)?);
+    /// assert!(!graph.reaches(b, a)?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_reachability_index(&mut self, edge_types: &[EdgeType]) {
+        info!("Building reachability index over {edge_types:?}");
+        self.reachability = Some(ReachabilityIndex::build(self, edge_types));
+    }
+
+    /// Rebuild a stale reachability index with its current edge types.
+    ///
+    /// Does nothing if no index has been built.
+    pub fn rebuild_reachability_index(&mut self) {
+        if let Some(edge_types) = self.reachability.as_ref().map(|i| i.edge_types().to_vec()) {
+            self.build_reachability_index(&edge_types);
+        }
+    }
+
+    /// Drop the reachability index.
+    pub fn drop_reachability_index(&mut self) {
+        self.reachability = None;
+    }
+
+    /// Get the reachability index, if one has been built.
+    pub fn reachability_index(&self) -> Option<&ReachabilityIndex> {
+        self.reachability.as_ref()
+    }
+
+    /// Check whether `to` is reachable from `from` along the indexed edge types.
+    ///
+    /// Every node reaches itself. Answers from the index in near-constant time;
+    /// if the index is stale, falls back to a breadth-first search.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::NodeNotFound`] if either node doesn't exist, or
+    /// [`GraphError::InvalidOperation`] if no reachability index has been built.
+    pub fn reaches(&self, from: NodeId, to: NodeId) -> Result<bool> {
+        self.get_node(from)?;
+        self.get_node(to)?;
+
+        let index = self
+            .reachability
+            .as_ref()
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: "No reachability index; call build_reachability_index first".to_string(),
+            })?;
+
+        if let Some(answer) = index.reaches(from, to) {
+            return Ok(answer);
+        }
+
+        debug!("Reachability index is stale, falling back to traversal");
+        let edge_types = index.edge_types();
+        let mut visited = HashSet::from([from]);
+        let mut queue = std::collections::VecDeque::from([from]);
+        while let Some(current) = queue.pop_front() {
+            if current == to {
+                return Ok(true);
+            }
+            for edge in self.outgoing_edges(current) {
+                if edge_types.contains(&edge.edge_type) && visited.insert(edge.target_id) {
+                    queue.push_back(edge.target_id);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    // ===== Search Index =====
+
+    /// Build (or replace) the full-text search index.
+    ///
+    /// Once built, the index follows node insertions, property updates made with
+    /// [`update_node_properties`](Self::update_node_properties), and deletions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codegraph::search::TextIndexConfig;
+    /// use codegraph::{helpers, CodeGraph};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// let file = helpers::add_file(&mut graph, "src/net.rs", "rust")?;
+    /// let handler = helpers::add_function(&mut graph, file, "handle_http_request", 1, 30)?;
+    ///
+    /// graph.build_search_index(TextIndexConfig::default());
+    /// let hits = graph.search("HttpRequest", 5)?;
+    /// assert_eq!(hits[0].node_id, handler);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_search_index(&mut self, config: TextIndexConfig) {
+        info!("Building search index over {:?}", config.fields);
+        self.search = Some(TextIndex::build(self, config));
+    }
+
+    /// Rebuild the search index with its current configuration.
+    ///
+    /// Needed after editing nodes through [`get_node_mut`](Self::get_node_mut).
+    /// Does nothing if no index has been built.
+    pub fn rebuild_search_index(&mut self) {
+        if let Some(config) = self.search.as_ref().map(|i| i.config().clone()) {
+            self.build_search_index(config);
+        }
+    }
+
+    /// Drop the search index.
+    pub fn drop_search_index(&mut self) {
+        self.search = None;
+    }
+
+    /// Get the search index, if one has been built.
+    pub fn search_index(&self) -> Option<&TextIndex> {
+        self.search.as_ref()
+    }
+
+    /// Rank nodes against a free-text query, returning at most `limit` hits.
+    ///
+    /// See [`TextIndex::search`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if no search index has been built.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        let index = self
+            .search
+            .as_ref()
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: "No search index; call build_search_index first".to_string(),
+            })?;
+        Ok(index.search(query, limit))
+    }
+
+    // ===== Symbol Index =====
+
+    /// Build (or replace) the fuzzy symbol index over `name` and `qualified_name`.
+    ///
+    /// Once built, the index is kept up to date like the search index.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codegraph::search::SymbolFilter;
+    /// use codegraph::{helpers, CodeGraph, NodeType};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// let file = helpers::add_file(&mut graph, "app.py", "python")?;
+    /// let handler = helpers::add_function(&mut graph, file, "handle_request", 1, 30)?;
+    ///
+    /// graph.build_symbol_index();
+    /// let filter = SymbolFilter::default().node_type(NodeType::Function).language("python");
+    /// let matches = graph.find_symbols("hndlReq", &filter, 10)?;
+    /// assert_eq!(matches[0].node_id, handler);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn build_symbol_index(&mut self) {
+        info!("Building symbol index");
+        self.symbols = Some(SymbolIndex::build(self));
+    }
+
+    /// Rebuild the symbol index, e.g. after editing nodes through
+    /// [`get_node_mut`](Self::get_node_mut). Does nothing if no index has been built.
+    pub fn rebuild_symbol_index(&mut self) {
+        if self.symbols.is_some() {
+            self.build_symbol_index();
+        }
+    }
+
+    /// Drop the symbol index.
+    pub fn drop_symbol_index(&mut self) {
+        self.symbols = None;
+    }
+
+    /// Get the symbol index, if one has been built.
+    pub fn symbol_index(&self) -> Option<&SymbolIndex> {
+        self.symbols.as_ref()
+    }
+
+    /// Find symbols matching an abbreviated or misspelled query.
+    ///
+    /// See [`SymbolIndex::find`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if no symbol index has been built.
+    pub fn find_symbols(
+        &self,
+        query: &str,
+        filter: &SymbolFilter,
+        limit: usize,
+    ) -> Result<Vec<SymbolMatch>> {
+        let index = self
+            .symbols
+            .as_ref()
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: "No symbol index; call build_symbol_index first".to_string(),
+            })?;
+        Ok(index.find(self, query, filter, limit))
+    }
+
+    // ===== Vector Index =====
+
+    /// Build (or replace) the vector similarity index.
+    ///
+    /// Unlike the other indexes, the vector index is persisted through the storage
+    /// backend: it is restored when the graph is reopened and every node mutation
+    /// updates the stored links.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codegraph::search::{SymbolFilter, VectorIndexConfig};
+    /// use codegraph::{helpers, CodeGraph, NodeType, PropertyMap};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// graph.build_vector_index(VectorIndexConfig::default())?;
+    ///
+    /// let file = helpers::add_file(&mut graph, "main.go", "go")?;
+    /// let parse = helpers::add_function(&mut graph, file, "parse", 1, 10)?;
+    /// graph.update_node_properties(
+    ///     parse,
+    ///     PropertyMap::new().with("embedding", vec![0.9f32, 0.1, 0.0]),
+    /// )?;
+    ///
+    /// // Go functions similar to a query embedding
+    /// let filter = SymbolFilter::default().node_type(NodeType::Function).language("go");
+    /// let matches = graph.nearest_neighbors(&[1.0, 0.0, 0.0], 5, &filter)?;
+    /// assert_eq!(matches[0].node_id, parse);
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::Storage`] if persisting the index fails.
+    pub fn build_vector_index(&mut self, config: VectorIndexConfig) -> Result<()> {
+        info!("Building vector index over '{}'", config.property);
+        let mut operations = self.stored_vector_index_deletes()?;
+        let mut index = VectorIndex::build(self, config);
+        operations.extend(index.changes()?);
+        self.storage.write_batch(operations)?;
+        index.changes_written();
+        self.vectors = Some(index);
+        Ok(())
+    }
+
+    /// Rebuild the vector index with its current configuration, e.g. after editing
+    /// nodes through [`get_node_mut`](Self::get_node_mut). Does nothing if no index
+    /// has been built.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::Storage`] if persisting the index fails.
+    pub fn rebuild_vector_index(&mut self) -> Result<()> {
+        match self.vectors.as_ref().map(|i| i.config().clone()) {
+            Some(config) => self.build_vector_index(config),
+            None => Ok(()),
+        }
+    }
+
+    /// Drop the vector index and remove it from storage.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::Storage`] if deleting the stored index fails.
+    pub fn drop_vector_index(&mut self) -> Result<()> {
+        let operations = self.stored_vector_index_deletes()?;
+        self.storage.write_batch(operations)?;
+        self.vectors = None;
+        Ok(())
+    }
+
+    /// Get the vector index, if one has been built.
+    pub fn vector_index(&self) -> Option<&VectorIndex> {
+        self.vectors.as_ref()
+    }
+
+    /// Find the `k` nodes whose vectors are closest to `query`, among the nodes
+    /// accepted by `filter`.
+    ///
+    /// See [`VectorIndex::search`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if no vector index has been built or
+    /// the query does not fit the index.
+    pub fn nearest_neighbors(
+        &self,
+        query: &[f32],
+        k: usize,
+        filter: &SymbolFilter,
+    ) -> Result<Vec<VectorMatch>> {
+        let index = self.require_vector_index()?;
+        index.search(query, k, |node_id| filter.accepts(self, node_id))
+    }
+
+    /// Find the `k` nodes most similar to a node, excluding the node itself.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::NodeNotFound`] if the node doesn't exist, and
+    /// [`GraphError::InvalidOperation`] if no vector index has been built or the
+    /// node has no indexable vector.
+    pub fn similar_nodes(
+        &self,
+        node_id: NodeId,
+        k: usize,
+        filter: &SymbolFilter,
+    ) -> Result<Vec<VectorMatch>> {
+        let index = self.require_vector_index()?;
+        let node = self.get_node(node_id)?;
+        let vector = node
+            .properties
+            .get_vector(&index.config().property)
+            .filter(|_| index.contains(node_id))
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: format!("Node {node_id} has no indexed vector"),
+            })?;
+        index.search(vector, k, |id| id != node_id && filter.accepts(self, id))
+    }
+
+    fn require_vector_index(&self) -> Result<&VectorIndex> {
+        self.vectors
+            .as_ref()
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: "No vector index; call build_vector_index first".to_string(),
+            })
+    }
+
+    // ===== Position Lookup =====
+
+    /// Find the entities enclosing a source position, innermost first.
+    ///
+    /// `line` is 1-indexed and `column` is a 0-indexed byte offset within the line,
+    /// matching the `line_start` and `col_start` properties written by the parsers.
+    /// Every node with a `path`, `line_start` and `line_end` takes part; nodes
+    /// without `col_start`/`col_end` cover their first and last lines entirely.
+    /// Spans are end-exclusive. Parsed from a syntax tree they are nested or
+    /// disjoint; partially overlapping spans are all returned, ordered by
+    /// descending start.
+    ///
+    /// The position index is always maintained, so this is a binary search per
+    /// nesting level rather than a scan. Returns an empty chain if nothing in the
+    /// file encloses the position.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codegraph::{CodeGraph, NodeType, PropertyMap};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// let span = |name: &str, lines: (i64, i64)| {
+    ///     PropertyMap::new()
+    ///         .with("name", name)
+    ///         .with("path", "src/server.rs")
+    ///         .with("line_start", lines.0)
+    ///         .with("line_end", lines.1)
+    /// };
+    /// let server = graph.add_node(NodeType::Class, span("Server", (10, 40)))?;
+    /// let handle = graph.add_node(NodeType::Function, span("handle", (12, 20)))?;
+    ///
+    /// assert_eq!(graph.entity_at("src/server.rs", 15, 8), vec![handle, server]);
+    /// assert_eq!(graph.entity_at("src/server.rs", 30, 0), vec![server]);
+    /// assert!(graph.entity_at("src/server.rs", 50, 0).is_empty());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn entity_at(&self, path: &str, line: usize, column: usize) -> Vec<NodeId> {
+        self.spans.enclosing(path, line as i64, column as i64)
+    }
+
+    // ===== Subgraphs and Merging =====
+
+    /// Copy a selection of nodes, and the edges between them, into a new
+    /// in-memory graph.
+    ///
+    /// Accepts a [`SubgraphSpec`] or a plain list of node IDs. Copied nodes and
+    /// edges keep their IDs, and nodes added to the subgraph later are numbered
+    /// after this graph's, so IDs from both can be used side by side. Search,
+    /// symbol, vector and reachability indexes are not copied.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use codegraph::graph::subgraph::SubgraphSpec;
+    /// use codegraph::{helpers, CodeGraph};
+    ///
+    /// # fn example() -> codegraph::Result<()> {
+    /// let mut graph = CodeGraph::in_memory()?;
+    /// let file = helpers::add_file(&mut graph, "main.rs", "rust")?;
+    /// let main = helpers::add_function(&mut graph, file, "main", 1, 10)?;
+    /// let run = helpers::add_function(&mut graph, file, "run", 12, 20)?;
+    /// let exec = helpers::add_function(&mut graph, file, "exec", 22, 30)?;
+    /// helpers::add_call(&mut graph, main, run, 5)?;
+    /// helpers::add_call(&mut graph, run, exec, 15)?;
+    ///
+    /// let report = graph.extract_subgraph(SubgraphSpec::neighborhood([main], 1))?;
+    /// assert!(report.get_node(run).is_ok());
+    /// assert!(report.get_node(exec).is_err());
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::NodeNotFound`] if a seed node doesn't exist.
+    pub fn extract_subgraph(&self, spec: impl Into<SubgraphSpec>) -> Result<CodeGraph> {
+        super::subgraph::extract_subgraph(self, &spec.into())
+    }
+
+    /// Import every node and edge of another graph into this one.
+    ///
+    /// Imported nodes and edges receive fresh IDs; the returned [`MergeMapping`]
+    /// translates IDs of `other` into IDs of this graph. With deduplication
+    /// enabled in [`MergeOptions`], files with the same path or entities with
+    /// the same qualified name are merged into the existing node instead, which
+    /// is how graphs of shards indexed separately are combined.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::Storage`] if persistence fails.
+    pub fn merge(&mut self, other: &CodeGraph, options: MergeOptions) -> Result<MergeMapping> {
+        info!(
+            "Merging graph with {} nodes and {} edges",
+            other.node_count(),
+            other.edge_count()
+        );
+        super::subgraph::merge(self, other, options)
+    }
+
     // ===== Export Methods =====
 
     /// Export graph to Graphviz DOT format for visualization.
@@ -847,6 +1611,27 @@ impl CodeGraph {
         crate::export::export_json_filtered(self, node_filter, include_edges)
     }
 
+    /// Import a graph from the JSON written by [`export_json`](Self::export_json).
+    ///
+    /// Node and edge IDs are preserved. See [`crate::export::import_json`].
+    pub fn import_json(json: &str) -> Result<CodeGraph> {
+        crate::export::import_json(json)
+    }
+
+    /// Import the JSON written by [`export_json`](Self::export_json) into this graph.
+    ///
+    /// Imported nodes and edges receive fresh IDs, as with [`merge`](Self::merge);
+    /// the returned [`MergeMapping`] translates IDs in the JSON into IDs of this graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::Serialization`] if the JSON is invalid, in which case
+    /// this graph is left unchanged.
+    pub fn import_json_into(&mut self, json: &str, options: MergeOptions) -> Result<MergeMapping> {
+        let imported = crate::export::import_json(json)?;
+        self.merge(&imported, options)
+    }
+
     /// Export nodes to CSV file.
     ///
     /// **Warning**: Large graphs (>10K nodes) will produce warnings.
@@ -875,6 +1660,128 @@ impl CodeGraph {
         crate::export::export_csv(self, nodes_path, edges_path)
     }
 
+    /// Export nodes and relationships to `neo4j-admin` import CSV files.
+    ///
+    /// See [`crate::export::neo4j`].
+    pub fn export_neo4j_csv(
+        &self,
+        nodes_path: &std::path::Path,
+        relationships_path: &std::path::Path,
+    ) -> Result<()> {
+        self.check_export_size()?;
+        crate::export::export_neo4j_csv(self, nodes_path, relationships_path)
+    }
+
+    /// Export graph as a self-contained interactive HTML report.
+    ///
+    /// See [`crate::export::html`].
+    pub fn export_html(&self, options: &crate::export::HtmlOptions) -> Result<String> {
+        self.check_export_size()?;
+        crate::export::export_html(self, options)
+    }
+
+    /// Export an LSIF code intelligence index to a file.
+    ///
+    /// Relative `path` properties are resolved against `project_root`. See
+    /// [`crate::export::lsif`] for what is indexed.
+    pub fn export_lsif(
+        &self,
+        project_root: &std::path::Path,
+        path: &std::path::Path,
+    ) -> Result<()> {
+        crate::export::export_lsif(self, project_root, path)
+    }
+
+    /// Export graph as a Cypher script of idempotent `MERGE` statements.
+    pub fn export_cypher(&self, options: &crate::export::CypherOptions) -> Result<String> {
+        self.check_export_size()?;
+        crate::export::export_cypher(self, options)
+    }
+
+    /// Export graph to GraphML format for yEd, Cytoscape and Gephi.
+    ///
+    /// **Warning**: Large graphs (>10K nodes) will produce warnings.
+    /// Graphs over 100K nodes will fail.
+    pub fn export_graphml(&self) -> Result<String> {
+        self.check_export_size()?;
+        crate::export::export_graphml(self)
+    }
+
+    /// Export the node and edge types chosen by `filter` to GraphML.
+    pub fn export_graphml_filtered(&self, filter: &crate::export::ExportFilter) -> Result<String> {
+        crate::export::export_graphml_filtered(self, filter)
+    }
+
+    /// Export graph to GEXF format for Gephi.
+    ///
+    /// **Warning**: Large graphs (>10K nodes) will produce warnings.
+    /// Graphs over 100K nodes will fail.
+    pub fn export_gexf(&self) -> Result<String> {
+        self.check_export_size()?;
+        crate::export::export_gexf(self)
+    }
+
+    /// Export the node and edge types chosen by `filter` to GEXF.
+    pub fn export_gexf_filtered(&self, filter: &crate::export::ExportFilter) -> Result<String> {
+        crate::export::export_gexf_filtered(self, filter)
+    }
+
+    /// Export graph to a Mermaid flowchart for Markdown documents.
+    ///
+    /// Large graphs are collapsed to one node per file and trimmed to
+    /// `options.max_nodes`; see [`crate::export::diagram`].
+    pub fn export_mermaid_flowchart(
+        &self,
+        options: &crate::export::DiagramOptions,
+    ) -> Result<String> {
+        crate::export::export_mermaid_flowchart(self, options)
+    }
+
+    /// Export classes and interfaces to a Mermaid class diagram.
+    pub fn export_mermaid_class_diagram(
+        &self,
+        options: &crate::export::DiagramOptions,
+    ) -> Result<String> {
+        crate::export::export_mermaid_class_diagram(self, options)
+    }
+
+    /// Export classes and interfaces to a PlantUML class diagram.
+    pub fn export_plantuml_class_diagram(
+        &self,
+        options: &crate::export::DiagramOptions,
+    ) -> Result<String> {
+        crate::export::export_plantuml_class_diagram(self, options)
+    }
+
+    /// Export files and modules to a PlantUML component diagram.
+    pub fn export_plantuml_component_diagram(
+        &self,
+        options: &crate::export::DiagramOptions,
+    ) -> Result<String> {
+        crate::export::export_plantuml_component_diagram(self, options)
+    }
+
+    /// Export the call chain starting at a function to a Mermaid sequence diagram.
+    ///
+    /// Calls are followed in call-site order; participants are the containing
+    /// classes or files. See [`crate::export::sequence`].
+    pub fn export_mermaid_sequence(
+        &self,
+        entry: NodeId,
+        options: &crate::export::SequenceOptions,
+    ) -> Result<String> {
+        crate::export::export_mermaid_sequence(self, entry, options)
+    }
+
+    /// Export the call chain starting at a function to a PlantUML sequence diagram.
+    pub fn export_plantuml_sequence(
+        &self,
+        entry: NodeId,
+        options: &crate::export::SequenceOptions,
+    ) -> Result<String> {
+        crate::export::export_plantuml_sequence(self, entry, options)
+    }
+
     /// Export graph as RDF triples in N-Triples format.
     ///
     /// **Warning**: Large graphs (>10K nodes) will produce warnings.
@@ -884,6 +1791,71 @@ impl CodeGraph {
         crate::export::export_triples(self)
     }
 
+    /// Export graph as RDF (N-Triples or Turtle) using the codegraph ontology.
+    ///
+    /// See [`crate::export::rdf`]. Subject to the same size limits as other
+    /// in-memory exports; use [`export_rdf_to`](Self::export_rdf_to) for large graphs.
+    pub fn export_rdf(&self, options: &crate::export::RdfOptions) -> Result<String> {
+        self.check_export_size()?;
+        crate::export::export_rdf(self, options)
+    }
+
+    /// Stream graph in Graphviz DOT format to a writer.
+    ///
+    /// Streaming exports have no size limit; wrap files in a
+    /// [`std::io::BufWriter`].
+    pub fn export_dot_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_dot_to(self, writer)
+    }
+
+    /// Stream graph in D3.js-compatible JSON format to a writer.
+    pub fn export_json_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_json_to(self, writer)
+    }
+
+    /// Stream nodes as CSV to a writer.
+    pub fn export_csv_nodes_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_csv_nodes_to(self, writer)
+    }
+
+    /// Stream edges as CSV to a writer.
+    pub fn export_csv_edges_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_csv_edges_to(self, writer)
+    }
+
+    /// Stream graph in GraphML format to a writer.
+    pub fn export_graphml_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_graphml_to(self, writer)
+    }
+
+    /// Stream graph in GEXF format to a writer.
+    pub fn export_gexf_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_gexf_to(self, writer)
+    }
+
+    /// Stream graph as RDF triples in N-Triples format to a writer.
+    pub fn export_triples_to<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        crate::export::export_triples_to(self, writer)
+    }
+
+    /// Stream graph as RDF to a writer.
+    pub fn export_rdf_to<W: std::io::Write>(
+        &self,
+        options: &crate::export::RdfOptions,
+        writer: W,
+    ) -> Result<()> {
+        crate::export::export_rdf_to(self, options, writer)
+    }
+
+    /// Stream graph as a Cypher `MERGE` script to a writer.
+    pub fn export_cypher_to<W: std::io::Write>(
+        &self,
+        options: &crate::export::CypherOptions,
+        writer: W,
+    ) -> Result<()> {
+        crate::export::export_cypher_to(self, options, writer)
+    }
+
     /// Check graph size for export operations and issue warnings/errors.
     fn check_export_size(&self) -> Result<()> {
         let node_count = self.node_count();
@@ -891,7 +1863,7 @@ impl CodeGraph {
         if node_count > 100_000 {
             return Err(GraphError::InvalidOperation {
                 message: format!(
-                    "Graph too large for export ({node_count} nodes > 100K limit). Use filtering to export a subset."
+                    "Graph too large for export ({node_count} nodes > 100K limit). Use filtering to export a subset, or a streaming `*_to` exporter."
                 ),
             });
         }
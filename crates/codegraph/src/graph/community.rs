@@ -0,0 +1,587 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Community detection for architecture recovery.
+//!
+//! Clusters nodes with Louvain modularity optimization or label propagation over
+//! a chosen set of edge types (typically file-level `Imports` or function-level
+//! `Calls`), and compares the discovered clusters with the directory layout to
+//! surface misplaced code and natural service boundaries.
+//!
+//! Edges are treated as undirected and weighted by multiplicity. Nodes are
+//! indexed in ascending ID order and label propagation uses a fixed seed, so
+//! results are deterministic for a given graph.
+
+use crate::error::Result;
+use crate::graph::{CodeGraph, Direction, EdgeType, NodeId, NodeType};
+use std::collections::{BTreeMap, HashMap};
+
+/// Identifier of a discovered community (dense, starting at 0).
+pub type CommunityId = usize;
+
+/// Result of a community detection run.
+#[derive(Debug, Clone)]
+pub struct Communities {
+    /// Community assignment for every clustered node
+    pub assignments: HashMap<NodeId, CommunityId>,
+    /// Modularity of the partition (higher means denser clusters)
+    pub modularity: f64,
+}
+
+impl Communities {
+    /// Get the community a node was assigned to.
+    pub fn community_of(&self, node_id: NodeId) -> Option<CommunityId> {
+        self.assignments.get(&node_id).copied()
+    }
+
+    /// Get the members of every community, sorted by node ID.
+    pub fn members(&self) -> BTreeMap<CommunityId, Vec<NodeId>> {
+        let mut members: BTreeMap<CommunityId, Vec<NodeId>> = BTreeMap::new();
+        for (&node_id, &community) in &self.assignments {
+            members.entry(community).or_default().push(node_id);
+        }
+        for nodes in members.values_mut() {
+            nodes.sort_unstable();
+        }
+        members
+    }
+
+    /// Get the number of communities.
+    pub fn len(&self) -> usize {
+        self.assignments
+            .values()
+            .copied()
+            .max()
+            .map_or(0, |max| max + 1)
+    }
+
+    /// Check if no nodes were clustered.
+    pub fn is_empty(&self) -> bool {
+        self.assignments.is_empty()
+    }
+}
+
+/// Detect communities using the Louvain modularity optimization method.
+///
+/// Repeatedly moves nodes to the neighboring community with the best modularity
+/// gain, then aggregates communities into single nodes until no move improves
+/// modularity.
+///
+/// # Parameters
+/// - `graph`: The graph to cluster
+/// - `edge_types`: Edge types to consider (e.g. `[EdgeType::Imports]`)
+///
+/// # Returns
+/// Community assignment for every node incident to an edge of the given types
+pub fn louvain(graph: &CodeGraph, edge_types: &[EdgeType]) -> Result<Communities> {
+    let base = WeightedGraph::from_code_graph(graph, edge_types);
+    let mut membership: Vec<usize> = (0..base.len()).collect();
+
+    if base.total_weight > 0.0 {
+        let mut level = base.clone();
+        loop {
+            let (partition, moved) = local_moving(&level);
+            if !moved {
+                break;
+            }
+            for community in membership.iter_mut() {
+                *community = partition[*community];
+            }
+            level = level.aggregate(&partition);
+        }
+    }
+
+    Ok(base.into_communities(&membership))
+}
+
+/// Detect communities using asynchronous label propagation.
+///
+/// Every node starts with its own label and repeatedly adopts the label with the
+/// highest edge weight among its neighbors. Nodes are visited in a shuffled order
+/// and ties are broken pseudo-randomly, as in the original algorithm; a fixed seed
+/// keeps the result reproducible.
+///
+/// # Parameters
+/// - `graph`: The graph to cluster
+/// - `edge_types`: Edge types to consider (e.g. `[EdgeType::Calls]`)
+/// - `max_iterations`: Maximum number of full passes over the nodes
+///
+/// # Returns
+/// Community assignment for every node incident to an edge of the given types
+pub fn label_propagation(
+    graph: &CodeGraph,
+    edge_types: &[EdgeType],
+    max_iterations: usize,
+) -> Result<Communities> {
+    let base = WeightedGraph::from_code_graph(graph, edge_types);
+    let mut labels: Vec<usize> = (0..base.len()).collect();
+    let mut order: Vec<usize> = (0..base.len()).collect();
+    let mut rng = SplitMix64(0x5eed_c0de);
+
+    for _ in 0..max_iterations {
+        rng.shuffle(&mut order);
+        let mut changed = false;
+
+        for &i in &order {
+            let mut weights: BTreeMap<usize, f64> = BTreeMap::new();
+            for &(j, w) in &base.adjacency[i] {
+                *weights.entry(labels[j]).or_insert(0.0) += w;
+            }
+
+            let Some(best_weight) = weights.values().copied().reduce(f64::max) else {
+                continue;
+            };
+
+            let current = labels[i];
+            if weights.get(&current) == Some(&best_weight) {
+                continue;
+            }
+
+            let candidates: Vec<usize> = weights
+                .iter()
+                .filter(|(_, &w)| w == best_weight)
+                .map(|(&label, _)| label)
+                .collect();
+            labels[i] = candidates[rng.below(candidates.len())];
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(base.into_communities(&labels))
+}
+
+/// Small deterministic PRNG for label propagation tie-breaking.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Comparison of discovered communities with the directory layout.
+#[derive(Debug, Clone)]
+pub struct LayoutReport {
+    /// Per-community summary, ordered by community ID
+    pub clusters: Vec<ClusterSummary>,
+    /// Nodes whose directory differs from the dominant directory of their community
+    pub misplaced: Vec<MisplacedNode>,
+    /// Directories whose nodes are spread over more than one community
+    pub split_directories: BTreeMap<String, Vec<CommunityId>>,
+    /// Fraction of located nodes that live in their community's dominant directory
+    pub purity: f64,
+}
+
+/// Directory breakdown of a single community.
+#[derive(Debug, Clone)]
+pub struct ClusterSummary {
+    /// Community identifier
+    pub community: CommunityId,
+    /// Number of nodes in the community
+    pub size: usize,
+    /// Directory holding the most members (None if no member has a known location)
+    pub dominant_directory: Option<String>,
+    /// Number of members per directory
+    pub directories: BTreeMap<String, usize>,
+    /// Fraction of located members in the dominant directory
+    pub purity: f64,
+}
+
+/// A node that clusters with code from a different directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MisplacedNode {
+    /// The misplaced node
+    pub node_id: NodeId,
+    /// Community the node was assigned to
+    pub community: CommunityId,
+    /// Directory the node currently lives in
+    pub directory: String,
+    /// Dominant directory of its community
+    pub expected_directory: String,
+}
+
+/// Compare discovered communities with the actual directory layout.
+///
+/// A node's directory is taken from its `path` property, or from the file that
+/// contains it (following incoming `Contains` edges). Nodes without a resolvable
+/// location (e.g. external modules) are counted in `size` but otherwise ignored.
+pub fn layout_report(graph: &CodeGraph, communities: &Communities) -> Result<LayoutReport> {
+    let mut clusters = Vec::new();
+    let mut misplaced = Vec::new();
+    let mut directory_communities: BTreeMap<String, Vec<CommunityId>> = BTreeMap::new();
+    let mut located_total = 0usize;
+    let mut pure_total = 0usize;
+
+    for (community, members) in communities.members() {
+        let mut located = Vec::new();
+        let mut directories: BTreeMap<String, usize> = BTreeMap::new();
+        for &node_id in &members {
+            if let Some(dir) = node_directory(graph, node_id)? {
+                *directories.entry(dir.clone()).or_insert(0) += 1;
+                located.push((node_id, dir));
+            }
+        }
+
+        // Highest count wins; BTreeMap order breaks ties alphabetically
+        let dominant_directory = directories
+            .iter()
+            .fold(None::<(&String, usize)>, |best, (dir, &count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((dir, count)),
+            })
+            .map(|(dir, _)| dir.clone());
+
+        let mut pure = 0;
+        if let Some(ref expected) = dominant_directory {
+            for (node_id, dir) in located.iter() {
+                if dir == expected {
+                    pure += 1;
+                } else {
+                    misplaced.push(MisplacedNode {
+                        node_id: *node_id,
+                        community,
+                        directory: dir.clone(),
+                        expected_directory: expected.clone(),
+                    });
+                }
+            }
+        }
+
+        for dir in directories.keys() {
+            directory_communities
+                .entry(dir.clone())
+                .or_default()
+                .push(community);
+        }
+
+        located_total += located.len();
+        pure_total += pure;
+
+        clusters.push(ClusterSummary {
+            community,
+            size: members.len(),
+            dominant_directory,
+            directories,
+            purity: ratio(pure, located.len()),
+        });
+    }
+
+    let split_directories = directory_communities
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .collect();
+
+    Ok(LayoutReport {
+        clusters,
+        misplaced,
+        split_directories,
+        purity: ratio(pure_total, located_total),
+    })
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+/// Resolve the directory of a node from its own path or its containing file.
+pub(crate) fn node_directory(graph: &CodeGraph, node_id: NodeId) -> Result<Option<String>> {
+    let mut current = node_id;
+
+    // Bounded walk up the containment hierarchy (file -> class -> method)
+    for _ in 0..8 {
+        let node = graph.get_node(current)?;
+        // Module paths name packages, not files, so they are not locations
+        if node.node_type != NodeType::Module {
+            if let Some(path) = node.properties.get_string("path") {
+                return Ok(Some(parent_directory(path)));
+            }
+        }
+        if node.node_type == NodeType::CodeFile {
+            return Ok(None);
+        }
+
+        let container = graph
+            .get_neighbors(current, Direction::Incoming)?
+            .into_iter()
+            .filter(|&parent| {
+                graph
+                    .get_edges_between(parent, current)
+                    .map(|edges| {
+                        edges.iter().any(|&e| {
+                            graph
+                                .get_edge(e)
+                                .is_ok_and(|edge| edge.edge_type == EdgeType::Contains)
+                        })
+                    })
+                    .unwrap_or(false)
+            })
+            .min();
+
+        match container {
+            Some(parent) => current = parent,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(None)
+}
+
+fn parent_directory(path: &str) -> String {
+    match path.rsplit_once('/') {
+        Some(("", _)) => "/".to_string(),
+        Some((dir, _)) => dir.to_string(),
+        None => ".".to_string(),
+    }
+}
+
+/// Undirected weighted graph used internally by the clustering algorithms.
+#[derive(Debug, Clone)]
+struct WeightedGraph {
+    /// Original node IDs (only meaningful for the base level)
+    node_ids: Vec<NodeId>,
+    /// Neighbor lists without self loops; each undirected edge appears in both lists
+    adjacency: Vec<Vec<(usize, f64)>>,
+    /// Self-loop weight per node
+    self_loops: Vec<f64>,
+    /// Sum of all edge weights (m)
+    total_weight: f64,
+}
+
+impl WeightedGraph {
+    fn from_code_graph(graph: &CodeGraph, edge_types: &[EdgeType]) -> Self {
+        let relevant: Vec<(NodeId, NodeId)> = graph
+            .iter_edges()
+            .filter(|(_, edge)| edge_types.contains(&edge.edge_type))
+            .map(|(_, edge)| (edge.source_id, edge.target_id))
+            .collect();
+
+        let mut node_ids: Vec<NodeId> = relevant.iter().flat_map(|&(s, t)| [s, t]).collect();
+        node_ids.sort_unstable();
+        node_ids.dedup();
+
+        let index: HashMap<NodeId, usize> = node_ids
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect();
+
+        let mut weights: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); node_ids.len()];
+        let mut self_loops = vec![0.0; node_ids.len()];
+        let mut total_weight = 0.0;
+
+        for (source, target) in relevant {
+            let (s, t) = (index[&source], index[&target]);
+            if s == t {
+                self_loops[s] += 1.0;
+            } else {
+                *weights[s].entry(t).or_insert(0.0) += 1.0;
+                *weights[t].entry(s).or_insert(0.0) += 1.0;
+            }
+            total_weight += 1.0;
+        }
+
+        Self {
+            node_ids,
+            adjacency: weights
+                .into_iter()
+                .map(|w| w.into_iter().collect())
+                .collect(),
+            self_loops,
+            total_weight,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    /// Weighted degree of a node (self loops count twice).
+    fn degree(&self, i: usize) -> f64 {
+        self.adjacency[i].iter().map(|&(_, w)| w).sum::<f64>() + 2.0 * self.self_loops[i]
+    }
+
+    /// Collapse every community of `partition` into a single node.
+    fn aggregate(&self, partition: &[usize]) -> Self {
+        let count = partition.iter().copied().max().map_or(0, |max| max + 1);
+        let mut weights: Vec<BTreeMap<usize, f64>> = vec![BTreeMap::new(); count];
+        let mut self_loops = vec![0.0; count];
+
+        for i in 0..self.len() {
+            let ci = partition[i];
+            self_loops[ci] += self.self_loops[i];
+            for &(j, w) in &self.adjacency[i] {
+                let cj = partition[j];
+                if ci == cj {
+                    // Internal edges are visited from both endpoints
+                    self_loops[ci] += w / 2.0;
+                } else {
+                    *weights[ci].entry(cj).or_insert(0.0) += w;
+                }
+            }
+        }
+
+        Self {
+            node_ids: Vec::new(),
+            adjacency: weights
+                .into_iter()
+                .map(|w| w.into_iter().collect())
+                .collect(),
+            self_loops,
+            total_weight: self.total_weight,
+        }
+    }
+
+    /// Modularity of a partition of this graph.
+    fn modularity(&self, partition: &[usize]) -> f64 {
+        if self.total_weight == 0.0 {
+            return 0.0;
+        }
+        let two_m = 2.0 * self.total_weight;
+        let mut internal: HashMap<usize, f64> = HashMap::new();
+        let mut totals: HashMap<usize, f64> = HashMap::new();
+
+        for i in 0..self.len() {
+            let ci = partition[i];
+            *totals.entry(ci).or_insert(0.0) += self.degree(i);
+            let mut inside = 2.0 * self.self_loops[i];
+            for &(j, w) in &self.adjacency[i] {
+                if partition[j] == ci {
+                    inside += w;
+                }
+            }
+            *internal.entry(ci).or_insert(0.0) += inside;
+        }
+
+        totals
+            .iter()
+            .map(|(c, &tot)| {
+                internal.get(c).copied().unwrap_or(0.0) / two_m - (tot / two_m).powi(2)
+            })
+            .sum()
+    }
+
+    /// Build the public result, renumbering labels in order of first appearance.
+    fn into_communities(self, labels: &[usize]) -> Communities {
+        let mut renumber: HashMap<usize, CommunityId> = HashMap::new();
+        let dense: Vec<CommunityId> = labels
+            .iter()
+            .map(|label| {
+                let next = renumber.len();
+                *renumber.entry(*label).or_insert(next)
+            })
+            .collect();
+
+        let modularity = self.modularity(&dense);
+        let assignments = self.node_ids.into_iter().zip(dense).collect();
+
+        Communities {
+            assignments,
+            modularity,
+        }
+    }
+}
+
+/// One Louvain phase: greedily move nodes between communities.
+///
+/// Returns the dense partition and whether any node changed community.
+fn local_moving(graph: &WeightedGraph) -> (Vec<usize>, bool) {
+    let n = graph.len();
+    let two_m = 2.0 * graph.total_weight;
+    let degrees: Vec<f64> = (0..n).map(|i| graph.degree(i)).collect();
+    let mut community: Vec<usize> = (0..n).collect();
+    let mut totals = degrees.clone();
+    let mut moved_any = false;
+
+    loop {
+        let mut moved = false;
+
+        for i in 0..n {
+            let current = community[i];
+            let mut links: BTreeMap<usize, f64> = BTreeMap::new();
+            for &(j, w) in &graph.adjacency[i] {
+                *links.entry(community[j]).or_insert(0.0) += w;
+            }
+
+            totals[current] -= degrees[i];
+
+            let gain = |c: usize, weight: f64| weight - totals[c] * degrees[i] / two_m;
+            let mut best = current;
+            let mut best_gain = gain(current, links.get(&current).copied().unwrap_or(0.0));
+            for (&c, &weight) in &links {
+                let g = gain(c, weight);
+                if g > best_gain + 1e-12 {
+                    best = c;
+                    best_gain = g;
+                }
+            }
+
+            totals[best] += degrees[i];
+            if best != current {
+                community[i] = best;
+                moved = true;
+                moved_any = true;
+            }
+        }
+
+        if !moved {
+            break;
+        }
+    }
+
+    let mut renumber: HashMap<usize, usize> = HashMap::new();
+    let dense = community
+        .iter()
+        .map(|c| {
+            let next = renumber.len();
+            *renumber.entry(*c).or_insert(next)
+        })
+        .collect();
+
+    (dense, moved_any)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parent_directory() {
+        assert_eq!(parent_directory("src/api/handler.rs"), "src/api");
+        assert_eq!(parent_directory("main.rs"), ".");
+        assert_eq!(parent_directory("/root.rs"), "/");
+    }
+
+    #[test]
+    fn test_modularity_of_single_community_is_zero() {
+        let mut graph = CodeGraph::in_memory().unwrap();
+        let a = crate::helpers::add_file(&mut graph, "a.py", "python").unwrap();
+        let b = crate::helpers::add_file(&mut graph, "b.py", "python").unwrap();
+        crate::helpers::add_import(&mut graph, a, b, vec![]).unwrap();
+
+        let weighted = WeightedGraph::from_code_graph(&graph, &[EdgeType::Imports]);
+        assert!(weighted.modularity(&[0, 0]).abs() < 1e-9);
+    }
+}
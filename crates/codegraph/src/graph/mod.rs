@@ -10,7 +10,11 @@
 
 pub mod algorithms;
 mod codegraph;
+pub mod community;
 mod property;
+pub mod reachability;
+mod spans;
+pub mod subgraph;
 mod types;
 
 pub use codegraph::CodeGraph;
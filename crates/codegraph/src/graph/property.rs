@@ -23,6 +23,8 @@ pub enum PropertyValue {
     StringList(Vec<String>),
     /// List of integers (line ranges, counts)
     IntList(Vec<i64>),
+    /// Dense float vector (embeddings)
+    Vector(Vec<f32>),
     /// Explicit null/absence of value
     Null,
 }
@@ -75,6 +77,12 @@ impl From<Vec<i64>> for PropertyValue {
     }
 }
 
+impl From<Vec<f32>> for PropertyValue {
+    fn from(value: Vec<f32>) -> Self {
+        PropertyValue::Vector(value)
+    }
+}
+
 /// Flexible key-value metadata store for nodes and edges.
 ///
 /// Provides builder pattern and type-safe getters for properties.
@@ -197,6 +205,14 @@ impl PropertyMap {
             _ => None,
         }
     }
+
+    /// Type-safe getter for vector properties.
+    pub fn get_vector(&self, key: &str) -> Option<&[f32]> {
+        match self.data.get(key) {
+            Some(PropertyValue::Vector(vector)) => Some(vector),
+            _ => None,
+        }
+    }
 }
 
 impl FromIterator<(String, PropertyValue)> for PropertyMap {
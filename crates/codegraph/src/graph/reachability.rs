@@ -0,0 +1,262 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Precomputed reachability index for repeated "does A depend on B" queries.
+//!
+//! The index collapses strongly connected components, numbers the resulting DAG
+//! in DFS post-order over a spanning forest, and stores for every component the
+//! merged set of post-order intervals it can reach (tree-cover interval labelling).
+//! A query is a binary search over those intervals.
+//!
+//! [`CodeGraph`] keeps an enabled index up to date: added nodes and edges are
+//! applied incrementally, while removals (or additions that merge components)
+//! mark it stale until [`CodeGraph::rebuild_reachability_index`] is called.
+
+use super::algorithms::strongly_connected_components_by_type;
+use crate::graph::{CodeGraph, Edge, EdgeType, NodeId};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Reachability index over a fixed set of edge types.
+#[derive(Debug, Clone)]
+pub struct ReachabilityIndex {
+    edge_types: Vec<EdgeType>,
+    /// Component of every indexed node
+    component: HashMap<NodeId, usize>,
+    /// Member nodes of every component
+    members: Vec<Vec<NodeId>>,
+    /// Post-order number of every component
+    post: Vec<u64>,
+    /// Sorted, disjoint post-order intervals reachable from every component
+    intervals: Vec<Vec<(u64, u64)>>,
+    next_post: u64,
+    stale: bool,
+}
+
+impl ReachabilityIndex {
+    /// Build an index over all nodes, following edges of the given types.
+    pub fn build(graph: &CodeGraph, edge_types: &[EdgeType]) -> Self {
+        let sccs = strongly_connected_components_by_type(graph, edge_types);
+
+        let mut component = HashMap::new();
+        for (c, scc) in sccs.iter().enumerate() {
+            for &node_id in scc {
+                component.insert(node_id, c);
+            }
+        }
+
+        // Successor components in the condensation DAG
+        let successors: Vec<Vec<usize>> = sccs
+            .iter()
+            .enumerate()
+            .map(|(c, scc)| {
+                let mut succ: Vec<usize> = scc
+                    .iter()
+                    .flat_map(|&node_id| graph.outgoing_edges(node_id))
+                    .filter(|edge| edge_types.contains(&edge.edge_type))
+                    .filter_map(|edge| component.get(&edge.target_id).copied())
+                    .filter(|&target| target != c)
+                    .collect();
+                succ.sort_unstable();
+                succ.dedup();
+                succ
+            })
+            .collect();
+
+        // Post-order numbering over a spanning forest; roots in topological order
+        let count = sccs.len();
+        let mut post = vec![0u64; count];
+        let mut low = vec![0u64; count];
+        let mut visited = vec![false; count];
+        let mut next_post = 0u64;
+
+        for root in (0..count).rev() {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            low[root] = next_post;
+            let mut stack = vec![(root, 0usize)];
+
+            while let Some((c, pos)) = stack.last_mut() {
+                let c = *c;
+                if let Some(&next) = successors[c].get(*pos) {
+                    *pos += 1;
+                    if !visited[next] {
+                        visited[next] = true;
+                        low[next] = next_post;
+                        stack.push((next, 0));
+                    }
+                } else {
+                    post[c] = next_post;
+                    next_post += 1;
+                    stack.pop();
+                }
+            }
+        }
+
+        // Components are in reverse topological order, so successors come first
+        let mut intervals: Vec<Vec<(u64, u64)>> = Vec::with_capacity(count);
+        for c in 0..count {
+            let mut own = vec![(low[c], post[c])];
+            for &s in &successors[c] {
+                own.extend_from_slice(&intervals[s]);
+            }
+            intervals.push(merge_intervals(own));
+        }
+
+        Self {
+            edge_types: edge_types.to_vec(),
+            component,
+            members: sccs,
+            post,
+            intervals,
+            next_post,
+            stale: false,
+        }
+    }
+
+    /// Check whether `to` is reachable from `from`.
+    ///
+    /// Every node reaches itself. Returns `None` if either node is not indexed or
+    /// the index is stale.
+    pub fn reaches(&self, from: NodeId, to: NodeId) -> Option<bool> {
+        if self.stale {
+            return None;
+        }
+        let from = *self.component.get(&from)?;
+        let to = *self.component.get(&to)?;
+        Some(self.component_reaches(from, to))
+    }
+
+    /// Edge types this index follows.
+    pub fn edge_types(&self) -> &[EdgeType] {
+        &self.edge_types
+    }
+
+    /// Whether the index must be rebuilt before it can answer queries.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Number of indexed nodes.
+    pub fn len(&self) -> usize {
+        self.component.len()
+    }
+
+    /// Check if the index covers no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.component.is_empty()
+    }
+
+    fn component_reaches(&self, from: usize, to: usize) -> bool {
+        let target = self.post[to];
+        let intervals = &self.intervals[from];
+        let i = intervals.partition_point(|&(start, _)| start <= target);
+        i > 0 && intervals[i - 1].1 >= target
+    }
+
+    /// Register a new node as its own component.
+    pub(crate) fn on_node_added(&mut self, node_id: NodeId) {
+        let c = self.post.len();
+        self.component.insert(node_id, c);
+        self.members.push(vec![node_id]);
+        self.post.push(self.next_post);
+        self.intervals.push(vec![(self.next_post, self.next_post)]);
+        self.next_post += 1;
+    }
+
+    /// Forget a deleted node (its edges have already been removed).
+    pub(crate) fn on_node_removed(&mut self, node_id: NodeId) {
+        if let Some(c) = self.component.remove(&node_id) {
+            self.members[c].retain(|&member| member != node_id);
+        }
+    }
+
+    /// Apply an added edge; `graph` must already contain it.
+    pub(crate) fn on_edge_added(&mut self, graph: &CodeGraph, edge: &Edge) {
+        if self.stale || !self.edge_types.contains(&edge.edge_type) {
+            return;
+        }
+        let (Some(&source), Some(&target)) = (
+            self.component.get(&edge.source_id),
+            self.component.get(&edge.target_id),
+        ) else {
+            self.stale = true;
+            return;
+        };
+
+        if self.component_reaches(source, target) {
+            return;
+        }
+        if self.component_reaches(target, source) {
+            // The edge closes a cycle: components merge, which needs a rebuild
+            self.stale = true;
+            return;
+        }
+
+        // Everything that reaches the source now reaches what the target reaches
+        let added = self.intervals[target].clone();
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([source]);
+        while let Some(c) = queue.pop_front() {
+            if !visited.insert(c) || self.component_reaches(c, target) {
+                continue;
+            }
+            let mut merged = std::mem::take(&mut self.intervals[c]);
+            merged.extend_from_slice(&added);
+            self.intervals[c] = merge_intervals(merged);
+
+            for &member in &self.members[c] {
+                for incoming in graph.incoming_edges(member) {
+                    if self.edge_types.contains(&incoming.edge_type) {
+                        if let Some(&pred) = self.component.get(&incoming.source_id) {
+                            queue.push_back(pred);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Apply a removed edge; `graph` must no longer contain it.
+    pub(crate) fn on_edge_removed(&mut self, graph: &CodeGraph, edge: &Edge) {
+        if self.stale || !self.edge_types.contains(&edge.edge_type) {
+            return;
+        }
+        // A parallel edge of an indexed type keeps reachability unchanged
+        let still_connected = graph.outgoing_edges(edge.source_id).any(|other| {
+            other.target_id == edge.target_id && self.edge_types.contains(&other.edge_type)
+        });
+        if !still_connected {
+            self.stale = true;
+        }
+    }
+}
+
+/// Sort and merge overlapping or adjacent integer intervals.
+fn merge_intervals(mut intervals: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    intervals.sort_unstable();
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(intervals.len());
+    for (start, end) in intervals {
+        match merged.last_mut() {
+            Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_intervals() {
+        assert_eq!(
+            merge_intervals(vec![(5, 6), (0, 1), (2, 3), (8, 9), (7, 7)]),
+            vec![(0, 3), (5, 9)]
+        );
+        assert_eq!(merge_intervals(vec![(0, 10), (2, 3)]), vec![(0, 10)]);
+        assert!(merge_intervals(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-file interval index over node source spans.
+//!
+//! Nodes with a `path`, `line_start` and `line_end` (plus optional `col_start`
+//! and `col_end`) are arranged per file into a forest by containment: every span
+//! is a child of an innermost span enclosing it. Siblings never enclose one
+//! another, so sorted by start they are also sorted by end, and the siblings
+//! holding a position are one contiguous run found by a binary search. Spans
+//! produced from a syntax tree are nested or disjoint, making that run at most one
+//! entry per nesting level, but partially overlapping spans are handled too.
+
+use super::{Node, NodeId};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Line (1-indexed) and column (0-indexed) of a source position.
+type Position = (i64, i64);
+
+/// Half-open source range `[start, end)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    start: Position,
+    end: Position,
+}
+
+impl Span {
+    /// Read the path and span of a node. Without columns, a span covers its
+    /// first and last lines entirely.
+    fn of(node: &Node) -> Option<(&str, Span)> {
+        let props = &node.properties;
+        let path = props.get_string("path")?;
+        let line_start = props.get_int("line_start")?;
+        let line_end = props.get_int("line_end")?;
+        let col_start = props.get_int("col_start").unwrap_or(0);
+        let col_end = props.get_int("col_end").unwrap_or(i64::MAX);
+        Some((
+            path,
+            Span {
+                start: (line_start, col_start),
+                end: (line_end, col_end),
+            },
+        ))
+    }
+
+    fn contains(&self, position: Position) -> bool {
+        self.start <= position && position < self.end
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    path: String,
+    span: Span,
+    parent: Option<NodeId>,
+    /// Directly nested entries, sorted by [`SpanIndex::key`]
+    children: Vec<NodeId>,
+}
+
+/// Containment forest of node spans, per file.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SpanIndex {
+    entries: HashMap<NodeId, Entry>,
+    /// Outermost entries of every file, sorted by [`SpanIndex::key`]
+    roots: HashMap<String, Vec<NodeId>>,
+}
+
+impl SpanIndex {
+    /// Build an index over nodes, inserted in ascending ID order.
+    pub(crate) fn build<'a>(nodes: impl IntoIterator<Item = (NodeId, &'a Node)>) -> Self {
+        let mut nodes: Vec<(NodeId, &Node)> = nodes.into_iter().collect();
+        nodes.sort_unstable_by_key(|(node_id, _)| *node_id);
+        let mut index = Self::default();
+        for (node_id, node) in nodes {
+            index.on_node_changed(node_id, node);
+        }
+        index
+    }
+
+    /// Index a new node, or re-index one whose path or span changed.
+    pub(crate) fn on_node_changed(&mut self, node_id: NodeId, node: &Node) {
+        let current = Span::of(node);
+        let previous = self
+            .entries
+            .get(&node_id)
+            .map(|entry| (entry.path.as_str(), entry.span));
+        if current == previous {
+            return;
+        }
+        self.remove(node_id);
+        if let Some((path, span)) = current {
+            self.insert(node_id, path.to_string(), span);
+        }
+    }
+
+    /// Drop a deleted node.
+    pub(crate) fn on_node_removed(&mut self, node_id: NodeId) {
+        self.remove(node_id);
+    }
+
+    /// Drop every node.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.roots.clear();
+    }
+
+    /// Entries of a file enclosing a position, innermost first.
+    pub(crate) fn enclosing(&self, path: &str, line: i64, column: i64) -> Vec<NodeId> {
+        let position = (line, column);
+        let mut found = Vec::new();
+        let mut levels: Vec<&[NodeId]> = match self.roots.get(path) {
+            Some(roots) => vec![roots],
+            None => return found,
+        };
+        while let Some(level) = levels.pop() {
+            // Sibling ends ascend with their starts, so the siblings holding the
+            // position end the run of those starting at or before it
+            let idx = level.partition_point(|id| self.entries[id].span.start <= position);
+            for &candidate in level[..idx].iter().rev() {
+                let entry = &self.entries[&candidate];
+                if !entry.span.contains(position) {
+                    break;
+                }
+                found.push(candidate);
+                levels.push(&entry.children);
+            }
+        }
+        found.sort_unstable_by_key(|&id| Reverse(self.entry_key(id)));
+        found
+    }
+
+    /// Sort key of an entry among its siblings: outer spans before the spans they
+    /// enclose, and identical spans nested by ascending node ID.
+    fn key(node_id: NodeId, span: Span) -> (Position, Reverse<Position>, NodeId) {
+        (span.start, Reverse(span.end), node_id)
+    }
+
+    fn entry_key(&self, node_id: NodeId) -> (Position, Reverse<Position>, NodeId) {
+        Self::key(node_id, self.entries[&node_id].span)
+    }
+
+    /// Whether `outer` encloses `inner`, with identical spans ordered by node ID.
+    fn encloses(outer: (NodeId, Span), inner: (NodeId, Span)) -> bool {
+        let ((outer_id, outer), (inner_id, inner)) = (outer, inner);
+        outer.start <= inner.start
+            && inner.end <= outer.end
+            && (outer != inner || outer_id < inner_id)
+    }
+
+    fn siblings_mut(&mut self, path: &str, parent: Option<NodeId>) -> &mut Vec<NodeId> {
+        match parent {
+            Some(parent) => {
+                &mut self
+                    .entries
+                    .get_mut(&parent)
+                    .expect("parent is indexed")
+                    .children
+            }
+            None => self.roots.entry(path.to_string()).or_default(),
+        }
+    }
+
+    fn insert(&mut self, node_id: NodeId, path: String, span: Span) {
+        let key = Self::key(node_id, span);
+
+        // Descend to the innermost enclosing entry
+        let mut parent = None;
+        let idx = loop {
+            let level: &[NodeId] = match parent {
+                Some(parent) => &self.entries[&parent].children,
+                None => self.roots.get(&path).map_or(&[][..], Vec::as_slice),
+            };
+            let idx = level.partition_point(|&id| self.entry_key(id) < key);
+            match idx.checked_sub(1).map(|i| level[i]) {
+                Some(candidate)
+                    if Self::encloses(
+                        (candidate, self.entries[&candidate].span),
+                        (node_id, span),
+                    ) =>
+                {
+                    parent = Some(candidate);
+                }
+                _ => break idx,
+            }
+        };
+
+        // Adopt the following siblings that the new span encloses
+        let mut siblings = std::mem::take(self.siblings_mut(&path, parent));
+        let mut end = idx;
+        while end < siblings.len() && self.entries[&siblings[end]].span.start < span.end {
+            end += 1;
+        }
+        let (children, kept): (Vec<NodeId>, Vec<NodeId>) = siblings[idx..end]
+            .iter()
+            .copied()
+            .partition(|&id| Self::encloses((node_id, span), (id, self.entries[&id].span)));
+        for &child in &children {
+            self.entries
+                .get_mut(&child)
+                .expect("child is indexed")
+                .parent = Some(node_id);
+        }
+        siblings.splice(idx..end, std::iter::once(node_id).chain(kept));
+        *self.siblings_mut(&path, parent) = siblings;
+
+        self.entries.insert(
+            node_id,
+            Entry {
+                path,
+                span,
+                parent,
+                children,
+            },
+        );
+    }
+
+    fn remove(&mut self, node_id: NodeId) {
+        let Some(entry) = self.entries.get(&node_id) else {
+            return;
+        };
+        let (path, parent) = (entry.path.clone(), entry.parent);
+        let key = Self::key(node_id, entry.span);
+        let idx = {
+            let level: &[NodeId] = match parent {
+                Some(parent) => &self.entries[&parent].children,
+                None => &self.roots[&path],
+            };
+            level.partition_point(|&id| self.entry_key(id) < key)
+        };
+        let siblings = self.siblings_mut(&path, parent);
+        siblings.remove(idx);
+        if parent.is_none() && siblings.is_empty() {
+            self.roots.remove(&path);
+        }
+
+        // Re-insert the descendants outermost first: with partially overlapping
+        // spans, a sibling of the removed entry may now be their innermost parent
+        let mut descendants = Vec::new();
+        let mut pending = self
+            .entries
+            .remove(&node_id)
+            .expect("entry exists")
+            .children;
+        while let Some(child) = pending.pop() {
+            let entry = self.entries.remove(&child).expect("child is indexed");
+            pending.extend(entry.children);
+            descendants.push((child, entry.path, entry.span));
+        }
+        descendants.sort_unstable_by_key(|&(id, _, span)| Self::key(id, span));
+        for (child, path, span) in descendants {
+            self.insert(child, path, span);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{NodeType, PropertyMap};
+
+    fn node(id: NodeId, path: &str, lines: (i64, i64), cols: (i64, i64)) -> Node {
+        Node::new(
+            id,
+            NodeType::Function,
+            PropertyMap::new()
+                .with("path", path)
+                .with("line_start", lines.0)
+                .with("line_end", lines.1)
+                .with("col_start", cols.0)
+                .with("col_end", cols.1),
+        )
+    }
+
+    /// Check parent links and sibling order of every entry.
+    fn assert_consistent(index: &SpanIndex) {
+        let check_level = |level: &[NodeId], parent: Option<NodeId>| {
+            for pair in level.windows(2) {
+                assert!(index.entry_key(pair[0]) < index.entry_key(pair[1]));
+                let (first, second) = (&index.entries[&pair[0]], &index.entries[&pair[1]]);
+                assert!(first.span.end < second.span.end);
+            }
+            for id in level {
+                assert_eq!(index.entries[id].parent, parent);
+            }
+        };
+        for roots in index.roots.values() {
+            check_level(roots, None);
+        }
+        for (&id, entry) in &index.entries {
+            check_level(&entry.children, Some(id));
+        }
+    }
+
+    #[test]
+    fn test_insertion_order_does_not_matter() {
+        let nodes = [
+            node(1, "a.rs", (1, 20), (0, 1)),
+            node(2, "a.rs", (2, 10), (4, 5)),
+            node(3, "a.rs", (3, 5), (8, 9)),
+            node(4, "a.rs", (12, 18), (4, 5)),
+            node(5, "a.rs", (2, 10), (4, 5)),
+        ];
+        let forward = SpanIndex::build(nodes.iter().map(|n| (n.id, n)));
+        let mut backward = SpanIndex::default();
+        for n in nodes.iter().rev() {
+            backward.on_node_changed(n.id, n);
+        }
+        assert_consistent(&forward);
+        assert_consistent(&backward);
+        for index in [&forward, &backward] {
+            assert_eq!(index.enclosing("a.rs", 4, 0), vec![3, 5, 2, 1]);
+            assert_eq!(index.enclosing("a.rs", 11, 0), vec![1]);
+            assert_eq!(index.roots["a.rs"], vec![1]);
+        }
+    }
+
+    #[test]
+    fn test_remove_reparents_children() {
+        let nodes = [
+            node(1, "a.rs", (1, 20), (0, 1)),
+            node(2, "a.rs", (2, 10), (4, 5)),
+            node(3, "a.rs", (3, 5), (8, 9)),
+            node(4, "a.rs", (6, 7), (8, 9)),
+        ];
+        let mut index = SpanIndex::build(nodes.iter().map(|n| (n.id, n)));
+        index.on_node_removed(2);
+        assert_consistent(&index);
+        assert_eq!(index.entries[&1].children, vec![3, 4]);
+
+        index.on_node_removed(1);
+        assert_consistent(&index);
+        assert_eq!(index.roots["a.rs"], vec![3, 4]);
+
+        index.on_node_removed(3);
+        index.on_node_removed(4);
+        assert!(index.roots.is_empty());
+    }
+
+    #[test]
+    fn test_partial_overlaps() {
+        let nodes = [
+            node(1, "a.rs", (1, 10), (0, 1)),
+            node(2, "a.rs", (6, 7), (0, 1)),
+            node(3, "a.rs", (5, 15), (0, 1)),
+            node(4, "a.rs", (12, 20), (0, 1)),
+        ];
+        let mut index = SpanIndex::build(nodes.iter().map(|n| (n.id, n)));
+        assert_consistent(&index);
+        assert_eq!(index.roots["a.rs"], vec![1, 3, 4]);
+        assert_eq!(index.enclosing("a.rs", 6, 0), vec![2, 3, 1]);
+        assert_eq!(index.enclosing("a.rs", 13, 0), vec![4, 3]);
+        assert_eq!(index.enclosing("a.rs", 3, 0), vec![1]);
+
+        // The nested span moves under the overlapping sibling of its parent
+        index.on_node_removed(1);
+        assert_consistent(&index);
+        assert_eq!(index.entries[&3].children, vec![2]);
+        assert_eq!(index.enclosing("a.rs", 6, 0), vec![2, 3]);
+
+        index.on_node_removed(3);
+        assert_consistent(&index);
+        assert_eq!(index.roots["a.rs"], vec![2, 4]);
+        assert_eq!(index.enclosing("a.rs", 6, 0), vec![2]);
+    }
+}
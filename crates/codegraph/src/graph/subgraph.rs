@@ -0,0 +1,325 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Subgraph extraction and graph merging.
+//!
+//! [`CodeGraph::extract_subgraph`] copies a selection of nodes, and the edges
+//! between them, into a new in-memory graph that keeps the original IDs; this
+//! is the usual starting point for a focused report. [`CodeGraph::merge`] goes
+//! the other way and imports a whole graph into an existing one, assigning
+//! fresh IDs and returning the mapping, so that shards indexed in separate
+//! processes can be combined. Files and qualified entities present in both
+//! graphs can optionally be merged into a single node.
+
+use crate::error::Result;
+use crate::graph::{
+    CodeGraph, Direction, Edge, EdgeId, EdgeType, Node, NodeId, NodeType, PropertyMap,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Selection of nodes copied by [`CodeGraph::extract_subgraph`].
+///
+/// Starts from a set of seed nodes and optionally widens it to every node
+/// within a number of hops. Edges between selected nodes are always copied,
+/// restricted to [`edge_types`](Self::edge_types) when set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubgraphSpec {
+    seeds: Vec<NodeId>,
+    depth: usize,
+    direction: Direction,
+    edge_types: Option<Vec<EdgeType>>,
+}
+
+impl SubgraphSpec {
+    /// Select exactly the given nodes.
+    pub fn nodes(ids: impl IntoIterator<Item = NodeId>) -> Self {
+        Self::neighborhood(ids, 0)
+    }
+
+    /// Select the given nodes and everything within `depth` hops of them,
+    /// following edges in both directions.
+    pub fn neighborhood(seeds: impl IntoIterator<Item = NodeId>, depth: usize) -> Self {
+        Self {
+            seeds: seeds.into_iter().collect(),
+            depth,
+            direction: Direction::Both,
+            edge_types: None,
+        }
+    }
+
+    /// Only follow edges in the given direction when widening the selection.
+    pub fn direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Only follow and copy edges of the given types.
+    pub fn edge_types(mut self, edge_types: &[EdgeType]) -> Self {
+        self.edge_types = Some(edge_types.to_vec());
+        self
+    }
+
+    fn follows(&self, edge: &Edge) -> bool {
+        self.edge_types
+            .as_ref()
+            .map_or(true, |types| types.contains(&edge.edge_type))
+    }
+}
+
+impl From<Vec<NodeId>> for SubgraphSpec {
+    fn from(ids: Vec<NodeId>) -> Self {
+        Self::nodes(ids)
+    }
+}
+
+impl From<&[NodeId]> for SubgraphSpec {
+    fn from(ids: &[NodeId]) -> Self {
+        Self::nodes(ids.iter().copied())
+    }
+}
+
+/// Options for [`CodeGraph::merge`].
+///
+/// By default every node of the merged graph is added as a new node. Enabling
+/// deduplication maps a node onto an existing one instead when both have the
+/// same key; properties missing on the existing node are copied over, and
+/// edges that would duplicate an existing edge of the same type are dropped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeOptions {
+    dedup_paths: bool,
+    dedup_qualified_names: bool,
+}
+
+impl MergeOptions {
+    /// Add every node as a new node.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Merge `CodeFile` nodes with the same `path` property.
+    pub fn dedup_by_path(mut self) -> Self {
+        self.dedup_paths = true;
+        self
+    }
+
+    /// Merge nodes of the same type with the same `qualified_name` property.
+    pub fn dedup_by_qualified_name(mut self) -> Self {
+        self.dedup_qualified_names = true;
+        self
+    }
+
+    fn key(&self, node: &Node) -> Option<DedupKey> {
+        if self.dedup_paths && node.node_type == NodeType::CodeFile {
+            if let Some(path) = node.properties.get_string("path") {
+                return Some(DedupKey::Path(path.to_string()));
+            }
+        }
+        if self.dedup_qualified_names {
+            if let Some(name) = node.properties.get_string("qualified_name") {
+                return Some(DedupKey::QualifiedName(node.node_type, name.to_string()));
+            }
+        }
+        None
+    }
+
+    fn enabled(&self) -> bool {
+        self.dedup_paths || self.dedup_qualified_names
+    }
+}
+
+/// ID mapping produced by [`CodeGraph::merge`], from IDs in the merged graph to
+/// IDs in the target graph.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MergeMapping {
+    /// Target node of every merged node
+    pub nodes: HashMap<NodeId, NodeId>,
+    /// Target edge of every merged edge
+    pub edges: HashMap<EdgeId, EdgeId>,
+    /// Number of merged nodes mapped onto an existing node
+    pub deduplicated_nodes: usize,
+    /// Number of merged edges mapped onto an existing edge
+    pub deduplicated_edges: usize,
+}
+
+impl MergeMapping {
+    /// Get the target ID of a merged node.
+    pub fn node(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes.get(&id).copied()
+    }
+
+    /// Get the target ID of a merged edge.
+    pub fn edge(&self, id: EdgeId) -> Option<EdgeId> {
+        self.edges.get(&id).copied()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DedupKey {
+    Path(String),
+    QualifiedName(NodeType, String),
+}
+
+/// Where a merged node ends up before new nodes are assigned IDs.
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    Existing(NodeId),
+    New(usize),
+}
+
+/// Copy the selected nodes and the edges between them into a new graph.
+pub(super) fn extract_subgraph(graph: &CodeGraph, spec: &SubgraphSpec) -> Result<CodeGraph> {
+    let mut selected = HashSet::new();
+    let mut queue = VecDeque::new();
+    for &seed in &spec.seeds {
+        graph.get_node(seed)?;
+        if selected.insert(seed) {
+            queue.push_back((seed, 0));
+        }
+    }
+
+    while let Some((current, depth)) = queue.pop_front() {
+        if depth >= spec.depth {
+            continue;
+        }
+        let outgoing = matches!(spec.direction, Direction::Outgoing | Direction::Both).then(|| {
+            graph
+                .outgoing_edges(current)
+                .map(|edge| (edge, edge.target_id))
+        });
+        let incoming = matches!(spec.direction, Direction::Incoming | Direction::Both).then(|| {
+            graph
+                .incoming_edges(current)
+                .map(|edge| (edge, edge.source_id))
+        });
+        let neighbors: Vec<NodeId> = outgoing
+            .into_iter()
+            .flatten()
+            .chain(incoming.into_iter().flatten())
+            .filter(|(edge, _)| spec.follows(edge))
+            .map(|(_, neighbor)| neighbor)
+            .collect();
+        for neighbor in neighbors {
+            if selected.insert(neighbor) {
+                queue.push_back((neighbor, depth + 1));
+            }
+        }
+    }
+
+    let nodes = selected
+        .iter()
+        .map(|&id| graph.get_node(id).cloned())
+        .collect::<Result<Vec<_>>>()?;
+    let edges = selected
+        .iter()
+        .flat_map(|&id| graph.outgoing_edges(id))
+        .filter(|edge| selected.contains(&edge.target_id) && spec.follows(edge))
+        .cloned()
+        .collect();
+    CodeGraph::from_parts(nodes, edges, graph.id_counters())
+}
+
+/// Import every node and edge of `other` into `graph`.
+pub(super) fn merge(
+    graph: &mut CodeGraph,
+    other: &CodeGraph,
+    options: MergeOptions,
+) -> Result<MergeMapping> {
+    let mut keys: HashMap<DedupKey, Target> = HashMap::new();
+    if options.enabled() {
+        for (id, node) in graph.iter_nodes() {
+            if let Some(key) = options.key(node) {
+                keys.entry(key).or_insert(Target::Existing(id));
+            }
+        }
+    }
+
+    let mut other_nodes: Vec<(NodeId, &Node)> = other.iter_nodes().collect();
+    other_nodes.sort_unstable_by_key(|(id, _)| *id);
+
+    let mut mapping = MergeMapping::default();
+    let mut targets = HashMap::with_capacity(other_nodes.len());
+    let mut new_nodes = Vec::new();
+    let mut updates: HashMap<NodeId, PropertyMap> = HashMap::new();
+
+    for (id, node) in other_nodes {
+        let target = match options.key(node) {
+            Some(key) => *keys.entry(key).or_insert(Target::New(new_nodes.len())),
+            None => Target::New(new_nodes.len()),
+        };
+        match target {
+            Target::Existing(existing) => {
+                let missing = updates.entry(existing).or_default();
+                copy_missing(
+                    &graph.get_node(existing)?.properties,
+                    &node.properties,
+                    missing,
+                );
+                mapping.deduplicated_nodes += 1;
+            }
+            Target::New(slot) if slot == new_nodes.len() => {
+                new_nodes.push((node.node_type, node.properties.clone()));
+            }
+            Target::New(_) => mapping.deduplicated_nodes += 1,
+        }
+        targets.insert(id, target);
+    }
+
+    let existing: HashSet<NodeId> = updates.keys().copied().collect();
+    for (id, properties) in updates {
+        if !properties.is_empty() {
+            graph.update_node_properties(id, properties)?;
+        }
+    }
+    let new_ids = graph.add_nodes_batch(new_nodes)?;
+    for (id, target) in targets {
+        let mapped = match target {
+            Target::Existing(existing) => existing,
+            Target::New(slot) => new_ids[slot],
+        };
+        mapping.nodes.insert(id, mapped);
+    }
+
+    let mut other_edges: Vec<(EdgeId, &Edge)> = other.iter_edges().collect();
+    other_edges.sort_unstable_by_key(|(id, _)| *id);
+
+    let mut new_edges = Vec::new();
+    let mut new_edge_sources = Vec::new();
+    for (id, edge) in other_edges {
+        let source = mapping.nodes[&edge.source_id];
+        let target = mapping.nodes[&edge.target_id];
+        // Only edges between pre-existing nodes can duplicate an existing edge
+        if existing.contains(&source) && existing.contains(&target) {
+            let existing = graph
+                .get_edges_between(source, target)?
+                .into_iter()
+                .find(|&e| {
+                    graph
+                        .get_edge(e)
+                        .is_ok_and(|e| e.edge_type == edge.edge_type)
+                });
+            if let Some(existing) = existing {
+                mapping.edges.insert(id, existing);
+                mapping.deduplicated_edges += 1;
+                continue;
+            }
+        }
+        new_edges.push((source, target, edge.edge_type, edge.properties.clone()));
+        new_edge_sources.push(id);
+    }
+
+    let new_edge_ids = graph.add_edges_batch(new_edges)?;
+    mapping
+        .edges
+        .extend(new_edge_sources.into_iter().zip(new_edge_ids));
+
+    Ok(mapping)
+}
+
+/// Collect the properties of `from` that `existing` (and earlier merges) lack.
+fn copy_missing(existing: &PropertyMap, from: &PropertyMap, missing: &mut PropertyMap) {
+    for (key, value) in from.iter() {
+        if !existing.contains_key(key) && !missing.contains_key(key) {
+            missing.insert(key.clone(), value.clone());
+        }
+    }
+}
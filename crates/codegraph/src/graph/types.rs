@@ -48,6 +48,34 @@ impl std::fmt::Display for NodeType {
     }
 }
 
+impl NodeType {
+    /// Every node type, in declaration order.
+    pub const ALL: [NodeType; 8] = [
+        NodeType::CodeFile,
+        NodeType::Function,
+        NodeType::Class,
+        NodeType::Module,
+        NodeType::Variable,
+        NodeType::Type,
+        NodeType::Interface,
+        NodeType::Generic,
+    ];
+}
+
+impl std::str::FromStr for NodeType {
+    type Err = crate::error::GraphError;
+
+    /// Parse a node type from its display name (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| crate::error::GraphError::InvalidOperation {
+                message: format!("Unknown node type '{s}'"),
+            })
+    }
+}
+
 /// Type of edge (relationship) between nodes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EdgeType {
@@ -96,6 +124,38 @@ impl std::fmt::Display for EdgeType {
     }
 }
 
+impl EdgeType {
+    /// Every edge type, in declaration order.
+    pub const ALL: [EdgeType; 12] = [
+        EdgeType::Imports,
+        EdgeType::ImportsFrom,
+        EdgeType::Contains,
+        EdgeType::Calls,
+        EdgeType::Invokes,
+        EdgeType::Instantiates,
+        EdgeType::Extends,
+        EdgeType::Implements,
+        EdgeType::Uses,
+        EdgeType::Defines,
+        EdgeType::References,
+        EdgeType::RuntimeCalls,
+    ];
+}
+
+impl std::str::FromStr for EdgeType {
+    type Err = crate::error::GraphError;
+
+    /// Parse an edge type from its display name (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| crate::error::GraphError::InvalidOperation {
+                message: format!("Unknown edge type '{s}'"),
+            })
+    }
+}
+
 /// Direction for neighbor queries.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Direction {
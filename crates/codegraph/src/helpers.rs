@@ -8,7 +8,9 @@
 //! and tracking relationships between them.
 
 use crate::error::Result;
-use crate::graph::{CodeGraph, Direction, EdgeId, EdgeType, NodeId, NodeType, PropertyMap};
+use crate::graph::{
+    CodeGraph, Direction, Edge, EdgeId, EdgeType, Node, NodeId, NodeType, PropertyMap,
+};
 
 /// Metadata for a function with extended properties.
 pub struct FunctionMetadata<'a> {
@@ -459,6 +461,43 @@ pub fn find_file_by_path(graph: &CodeGraph, path: &str) -> Result<Option<NodeId>
     Ok(results.into_iter().next())
 }
 
+/// Find the file that contains a node.
+///
+/// Follows incoming Contains edges upwards (e.g. method -> class -> file) until a
+/// CodeFile node is reached. A CodeFile node is its own containing file.
+///
+/// # Arguments
+///
+/// * `graph` - The code graph
+/// * `node_id` - The node to locate
+///
+/// # Returns
+///
+/// `Some(NodeId)` of the containing file, or `None` if the node is not contained in one.
+pub fn containing_file(graph: &CodeGraph, node_id: NodeId) -> Result<Option<NodeId>> {
+    let mut current = node_id;
+    let mut visited = std::collections::HashSet::new();
+
+    while visited.insert(current) {
+        if graph.get_node(current)?.node_type == NodeType::CodeFile {
+            return Ok(Some(current));
+        }
+
+        let container = graph
+            .incoming_edges(current)
+            .filter(|edge| edge.edge_type == EdgeType::Contains)
+            .map(|edge| edge.source_id)
+            .min();
+
+        match container {
+            Some(parent) => current = parent,
+            None => return Ok(None),
+        }
+    }
+
+    Ok(None)
+}
+
 /// Convert a slice of node IDs to their corresponding file paths.
 ///
 /// Looks up each node and extracts the "path" property. Nodes that don't exist
@@ -653,3 +692,90 @@ pub fn circular_deps(graph: &CodeGraph) -> Result<Vec<Vec<NodeId>>> {
 
     Ok(file_cycles)
 }
+
+/// A suggested import to remove in order to break a circular dependency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleBreak {
+    /// The Imports/ImportsFrom edge to remove
+    pub edge_id: EdgeId,
+    /// The importing file
+    pub from_id: NodeId,
+    /// Path (or name) of the importing file
+    pub from_path: String,
+    /// The imported file or module
+    pub to_id: NodeId,
+    /// Path (or name) of the imported file or module
+    pub to_path: String,
+    /// Line of the import statement, from the `line` edge property written by
+    /// the parser mappers
+    pub line: Option<i64>,
+    /// Symbols pulled in by the import (the code that has to move)
+    pub symbols: Vec<String>,
+    /// All nodes of the cycle this edge participates in
+    pub cycle: Vec<NodeId>,
+}
+
+/// Suggest a small set of imports whose removal breaks every circular dependency.
+///
+/// Runs [`crate::graph::algorithms::minimum_feedback_edges`] over Imports and
+/// ImportsFrom edges, weighting each edge by the number of imported symbols so
+/// that narrow imports are preferred. The result is an actionable refactoring
+/// plan: for each suggested edge, the file pair, line evidence and symbols involved.
+///
+/// # Arguments
+///
+/// * `graph` - The code graph
+///
+/// # Returns
+///
+/// Vector of suggested cycle breaks, grouped by cycle and ordered by edge ID.
+pub fn suggest_cycle_breaks(graph: &CodeGraph) -> Result<Vec<CycleBreak>> {
+    use crate::graph::algorithms;
+
+    let import_types = [EdgeType::Imports, EdgeType::ImportsFrom];
+    let edges = algorithms::minimum_feedback_edges(graph, &import_types, |edge| {
+        import_symbols(edge).len().max(1) as f64
+    })?;
+
+    let mut cycle_of: std::collections::HashMap<NodeId, Vec<NodeId>> =
+        std::collections::HashMap::new();
+    for scc in algorithms::strongly_connected_components_by_type(graph, &import_types) {
+        for &node_id in &scc {
+            cycle_of.insert(node_id, scc.clone());
+        }
+    }
+
+    let mut breaks = Vec::with_capacity(edges.len());
+    for edge_id in edges {
+        let edge = graph.get_edge(edge_id)?;
+        breaks.push(CycleBreak {
+            edge_id,
+            from_id: edge.source_id,
+            from_path: display_path(graph.get_node(edge.source_id)?),
+            to_id: edge.target_id,
+            to_path: display_path(graph.get_node(edge.target_id)?),
+            line: edge.properties.get_int("line"),
+            symbols: import_symbols(edge),
+            cycle: cycle_of.get(&edge.source_id).cloned().unwrap_or_default(),
+        });
+    }
+
+    breaks.sort_by(|a, b| a.cycle.cmp(&b.cycle).then(a.edge_id.cmp(&b.edge_id)));
+    Ok(breaks)
+}
+
+/// Imported symbols recorded on an import edge.
+fn import_symbols(edge: &Edge) -> Vec<String> {
+    edge.properties
+        .get_string_list_compat("symbols")
+        .unwrap_or_default()
+}
+
+/// Best human-readable location of a node: its path, falling back to its name.
+fn display_path(node: &Node) -> String {
+    node.properties
+        .get_string("path")
+        .or_else(|| node.properties.get_string("name"))
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("node:{}", node.id))
+}
@@ -58,6 +58,7 @@ pub mod graph;
 pub mod helpers;
 pub mod metadata;
 pub mod query;
+pub mod search;
 pub mod storage;
 
 // Re-export main types
@@ -66,7 +67,7 @@ pub use graph::{
     CodeGraph, Direction, Edge, EdgeId, EdgeType, Node, NodeId, NodeType, PropertyMap,
     PropertyValue,
 };
-pub use query::QueryBuilder;
+pub use query::{EdgeQueryBuilder, QueryBuilder};
 #[cfg(feature = "rocksdb-backend")]
 pub use storage::RocksDBBackend;
 pub use storage::{MemoryBackend, NamespacedBackend, StorageBackend};
@@ -0,0 +1,293 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregations over node query results.
+//!
+//! [`QueryBuilder::aggregate`](super::QueryBuilder::aggregate) reduces the matching
+//! nodes to a [`QueryResult`] table, optionally split into groups by a property,
+//! the containing file, a property of that file, or its directory (module/package).
+//!
+//! ```
+//! use codegraph::query::{Aggregate, GroupBy, SortOrder};
+//! use codegraph::{CodeGraph, NodeType};
+//!
+//! # fn example() -> codegraph::Result<()> {
+//! let graph = CodeGraph::in_memory()?;
+//!
+//! // Average complexity per file, most complex first
+//! let mut per_file = graph.query()
+//!     .node_type(NodeType::Function)
+//!     .group_by(GroupBy::File)
+//!     .aggregate(&[Aggregate::count(), Aggregate::avg("complexity")])?;
+//! per_file.sort_by("avg(complexity)", SortOrder::Descending)?;
+//!
+//! // Number of functions per language
+//! let per_language = graph.query()
+//!     .node_type(NodeType::Function)
+//!     .group_by(GroupBy::file_property("language"))
+//!     .aggregate(&[Aggregate::count()])?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::order::total_cmp;
+use super::QueryResult;
+use crate::error::Result;
+use crate::graph::community::node_directory;
+use crate::graph::{CodeGraph, NodeId, PropertyValue};
+use crate::helpers::containing_file;
+
+/// How to split query results into groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Value of a node property (column named after the property)
+    Property(String),
+    /// Path of the containing file (column `file`)
+    File,
+    /// Property of the containing file, e.g. `language` (column `file.<key>`)
+    FileProperty(String),
+    /// Directory of the containing file, i.e. its module or package (column `directory`)
+    Directory,
+}
+
+impl GroupBy {
+    /// Group by a node property.
+    pub fn property(key: &str) -> Self {
+        Self::Property(key.to_string())
+    }
+
+    /// Group by a property of the containing file.
+    pub fn file_property(key: &str) -> Self {
+        Self::FileProperty(key.to_string())
+    }
+
+    /// Name of the group column in the result.
+    pub fn column(&self) -> String {
+        match self {
+            Self::Property(key) => key.clone(),
+            Self::File => "file".to_string(),
+            Self::FileProperty(key) => format!("file.{key}"),
+            Self::Directory => "directory".to_string(),
+        }
+    }
+
+    /// Group value of a node; [`PropertyValue::Null`] if it has none.
+    fn value(&self, graph: &CodeGraph, node_id: NodeId) -> Result<PropertyValue> {
+        let value = match self {
+            Self::Property(key) => graph.get_node(node_id)?.properties.get(key).cloned(),
+            Self::File => file_property(graph, node_id, "path")?,
+            Self::FileProperty(key) => file_property(graph, node_id, key)?,
+            Self::Directory => node_directory(graph, node_id)?.map(PropertyValue::String),
+        };
+        Ok(value.unwrap_or(PropertyValue::Null))
+    }
+}
+
+fn file_property(graph: &CodeGraph, node_id: NodeId, key: &str) -> Result<Option<PropertyValue>> {
+    Ok(match containing_file(graph, node_id)? {
+        Some(file_id) => graph.get_node(file_id)?.properties.get(key).cloned(),
+        None => None,
+    })
+}
+
+/// An aggregate operator, producing one column of the result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Aggregate {
+    /// Number of nodes (column `count`)
+    Count,
+    /// Sum of a numeric property (column `sum(<key>)`)
+    Sum(String),
+    /// Mean of a numeric property (column `avg(<key>)`)
+    Avg(String),
+    /// Smallest value of a numeric property (column `min(<key>)`)
+    Min(String),
+    /// Largest value of a numeric property (column `max(<key>)`)
+    Max(String),
+}
+
+impl Aggregate {
+    /// Number of nodes.
+    pub fn count() -> Self {
+        Self::Count
+    }
+
+    /// Sum of a numeric property.
+    pub fn sum(key: &str) -> Self {
+        Self::Sum(key.to_string())
+    }
+
+    /// Mean of a numeric property.
+    pub fn avg(key: &str) -> Self {
+        Self::Avg(key.to_string())
+    }
+
+    /// Smallest value of a numeric property.
+    pub fn min(key: &str) -> Self {
+        Self::Min(key.to_string())
+    }
+
+    /// Largest value of a numeric property.
+    pub fn max(key: &str) -> Self {
+        Self::Max(key.to_string())
+    }
+
+    /// Name of the aggregate column in the result.
+    pub fn column(&self) -> String {
+        match self {
+            Self::Count => "count".to_string(),
+            Self::Sum(key) => format!("sum({key})"),
+            Self::Avg(key) => format!("avg({key})"),
+            Self::Min(key) => format!("min({key})"),
+            Self::Max(key) => format!("max({key})"),
+        }
+    }
+
+    /// Reduce a group of nodes.
+    ///
+    /// Non-numeric and missing values are skipped; with no values left, `sum`,
+    /// `avg`, `min` and `max` are [`PropertyValue::Null`].
+    fn apply(&self, graph: &CodeGraph, nodes: &[NodeId]) -> PropertyValue {
+        let key = match self {
+            Self::Count => return PropertyValue::Int(nodes.len() as i64),
+            Self::Sum(key) | Self::Avg(key) | Self::Min(key) | Self::Max(key) => key,
+        };
+        let values: Vec<PropertyValue> = nodes
+            .iter()
+            .filter_map(|&id| graph.get_node(id).ok()?.properties.get(key))
+            .filter_map(numeric)
+            .collect();
+        if values.is_empty() {
+            return PropertyValue::Null;
+        }
+
+        match self {
+            Self::Sum(_) => sum(&values),
+            Self::Avg(_) => {
+                let total: f64 = values.iter().map(as_f64).sum();
+                PropertyValue::Float(total / values.len() as f64)
+            }
+            Self::Min(_) => values
+                .into_iter()
+                .min_by(total_cmp)
+                .unwrap_or(PropertyValue::Null),
+            Self::Max(_) => values
+                .into_iter()
+                .max_by(total_cmp)
+                .unwrap_or(PropertyValue::Null),
+            Self::Count => unreachable!("count returns early"),
+        }
+    }
+}
+
+/// Integer sum while every value is an integer and it fits, float sum otherwise.
+fn sum(values: &[PropertyValue]) -> PropertyValue {
+    let int_sum = values.iter().try_fold(0i64, |total, value| match value {
+        PropertyValue::Int(i) => total.checked_add(*i),
+        _ => None,
+    });
+    match int_sum {
+        Some(total) => PropertyValue::Int(total),
+        None => PropertyValue::Float(values.iter().map(as_f64).sum()),
+    }
+}
+
+/// Numeric view of a value: ints, floats and numeric strings.
+fn numeric(value: &PropertyValue) -> Option<PropertyValue> {
+    match value {
+        PropertyValue::Int(_) | PropertyValue::Float(_) => Some(value.clone()),
+        PropertyValue::String(s) => {
+            let s = s.trim();
+            s.parse()
+                .map(PropertyValue::Int)
+                .or_else(|_| s.parse().map(PropertyValue::Float))
+                .ok()
+        }
+        _ => None,
+    }
+}
+
+fn as_f64(value: &PropertyValue) -> f64 {
+    match value {
+        PropertyValue::Int(i) => *i as f64,
+        PropertyValue::Float(f) => *f,
+        _ => 0.0,
+    }
+}
+
+/// Aggregate nodes into a table: one row per group (sorted by group value, `Null`
+/// last), or a single row without a group column when `group_by` is `None`.
+pub(crate) fn aggregate(
+    graph: &CodeGraph,
+    nodes: &[NodeId],
+    group_by: Option<&GroupBy>,
+    aggregates: &[Aggregate],
+) -> Result<QueryResult> {
+    let aggregate_columns = aggregates.iter().map(Aggregate::column);
+    let Some(group_by) = group_by else {
+        return Ok(QueryResult {
+            columns: aggregate_columns.collect(),
+            rows: vec![aggregates
+                .iter()
+                .map(|aggregate| aggregate.apply(graph, nodes))
+                .collect()],
+        });
+    };
+
+    let mut keyed = nodes
+        .iter()
+        .map(|&id| Ok((group_by.value(graph, id)?, id)))
+        .collect::<Result<Vec<_>>>()?;
+    keyed.sort_by(|a, b| total_cmp(&a.0, &b.0));
+
+    let mut rows = Vec::new();
+    let mut start = 0;
+    while start < keyed.len() {
+        let end = keyed[start..]
+            .iter()
+            .position(|(value, _)| total_cmp(value, &keyed[start].0).is_ne())
+            .map_or(keyed.len(), |len| start + len);
+        let group = &keyed[start..end];
+        start = end;
+
+        let members: Vec<NodeId> = group.iter().map(|(_, id)| *id).collect();
+        let mut row = vec![group[0].0.clone()];
+        row.extend(
+            aggregates
+                .iter()
+                .map(|aggregate| aggregate.apply(graph, &members)),
+        );
+        rows.push(row);
+    }
+
+    Ok(QueryResult {
+        columns: std::iter::once(group_by.column())
+            .chain(aggregate_columns)
+            .collect(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_falls_back_to_float() {
+        let ints = [PropertyValue::Int(2), PropertyValue::Int(3)];
+        assert_eq!(sum(&ints), PropertyValue::Int(5));
+
+        let mixed = [PropertyValue::Int(2), PropertyValue::Float(0.5)];
+        assert_eq!(sum(&mixed), PropertyValue::Float(2.5));
+
+        let overflow = [PropertyValue::Int(i64::MAX), PropertyValue::Int(1)];
+        assert!(matches!(sum(&overflow), PropertyValue::Float(_)));
+    }
+
+    #[test]
+    fn test_numeric_strings() {
+        assert_eq!(numeric(&"12".into()), Some(PropertyValue::Int(12)));
+        assert_eq!(numeric(&" 1.5".into()), Some(PropertyValue::Float(1.5)));
+        assert_eq!(numeric(&"high".into()), None);
+        assert_eq!(numeric(&PropertyValue::Bool(true)), None);
+    }
+}
@@ -0,0 +1,267 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Query builder for edges.
+//!
+//! Mirrors [`QueryBuilder`](super::QueryBuilder) for relationships: edges can be
+//! filtered by type, properties and endpoints, then returned as IDs, as edges, or
+//! handed back to a node query via [`EdgeQueryBuilder::sources`] and
+//! [`EdgeQueryBuilder::targets`].
+
+use super::{check_error, record_error, Predicate, QueryBuilder};
+use crate::error::Result;
+use crate::graph::{CodeGraph, Edge, EdgeId, EdgeType, Node, NodeId, PropertyValue};
+
+/// Fluent query builder for edges.
+///
+/// # Examples
+///
+/// ```
+/// use codegraph::{CodeGraph, EdgeType, NodeType};
+///
+/// # fn example() -> codegraph::Result<()> {
+/// let graph = CodeGraph::in_memory()?;
+/// // ... populate graph ...
+///
+/// // Indirect calls
+/// let indirect = graph.edge_query()
+///     .edge_type(EdgeType::Calls)
+///     .property("is_direct", false)
+///     .execute()?;
+///
+/// // Files pulled in by wildcard imports
+/// let wildcard_targets = graph.edge_query()
+///     .edge_type(EdgeType::Imports)
+///     .property("is_wildcard", true)
+///     .targets()
+///     .node_type(NodeType::CodeFile)
+///     .dedup()
+///     .execute()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct EdgeQueryBuilder<'a> {
+    graph: &'a CodeGraph,
+    filters: Vec<Predicate<Edge>>,
+    source_filters: Vec<Predicate<Node>>,
+    target_filters: Vec<Predicate<Node>>,
+    from_node: Option<NodeId>,
+    to_node: Option<NodeId>,
+    limit_value: Option<usize>,
+    // First invalid argument (e.g. a bad regex), reported when the query runs
+    error: Option<String>,
+}
+
+impl<'a> EdgeQueryBuilder<'a> {
+    /// Create a new edge query builder for the given graph.
+    pub fn new(graph: &'a CodeGraph) -> Self {
+        Self {
+            graph,
+            filters: Vec::new(),
+            source_filters: Vec::new(),
+            target_filters: Vec::new(),
+            from_node: None,
+            to_node: None,
+            limit_value: None,
+            error: None,
+        }
+    }
+
+    /// Filter edges by type.
+    pub fn edge_type(self, edge_type: EdgeType) -> Self {
+        self.filter(Predicate::edge_type(edge_type))
+    }
+
+    /// Filter edges whose type is any of the given types.
+    pub fn edge_types(self, edge_types: &[EdgeType]) -> Self {
+        self.filter(Predicate::any(
+            edge_types.iter().map(|&t| Predicate::edge_type(t)),
+        ))
+    }
+
+    /// Only edges leaving a specific node.
+    pub fn from(mut self, node_id: NodeId) -> Self {
+        self.from_node = Some(node_id);
+        self
+    }
+
+    /// Only edges entering a specific node.
+    pub fn to(mut self, node_id: NodeId) -> Self {
+        self.to_node = Some(node_id);
+        self
+    }
+
+    /// Only edges whose source node matches a predicate.
+    pub fn source(mut self, predicate: Predicate<Node>) -> Self {
+        self.source_filters.push(predicate);
+        self
+    }
+
+    /// Only edges whose target node matches a predicate.
+    pub fn target(mut self, predicate: Predicate<Node>) -> Self {
+        self.target_filters.push(predicate);
+        self
+    }
+
+    /// Filter edges by exact property match.
+    pub fn property<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property(key, value))
+    }
+
+    /// Filter edges that have a specific property (regardless of value).
+    pub fn property_exists(self, key: &str) -> Self {
+        self.filter(Predicate::property_exists(key))
+    }
+
+    /// Filter edges whose property is greater than `value`.
+    pub fn property_gt<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_gt(key, value))
+    }
+
+    /// Filter edges whose property is greater than or equal to `value`.
+    pub fn property_ge<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_ge(key, value))
+    }
+
+    /// Filter edges whose property is less than `value`.
+    pub fn property_lt<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_lt(key, value))
+    }
+
+    /// Filter edges whose property is less than or equal to `value`.
+    pub fn property_le<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_le(key, value))
+    }
+
+    /// Filter edges whose property lies in the inclusive range `low..=high`.
+    pub fn between<V: Into<PropertyValue>>(self, key: &str, low: V, high: V) -> Self {
+        self.filter(Predicate::between(key, low, high))
+    }
+
+    /// Filter edges whose list property (e.g. `symbols`) contains `value`.
+    pub fn list_contains<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::list_contains(key, value))
+    }
+
+    /// Filter edges whose string property matches a regular expression.
+    pub fn property_matches(mut self, key: &str, pattern: &str) -> Self {
+        match Predicate::property_matches(key, pattern) {
+            Ok(predicate) => self.filter(predicate),
+            Err(e) => {
+                record_error(&mut self.error, e);
+                self
+            }
+        }
+    }
+
+    /// Filter edges by a composed [`Predicate`].
+    pub fn filter(mut self, predicate: Predicate<Edge>) -> Self {
+        self.filters.push(predicate);
+        self
+    }
+
+    /// Keep edges matching either predicate.
+    pub fn or(self, first: Predicate<Edge>, second: Predicate<Edge>) -> Self {
+        self.filter(first.or(second))
+    }
+
+    /// Exclude edges matching a predicate.
+    pub fn not(self, predicate: Predicate<Edge>) -> Self {
+        self.filter(!predicate)
+    }
+
+    /// Filter edges using a custom predicate function.
+    pub fn custom<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&Edge) -> bool + 'static,
+    {
+        self.filter(Predicate::new(predicate))
+    }
+
+    /// Limit the number of results returned.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit_value = Some(n);
+        self
+    }
+
+    /// Execute the query and return matching edge IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`](crate::GraphError::InvalidOperation)
+    /// if a filter argument was invalid.
+    pub fn execute(&self) -> Result<Vec<EdgeId>> {
+        Ok(self.edges()?.into_iter().map(|edge| edge.id).collect())
+    }
+
+    /// Execute the query and return the matching edges.
+    pub fn edges(&self) -> Result<Vec<&'a Edge>> {
+        check_error(&self.error)?;
+        let limit = self.limit_value.unwrap_or(usize::MAX);
+        Ok(self
+            .candidates()
+            .filter(|edge| self.matches(edge))
+            .take(limit)
+            .collect())
+    }
+
+    /// Count the number of matching edges.
+    pub fn count(&self) -> Result<usize> {
+        check_error(&self.error)?;
+        Ok(self.candidates().filter(|edge| self.matches(edge)).count())
+    }
+
+    /// Check if any edges match the query.
+    pub fn exists(&self) -> Result<bool> {
+        check_error(&self.error)?;
+        Ok(self.candidates().any(|edge| self.matches(edge)))
+    }
+
+    /// Continue with a node query over the sources of the matching edges.
+    ///
+    /// A node appears once per matching edge; use [`QueryBuilder::dedup`] to collapse
+    /// repeats. An invalid filter argument is carried over to the node query.
+    pub fn sources(self) -> QueryBuilder<'a> {
+        self.endpoints(|edge| edge.source_id)
+    }
+
+    /// Continue with a node query over the targets of the matching edges.
+    pub fn targets(self) -> QueryBuilder<'a> {
+        self.endpoints(|edge| edge.target_id)
+    }
+
+    fn endpoints(self, endpoint: fn(&Edge) -> NodeId) -> QueryBuilder<'a> {
+        let builder = QueryBuilder::new(self.graph);
+        match self.edges() {
+            Ok(edges) => builder.with_start_nodes(edges.into_iter().map(endpoint).collect()),
+            Err(e) => builder.with_start_nodes(Vec::new()).fail(e),
+        }
+    }
+
+    /// Edges to consider, narrowed by `from`/`to` via the adjacency index.
+    fn candidates(&self) -> Box<dyn Iterator<Item = &'a Edge> + 'a> {
+        let graph = self.graph;
+        match (self.from_node, self.to_node) {
+            (Some(from), to) => Box::new(
+                graph
+                    .outgoing_edges(from)
+                    .filter(move |edge| to.map_or(true, |to| edge.target_id == to)),
+            ),
+            (None, Some(to)) => Box::new(graph.incoming_edges(to)),
+            (None, None) => Box::new(graph.iter_edges().map(|(_, edge)| edge)),
+        }
+    }
+
+    fn matches(&self, edge: &Edge) -> bool {
+        let node_matches = |filters: &[Predicate<Node>], node_id: NodeId| {
+            filters.is_empty()
+                || self
+                    .graph
+                    .get_node(node_id)
+                    .is_ok_and(|node| filters.iter().all(|p| p.test(node)))
+        };
+        self.filters.iter().all(|p| p.test(edge))
+            && node_matches(&self.source_filters, edge.source_id)
+            && node_matches(&self.target_filters, edge.target_id)
+    }
+}
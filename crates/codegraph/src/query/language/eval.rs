@@ -0,0 +1,328 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Expression values and evaluation.
+//!
+//! Evaluation follows Cypher's three-valued logic: comparisons involving a missing
+//! property yield `Null`, and `WHERE` keeps only rows that evaluate to `true`.
+
+use super::parser::{compile_regex, CompareOp, Expr};
+use crate::error::{GraphError, Result};
+use crate::graph::{CodeGraph, PropertyValue};
+use crate::query::PatternMatch;
+use std::cmp::Ordering;
+
+/// Runtime value of an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<Value>),
+}
+
+impl From<&PropertyValue> for Value {
+    fn from(value: &PropertyValue) -> Self {
+        match value {
+            PropertyValue::String(s) => Value::String(s.clone()),
+            PropertyValue::Int(i) => Value::Int(*i),
+            PropertyValue::Float(f) => Value::Float(*f),
+            PropertyValue::Bool(b) => Value::Bool(*b),
+            PropertyValue::StringList(items) => {
+                Value::List(items.iter().cloned().map(Value::String).collect())
+            }
+            PropertyValue::IntList(items) => {
+                Value::List(items.iter().copied().map(Value::Int).collect())
+            }
+            PropertyValue::Vector(items) => {
+                Value::List(items.iter().map(|&x| Value::Float(f64::from(x))).collect())
+            }
+            PropertyValue::Null => Value::Null,
+        }
+    }
+}
+
+/// Computed values as property values. Stored vectors are returned without this
+/// conversion (see [`property`]); a computed list of floats has no `f64` property
+/// type and is an error rather than being narrowed to a vector.
+impl TryFrom<Value> for PropertyValue {
+    type Error = GraphError;
+
+    fn try_from(value: Value) -> Result<Self> {
+        Ok(match value {
+            Value::Null => PropertyValue::Null,
+            Value::Bool(b) => PropertyValue::Bool(b),
+            Value::Int(i) => PropertyValue::Int(i),
+            Value::Float(f) => PropertyValue::Float(f),
+            Value::String(s) => PropertyValue::String(s),
+            Value::List(items) if items.iter().all(|v| matches!(v, Value::Int(_))) => {
+                PropertyValue::IntList(
+                    items
+                        .into_iter()
+                        .filter_map(|v| match v {
+                            Value::Int(i) => Some(i),
+                            _ => None,
+                        })
+                        .collect(),
+                )
+            }
+            Value::List(items) if items.iter().all(|v| matches!(v, Value::Float(_))) => {
+                return Err(GraphError::InvalidOperation {
+                    message: format!(
+                        "Cannot return the list of floats {}; only vector properties are returned as vectors",
+                        Value::List(items)
+                    ),
+                });
+            }
+            Value::List(items) => {
+                PropertyValue::StringList(items.into_iter().map(|v| v.to_string()).collect())
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Int(i) => write!(f, "{i}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::String(s) => write!(f, "{s}"),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", items.join(", "))
+            }
+        }
+    }
+}
+
+impl Value {
+    /// Loose equality: numbers compare numerically, `Null` is unknown.
+    pub(crate) fn equals(&self, other: &Value) -> Option<bool> {
+        match (self, other) {
+            (Value::Null, _) | (_, Value::Null) => None,
+            (Value::List(a), Value::List(b)) => {
+                if a.len() != b.len() {
+                    return Some(false);
+                }
+                let mut result = Some(true);
+                for (x, y) in a.iter().zip(b) {
+                    match x.equals(y) {
+                        Some(false) => return Some(false),
+                        None => result = None,
+                        Some(true) => {}
+                    }
+                }
+                result
+            }
+            _ => match self.compare(other) {
+                Some(ordering) => Some(ordering == Ordering::Equal),
+                None => Some(false),
+            },
+        }
+    }
+
+    /// Key under which `DISTINCT` treats rows as duplicates, consistent with
+    /// [`equals`](Self::equals) on numbers: `1.0` and `1` share a key.
+    pub(crate) fn distinct_key(&self) -> String {
+        match self {
+            Value::Float(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => {
+                format!("{:?}", Value::Int(*f as i64))
+            }
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(Value::distinct_key).collect();
+                format!("[{}]", items.join(", "))
+            }
+            other => format!("{other:?}"),
+        }
+    }
+
+    /// Ordering between numbers, strings or booleans; `None` if incomparable.
+    fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Int(a), Value::Int(b)) => Some(a.cmp(b)),
+            (Value::Int(a), Value::Float(b)) => (*a as f64).partial_cmp(b),
+            (Value::Float(a), Value::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Value::Float(a), Value::Float(b)) => a.partial_cmp(b),
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Truth value for boolean operators: `None` means unknown.
+    fn truth(&self) -> Option<bool> {
+        match self {
+            Value::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+fn from_truth(truth: Option<bool>) -> Value {
+    truth.map_or(Value::Null, Value::Bool)
+}
+
+/// The stored value of `var.key`, if the variable is bound and the property set.
+pub(crate) fn property<'g>(
+    graph: &'g CodeGraph,
+    bindings: &PatternMatch,
+    var: &str,
+    key: &str,
+) -> Option<&'g PropertyValue> {
+    bindings
+        .get(var)
+        .and_then(|id| graph.get_node(id).ok())
+        .and_then(|node| node.properties.get(key))
+}
+
+/// Evaluate an expression against one set of pattern bindings.
+pub(crate) fn evaluate(expr: &Expr, graph: &CodeGraph, bindings: &PatternMatch) -> Value {
+    match expr {
+        Expr::Literal(value) => value.clone(),
+        Expr::List(items) => Value::List(
+            items
+                .iter()
+                .map(|item| evaluate(item, graph, bindings))
+                .collect(),
+        ),
+        Expr::Variable(var) | Expr::Id(var) => bindings
+            .get(var)
+            .map_or(Value::Null, |id| Value::Int(id as i64)),
+        Expr::Property(var, key) => {
+            property(graph, bindings, var, key).map_or(Value::Null, Value::from)
+        }
+        Expr::Type(var) => bindings
+            .get(var)
+            .and_then(|id| graph.get_node(id).ok())
+            .map_or(Value::Null, |node| {
+                Value::String(node.node_type.to_string())
+            }),
+        Expr::Neg(inner) => match evaluate(inner, graph, bindings) {
+            Value::Int(i) => i.checked_neg().map_or(Value::Null, Value::Int),
+            Value::Float(f) => Value::Float(-f),
+            _ => Value::Null,
+        },
+        Expr::Not(inner) => from_truth(evaluate(inner, graph, bindings).truth().map(|b| !b)),
+        Expr::And(lhs, rhs) => {
+            let lhs = evaluate(lhs, graph, bindings).truth();
+            if lhs == Some(false) {
+                return Value::Bool(false);
+            }
+            match (lhs, evaluate(rhs, graph, bindings).truth()) {
+                (_, Some(false)) => Value::Bool(false),
+                (Some(true), Some(true)) => Value::Bool(true),
+                _ => Value::Null,
+            }
+        }
+        Expr::Or(lhs, rhs) => {
+            let lhs = evaluate(lhs, graph, bindings).truth();
+            if lhs == Some(true) {
+                return Value::Bool(true);
+            }
+            match (lhs, evaluate(rhs, graph, bindings).truth()) {
+                (_, Some(true)) => Value::Bool(true),
+                (Some(false), Some(false)) => Value::Bool(false),
+                _ => Value::Null,
+            }
+        }
+        Expr::IsNull(inner, negated) => {
+            let is_null = evaluate(inner, graph, bindings) == Value::Null;
+            Value::Bool(is_null != *negated)
+        }
+        Expr::Matches(inner, regex) => match evaluate(inner, graph, bindings) {
+            Value::String(text) => Value::Bool(regex.is_match(&text)),
+            _ => Value::Null,
+        },
+        Expr::Compare(op, lhs, rhs) => {
+            let lhs = evaluate(lhs, graph, bindings);
+            let rhs = evaluate(rhs, graph, bindings);
+            from_truth(compare(*op, &lhs, &rhs))
+        }
+    }
+}
+
+fn compare(op: CompareOp, lhs: &Value, rhs: &Value) -> Option<bool> {
+    if *lhs == Value::Null || *rhs == Value::Null {
+        return None;
+    }
+    match op {
+        CompareOp::Eq => lhs.equals(rhs),
+        CompareOp::Ne => lhs.equals(rhs).map(|eq| !eq),
+        CompareOp::Lt => lhs.compare(rhs).map(Ordering::is_lt),
+        CompareOp::Le => lhs.compare(rhs).map(Ordering::is_le),
+        CompareOp::Gt => lhs.compare(rhs).map(Ordering::is_gt),
+        CompareOp::Ge => lhs.compare(rhs).map(Ordering::is_ge),
+        CompareOp::In => match rhs {
+            Value::List(items) => {
+                let mut result = Some(false);
+                for item in items {
+                    match lhs.equals(item) {
+                        Some(true) => return Some(true),
+                        None => result = None,
+                        Some(false) => {}
+                    }
+                }
+                result
+            }
+            _ => None,
+        },
+        CompareOp::Contains | CompareOp::StartsWith | CompareOp::EndsWith | CompareOp::Regex => {
+            let (Value::String(text), Value::String(needle)) = (lhs, rhs) else {
+                return None;
+            };
+            Some(match op {
+                CompareOp::Contains => text.contains(needle.as_str()),
+                CompareOp::StartsWith => text.starts_with(needle.as_str()),
+                CompareOp::EndsWith => text.ends_with(needle.as_str()),
+                _ => match compile_regex(needle) {
+                    Ok(regex) => regex.is_match(text),
+                    Err(_) => return None,
+                },
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_comparison_across_types() {
+        assert_eq!(
+            compare(CompareOp::Gt, &Value::Int(11), &Value::Float(10.5)),
+            Some(true)
+        );
+        assert_eq!(
+            compare(CompareOp::Eq, &Value::Float(2.0), &Value::Int(2)),
+            Some(true)
+        );
+        assert_eq!(
+            compare(CompareOp::Lt, &Value::Int(1), &Value::String("2".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn test_null_propagates() {
+        assert_eq!(compare(CompareOp::Eq, &Value::Null, &Value::Null), None);
+        assert_eq!(compare(CompareOp::Ne, &Value::Int(1), &Value::Null), None);
+    }
+
+    #[test]
+    fn test_in_list() {
+        let list = Value::List(vec![Value::String("a".into()), Value::String("b".into())]);
+        assert_eq!(
+            compare(CompareOp::In, &Value::String("b".into()), &list),
+            Some(true)
+        );
+        assert_eq!(
+            compare(CompareOp::In, &Value::String("c".into()), &list),
+            Some(false)
+        );
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tokenizer for the textual query language.
+
+use crate::error::{GraphError, Result};
+
+/// Kind of a lexical token.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TokenKind {
+    /// Identifier or keyword (keywords are recognized by the parser)
+    Ident(String),
+    /// Quoted string literal with escapes resolved
+    Str(String),
+    Int(i64),
+    Float(f64),
+    LParen,
+    RParen,
+    LBracket,
+    RBracket,
+    LBrace,
+    RBrace,
+    Colon,
+    Comma,
+    Dot,
+    DotDot,
+    Pipe,
+    Star,
+    Minus,
+    Arrow,
+    LeftArrow,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    RegexMatch,
+    Eof,
+}
+
+impl TokenKind {
+    /// Human-readable description for error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            TokenKind::Ident(name) => format!("'{name}'"),
+            TokenKind::Str(value) => format!("string \"{value}\""),
+            TokenKind::Int(value) => format!("number {value}"),
+            TokenKind::Float(value) => format!("number {value}"),
+            TokenKind::Eof => "end of query".to_string(),
+            other => format!("'{}'", other.symbol()),
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            TokenKind::LParen => "(",
+            TokenKind::RParen => ")",
+            TokenKind::LBracket => "[",
+            TokenKind::RBracket => "]",
+            TokenKind::LBrace => "{",
+            TokenKind::RBrace => "}",
+            TokenKind::Colon => ":",
+            TokenKind::Comma => ",",
+            TokenKind::Dot => ".",
+            TokenKind::DotDot => "..",
+            TokenKind::Pipe => "|",
+            TokenKind::Star => "*",
+            TokenKind::Minus => "-",
+            TokenKind::Arrow => "->",
+            TokenKind::LeftArrow => "<-",
+            TokenKind::Eq => "=",
+            TokenKind::Ne => "<>",
+            TokenKind::Lt => "<",
+            TokenKind::Le => "<=",
+            TokenKind::Gt => ">",
+            TokenKind::Ge => ">=",
+            TokenKind::RegexMatch => "=~",
+            _ => "",
+        }
+    }
+}
+
+/// A token with its byte span in the source text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Build a [`GraphError::QuerySyntax`] pointing at a byte offset of `source`.
+pub(crate) fn error_at(source: &str, offset: usize, message: impl Into<String>) -> GraphError {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    GraphError::QuerySyntax {
+        message: message.into(),
+        line,
+        column,
+    }
+}
+
+/// Split a query into tokens. The last token is always [`TokenKind::Eof`].
+pub(crate) fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        let c = bytes[pos];
+        let start = pos;
+
+        if c.is_ascii_whitespace() {
+            pos += 1;
+            continue;
+        }
+        // Line comments
+        if source[pos..].starts_with("//") {
+            pos = source[pos..].find('\n').map_or(bytes.len(), |n| pos + n);
+            continue;
+        }
+
+        let kind = if c.is_ascii_alphabetic() || c == b'_' {
+            while pos < bytes.len() && (bytes[pos].is_ascii_alphanumeric() || bytes[pos] == b'_') {
+                pos += 1;
+            }
+            TokenKind::Ident(source[start..pos].to_string())
+        } else if c == b'`' {
+            // Backquoted identifier
+            let close = source[pos + 1..]
+                .find('`')
+                .ok_or_else(|| error_at(source, start, "unterminated backquoted identifier"))?;
+            pos += close + 2;
+            TokenKind::Ident(source[start + 1..pos - 1].to_string())
+        } else if c.is_ascii_digit() {
+            while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                pos += 1;
+            }
+            // A single '.' followed by a digit is a fraction; '..' is a range
+            let is_float =
+                pos + 1 < bytes.len() && bytes[pos] == b'.' && bytes[pos + 1].is_ascii_digit();
+            if is_float {
+                pos += 1;
+                while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+                let text = &source[start..pos];
+                TokenKind::Float(
+                    text.parse()
+                        .map_err(|_| error_at(source, start, format!("invalid number '{text}'")))?,
+                )
+            } else {
+                let text = &source[start..pos];
+                TokenKind::Int(text.parse().map_err(|_| {
+                    error_at(source, start, format!("integer '{text}' is out of range"))
+                })?)
+            }
+        } else if c == b'"' || c == b'\'' {
+            let (value, next) = read_string(source, pos)?;
+            pos = next;
+            TokenKind::Str(value)
+        } else {
+            let two = source.get(pos..pos + 2).unwrap_or("");
+            let (kind, len) = match two {
+                ".." => (TokenKind::DotDot, 2),
+                "->" => (TokenKind::Arrow, 2),
+                "<-" => (TokenKind::LeftArrow, 2),
+                "<>" => (TokenKind::Ne, 2),
+                "!=" => (TokenKind::Ne, 2),
+                "<=" => (TokenKind::Le, 2),
+                ">=" => (TokenKind::Ge, 2),
+                "=~" => (TokenKind::RegexMatch, 2),
+                _ => {
+                    let kind = match c {
+                        b'(' => TokenKind::LParen,
+                        b')' => TokenKind::RParen,
+                        b'[' => TokenKind::LBracket,
+                        b']' => TokenKind::RBracket,
+                        b'{' => TokenKind::LBrace,
+                        b'}' => TokenKind::RBrace,
+                        b':' => TokenKind::Colon,
+                        b',' => TokenKind::Comma,
+                        b'.' => TokenKind::Dot,
+                        b'|' => TokenKind::Pipe,
+                        b'*' => TokenKind::Star,
+                        b'-' => TokenKind::Minus,
+                        b'=' => TokenKind::Eq,
+                        b'<' => TokenKind::Lt,
+                        b'>' => TokenKind::Gt,
+                        _ => {
+                            let ch = source[pos..].chars().next().unwrap_or('?');
+                            return Err(error_at(
+                                source,
+                                start,
+                                format!("unexpected character '{ch}'"),
+                            ));
+                        }
+                    };
+                    (kind, 1)
+                }
+            };
+            pos += len;
+            kind
+        };
+
+        tokens.push(Token {
+            kind,
+            start,
+            end: pos,
+        });
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::Eof,
+        start: source.len(),
+        end: source.len(),
+    });
+    Ok(tokens)
+}
+
+/// Read a quoted string starting at `start`; returns the value and the offset after it.
+fn read_string(source: &str, start: usize) -> Result<(String, usize)> {
+    let mut chars = source[start..].char_indices();
+    let (_, quote) = chars.next().expect("string starts with a quote");
+    let mut value = String::new();
+
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            c if c == quote => return Ok((value, start + i + 1)),
+            '\\' => {
+                let (j, escaped) = chars
+                    .next()
+                    .ok_or_else(|| error_at(source, start, "unterminated string literal"))?;
+                value.push(match escaped {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    '\\' | '\'' | '"' => escaped,
+                    other => {
+                        return Err(error_at(
+                            source,
+                            start + j - 1,
+                            format!("unknown escape sequence '\\{other}'"),
+                        ))
+                    }
+                });
+            }
+            other => value.push(other),
+        }
+    }
+
+    Err(error_at(source, start, "unterminated string literal"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_relationship() {
+        assert_eq!(
+            kinds("-[:Calls*1..3]->"),
+            vec![
+                TokenKind::Minus,
+                TokenKind::LBracket,
+                TokenKind::Colon,
+                TokenKind::Ident("Calls".into()),
+                TokenKind::Star,
+                TokenKind::Int(1),
+                TokenKind::DotDot,
+                TokenKind::Int(3),
+                TokenKind::RBracket,
+                TokenKind::Arrow,
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_literals() {
+        assert_eq!(
+            kinds(r#"'it\'s' "a\"b" 1.5 42"#),
+            vec![
+                TokenKind::Str("it's".into()),
+                TokenKind::Str("a\"b".into()),
+                TokenKind::Float(1.5),
+                TokenKind::Int(42),
+                TokenKind::Eof,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = tokenize("MATCH (f)\nWHERE f.x = #").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Query error at line 2, column 13: unexpected character '#'"
+        );
+    }
+}
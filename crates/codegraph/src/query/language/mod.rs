@@ -0,0 +1,258 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Textual graph query language: a small Cypher subset.
+//!
+//! ```text
+//! MATCH (f:Function)-[:Calls*1..3]->(g:Function {name: "exec"})
+//! WHERE f.complexity > 10
+//! RETURN f.name, f.path
+//! ```
+//!
+//! Supported syntax:
+//!
+//! - `MATCH` one or more comma-separated paths. Nodes are `(var:Label {key: value})`
+//!   where every part is optional and labels are [`NodeType`](crate::NodeType) names. Relationships are
+//!   `-[:Type]->` or `<-[:Type]-`, with `|` between alternative [`EdgeType`](crate::EdgeType)s, an
+//!   optional `{key: value}` edge property map, and `*`, `*n`, `*n..m`, `*..m` or `*n..`
+//!   for variable-length paths. `-->` and `<--` match any edge type.
+//! - `WHERE` with `AND`/`OR`/`NOT`, `=`, `<>`, `<`, `<=`, `>`, `>=`, `CONTAINS`,
+//!   `STARTS WITH`, `ENDS WITH`, `=~` (regex), `IN [..]` and `IS [NOT] NULL`.
+//!   Comparisons with a missing property are unknown and the row is dropped.
+//! - `RETURN [DISTINCT]` expressions such as `f`, `f.name`, `id(f)` or `type(f)`,
+//!   each optionally renamed with `AS`, followed by an optional `LIMIT n`.
+//!   Properties are returned as stored; computed lists of floats are rejected.
+//!
+//! Keywords, labels and relationship types are case-insensitive. Node variables
+//! evaluate to node IDs. Relationship variables and undirected relationships are
+//! not supported.
+//!
+//! Errors are reported as [`GraphError::QuerySyntax`](crate::GraphError::QuerySyntax)
+//! with the line and column of the offending token.
+
+mod eval;
+mod lexer;
+mod parser;
+
+use self::eval::{evaluate, property, Value};
+use self::lexer::error_at;
+use self::parser::{Expr, NodePattern, Statement};
+use super::order::{total_cmp, SortOrder};
+use super::pattern::{EdgePredicate, NodeMatcher, Pattern};
+use crate::error::{GraphError, Result};
+use crate::graph::{CodeGraph, Direction, PropertyMap, PropertyValue};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// A parsed textual query, reusable across graphs.
+#[derive(Debug, Clone)]
+pub struct GraphQuery {
+    statement: Statement,
+    /// Merged node patterns, one per variable, in order of first appearance
+    nodes: Vec<NodePattern>,
+}
+
+impl GraphQuery {
+    /// Parse and validate a query.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::QuerySyntax`](crate::GraphError::QuerySyntax) describing the
+    /// first problem found.
+    pub fn parse(text: &str) -> Result<Self> {
+        let statement = parser::parse(text)?;
+
+        let mut nodes: Vec<NodePattern> = Vec::new();
+        let occurrences = statement
+            .paths
+            .iter()
+            .flat_map(|path| std::iter::once(&path.start).chain(path.steps.iter().map(|(_, n)| n)));
+        for node in occurrences {
+            match nodes.iter_mut().find(|n| n.var == node.var) {
+                Some(existing) => {
+                    match (existing.label, node.label) {
+                        (Some(a), Some(b)) if a != b => {
+                            return Err(error_at(
+                                text,
+                                node.offset,
+                                format!("variable '{}' is labelled both {a} and {b}", node.var),
+                            ));
+                        }
+                        (None, Some(b)) => existing.label = Some(b),
+                        _ => {}
+                    }
+                    existing.properties.extend(node.properties.iter().cloned());
+                }
+                None => nodes.push(node.clone()),
+            }
+        }
+
+        Ok(Self { statement, nodes })
+    }
+
+    /// Names of the result columns.
+    pub fn columns(&self) -> Vec<String> {
+        self.statement
+            .returns
+            .iter()
+            .map(|item| item.name.clone())
+            .collect()
+    }
+
+    /// Run the query against a graph.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if a `RETURN` expression computes
+    /// a list of floats, which has no property type; vector properties are
+    /// returned unchanged.
+    pub fn execute(&self, graph: &CodeGraph) -> Result<QueryResult> {
+        let statement = &self.statement;
+        let mut pattern = Pattern::new();
+
+        for node in &self.nodes {
+            let matcher = match node.label {
+                Some(label) => NodeMatcher::of_type(label),
+                None => NodeMatcher::any(),
+            };
+            let expected = node.properties.clone();
+            pattern = pattern.node(
+                &node.var,
+                matcher.custom(move |n| properties_match(&n.properties, &expected)),
+            );
+        }
+
+        for path in &statement.paths {
+            let mut previous = &path.start.var;
+            for (rel, node) in &path.steps {
+                let (from, to) = match rel.direction {
+                    Direction::Incoming => (&node.var, previous),
+                    _ => (previous, &node.var),
+                };
+                let predicate: Option<EdgePredicate> = if rel.properties.is_empty() {
+                    None
+                } else {
+                    let expected = rel.properties.clone();
+                    Some(Box::new(move |edge| {
+                        properties_match(&edge.properties, &expected)
+                    }))
+                };
+                pattern =
+                    pattern.constrain(from, to, &rel.types, rel.min_hops, rel.max_hops, predicate);
+                previous = &node.var;
+            }
+        }
+
+        if let Some(condition) = &statement.where_clause {
+            let condition = Arc::new(condition.clone());
+            pattern = pattern.filter(move |graph, bindings| {
+                evaluate(&condition, graph, bindings) == Value::Bool(true)
+            });
+        }
+        if let (Some(limit), false) = (statement.limit, statement.distinct) {
+            pattern = pattern.limit(limit);
+        }
+
+        let mut seen = HashSet::new();
+        let mut rows = Vec::new();
+        for bindings in pattern.execute(graph)? {
+            let row: Vec<Value> = statement
+                .returns
+                .iter()
+                .map(|item| evaluate(&item.expr, graph, &bindings))
+                .collect();
+            if statement.distinct {
+                let key: Vec<String> = row.iter().map(Value::distinct_key).collect();
+                if !seen.insert(key) {
+                    continue;
+                }
+            }
+            let row = statement
+                .returns
+                .iter()
+                .zip(row)
+                .map(|(item, value)| match &item.expr {
+                    // Stored values are returned as-is, keeping vectors exact
+                    Expr::Property(var, key) => Ok(property(graph, &bindings, var, key)
+                        .cloned()
+                        .unwrap_or(PropertyValue::Null)),
+                    _ => PropertyValue::try_from(value),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(row);
+            if statement.limit.is_some_and(|limit| rows.len() >= limit) {
+                break;
+            }
+        }
+
+        Ok(QueryResult {
+            columns: self.columns(),
+            rows,
+        })
+    }
+}
+
+/// Check a node's or edge's properties against an inline `{key: value}` map.
+fn properties_match(properties: &PropertyMap, expected: &[(String, Value)]) -> bool {
+    expected.iter().all(|(key, value)| {
+        properties
+            .get(key)
+            .is_some_and(|actual| Value::from(actual).equals(value) == Some(true))
+    })
+}
+
+/// Tabular result of a textual query.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct QueryResult {
+    /// Column names (the `AS` alias or the expression text)
+    pub columns: Vec<String>,
+    /// One row per match; missing properties are [`PropertyValue::Null`]
+    pub rows: Vec<Vec<PropertyValue>>,
+}
+
+impl QueryResult {
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check if the query returned no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Position of a column by name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == name)
+    }
+
+    /// Value of a named column in a row.
+    pub fn get(&self, row: usize, column: &str) -> Option<&PropertyValue> {
+        self.rows.get(row)?.get(self.column_index(column)?)
+    }
+
+    /// Sort rows by a column (stable; `Null` values last in either direction).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if there is no such column.
+    pub fn sort_by(&mut self, column: &str, order: SortOrder) -> Result<()> {
+        let index = self
+            .column_index(column)
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: format!("Unknown column '{column}'"),
+            })?;
+        self.rows.sort_by(|a, b| match (&a[index], &b[index]) {
+            (PropertyValue::Null, PropertyValue::Null) => Ordering::Equal,
+            (PropertyValue::Null, _) => Ordering::Greater,
+            (_, PropertyValue::Null) => Ordering::Less,
+            (a, b) => match order {
+                SortOrder::Ascending => total_cmp(a, b),
+                SortOrder::Descending => total_cmp(b, a),
+            },
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,647 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Recursive-descent parser producing the query AST.
+
+use super::eval::Value;
+use super::lexer::{error_at, tokenize, Token, TokenKind};
+use crate::error::{GraphError, Result};
+use crate::graph::{Direction, EdgeType, NodeType};
+use regex::Regex;
+use std::collections::HashSet;
+
+/// Parsed `MATCH ... [WHERE ...] RETURN ... [LIMIT n]` statement.
+#[derive(Debug, Clone)]
+pub(crate) struct Statement {
+    pub paths: Vec<PathPattern>,
+    pub where_clause: Option<Expr>,
+    pub distinct: bool,
+    pub returns: Vec<ReturnItem>,
+    pub limit: Option<usize>,
+}
+
+/// `(a)-[...]->(b)<-[...]-(c)`
+#[derive(Debug, Clone)]
+pub(crate) struct PathPattern {
+    pub start: NodePattern,
+    pub steps: Vec<(RelPattern, NodePattern)>,
+}
+
+/// `(var:Label {key: value})`
+#[derive(Debug, Clone)]
+pub(crate) struct NodePattern {
+    /// Variable name; anonymous nodes get a generated name that cannot clash
+    pub var: String,
+    pub label: Option<NodeType>,
+    pub properties: Vec<(String, Value)>,
+    /// Byte offset of the opening parenthesis
+    pub offset: usize,
+}
+
+/// `-[:Type1|Type2*min..max {key: value}]->`
+#[derive(Debug, Clone)]
+pub(crate) struct RelPattern {
+    pub types: Vec<EdgeType>,
+    pub min_hops: usize,
+    pub max_hops: Option<usize>,
+    pub properties: Vec<(String, Value)>,
+    /// `Outgoing` for `->`, `Incoming` for `<-`
+    pub direction: Direction,
+}
+
+/// A projected column.
+#[derive(Debug, Clone)]
+pub(crate) struct ReturnItem {
+    pub expr: Expr,
+    pub name: String,
+}
+
+/// Comparison operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+    StartsWith,
+    EndsWith,
+    Regex,
+    In,
+}
+
+/// Expression AST for `WHERE` and `RETURN`.
+#[derive(Debug, Clone)]
+pub(crate) enum Expr {
+    Literal(Value),
+    List(Vec<Expr>),
+    /// A bound node (evaluates to its ID)
+    Variable(String),
+    /// `var.key`
+    Property(String, String),
+    /// `id(var)`
+    Id(String),
+    /// `type(var)`: the node type name
+    Type(String),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(CompareOp, Box<Expr>, Box<Expr>),
+    /// `expr =~ 'literal'`, compiled once at parse time
+    Matches(Box<Expr>, Regex),
+    IsNull(Box<Expr>, bool),
+}
+
+/// Compile a `=~` pattern; like Cypher, it must match the whole string.
+pub(crate) fn compile_regex(pattern: &str) -> std::result::Result<Regex, regex::Error> {
+    Regex::new(&format!("^(?:{pattern})$"))
+}
+
+/// Parse a query string into a statement.
+pub(crate) fn parse(source: &str) -> Result<Statement> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        source,
+        tokens,
+        pos: 0,
+        declared: HashSet::new(),
+        anonymous: 0,
+    };
+    parser.statement()
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    declared: HashSet<String>,
+    anonymous: usize,
+}
+
+impl Parser<'_> {
+    fn statement(&mut self) -> Result<Statement> {
+        self.expect_keyword("MATCH")?;
+        let mut paths = vec![self.path()?];
+        while self.eat(&TokenKind::Comma) {
+            paths.push(self.path()?);
+        }
+
+        let where_clause = if self.eat_keyword("WHERE") {
+            Some(self.expr()?)
+        } else {
+            None
+        };
+
+        self.expect_keyword("RETURN")?;
+        let distinct = self.eat_keyword("DISTINCT");
+        let mut returns = vec![self.return_item()?];
+        while self.eat(&TokenKind::Comma) {
+            returns.push(self.return_item()?);
+        }
+
+        let limit = if self.eat_keyword("LIMIT") {
+            match self.peek().kind {
+                TokenKind::Int(n) if n >= 0 => {
+                    self.pos += 1;
+                    Some(n as usize)
+                }
+                _ => return Err(self.unexpected("a non-negative integer after LIMIT")),
+            }
+        } else {
+            None
+        };
+
+        if self.peek().kind != TokenKind::Eof {
+            return Err(self.unexpected("end of query"));
+        }
+
+        Ok(Statement {
+            paths,
+            where_clause,
+            distinct,
+            returns,
+            limit,
+        })
+    }
+
+    fn path(&mut self) -> Result<PathPattern> {
+        let start = self.node()?;
+        let mut steps = Vec::new();
+        while matches!(self.peek().kind, TokenKind::Minus | TokenKind::LeftArrow) {
+            let rel = self.relationship()?;
+            steps.push((rel, self.node()?));
+        }
+        Ok(PathPattern { start, steps })
+    }
+
+    fn node(&mut self) -> Result<NodePattern> {
+        let offset = self.peek().start;
+        self.expect(&TokenKind::LParen, "'(' to start a node pattern")?;
+
+        let var = match self.peek().kind.clone() {
+            TokenKind::Ident(name) => {
+                self.pos += 1;
+                self.declared.insert(name.clone());
+                name
+            }
+            _ => {
+                self.anonymous += 1;
+                format!("anon {}", self.anonymous)
+            }
+        };
+
+        let label = if self.eat(&TokenKind::Colon) {
+            let (name, at) = self.ident("a node label")?;
+            Some(name.parse::<NodeType>().map_err(|_| {
+                let expected: Vec<String> = NodeType::ALL.iter().map(|t| t.to_string()).collect();
+                error_at(
+                    self.source,
+                    at,
+                    format!(
+                        "unknown node label '{name}'; expected one of {}",
+                        expected.join(", ")
+                    ),
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let properties = self.property_map()?;
+        self.expect(&TokenKind::RParen, "')' to close the node pattern")?;
+
+        Ok(NodePattern {
+            var,
+            label,
+            properties,
+            offset,
+        })
+    }
+
+    fn relationship(&mut self) -> Result<RelPattern> {
+        let incoming = self.eat(&TokenKind::LeftArrow);
+        if !incoming {
+            self.expect(&TokenKind::Minus, "'-' or '<-' to start a relationship")?;
+        }
+
+        let mut rel = RelPattern {
+            types: Vec::new(),
+            min_hops: 1,
+            max_hops: Some(1),
+            properties: Vec::new(),
+            direction: if incoming {
+                Direction::Incoming
+            } else {
+                Direction::Outgoing
+            },
+        };
+
+        // `-->` and `<--` have no bracketed detail
+        if self.eat(&TokenKind::LBracket) {
+            if let TokenKind::Ident(name) = &self.peek().kind {
+                let message = format!(
+                    "relationship variables are not supported; remove '{name}' and use -[:Type]->"
+                );
+                return Err(self.error_here(message));
+            }
+            if self.eat(&TokenKind::Colon) {
+                loop {
+                    let (name, at) = self.ident("a relationship type")?;
+                    rel.types.push(name.parse::<EdgeType>().map_err(|_| {
+                        let expected: Vec<String> =
+                            EdgeType::ALL.iter().map(|t| t.to_string()).collect();
+                        error_at(
+                            self.source,
+                            at,
+                            format!(
+                                "unknown relationship type '{name}'; expected one of {}",
+                                expected.join(", ")
+                            ),
+                        )
+                    })?);
+                    if !self.eat(&TokenKind::Pipe) {
+                        break;
+                    }
+                    // Cypher also allows `:A|:B`
+                    self.eat(&TokenKind::Colon);
+                }
+            }
+            if self.eat(&TokenKind::Star) {
+                self.hop_range(&mut rel)?;
+            }
+            rel.properties = self.property_map()?;
+            self.expect(&TokenKind::RBracket, "']' to close the relationship")?;
+        }
+
+        if incoming {
+            self.expect(&TokenKind::Minus, "'-' to end a '<-' relationship")?;
+        } else if !self.eat(&TokenKind::Arrow) {
+            return Err(self.unexpected("'->' (undirected relationships are not supported)"));
+        }
+
+        Ok(rel)
+    }
+
+    /// Parse the part after `*`: nothing, `n`, `n..`, `..m` or `n..m`.
+    fn hop_range(&mut self, rel: &mut RelPattern) -> Result<()> {
+        let at = self.peek().start;
+        let min = self.hop_count()?;
+        if self.eat(&TokenKind::DotDot) {
+            rel.min_hops = min.unwrap_or(1);
+            rel.max_hops = self.hop_count()?;
+        } else {
+            rel.min_hops = min.unwrap_or(1);
+            rel.max_hops = min;
+        }
+        if rel.max_hops.is_some_and(|max| max < rel.min_hops) {
+            return Err(error_at(
+                self.source,
+                at,
+                "the maximum path length is smaller than the minimum",
+            ));
+        }
+        Ok(())
+    }
+
+    fn hop_count(&mut self) -> Result<Option<usize>> {
+        match self.peek().kind {
+            TokenKind::Int(n) if n >= 0 => {
+                self.pos += 1;
+                Ok(Some(n as usize))
+            }
+            TokenKind::Int(_) => Err(self.error_here("path lengths must be non-negative")),
+            _ => Ok(None),
+        }
+    }
+
+    /// Optional `{key: literal, ...}`.
+    fn property_map(&mut self) -> Result<Vec<(String, Value)>> {
+        let mut properties = Vec::new();
+        if !self.eat(&TokenKind::LBrace) {
+            return Ok(properties);
+        }
+        if self.eat(&TokenKind::RBrace) {
+            return Ok(properties);
+        }
+        loop {
+            let (key, _) = self.ident("a property name")?;
+            self.expect(&TokenKind::Colon, "':' after the property name")?;
+            properties.push((key, self.literal()?));
+            if !self.eat(&TokenKind::Comma) {
+                break;
+            }
+        }
+        self.expect(&TokenKind::RBrace, "'}' to close the property map")?;
+        Ok(properties)
+    }
+
+    fn literal(&mut self) -> Result<Value> {
+        let negative = self.eat(&TokenKind::Minus);
+        let value = match self.peek().kind.clone() {
+            TokenKind::Int(n) => Value::Int(if negative { -n } else { n }),
+            TokenKind::Float(f) => Value::Float(if negative { -f } else { f }),
+            _ if negative => return Err(self.unexpected("a number after '-'")),
+            TokenKind::Str(s) => Value::String(s),
+            TokenKind::Ident(word) if word.eq_ignore_ascii_case("true") => Value::Bool(true),
+            TokenKind::Ident(word) if word.eq_ignore_ascii_case("false") => Value::Bool(false),
+            TokenKind::Ident(word) if word.eq_ignore_ascii_case("null") => Value::Null,
+            TokenKind::LBracket => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat(&TokenKind::RBracket) {
+                    loop {
+                        items.push(self.literal()?);
+                        if !self.eat(&TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(&TokenKind::RBracket, "']' to close the list")?;
+                }
+                return Ok(Value::List(items));
+            }
+            _ => return Err(self.unexpected("a literal value")),
+        };
+        self.pos += 1;
+        Ok(value)
+    }
+
+    fn return_item(&mut self) -> Result<ReturnItem> {
+        let start = self.peek().start;
+        let expr = self.expr()?;
+        let end = self.tokens[self.pos - 1].end;
+        let name = if self.eat_keyword("AS") {
+            self.ident("a column alias")?.0
+        } else {
+            self.source[start..end].to_string()
+        };
+        Ok(ReturnItem { expr, name })
+    }
+
+    // Expressions, lowest precedence first
+
+    fn expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.and_expr()?;
+        while self.eat_keyword("OR") {
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and_expr()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr> {
+        let mut lhs = self.not_expr()?;
+        while self.eat_keyword("AND") {
+            lhs = Expr::And(Box::new(lhs), Box::new(self.not_expr()?));
+        }
+        Ok(lhs)
+    }
+
+    fn not_expr(&mut self) -> Result<Expr> {
+        if self.eat_keyword("NOT") {
+            return Ok(Expr::Not(Box::new(self.not_expr()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Expr> {
+        let lhs = self.unary()?;
+
+        if self.eat_keyword("IS") {
+            let negated = self.eat_keyword("NOT");
+            self.expect_keyword("NULL")?;
+            return Ok(Expr::IsNull(Box::new(lhs), negated));
+        }
+
+        let op = match &self.peek().kind {
+            TokenKind::Eq => CompareOp::Eq,
+            TokenKind::Ne => CompareOp::Ne,
+            TokenKind::Lt => CompareOp::Lt,
+            TokenKind::Le => CompareOp::Le,
+            TokenKind::Gt => CompareOp::Gt,
+            TokenKind::Ge => CompareOp::Ge,
+            TokenKind::RegexMatch => CompareOp::Regex,
+            // `a<-1` lexes as a left arrow
+            TokenKind::LeftArrow => {
+                self.pos += 1;
+                let rhs = self.unary()?;
+                return Ok(Expr::Compare(
+                    CompareOp::Lt,
+                    Box::new(lhs),
+                    Box::new(Expr::Neg(Box::new(rhs))),
+                ));
+            }
+            TokenKind::Ident(word) => match word.to_ascii_uppercase().as_str() {
+                "CONTAINS" => CompareOp::Contains,
+                "IN" => CompareOp::In,
+                "STARTS" | "ENDS" => {
+                    let op = if word.eq_ignore_ascii_case("STARTS") {
+                        CompareOp::StartsWith
+                    } else {
+                        CompareOp::EndsWith
+                    };
+                    self.pos += 1;
+                    self.expect_keyword("WITH")?;
+                    let rhs = self.unary()?;
+                    return Ok(Expr::Compare(op, Box::new(lhs), Box::new(rhs)));
+                }
+                _ => return Ok(lhs),
+            },
+            _ => return Ok(lhs),
+        };
+        self.pos += 1;
+        let rhs_at = self.peek().start;
+        let rhs = self.unary()?;
+        if let (CompareOp::Regex, Expr::Literal(Value::String(pattern))) = (op, &rhs) {
+            let regex = compile_regex(pattern)
+                .map_err(|e| error_at(self.source, rhs_at, format!("invalid regex: {e}")))?;
+            return Ok(Expr::Matches(Box::new(lhs), regex));
+        }
+        Ok(Expr::Compare(op, Box::new(lhs), Box::new(rhs)))
+    }
+
+    fn unary(&mut self) -> Result<Expr> {
+        if self.eat(&TokenKind::Minus) {
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr> {
+        let token = self.peek().clone();
+        match token.kind {
+            TokenKind::Int(_) | TokenKind::Float(_) | TokenKind::Str(_) => {
+                Ok(Expr::Literal(self.literal()?))
+            }
+            TokenKind::LParen => {
+                self.pos += 1;
+                let inner = self.expr()?;
+                self.expect(&TokenKind::RParen, "')' to close the expression")?;
+                Ok(inner)
+            }
+            TokenKind::LBracket => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                if !self.eat(&TokenKind::RBracket) {
+                    loop {
+                        items.push(self.expr()?);
+                        if !self.eat(&TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                    self.expect(&TokenKind::RBracket, "']' to close the list")?;
+                }
+                Ok(Expr::List(items))
+            }
+            TokenKind::Ident(word) => {
+                self.pos += 1;
+                match word.to_ascii_lowercase().as_str() {
+                    "true" => return Ok(Expr::Literal(Value::Bool(true))),
+                    "false" => return Ok(Expr::Literal(Value::Bool(false))),
+                    "null" => return Ok(Expr::Literal(Value::Null)),
+                    _ => {}
+                }
+
+                if self.eat(&TokenKind::LParen) {
+                    let (var, at) = self.ident("a variable")?;
+                    self.check_declared(&var, at)?;
+                    self.expect(&TokenKind::RParen, "')' after the function argument")?;
+                    return match word.to_ascii_lowercase().as_str() {
+                        "id" => Ok(Expr::Id(var)),
+                        "type" | "label" => Ok(Expr::Type(var)),
+                        _ => Err(error_at(
+                            self.source,
+                            token.start,
+                            format!("unknown function '{word}'; expected id() or type()"),
+                        )),
+                    };
+                }
+
+                self.check_declared(&word, token.start)?;
+                if self.eat(&TokenKind::Dot) {
+                    let (key, _) = self.ident("a property name")?;
+                    Ok(Expr::Property(word, key))
+                } else {
+                    Ok(Expr::Variable(word))
+                }
+            }
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+
+    // Token helpers
+
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if &self.peek().kind == kind {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, kind: &TokenKind, expected: &str) -> Result<()> {
+        if self.eat(kind) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        match &self.peek().kind {
+            TokenKind::Ident(word) if word.eq_ignore_ascii_case(keyword) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.eat_keyword(keyword) {
+            Ok(())
+        } else {
+            Err(self.unexpected(keyword))
+        }
+    }
+
+    fn ident(&mut self, expected: &str) -> Result<(String, usize)> {
+        match self.peek().kind.clone() {
+            TokenKind::Ident(name) => {
+                let at = self.peek().start;
+                self.pos += 1;
+                Ok((name, at))
+            }
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    fn check_declared(&self, var: &str, at: usize) -> Result<()> {
+        if self.declared.contains(var) {
+            Ok(())
+        } else {
+            Err(error_at(
+                self.source,
+                at,
+                format!("variable '{var}' is not defined in MATCH"),
+            ))
+        }
+    }
+
+    fn error_here(&self, message: impl Into<String>) -> GraphError {
+        error_at(self.source, self.peek().start, message)
+    }
+
+    fn unexpected(&self, expected: &str) -> GraphError {
+        self.error_here(format!(
+            "expected {expected}, found {}",
+            self.peek().kind.describe()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_variable_length_path() {
+        let statement =
+            parse("MATCH (f:Function)-[:Calls*1..3]->(g {name: 'exec'}) RETURN f.name AS caller")
+                .unwrap();
+        let path = &statement.paths[0];
+        assert_eq!(path.start.label, Some(NodeType::Function));
+        let (rel, end) = &path.steps[0];
+        assert_eq!(rel.types, vec![EdgeType::Calls]);
+        assert_eq!((rel.min_hops, rel.max_hops), (1, Some(3)));
+        assert_eq!(end.properties[0].0, "name");
+        assert_eq!(statement.returns[0].name, "caller");
+    }
+
+    #[test]
+    fn test_parse_hop_ranges() {
+        let hops = |text: &str| {
+            let statement = parse(&format!("MATCH (a)-[{text}]->(b) RETURN a")).unwrap();
+            let rel = &statement.paths[0].steps[0].0;
+            (rel.min_hops, rel.max_hops)
+        };
+        assert_eq!(hops(""), (1, Some(1)));
+        assert_eq!(hops("*"), (1, None));
+        assert_eq!(hops("*2"), (2, Some(2)));
+        assert_eq!(hops("*..4"), (1, Some(4)));
+        assert_eq!(hops("*0.."), (0, None));
+    }
+
+    #[test]
+    fn test_default_column_name_is_source_text() {
+        let statement = parse("MATCH (f) RETURN f.name,  id( f )").unwrap();
+        assert_eq!(statement.returns[0].name, "f.name");
+        assert_eq!(statement.returns[1].name, "id( f )");
+    }
+}
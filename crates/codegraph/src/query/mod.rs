@@ -0,0 +1,780 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Query builder for fluent graph queries.
+//!
+//! Provides a fluent interface for constructing and executing complex graph queries
+//! with multiple filters and optimizations.
+
+use crate::error::{GraphError, Result};
+use crate::graph::{CodeGraph, Direction, Edge, EdgeType, Node, NodeId, NodeType, PropertyValue};
+use std::collections::HashSet;
+
+pub mod aggregate;
+pub mod edge;
+pub mod language;
+pub mod order;
+pub mod pattern;
+pub mod predicate;
+
+pub use aggregate::{Aggregate, GroupBy};
+pub use edge::EdgeQueryBuilder;
+pub use language::{GraphQuery, QueryResult};
+pub use order::{Cursor, Page, SortOrder};
+pub use pattern::{NodeMatcher, Pattern, PatternMatch};
+pub use predicate::{HasProperties, Predicate};
+
+/// One stage of a node query pipeline.
+enum Step {
+    /// Keep nodes matching a predicate
+    Filter(Predicate<Node>),
+    /// Replace every node with its neighbors along matching edges
+    Traverse(Direction, Predicate<Edge>),
+    /// Drop repeated nodes, keeping the first occurrence
+    Dedup,
+}
+
+/// Fluent query builder for graph operations.
+///
+/// Allows chaining multiple filters to find specific nodes in the graph.
+/// Traversal steps ([`out`](Self::out), [`in_`](Self::in_), [`both`](Self::both))
+/// replace the current nodes with their neighbors, and filters added after a step
+/// apply to the nodes it reached.
+///
+/// # Examples
+///
+/// ```
+/// use codegraph::query::SortOrder;
+/// use codegraph::{CodeGraph, EdgeType, NodeType};
+///
+/// # fn example() -> codegraph::Result<()> {
+/// let mut graph = CodeGraph::in_memory()?;
+/// // ... populate graph ...
+///
+/// // Find all public functions in a specific file
+/// let results = graph.query()
+///     .node_type(NodeType::Function)
+///     .in_file("src/main.rs")
+///     .property("visibility", "public")
+///     .execute()?;
+///
+/// // Functions called by tests, each listed once
+/// let called = graph.query()
+///     .node_type(NodeType::Function)
+///     .name_matches("^test_")
+///     .out(EdgeType::Calls)
+///     .dedup()
+///     .execute()?;
+///
+/// // The ten most complex functions with their names
+/// let top = graph.query()
+///     .node_type(NodeType::Function)
+///     .order_by("complexity", SortOrder::Descending)
+///     .limit(10)
+///     .project(&["name", "complexity"])?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct QueryBuilder<'a> {
+    graph: &'a CodeGraph,
+    steps: Vec<Step>,
+    order: Vec<(String, SortOrder)>,
+    group_by: Option<GroupBy>,
+    limit_value: Option<usize>,
+    in_file_filter: Option<String>,
+    // Explicit starting nodes instead of the whole graph
+    start_nodes: Option<Vec<NodeId>>,
+    // First invalid argument (e.g. a bad regex), reported when the query runs
+    error: Option<String>,
+}
+
+impl<'a> QueryBuilder<'a> {
+    /// Create a new query builder for the given graph.
+    pub fn new(graph: &'a CodeGraph) -> Self {
+        Self {
+            graph,
+            steps: Vec::new(),
+            order: Vec::new(),
+            group_by: None,
+            limit_value: None,
+            in_file_filter: None,
+            start_nodes: None,
+            error: None,
+        }
+    }
+
+    /// Start from the given nodes instead of every node in the graph.
+    pub(crate) fn with_start_nodes(mut self, nodes: Vec<NodeId>) -> Self {
+        self.start_nodes = Some(nodes);
+        self
+    }
+
+    /// Filter nodes by type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::{CodeGraph, NodeType};
+    /// # fn example() -> codegraph::Result<()> {
+    /// # let graph = CodeGraph::in_memory()?;
+    /// let functions = graph.query()
+    ///     .node_type(NodeType::Function)
+    ///     .execute()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn node_type(self, node_type: NodeType) -> Self {
+        self.filter(Predicate::node_type(node_type))
+    }
+
+    /// Filter nodes that are contained in a specific file.
+    ///
+    /// Looks up the file by path and finds all nodes connected via Contains edges.
+    /// Applies to the starting nodes, before any traversal step.
+    pub fn in_file(mut self, file_path: &str) -> Self {
+        self.in_file_filter = Some(file_path.to_string());
+        self
+    }
+
+    /// Filter files by glob pattern.
+    ///
+    /// Supports wildcards: `*` matches any characters, `**` matches directories.
+    ///
+    /// # Examples
+    ///
+    /// - `src/*.rs` - All Rust files in src/
+    /// - `**/*.py` - All Python files recursively
+    /// - `tests/**/*.rs` - All Rust files under tests/
+    pub fn file_pattern(self, pattern: &str) -> Self {
+        let pattern = pattern.to_string();
+        self.custom(move |node| {
+            if let Some(path) = node.properties.get_string("path") {
+                glob_match(&pattern, path)
+            } else {
+                false
+            }
+        })
+    }
+
+    /// Filter nodes by exact property match.
+    ///
+    /// Supports string, int, float, and bool property values.
+    pub fn property<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property(key, value))
+    }
+
+    /// Filter nodes that have a specific property (regardless of value).
+    pub fn property_exists(self, key: &str) -> Self {
+        self.filter(Predicate::property_exists(key))
+    }
+
+    /// Filter nodes whose property is greater than `value`.
+    ///
+    /// See [`Predicate::property_gt`] for how values are compared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::{CodeGraph, NodeType};
+    /// # fn example() -> codegraph::Result<()> {
+    /// # let graph = CodeGraph::in_memory()?;
+    /// let complex = graph.query()
+    ///     .node_type(NodeType::Function)
+    ///     .property_gt("complexity", 10)
+    ///     .execute()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn property_gt<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_gt(key, value))
+    }
+
+    /// Filter nodes whose property is greater than or equal to `value`.
+    pub fn property_ge<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_ge(key, value))
+    }
+
+    /// Filter nodes whose property is less than `value`.
+    pub fn property_lt<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_lt(key, value))
+    }
+
+    /// Filter nodes whose property is less than or equal to `value`.
+    pub fn property_le<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::property_le(key, value))
+    }
+
+    /// Filter nodes whose property lies in the inclusive range `low..=high`.
+    pub fn between<V: Into<PropertyValue>>(self, key: &str, low: V, high: V) -> Self {
+        self.filter(Predicate::between(key, low, high))
+    }
+
+    /// Filter nodes whose list property (e.g. `attributes`) contains `value`.
+    pub fn list_contains<V: Into<PropertyValue>>(self, key: &str, value: V) -> Self {
+        self.filter(Predicate::list_contains(key, value))
+    }
+
+    /// Filter nodes by name containing a substring (case-insensitive).
+    pub fn name_contains(self, substring: &str) -> Self {
+        self.filter(Predicate::name_contains(substring))
+    }
+
+    /// Filter nodes by name matching a regular expression.
+    ///
+    /// The match is unanchored; use `^` and `$` to match the whole name. An invalid
+    /// pattern makes the query fail with [`GraphError::InvalidOperation`] when run.
+    pub fn name_matches(self, pattern: &str) -> Self {
+        self.property_matches("name", pattern)
+    }
+
+    /// Filter nodes whose string property matches a regular expression.
+    ///
+    /// An invalid pattern makes the query fail with [`GraphError::InvalidOperation`]
+    /// when run.
+    pub fn property_matches(self, key: &str, pattern: &str) -> Self {
+        match Predicate::property_matches(key, pattern) {
+            Ok(predicate) => self.filter(predicate),
+            Err(e) => self.fail(e),
+        }
+    }
+
+    /// Filter nodes by a composed [`Predicate`].
+    pub fn filter(mut self, predicate: Predicate<Node>) -> Self {
+        self.steps.push(Step::Filter(predicate));
+        self
+    }
+
+    /// Keep nodes matching either predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::query::Predicate;
+    /// # use codegraph::{CodeGraph, NodeType};
+    /// # fn example() -> codegraph::Result<()> {
+    /// # let graph = CodeGraph::in_memory()?;
+    /// let types = graph.query()
+    ///     .or(
+    ///         Predicate::node_type(NodeType::Class),
+    ///         Predicate::node_type(NodeType::Interface),
+    ///     )
+    ///     .execute()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn or(self, first: Predicate<Node>, second: Predicate<Node>) -> Self {
+        self.filter(first.or(second))
+    }
+
+    /// Exclude nodes matching a predicate.
+    pub fn not(self, predicate: Predicate<Node>) -> Self {
+        self.filter(!predicate)
+    }
+
+    /// Filter nodes using a custom predicate function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::{CodeGraph, NodeType};
+    /// # fn example() -> codegraph::Result<()> {
+    /// # let graph = CodeGraph::in_memory()?;
+    /// // Find functions longer than 50 lines
+    /// let results = graph.query()
+    ///     .node_type(NodeType::Function)
+    ///     .custom(|node| {
+    ///         if let (Some(start), Some(end)) = (
+    ///             node.properties.get_int("line_start"),
+    ///             node.properties.get_int("line_end")
+    ///         ) {
+    ///             (end - start) > 50
+    ///         } else {
+    ///             false
+    ///         }
+    ///     })
+    ///     .execute()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn custom<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&Node) -> bool + 'static,
+    {
+        self.filter(Predicate::new(predicate))
+    }
+
+    /// Move to the targets of outgoing edges of the given type.
+    ///
+    /// A node reached along several edges appears once per edge; use
+    /// [`dedup`](Self::dedup) to collapse repeats.
+    pub fn out(self, edge_type: EdgeType) -> Self {
+        self.traverse(Direction::Outgoing, Predicate::edge_type(edge_type))
+    }
+
+    /// Move to the sources of incoming edges of the given type.
+    pub fn in_(self, edge_type: EdgeType) -> Self {
+        self.traverse(Direction::Incoming, Predicate::edge_type(edge_type))
+    }
+
+    /// Move to neighbors along edges of the given type in either direction.
+    pub fn both(self, edge_type: EdgeType) -> Self {
+        self.traverse(Direction::Both, Predicate::edge_type(edge_type))
+    }
+
+    /// Move to neighbors along edges matching an edge predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::query::Predicate;
+    /// # use codegraph::{CodeGraph, Direction, EdgeType};
+    /// # fn example() -> codegraph::Result<()> {
+    /// # let graph = CodeGraph::in_memory()?;
+    /// // Files reached through wildcard imports of main.rs
+    /// let wildcard = graph.query()
+    ///     .property("path", "src/main.rs")
+    ///     .traverse(
+    ///         Direction::Outgoing,
+    ///         Predicate::edge_type(EdgeType::Imports).and(Predicate::property("is_wildcard", true)),
+    ///     )
+    ///     .execute()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn traverse(mut self, direction: Direction, edges: Predicate<Edge>) -> Self {
+        self.steps.push(Step::Traverse(direction, edges));
+        self
+    }
+
+    /// Drop repeated nodes, keeping the first occurrence.
+    pub fn dedup(mut self) -> Self {
+        self.steps.push(Step::Dedup);
+        self
+    }
+
+    /// Sort results by a property.
+    ///
+    /// Further calls add secondary keys. Nodes missing the property sort last in
+    /// either direction, and ties are broken by ascending node ID, so ordered
+    /// results are deterministic.
+    pub fn order_by(mut self, key: &str, order: SortOrder) -> Self {
+        self.order.push((key.to_string(), order));
+        self
+    }
+
+    /// Limit the number of results returned.
+    ///
+    /// Without [`order_by`](Self::order_by) the nodes kept are arbitrary; with it,
+    /// the first `n` in order are selected without sorting the whole result.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit_value = Some(n);
+        self
+    }
+
+    /// Execute the query and return matching node IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if a filter argument was invalid.
+    pub fn execute(&self) -> Result<Vec<NodeId>> {
+        let limit = self.limit_value.unwrap_or(usize::MAX);
+        if self.order.is_empty() {
+            return self.run(limit);
+        }
+        let nodes = self.run(usize::MAX)?;
+        Ok(self.sorted(nodes, None, limit))
+    }
+
+    /// Execute the query and return selected property values, one row per node.
+    ///
+    /// Rows follow [`execute`](Self::execute); missing properties are
+    /// [`PropertyValue::Null`].
+    pub fn project(&self, keys: &[&str]) -> Result<QueryResult> {
+        Ok(order::project(self.graph, &self.execute()?, keys))
+    }
+
+    /// Split results into groups for [`aggregate`](Self::aggregate).
+    pub fn group_by(mut self, group_by: GroupBy) -> Self {
+        self.group_by = Some(group_by);
+        self
+    }
+
+    /// Execute the query and reduce the results with aggregate operators.
+    ///
+    /// Returns one row per group, sorted by group value with nodes lacking one
+    /// (`Null`) last, or a single row if [`group_by`](Self::group_by) was not set.
+    /// Aggregates run over the nodes [`execute`](Self::execute) returns, so a node
+    /// reached twice by a traversal counts twice unless [`dedup`](Self::dedup) is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::query::{Aggregate, GroupBy};
+    /// # use codegraph::{CodeGraph, NodeType};
+    /// # fn example() -> codegraph::Result<()> {
+    /// # let graph = CodeGraph::in_memory()?;
+    /// // Public API count per package
+    /// let public_api = graph.query()
+    ///     .node_type(NodeType::Function)
+    ///     .property("visibility", "public")
+    ///     .group_by(GroupBy::Directory)
+    ///     .aggregate(&[Aggregate::count()])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn aggregate(&self, aggregates: &[Aggregate]) -> Result<QueryResult> {
+        aggregate::aggregate(
+            self.graph,
+            &self.execute()?,
+            self.group_by.as_ref(),
+            aggregates,
+        )
+    }
+
+    /// Fetch one page of results in query order.
+    ///
+    /// Pass the previous page's [`Page::next`] cursor to continue; pages resume
+    /// strictly after the cursor's position, so results stay stable across calls
+    /// even if the graph changes in between. Without [`order_by`](Self::order_by)
+    /// pages are ordered by node ID. The [`limit`](Self::limit) setting is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if a filter argument was invalid or
+    /// the cursor was created with different `order_by` keys.
+    pub fn page(&self, size: usize, after: Option<&Cursor>) -> Result<Page> {
+        if let Some(cursor) = after {
+            cursor.check_order(&self.order)?;
+        }
+        let mut nodes = self.run(usize::MAX)?;
+        if size == 0 {
+            nodes.clear();
+        } else {
+            nodes = self.sorted(nodes, after, size + 1);
+        }
+
+        let next = if nodes.len() > size {
+            nodes.truncate(size);
+            nodes
+                .last()
+                .map(|&last| Cursor::at(self.graph, last, &self.order))
+        } else {
+            None
+        };
+        Ok(Page { nodes, next })
+    }
+
+    /// Count the number of matching nodes without allocating a result vector.
+    pub fn count(&self) -> Result<usize> {
+        if self.has_pipeline() {
+            return Ok(self.run(usize::MAX)?.len());
+        }
+        self.check()?;
+        let mut count = 0;
+        self.scan(|_| {
+            count += 1;
+            true
+        })?;
+        Ok(count)
+    }
+
+    /// Check if any nodes match the query (short-circuits on first match).
+    pub fn exists(&self) -> Result<bool> {
+        Ok(!self.run(1)?.is_empty())
+    }
+
+    /// Number of leading filter steps, which are applied while scanning.
+    fn leading_filters(&self) -> usize {
+        self.steps
+            .iter()
+            .take_while(|step| matches!(step, Step::Filter(_)))
+            .count()
+    }
+
+    /// Whether any traversal or dedup step follows the leading filters.
+    fn has_pipeline(&self) -> bool {
+        self.leading_filters() < self.steps.len()
+    }
+
+    fn run(&self, limit: usize) -> Result<Vec<NodeId>> {
+        self.check()?;
+        let mut results = Vec::new();
+        if limit == 0 {
+            return Ok(results);
+        }
+
+        if !self.has_pipeline() {
+            self.scan(|node_id| {
+                results.push(node_id);
+                results.len() < limit
+            })?;
+            return Ok(results);
+        }
+
+        self.scan(|node_id| {
+            results.push(node_id);
+            true
+        })?;
+        for step in &self.steps[self.leading_filters()..] {
+            results = self.apply(step, results);
+        }
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    /// Sort nodes by the `order_by` keys, keeping the first `limit` after `after`.
+    fn sorted(&self, nodes: Vec<NodeId>, after: Option<&Cursor>, limit: usize) -> Vec<NodeId> {
+        let graph = self.graph;
+        let order = &self.order;
+        let cursor = after.map(|cursor| (cursor.key(), cursor.node_id));
+        let mut keyed: Vec<_> = nodes
+            .into_iter()
+            .map(|id| (order::sort_key(graph, id, order), id))
+            .filter(|(key, id)| {
+                cursor.as_ref().map_or(true, |(after, after_id)| {
+                    order::compare_positions(order, (key, *id), (after, *after_id)).is_gt()
+                })
+            })
+            .collect();
+
+        let compare = |a: &(order::SortKey, NodeId), b: &(order::SortKey, NodeId)| {
+            order::compare_positions(order, (&a.0, a.1), (&b.0, b.1))
+        };
+        if limit < keyed.len() {
+            // Partition the first `limit` positions, then sort only those
+            keyed.select_nth_unstable_by(limit, compare);
+            keyed.truncate(limit);
+        }
+        keyed.sort_by(compare);
+        keyed.into_iter().map(|(_, id)| id).collect()
+    }
+
+    /// Visit starting nodes that pass the leading filters until `visit` returns false.
+    fn scan(&self, mut visit: impl FnMut(NodeId) -> bool) -> Result<()> {
+        let filters = &self.steps[..self.leading_filters()];
+        let matches = |node: &Node| {
+            filters.iter().all(|step| match step {
+                Step::Filter(predicate) => predicate.test(node),
+                _ => true,
+            })
+        };
+
+        // If a start set or in_file filter is given, only search those nodes
+        let candidates = match (&self.start_nodes, &self.in_file_filter) {
+            (Some(start), Some(file_path)) => {
+                let in_file: HashSet<NodeId> =
+                    self.get_nodes_in_file(file_path)?.into_iter().collect();
+                Some(
+                    start
+                        .iter()
+                        .copied()
+                        .filter(|id| in_file.contains(id))
+                        .collect(),
+                )
+            }
+            (Some(start), None) => Some(start.clone()),
+            (None, Some(file_path)) => Some(self.get_nodes_in_file(file_path)?),
+            (None, None) => None,
+        };
+
+        if let Some(candidates) = candidates {
+            for node_id in candidates {
+                if let Ok(node) = self.graph.get_node(node_id) {
+                    if matches(node) && !visit(node_id) {
+                        break;
+                    }
+                }
+            }
+        } else {
+            for (&node_id, node) in self.graph.nodes_iter() {
+                if matches(node) && !visit(node_id) {
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a pipeline step to the current nodes.
+    fn apply(&self, step: &Step, nodes: Vec<NodeId>) -> Vec<NodeId> {
+        match step {
+            Step::Filter(predicate) => nodes
+                .into_iter()
+                .filter(|&id| {
+                    self.graph
+                        .get_node(id)
+                        .is_ok_and(|node| predicate.test(node))
+                })
+                .collect(),
+            Step::Traverse(direction, edges) => nodes
+                .into_iter()
+                .flat_map(|id| self.neighbors(id, *direction, edges))
+                .collect(),
+            Step::Dedup => {
+                let mut seen = HashSet::new();
+                nodes.into_iter().filter(|&id| seen.insert(id)).collect()
+            }
+        }
+    }
+
+    fn neighbors(
+        &self,
+        node_id: NodeId,
+        direction: Direction,
+        edges: &Predicate<Edge>,
+    ) -> Vec<NodeId> {
+        let mut found = Vec::new();
+        if matches!(direction, Direction::Outgoing | Direction::Both) {
+            found.extend(
+                self.graph
+                    .outgoing_edges(node_id)
+                    .filter(|edge| edges.test(edge))
+                    .map(|edge| edge.target_id),
+            );
+        }
+        if matches!(direction, Direction::Incoming | Direction::Both) {
+            found.extend(
+                self.graph
+                    .incoming_edges(node_id)
+                    .filter(|edge| edges.test(edge))
+                    .map(|edge| edge.source_id),
+            );
+        }
+        found
+    }
+
+    /// Get all nodes contained in a specific file.
+    fn get_nodes_in_file(&self, file_path: &str) -> Result<Vec<NodeId>> {
+        // First find the file node
+        for (&node_id, node) in self.graph.nodes_iter() {
+            if node.node_type == NodeType::CodeFile {
+                if let Some(path) = node.properties.get_string("path") {
+                    if path == file_path {
+                        // Found the file, now get all nodes it contains
+                        return self.graph.get_neighbors(node_id, Direction::Outgoing);
+                    }
+                }
+            }
+        }
+
+        // File not found
+        Ok(Vec::new())
+    }
+
+    /// Record the first invalid argument.
+    pub(crate) fn fail(mut self, error: GraphError) -> Self {
+        record_error(&mut self.error, error);
+        self
+    }
+
+    /// Report an invalid argument recorded while building.
+    fn check(&self) -> Result<()> {
+        check_error(&self.error)
+    }
+}
+
+/// Keep the first builder error as a message.
+fn record_error(slot: &mut Option<String>, error: GraphError) {
+    if slot.is_none() {
+        *slot = Some(match error {
+            GraphError::InvalidOperation { message } => message,
+            other => other.to_string(),
+        });
+    }
+}
+
+/// Turn a recorded builder error back into [`GraphError::InvalidOperation`].
+fn check_error(slot: &Option<String>) -> Result<()> {
+    match slot {
+        Some(message) => Err(GraphError::InvalidOperation {
+            message: message.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Compare an expected property value against an actual one.
+///
+/// Values of different types never compare equal; list and null values never match.
+fn property_equals(expected: &PropertyValue, actual: &PropertyValue) -> bool {
+    match (expected, actual) {
+        (PropertyValue::String(v1), PropertyValue::String(v2)) => v1 == v2,
+        (PropertyValue::Int(v1), PropertyValue::Int(v2)) => v1 == v2,
+        (PropertyValue::Float(v1), PropertyValue::Float(v2)) => (v1 - v2).abs() < f64::EPSILON,
+        (PropertyValue::Bool(v1), PropertyValue::Bool(v2)) => v1 == v2,
+        _ => false,
+    }
+}
+
+/// Simple glob pattern matching.
+///
+/// Supports * (any characters) and ** (directories).
+fn glob_match(pattern: &str, path: &str) -> bool {
+    // Handle ** for directory matching
+    if pattern.contains("**") {
+        let parts: Vec<&str> = pattern.split("**").collect();
+        if parts.len() == 2 {
+            let prefix = parts[0];
+            let suffix = parts[1].trim_start_matches('/');
+
+            // Check prefix
+            if !prefix.is_empty() && !path.starts_with(prefix) {
+                return false;
+            }
+
+            // If suffix contains *, we need to handle it recursively
+            if suffix.contains('*') {
+                // Get the part after the last /
+                if let Some(last_slash) = path.rfind('/') {
+                    let filename = &path[last_slash + 1..];
+                    return glob_match(suffix, filename);
+                } else {
+                    return glob_match(suffix, path);
+                }
+            }
+
+            // Simple suffix match
+            if !suffix.is_empty() && !path.ends_with(suffix) {
+                return false;
+            }
+            return true;
+        }
+    }
+
+    // Handle * for simple wildcard matching
+    let pattern_parts: Vec<&str> = pattern.split('*').collect();
+    if pattern_parts.len() == 1 {
+        // No wildcards - exact match
+        return pattern == path;
+    }
+
+    // Check if path matches the pattern
+    let mut pos = 0;
+    for (i, part) in pattern_parts.iter().enumerate() {
+        if part.is_empty() {
+            // Empty part from consecutive * or at start/end
+            continue;
+        }
+
+        if i == 0 {
+            // First part must match start
+            if !path[pos..].starts_with(part) {
+                return false;
+            }
+            pos += part.len();
+        } else if i == pattern_parts.len() - 1 {
+            // Last part must match end
+            return path[pos..].ends_with(part);
+        } else {
+            // Middle parts must exist in order
+            if let Some(index) = path[pos..].find(part) {
+                pos += index + part.len();
+            } else {
+                return false;
+            }
+        }
+    }
+
+    true
+}
@@ -0,0 +1,265 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ordering, keyset pagination and projections for node queries.
+//!
+//! Ordered queries sort by the [`order_by`](super::QueryBuilder::order_by) keys and
+//! break ties by node ID, so every result position is fully determined. A [`Cursor`]
+//! records the sort key of the last node on a page; the next page resumes strictly
+//! after that key, which keeps pages stable while the graph changes between calls
+//! (no node is repeated, and only nodes whose keys changed can move across pages).
+//!
+//! ```
+//! use codegraph::query::SortOrder;
+//! use codegraph::{CodeGraph, NodeType};
+//!
+//! # fn example() -> codegraph::Result<()> {
+//! let graph = CodeGraph::in_memory()?;
+//!
+//! // "Top 50 most complex functions", one page at a time
+//! let query = graph.query()
+//!     .node_type(NodeType::Function)
+//!     .order_by("complexity", SortOrder::Descending);
+//!
+//! let first = query.page(50, None)?;
+//! let rows = first.project(&graph, &["name", "complexity"]);
+//!
+//! // Later request: the client sends the cursor back
+//! if let Some(cursor) = &first.next {
+//!     let second = query.page(50, Some(cursor))?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::QueryResult;
+use crate::error::{GraphError, Result};
+use crate::graph::{CodeGraph, NodeId, PropertyValue};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Direction of an [`order_by`](super::QueryBuilder::order_by) key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SortOrder {
+    /// Smallest values first
+    Ascending,
+    /// Largest values first
+    Descending,
+}
+
+/// Position in an ordered query, used to fetch the following page.
+///
+/// Cursors are serializable so they can be handed to a client and sent back with
+/// the next request. A cursor only fits the ordering it was created with; using it
+/// with different `order_by` keys fails with [`GraphError::InvalidOperation`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    /// Sort keys of the query that produced the cursor
+    pub order: Vec<(String, SortOrder)>,
+    /// Key values of the last node on the page ([`PropertyValue::Null`] if missing)
+    pub values: Vec<PropertyValue>,
+    /// ID of the last node on the page (final tie-breaker)
+    pub node_id: NodeId,
+}
+
+/// One page of an ordered query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    /// Nodes on this page, in query order
+    pub nodes: Vec<NodeId>,
+    /// Cursor for the next page, `None` on the last page
+    pub next: Option<Cursor>,
+}
+
+impl Page {
+    /// Property values of the nodes on this page.
+    ///
+    /// See [`QueryBuilder::project`](super::QueryBuilder::project).
+    pub fn project(&self, graph: &CodeGraph, keys: &[&str]) -> QueryResult {
+        project(graph, &self.nodes, keys)
+    }
+}
+
+/// Sort key of a node: one optional value per `order_by` key.
+pub(crate) type SortKey<'a> = Vec<Option<&'a PropertyValue>>;
+
+/// Look up the sort key of a node.
+pub(crate) fn sort_key<'a>(
+    graph: &'a CodeGraph,
+    node_id: NodeId,
+    order: &[(String, SortOrder)],
+) -> SortKey<'a> {
+    let node = graph.get_node(node_id).ok();
+    order
+        .iter()
+        .map(|(key, _)| {
+            node.and_then(|node| node.properties.get(key))
+                .filter(|value| !matches!(value, PropertyValue::Null))
+        })
+        .collect()
+}
+
+/// Compare two `(key, id)` positions.
+///
+/// Missing values sort last in either direction; equal keys fall back to ascending
+/// node ID.
+pub(crate) fn compare_positions(
+    order: &[(String, SortOrder)],
+    (a, a_id): (&SortKey, NodeId),
+    (b, b_id): (&SortKey, NodeId),
+) -> Ordering {
+    for ((_, direction), (a, b)) in order.iter().zip(a.iter().zip(b)) {
+        let ordering = match (a, b) {
+            (Some(a), Some(b)) => {
+                let ordering = total_cmp(a, b);
+                match direction {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering.is_ne() {
+            return ordering;
+        }
+    }
+    a_id.cmp(&b_id)
+}
+
+/// Total order over property values.
+///
+/// Values of the same kind compare naturally (`Int` and `Float` numerically);
+/// different kinds are ranked bool < number < string < string list < int list
+/// < vector.
+pub(crate) fn total_cmp(a: &PropertyValue, b: &PropertyValue) -> Ordering {
+    use PropertyValue::*;
+    match (a, b) {
+        (Bool(a), Bool(b)) => a.cmp(b),
+        (Int(a), Int(b)) => a.cmp(b),
+        (Int(a), Float(b)) => (*a as f64).total_cmp(b),
+        (Float(a), Int(b)) => a.total_cmp(&(*b as f64)),
+        (Float(a), Float(b)) => a.total_cmp(b),
+        (String(a), String(b)) => a.cmp(b),
+        (StringList(a), StringList(b)) => a.cmp(b),
+        (IntList(a), IntList(b)) => a.cmp(b),
+        (Vector(a), Vector(b)) => a
+            .iter()
+            .zip(b)
+            .map(|(x, y)| x.total_cmp(y))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn rank(value: &PropertyValue) -> u8 {
+    match value {
+        PropertyValue::Bool(_) => 0,
+        PropertyValue::Int(_) | PropertyValue::Float(_) => 1,
+        PropertyValue::String(_) => 2,
+        PropertyValue::StringList(_) => 3,
+        PropertyValue::IntList(_) => 4,
+        PropertyValue::Vector(_) => 5,
+        PropertyValue::Null => 6,
+    }
+}
+
+impl Cursor {
+    /// Cursor positioned at a node.
+    pub(crate) fn at(graph: &CodeGraph, node_id: NodeId, order: &[(String, SortOrder)]) -> Self {
+        Self {
+            order: order.to_vec(),
+            values: sort_key(graph, node_id, order)
+                .into_iter()
+                .map(|value| value.cloned().unwrap_or(PropertyValue::Null))
+                .collect(),
+            node_id,
+        }
+    }
+
+    /// Check that the cursor was created for the given ordering.
+    pub(crate) fn check_order(&self, order: &[(String, SortOrder)]) -> Result<()> {
+        if self.order == order && self.values.len() == order.len() {
+            Ok(())
+        } else {
+            Err(GraphError::InvalidOperation {
+                message: "Cursor was created for a different ordering".to_string(),
+            })
+        }
+    }
+
+    /// The cursor position as a sort key.
+    pub(crate) fn key(&self) -> SortKey<'_> {
+        self.values
+            .iter()
+            .map(|value| Some(value).filter(|v| !matches!(v, PropertyValue::Null)))
+            .collect()
+    }
+}
+
+/// Build a table of property values, one row per node (missing values are `Null`).
+pub(crate) fn project(graph: &CodeGraph, nodes: &[NodeId], keys: &[&str]) -> QueryResult {
+    let rows = nodes
+        .iter()
+        .map(|&node_id| {
+            let node = graph.get_node(node_id).ok();
+            keys.iter()
+                .map(|key| {
+                    node.and_then(|node| node.properties.get(key))
+                        .cloned()
+                        .unwrap_or(PropertyValue::Null)
+                })
+                .collect()
+        })
+        .collect();
+    QueryResult {
+        columns: keys.iter().map(|key| key.to_string()).collect(),
+        rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_total_cmp() {
+        assert_eq!(
+            total_cmp(&PropertyValue::Int(2), &PropertyValue::Float(1.5)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            total_cmp(&PropertyValue::from("b"), &PropertyValue::from("a")),
+            Ordering::Greater
+        );
+        // Numbers rank before strings regardless of content
+        assert_eq!(
+            total_cmp(&PropertyValue::Int(100), &PropertyValue::from("1")),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_missing_values_sort_last() {
+        let order = vec![("complexity".to_string(), SortOrder::Descending)];
+        let high = PropertyValue::Int(9);
+        let low = PropertyValue::Int(1);
+        let (high, low, missing): (SortKey, SortKey, SortKey) =
+            (vec![Some(&high)], vec![Some(&low)], vec![None]);
+
+        assert_eq!(
+            compare_positions(&order, (&high, 5), (&low, 1)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_positions(&order, (&low, 1), (&missing, 0)),
+            Ordering::Less
+        );
+        assert_eq!(
+            compare_positions(&order, (&high, 1), (&high, 2)),
+            Ordering::Less
+        );
+    }
+}
@@ -0,0 +1,609 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Subgraph pattern matching.
+//!
+//! A [`Pattern`] is a set of named variables, each with a [`NodeMatcher`], plus
+//! typed edge constraints between them. Matching a pattern against a graph yields
+//! every assignment of nodes to variables that satisfies all constraints.
+//!
+//! ```
+//! use codegraph::query::{NodeMatcher, Pattern};
+//! use codegraph::{helpers, CodeGraph, EdgeType, NodeType};
+//!
+//! # fn example() -> codegraph::Result<()> {
+//! let mut graph = CodeGraph::in_memory()?;
+//! // ... populate graph ...
+//!
+//! // Test functions calling a method of a class that implements an interface
+//! let pattern = Pattern::new()
+//!     .node("c", NodeMatcher::of_type(NodeType::Class))
+//!     .node("i", NodeMatcher::of_type(NodeType::Interface))
+//!     .node("m", NodeMatcher::of_type(NodeType::Function))
+//!     .node("f", NodeMatcher::of_type(NodeType::Function).in_file_matching("tests/**"))
+//!     .edge("c", EdgeType::Implements, "i")
+//!     .edge("c", EdgeType::Contains, "m")
+//!     .edge("f", EdgeType::Calls, "m");
+//!
+//! for found in graph.match_pattern(&pattern)? {
+//!     println!("{:?} calls {:?}", found.get("f"), found.get("m"));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Variables may bind the same node unless [`Pattern::distinct_nodes`] is set.
+
+use super::{glob_match, property_equals};
+use crate::error::{GraphError, Result};
+use crate::graph::{CodeGraph, Direction, Edge, EdgeType, Node, NodeId, NodeType, PropertyValue};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// A node predicate that may consult the graph (e.g. to find the containing file).
+type NodePredicate = Box<dyn Fn(&CodeGraph, &Node) -> bool>;
+
+/// An edge predicate for pattern edge constraints.
+pub(crate) type EdgePredicate = Box<dyn Fn(&Edge) -> bool>;
+
+/// A predicate over a complete set of bindings.
+type MatchPredicate = Box<dyn Fn(&CodeGraph, &PatternMatch) -> bool>;
+
+/// Predicate describing which nodes a pattern variable may bind to.
+///
+/// All conditions are ANDed together.
+#[derive(Default)]
+pub struct NodeMatcher {
+    node_type: Option<NodeType>,
+    predicates: Vec<NodePredicate>,
+}
+
+impl NodeMatcher {
+    /// Match any node.
+    pub fn any() -> Self {
+        Self::default()
+    }
+
+    /// Match nodes of the given type.
+    pub fn of_type(node_type: NodeType) -> Self {
+        Self {
+            node_type: Some(node_type),
+            predicates: Vec::new(),
+        }
+    }
+
+    /// Require an exact property value (same semantics as [`QueryBuilder::property`](super::QueryBuilder::property)).
+    pub fn property<V: Into<PropertyValue>>(mut self, key: &str, value: V) -> Self {
+        let key = key.to_string();
+        let value = value.into();
+        self.predicates.push(Box::new(move |_, node| {
+            node.properties
+                .get(&key)
+                .is_some_and(|actual| property_equals(&value, actual))
+        }));
+        self
+    }
+
+    /// Require an exact `name` property.
+    pub fn name(self, name: &str) -> Self {
+        self.property("name", name)
+    }
+
+    /// Require the `path` property to match a glob pattern.
+    pub fn file_pattern(mut self, pattern: &str) -> Self {
+        let pattern = pattern.to_string();
+        self.predicates.push(Box::new(move |_, node| {
+            node.properties
+                .get_string("path")
+                .is_some_and(|path| glob_match(&pattern, path))
+        }));
+        self
+    }
+
+    /// Require the node's containing file (see [`crate::helpers::containing_file`])
+    /// to have a path matching a glob pattern.
+    pub fn in_file_matching(mut self, pattern: &str) -> Self {
+        let pattern = pattern.to_string();
+        self.predicates.push(Box::new(move |graph, node| {
+            crate::helpers::containing_file(graph, node.id)
+                .ok()
+                .flatten()
+                .and_then(|file_id| graph.get_node(file_id).ok())
+                .and_then(|file| file.properties.get_string("path"))
+                .is_some_and(|path| glob_match(&pattern, path))
+        }));
+        self
+    }
+
+    /// Require a custom predicate.
+    pub fn custom<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&Node) -> bool + 'static,
+    {
+        self.predicates
+            .push(Box::new(move |_, node| predicate(node)));
+        self
+    }
+
+    /// Check whether a node satisfies this matcher.
+    pub fn matches(&self, graph: &CodeGraph, node: &Node) -> bool {
+        self.node_type.map_or(true, |t| node.node_type == t)
+            && self.predicates.iter().all(|p| p(graph, node))
+    }
+}
+
+/// Edge constraint between two pattern variables.
+///
+/// A single edge when `min_hops == max_hops == Some(1)`, otherwise a path of
+/// accepted edges whose length lies within the bounds.
+struct EdgeConstraint {
+    from: String,
+    to: String,
+    edge_types: Vec<EdgeType>,
+    predicate: Option<EdgePredicate>,
+    min_hops: usize,
+    max_hops: Option<usize>,
+}
+
+impl EdgeConstraint {
+    fn new(from: &str, to: &str, edge_types: &[EdgeType]) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            edge_types: edge_types.to_vec(),
+            predicate: None,
+            min_hops: 1,
+            max_hops: Some(1),
+        }
+    }
+
+    fn accepts(&self, edge: &Edge) -> bool {
+        (self.edge_types.is_empty() || self.edge_types.contains(&edge.edge_type))
+            && self.predicate.as_ref().map_or(true, |p| p(edge))
+    }
+
+    fn is_single_hop(&self) -> bool {
+        self.min_hops == 1 && self.max_hops == Some(1)
+    }
+
+    /// Nodes at the other end of this constraint, starting from `start`.
+    ///
+    /// `Direction::Outgoing` walks from a bound source towards targets,
+    /// `Direction::Incoming` walks back from a bound target.
+    fn endpoints(&self, graph: &CodeGraph, start: NodeId, direction: Direction) -> Vec<NodeId> {
+        let step = |node: NodeId| -> Vec<NodeId> {
+            match direction {
+                Direction::Incoming => graph
+                    .incoming_edges(node)
+                    .filter(|edge| self.accepts(edge))
+                    .map(|edge| edge.source_id)
+                    .collect(),
+                _ => graph
+                    .outgoing_edges(node)
+                    .filter(|edge| self.accepts(edge))
+                    .map(|edge| edge.target_id)
+                    .collect(),
+            }
+        };
+
+        // Walk exactly `min_hops` steps (walks may revisit nodes)...
+        let mut frontier: HashSet<NodeId> = HashSet::from([start]);
+        for _ in 0..self.min_hops {
+            frontier = frontier.into_iter().flat_map(step).collect();
+            if frontier.is_empty() {
+                return Vec::new();
+            }
+        }
+
+        // ...then anything within the remaining budget is reachable by a walk in range
+        let mut reached = frontier.clone();
+        let mut remaining = self.max_hops.map(|max| max.saturating_sub(self.min_hops));
+        while !frontier.is_empty() && remaining != Some(0) {
+            frontier = frontier
+                .into_iter()
+                .flat_map(step)
+                .filter(|&node| reached.insert(node))
+                .collect();
+            remaining = remaining.map(|r| r - 1);
+        }
+
+        let mut reached: Vec<NodeId> = reached.into_iter().collect();
+        reached.sort_unstable();
+        reached
+    }
+}
+
+/// A subgraph pattern: variables with node predicates and typed edge constraints.
+#[derive(Default)]
+pub struct Pattern {
+    variables: Vec<(String, NodeMatcher)>,
+    edges: Vec<EdgeConstraint>,
+    filters: Vec<MatchPredicate>,
+    distinct: bool,
+    limit: Option<usize>,
+}
+
+impl Pattern {
+    /// Create an empty pattern.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declare a variable and the nodes it may bind to.
+    ///
+    /// Declaring the same name twice replaces the earlier matcher.
+    pub fn node(mut self, name: &str, matcher: NodeMatcher) -> Self {
+        if let Some(existing) = self.variables.iter_mut().find(|(n, _)| n == name) {
+            existing.1 = matcher;
+        } else {
+            self.variables.push((name.to_string(), matcher));
+        }
+        self
+    }
+
+    /// Require an edge of the given type from `from` to `to`.
+    pub fn edge(self, from: &str, edge_type: EdgeType, to: &str) -> Self {
+        self.edge_of_types(from, &[edge_type], to)
+    }
+
+    /// Require an edge of any of the given types (any type if empty) from `from` to `to`.
+    pub fn edge_of_types(mut self, from: &str, edge_types: &[EdgeType], to: &str) -> Self {
+        self.edges.push(EdgeConstraint::new(from, to, edge_types));
+        self
+    }
+
+    /// Require a path of `min_hops..=max_hops` edges of the given types (any type
+    /// if empty) from `from` to `to`. `max_hops = None` leaves the length unbounded.
+    ///
+    /// With `min_hops = 0` a variable is also connected to itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::query::{NodeMatcher, Pattern};
+    /// # use codegraph::{EdgeType, NodeType};
+    /// // Functions that reach `exec` within three calls
+    /// let pattern = Pattern::new()
+    ///     .node("f", NodeMatcher::of_type(NodeType::Function))
+    ///     .node("g", NodeMatcher::of_type(NodeType::Function).name("exec"))
+    ///     .path("f", &[EdgeType::Calls], 1, Some(3), "g");
+    /// ```
+    pub fn path(
+        self,
+        from: &str,
+        edge_types: &[EdgeType],
+        min_hops: usize,
+        max_hops: Option<usize>,
+        to: &str,
+    ) -> Self {
+        self.constrain(from, to, edge_types, min_hops, max_hops, None)
+    }
+
+    /// Add an edge constraint with every option spelled out.
+    pub(crate) fn constrain(
+        mut self,
+        from: &str,
+        to: &str,
+        edge_types: &[EdgeType],
+        min_hops: usize,
+        max_hops: Option<usize>,
+        predicate: Option<EdgePredicate>,
+    ) -> Self {
+        let mut constraint = EdgeConstraint::new(from, to, edge_types);
+        constraint.min_hops = min_hops;
+        constraint.max_hops = max_hops;
+        constraint.predicate = predicate;
+        self.edges.push(constraint);
+        self
+    }
+
+    /// Require an edge of the given type that also satisfies a predicate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use codegraph::query::{NodeMatcher, Pattern};
+    /// # use codegraph::{EdgeType, NodeType};
+    /// // Indirect calls only
+    /// let pattern = Pattern::new()
+    ///     .node("f", NodeMatcher::of_type(NodeType::Function))
+    ///     .node("g", NodeMatcher::of_type(NodeType::Function))
+    ///     .edge_where("f", EdgeType::Calls, "g", |edge| {
+    ///         edge.properties.get_bool("is_direct") == Some(false)
+    ///     });
+    /// ```
+    pub fn edge_where<F>(self, from: &str, edge_type: EdgeType, to: &str, predicate: F) -> Self
+    where
+        F: Fn(&Edge) -> bool + 'static,
+    {
+        self.constrain(
+            from,
+            to,
+            &[edge_type],
+            1,
+            Some(1),
+            Some(Box::new(predicate)),
+        )
+    }
+
+    /// Require a predicate over the complete bindings (e.g. comparing two variables).
+    pub fn filter<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&CodeGraph, &PatternMatch) -> bool + 'static,
+    {
+        self.filters.push(Box::new(predicate));
+        self
+    }
+
+    /// Require every variable to bind a different node.
+    pub fn distinct_nodes(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Stop after finding `n` matches.
+    pub fn limit(mut self, n: usize) -> Self {
+        self.limit = Some(n);
+        self
+    }
+
+    /// Find all matches of this pattern in a graph.
+    ///
+    /// Matches are produced in a deterministic order.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if an edge constraint references
+    /// an undeclared variable.
+    pub fn execute(&self, graph: &CodeGraph) -> Result<Vec<PatternMatch>> {
+        let names: Arc<[String]> = self.variables.iter().map(|(n, _)| n.clone()).collect();
+        let index_of = |name: &str| -> Result<usize> {
+            names
+                .iter()
+                .position(|n| n == name)
+                .ok_or_else(|| GraphError::InvalidOperation {
+                    message: format!("Unknown pattern variable '{name}'"),
+                })
+        };
+
+        let mut constraints = Vec::with_capacity(self.edges.len());
+        for edge in &self.edges {
+            constraints.push((index_of(&edge.from)?, index_of(&edge.to)?, edge));
+        }
+
+        let mut node_ids: Vec<NodeId> = graph.iter_nodes().map(|(id, _)| id).collect();
+        node_ids.sort_unstable();
+
+        let candidates: Vec<Vec<NodeId>> = self
+            .variables
+            .iter()
+            .map(|(_, matcher)| {
+                node_ids
+                    .iter()
+                    .copied()
+                    .filter(|&id| {
+                        graph
+                            .get_node(id)
+                            .is_ok_and(|node| matcher.matches(graph, node))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let order = plan_order(&candidates, &constraints);
+        let candidate_sets: Vec<HashSet<NodeId>> = candidates
+            .iter()
+            .map(|c| c.iter().copied().collect())
+            .collect();
+
+        let mut search = Search {
+            graph,
+            pattern: self,
+            names,
+            constraints: &constraints,
+            candidates: &candidates,
+            candidate_sets: &candidate_sets,
+            order: &order,
+            bound: vec![None; self.variables.len()],
+            results: Vec::new(),
+            limit: self.limit.unwrap_or(usize::MAX),
+            path_cache: RefCell::default(),
+        };
+        if !self.variables.is_empty() {
+            search.extend(0);
+        }
+
+        Ok(search.results)
+    }
+}
+
+/// Choose a variable binding order: start from the most selective variable, then
+/// prefer variables connected to already-bound ones.
+fn plan_order(
+    candidates: &[Vec<NodeId>],
+    constraints: &[(usize, usize, &EdgeConstraint)],
+) -> Vec<usize> {
+    let mut order = Vec::with_capacity(candidates.len());
+    let mut placed = vec![false; candidates.len()];
+
+    while order.len() < candidates.len() {
+        let connected = |v: usize| {
+            constraints
+                .iter()
+                .any(|&(f, t, _)| (f == v && placed[t]) || (t == v && placed[f]))
+        };
+        let next = (0..candidates.len())
+            .filter(|&v| !placed[v])
+            .min_by_key(|&v| (!connected(v), candidates[v].len(), v))
+            .expect("at least one unplaced variable");
+        placed[next] = true;
+        order.push(next);
+    }
+
+    order
+}
+
+/// Multi-hop endpoints keyed by (constraint index, source node).
+type PathCache = HashMap<(usize, NodeId), Rc<Vec<NodeId>>>;
+
+/// Backtracking state for a single pattern execution.
+struct Search<'a> {
+    graph: &'a CodeGraph,
+    pattern: &'a Pattern,
+    names: Arc<[String]>,
+    constraints: &'a [(usize, usize, &'a EdgeConstraint)],
+    candidates: &'a [Vec<NodeId>],
+    candidate_sets: &'a [HashSet<NodeId>],
+    order: &'a [usize],
+    bound: Vec<Option<NodeId>>,
+    results: Vec<PatternMatch>,
+    limit: usize,
+    path_cache: RefCell<PathCache>,
+}
+
+impl Search<'_> {
+    fn extend(&mut self, depth: usize) {
+        if self.results.len() >= self.limit {
+            return;
+        }
+        if depth == self.order.len() {
+            let found = PatternMatch {
+                names: Arc::clone(&self.names),
+                nodes: self.bound.iter().map(|b| b.expect("all bound")).collect(),
+            };
+            if self.pattern.filters.iter().all(|f| f(self.graph, &found)) {
+                self.results.push(found);
+            }
+            return;
+        }
+
+        let var = self.order[depth];
+        for candidate in self.expand(var) {
+            if self.pattern.distinct && self.bound.contains(&Some(candidate)) {
+                continue;
+            }
+            self.bound[var] = Some(candidate);
+            if self.satisfies_constraints(var) {
+                self.extend(depth + 1);
+            }
+            self.bound[var] = None;
+        }
+    }
+
+    /// Candidate nodes for `var`, generated from adjacency of a bound neighbor when possible.
+    fn expand(&self, var: usize) -> Vec<NodeId> {
+        for (index, &(from, to, constraint)) in self.constraints.iter().enumerate() {
+            let mut found = if to == var && from != var {
+                match self.bound[from] {
+                    Some(source) if constraint.is_single_hop() => {
+                        constraint.endpoints(self.graph, source, Direction::Outgoing)
+                    }
+                    Some(source) => self.paths_from(index, source).to_vec(),
+                    None => continue,
+                }
+            } else if from == var && to != var {
+                match self.bound[to] {
+                    Some(target) => constraint.endpoints(self.graph, target, Direction::Incoming),
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+
+            found.retain(|id| self.candidate_sets[var].contains(id));
+            found.sort_unstable();
+            found.dedup();
+            return found;
+        }
+
+        self.candidates[var].clone()
+    }
+
+    /// Check every constraint between `var` and already-bound variables.
+    fn satisfies_constraints(&self, var: usize) -> bool {
+        self.constraints
+            .iter()
+            .enumerate()
+            .all(|(index, &(from, to, constraint))| {
+                if from != var && to != var {
+                    return true;
+                }
+                match (self.bound[from], self.bound[to]) {
+                    (Some(source), Some(target)) if constraint.is_single_hop() => self
+                        .graph
+                        .outgoing_edges(source)
+                        .any(|edge| edge.target_id == target && constraint.accepts(edge)),
+                    (Some(source), Some(target)) => self
+                        .paths_from(index, source)
+                        .binary_search(&target)
+                        .is_ok(),
+                    _ => true,
+                }
+            })
+    }
+
+    /// Memoized forward endpoints of a multi-hop constraint.
+    fn paths_from(&self, index: usize, source: NodeId) -> Rc<Vec<NodeId>> {
+        let key = (index, source);
+        if let Some(cached) = self.path_cache.borrow().get(&key) {
+            return Rc::clone(cached);
+        }
+        let constraint = self.constraints[index].2;
+        let found = Rc::new(constraint.endpoints(self.graph, source, Direction::Outgoing));
+        self.path_cache.borrow_mut().insert(key, Rc::clone(&found));
+        found
+    }
+}
+
+/// One set of variable bindings produced by a pattern match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    names: Arc<[String]>,
+    nodes: Vec<NodeId>,
+}
+
+impl PatternMatch {
+    /// Get the node bound to a variable.
+    pub fn get(&self, name: &str) -> Option<NodeId> {
+        self.names
+            .iter()
+            .position(|n| n == name)
+            .map(|i| self.nodes[i])
+    }
+
+    /// Iterate over `(variable, node)` bindings in declaration order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, NodeId)> {
+        self.names
+            .iter()
+            .map(String::as_str)
+            .zip(self.nodes.iter().copied())
+    }
+
+    /// Convert the bindings into a map.
+    pub fn to_map(&self) -> HashMap<String, NodeId> {
+        self.iter().map(|(n, id)| (n.to_string(), id)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_variable_is_an_error() {
+        let graph = CodeGraph::in_memory().unwrap();
+        let pattern = Pattern::new()
+            .node("a", NodeMatcher::any())
+            .edge("a", EdgeType::Calls, "b");
+        assert!(pattern.execute(&graph).is_err());
+    }
+
+    #[test]
+    fn test_plan_order_starts_with_most_selective() {
+        let edge = EdgeConstraint::new("file", "f", &[EdgeType::Contains]);
+        let order = plan_order(&[vec![1, 2], vec![0], vec![3, 4, 5]], &[(1, 0, &edge)]);
+        assert_eq!(order, vec![1, 0, 2]);
+    }
+}
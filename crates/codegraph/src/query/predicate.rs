@@ -0,0 +1,282 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Composable predicates over nodes and edges.
+//!
+//! A [`Predicate`] wraps a boolean test and can be combined with [`Predicate::and`],
+//! [`Predicate::or`] and `!`. Property predicates work on anything implementing
+//! [`HasProperties`], so the same set is available for node and edge queries.
+//!
+//! ```
+//! use codegraph::query::Predicate;
+//! use codegraph::{CodeGraph, NodeType};
+//!
+//! # fn example() -> codegraph::Result<()> {
+//! let graph = CodeGraph::in_memory()?;
+//!
+//! // Complex functions, or any test function
+//! let risky = Predicate::property_gt("complexity", 10).or(Predicate::name_matches("^test_")?);
+//! let results = graph.query()
+//!     .node_type(NodeType::Function)
+//!     .filter(risky)
+//!     .not(Predicate::list_contains("attributes", "deprecated"))
+//!     .execute()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::property_equals;
+use crate::error::{GraphError, Result};
+use crate::graph::{Edge, EdgeType, Node, NodeType, PropertyMap, PropertyValue};
+use regex::Regex;
+use std::cmp::Ordering;
+
+/// Items that carry a property map.
+pub trait HasProperties {
+    /// The item's properties.
+    fn properties(&self) -> &PropertyMap;
+}
+
+impl HasProperties for Node {
+    fn properties(&self) -> &PropertyMap {
+        &self.properties
+    }
+}
+
+impl HasProperties for Edge {
+    fn properties(&self) -> &PropertyMap {
+        &self.properties
+    }
+}
+
+/// A boolean test over nodes or edges.
+pub struct Predicate<T: ?Sized> {
+    test: Box<dyn Fn(&T) -> bool>,
+}
+
+impl<T: ?Sized + 'static> Predicate<T> {
+    /// Wrap a custom test.
+    pub fn new<F>(test: F) -> Self
+    where
+        F: Fn(&T) -> bool + 'static,
+    {
+        Self {
+            test: Box::new(test),
+        }
+    }
+
+    /// Evaluate the predicate.
+    pub fn test(&self, item: &T) -> bool {
+        (self.test)(item)
+    }
+
+    /// Both predicates must hold.
+    pub fn and(self, other: Self) -> Self {
+        Self::new(move |item| self.test(item) && other.test(item))
+    }
+
+    /// Either predicate must hold.
+    pub fn or(self, other: Self) -> Self {
+        Self::new(move |item| self.test(item) || other.test(item))
+    }
+
+    /// Every predicate must hold (true if empty).
+    pub fn all(predicates: impl IntoIterator<Item = Self>) -> Self {
+        let predicates: Vec<Self> = predicates.into_iter().collect();
+        Self::new(move |item| predicates.iter().all(|p| p.test(item)))
+    }
+
+    /// At least one predicate must hold (false if empty).
+    pub fn any(predicates: impl IntoIterator<Item = Self>) -> Self {
+        let predicates: Vec<Self> = predicates.into_iter().collect();
+        Self::new(move |item| predicates.iter().any(|p| p.test(item)))
+    }
+}
+
+impl<T: ?Sized + 'static> std::ops::Not for Predicate<T> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::new(move |item| !self.test(item))
+    }
+}
+
+impl<T: HasProperties + ?Sized + 'static> Predicate<T> {
+    /// Exact property match (same semantics as [`QueryBuilder::property`](super::QueryBuilder::property)).
+    pub fn property<V: Into<PropertyValue>>(key: &str, value: V) -> Self {
+        let key = key.to_string();
+        let value = value.into();
+        Self::new(move |item| {
+            item.properties()
+                .get(&key)
+                .is_some_and(|actual| property_equals(&value, actual))
+        })
+    }
+
+    /// The property is present, whatever its value.
+    pub fn property_exists(key: &str) -> Self {
+        let key = key.to_string();
+        Self::new(move |item| item.properties().contains_key(&key))
+    }
+
+    /// Property is strictly greater than `value`.
+    ///
+    /// Numbers compare numerically across `Int` and `Float` (numeric strings are
+    /// accepted too), strings compare lexicographically. Missing or incomparable
+    /// properties never match.
+    pub fn property_gt<V: Into<PropertyValue>>(key: &str, value: V) -> Self {
+        Self::compare(key, value.into(), Ordering::is_gt)
+    }
+
+    /// Property is greater than or equal to `value`.
+    pub fn property_ge<V: Into<PropertyValue>>(key: &str, value: V) -> Self {
+        Self::compare(key, value.into(), Ordering::is_ge)
+    }
+
+    /// Property is strictly less than `value`.
+    pub fn property_lt<V: Into<PropertyValue>>(key: &str, value: V) -> Self {
+        Self::compare(key, value.into(), Ordering::is_lt)
+    }
+
+    /// Property is less than or equal to `value`.
+    pub fn property_le<V: Into<PropertyValue>>(key: &str, value: V) -> Self {
+        Self::compare(key, value.into(), Ordering::is_le)
+    }
+
+    /// Property lies in the inclusive range `low..=high`.
+    pub fn between<V: Into<PropertyValue>>(key: &str, low: V, high: V) -> Self {
+        Self::property_ge(key, low).and(Self::property_le(key, high))
+    }
+
+    fn compare(key: &str, bound: PropertyValue, accept: fn(Ordering) -> bool) -> Self {
+        let key = key.to_string();
+        Self::new(move |item| {
+            item.properties()
+                .get(&key)
+                .and_then(|actual| compare_values(actual, &bound))
+                .is_some_and(accept)
+        })
+    }
+
+    /// String property matches a regular expression (unanchored, like [`Regex::is_match`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if the pattern is not a valid regex.
+    pub fn property_matches(key: &str, pattern: &str) -> Result<Self> {
+        let key = key.to_string();
+        let regex = compile(pattern)?;
+        Ok(Self::new(move |item| {
+            item.properties()
+                .get_string(&key)
+                .is_some_and(|value| regex.is_match(value))
+        }))
+    }
+
+    /// List property contains `value`.
+    ///
+    /// String lists (including legacy comma-separated strings) are searched for a
+    /// string value, integer lists for an integer value.
+    pub fn list_contains<V: Into<PropertyValue>>(key: &str, value: V) -> Self {
+        let key = key.to_string();
+        let value = value.into();
+        Self::new(move |item| {
+            let properties = item.properties();
+            match &value {
+                PropertyValue::Int(wanted) => properties
+                    .get_int_list(&key)
+                    .is_some_and(|list| list.contains(wanted)),
+                PropertyValue::String(wanted) => properties
+                    .get_string_list_compat(&key)
+                    .is_some_and(|list| list.iter().any(|s| s == wanted)),
+                _ => false,
+            }
+        })
+    }
+}
+
+impl Predicate<Node> {
+    /// Node has the given type.
+    pub fn node_type(node_type: NodeType) -> Self {
+        Self::new(move |node| node.node_type == node_type)
+    }
+
+    /// `name` contains a substring (case-insensitive).
+    pub fn name_contains(substring: &str) -> Self {
+        let substring = substring.to_lowercase();
+        Self::new(move |node| {
+            node.properties
+                .get_string("name")
+                .is_some_and(|name| name.to_lowercase().contains(&substring))
+        })
+    }
+
+    /// `name` matches a regular expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if the pattern is not a valid regex.
+    pub fn name_matches(pattern: &str) -> Result<Self> {
+        Self::property_matches("name", pattern)
+    }
+}
+
+impl Predicate<Edge> {
+    /// Edge has the given type.
+    pub fn edge_type(edge_type: EdgeType) -> Self {
+        Self::new(move |edge| edge.edge_type == edge_type)
+    }
+}
+
+/// Compile a regex, mapping failures to [`GraphError::InvalidOperation`].
+pub(crate) fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| GraphError::InvalidOperation {
+        message: format!("Invalid regex '{pattern}': {e}"),
+    })
+}
+
+/// Order an actual property value against a bound.
+///
+/// Numbers compare numerically (numeric strings on the actual side are parsed, as
+/// [`PropertyMap::get_int`] does); strings and booleans compare naturally.
+pub(crate) fn compare_values(actual: &PropertyValue, bound: &PropertyValue) -> Option<Ordering> {
+    match (actual, bound) {
+        (PropertyValue::Int(a), PropertyValue::Int(b)) => Some(a.cmp(b)),
+        (PropertyValue::String(a), PropertyValue::String(b)) => Some(a.cmp(b)),
+        (PropertyValue::Bool(a), PropertyValue::Bool(b)) => Some(a.cmp(b)),
+        _ => as_number(actual)?.partial_cmp(&as_number(bound)?),
+    }
+}
+
+fn as_number(value: &PropertyValue) -> Option<f64> {
+    match value {
+        PropertyValue::Int(i) => Some(*i as f64),
+        PropertyValue::Float(f) => Some(*f),
+        PropertyValue::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_values() {
+        let int = PropertyValue::Int(10);
+        assert_eq!(
+            compare_values(&int, &PropertyValue::Float(9.5)),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(&PropertyValue::from("12"), &int),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            compare_values(&PropertyValue::from("abc"), &PropertyValue::from("abd")),
+            Some(Ordering::Less)
+        );
+        assert_eq!(compare_values(&PropertyValue::from("abc"), &int), None);
+        assert_eq!(compare_values(&PropertyValue::Null, &int), None);
+    }
+}
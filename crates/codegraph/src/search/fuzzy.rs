@@ -0,0 +1,455 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuzzy symbol lookup for "go to symbol".
+//!
+//! Symbols are indexed by the `name` and `qualified_name` properties. Candidates
+//! come from two postings lists:
+//!
+//! - word initials: symbols with a word starting with the query's first character,
+//!   which are scored as subsequence matches (`hndlReq` → `handle_request`);
+//! - trigrams of the normalized text, which recover typos and reordered words
+//!   (`hndlReq` → `HttpRequestHandler`) when the query is not a subsequence.
+//!
+//! Subsequence matches always rank above trigram-only matches. Within them, matches
+//! on word boundaries and runs of consecutive characters score higher, and gaps and
+//! longer symbols score lower.
+
+use crate::graph::{CodeGraph, Node, NodeId, NodeType};
+use crate::helpers::containing_file;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Properties searched by the symbol index.
+const SYMBOL_FIELDS: [&str; 2] = ["name", "qualified_name"];
+
+/// Upper bound on trigram-only candidates scored per lookup, keeping latency flat
+/// on large graphs. Subsequence candidates are always scored.
+const MAX_CANDIDATES: usize = 10_000;
+
+/// Minimum share of query trigrams a non-subsequence match must contain.
+const MIN_TRIGRAM_COVERAGE: f64 = 0.34;
+
+type Trigram = [char; 3];
+
+/// Restricts a symbol or vector lookup to some node types and languages.
+///
+/// Empty lists accept everything. A node's language is its own `language`
+/// property, or that of its containing file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolFilter {
+    /// Accepted node types
+    pub node_types: Vec<NodeType>,
+    /// Accepted languages (case-insensitive)
+    pub languages: Vec<String>,
+}
+
+impl SymbolFilter {
+    /// Also accept a node type.
+    pub fn node_type(mut self, node_type: NodeType) -> Self {
+        self.node_types.push(node_type);
+        self
+    }
+
+    /// Also accept a language.
+    pub fn language(mut self, language: &str) -> Self {
+        self.languages.push(language.to_lowercase());
+        self
+    }
+
+    /// Check a node against both criteria.
+    pub(crate) fn accepts(&self, graph: &CodeGraph, node_id: NodeId) -> bool {
+        graph
+            .get_node(node_id)
+            .is_ok_and(|node| self.accepts_type(node.node_type))
+            && self.accepts_language(graph, node_id)
+    }
+
+    fn accepts_type(&self, node_type: NodeType) -> bool {
+        self.node_types.is_empty() || self.node_types.contains(&node_type)
+    }
+
+    fn accepts_language(&self, graph: &CodeGraph, node_id: NodeId) -> bool {
+        if self.languages.is_empty() {
+            return true;
+        }
+        let language = |id: NodeId| {
+            graph
+                .get_node(id)
+                .ok()
+                .and_then(|node| node.properties.get_string("language"))
+                .map(str::to_lowercase)
+        };
+        let language = language(node_id).or_else(|| {
+            containing_file(graph, node_id)
+                .ok()
+                .flatten()
+                .and_then(language)
+        });
+        language.is_some_and(|language| self.languages.contains(&language))
+    }
+}
+
+/// A ranked symbol lookup result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolMatch {
+    /// Matching node
+    pub node_id: NodeId,
+    /// Match quality (higher is better); subsequence matches score above 1.0
+    pub score: f64,
+    /// The `name` or `qualified_name` value that matched best
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+struct SymbolEntry {
+    node_type: NodeType,
+    texts: Vec<String>,
+    initials: Vec<char>,
+    trigrams: Vec<Trigram>,
+}
+
+/// Trigram and initials index over symbol names.
+#[derive(Debug, Clone, Default)]
+pub struct SymbolIndex {
+    entries: HashMap<NodeId, SymbolEntry>,
+    by_initial: HashMap<char, HashSet<NodeId>>,
+    by_trigram: HashMap<Trigram, HashSet<NodeId>>,
+}
+
+impl SymbolIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an index over every node of a graph.
+    pub fn build(graph: &CodeGraph) -> Self {
+        let mut index = Self::new();
+        for (node_id, node) in graph.iter_nodes() {
+            index.insert(node_id, node);
+        }
+        index
+    }
+
+    /// Number of indexed symbols.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no symbol is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find symbols matching an abbreviated or misspelled query.
+    ///
+    /// Returns at most `limit` matches, best first, with ties broken by ascending
+    /// node ID. The graph is used to resolve language filters.
+    pub fn find(
+        &self,
+        graph: &CodeGraph,
+        query: &str,
+        filter: &SymbolFilter,
+        limit: usize,
+    ) -> Vec<SymbolMatch> {
+        let query = normalize(query);
+        let Some(&first) = query.first() else {
+            return Vec::new();
+        };
+        let query_trigrams = trigrams(&query);
+
+        // Shared trigram count per candidate
+        let mut candidates: HashMap<NodeId, usize> = HashMap::new();
+        for node_id in self.by_initial.get(&first).into_iter().flatten() {
+            candidates.entry(*node_id).or_default();
+        }
+        for trigram in &query_trigrams {
+            for node_id in self.by_trigram.get(trigram).into_iter().flatten() {
+                *candidates.entry(*node_id).or_default() += 1;
+            }
+        }
+
+        // Filter first so that rejected nodes never crowd out accepted ones, and
+        // only cut trigram-only candidates, which always rank below subsequences
+        let (mut candidates, mut trigram_only): (Vec<_>, Vec<_>) = candidates
+            .into_iter()
+            .filter(|&(node_id, _)| {
+                filter.accepts_type(self.entries[&node_id].node_type)
+                    && filter.accepts_language(graph, node_id)
+            })
+            .partition(|(node_id, _)| {
+                self.entries[node_id]
+                    .texts
+                    .iter()
+                    .any(|text| is_subsequence(&query, text))
+            });
+        if trigram_only.len() > MAX_CANDIDATES {
+            let by_overlap =
+                |a: &(NodeId, usize), b: &(NodeId, usize)| b.1.cmp(&a.1).then(a.0.cmp(&b.0));
+            trigram_only.select_nth_unstable_by(MAX_CANDIDATES, by_overlap);
+            trigram_only.truncate(MAX_CANDIDATES);
+        }
+        candidates.extend(trigram_only);
+
+        let mut matches: Vec<SymbolMatch> = candidates
+            .into_iter()
+            .filter_map(|(node_id, shared)| {
+                let entry = &self.entries[&node_id];
+                let coverage = if query_trigrams.is_empty() {
+                    0.0
+                } else {
+                    shared as f64 / query_trigrams.len() as f64
+                };
+                let (score, text) = entry
+                    .texts
+                    .iter()
+                    .filter_map(|text| Some((score(&query, text, coverage)?, text)))
+                    .max_by(|a, b| a.0.total_cmp(&b.0))?;
+                Some(SymbolMatch {
+                    node_id,
+                    score,
+                    text: text.clone(),
+                })
+            })
+            .collect();
+
+        let rank = |a: &SymbolMatch, b: &SymbolMatch| {
+            b.score.total_cmp(&a.score).then(a.node_id.cmp(&b.node_id))
+        };
+        matches.sort_by(rank);
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Index a new node, or re-index one whose properties changed.
+    pub(crate) fn on_node_changed(&mut self, node_id: NodeId, node: &Node) {
+        self.remove(node_id);
+        self.insert(node_id, node);
+    }
+
+    /// Drop a deleted node.
+    pub(crate) fn on_node_removed(&mut self, node_id: NodeId) {
+        self.remove(node_id);
+    }
+
+    /// Drop every node.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn insert(&mut self, node_id: NodeId, node: &Node) {
+        let texts: Vec<String> = SYMBOL_FIELDS
+            .iter()
+            .filter_map(|key| node.properties.get_string(key))
+            .filter(|text| !normalize(text).is_empty())
+            .map(str::to_string)
+            .collect();
+        if texts.is_empty() {
+            return;
+        }
+
+        let mut initials = HashSet::new();
+        let mut grams = HashSet::new();
+        for text in &texts {
+            let chars: Vec<char> = text.chars().collect();
+            for (i, is_start) in word_starts(&chars).into_iter().enumerate() {
+                if is_start {
+                    initials.extend(chars[i].to_lowercase());
+                }
+            }
+            grams.extend(trigrams(&normalize(text)));
+        }
+
+        for &initial in &initials {
+            self.by_initial.entry(initial).or_default().insert(node_id);
+        }
+        for &gram in &grams {
+            self.by_trigram.entry(gram).or_default().insert(node_id);
+        }
+        self.entries.insert(
+            node_id,
+            SymbolEntry {
+                node_type: node.node_type,
+                texts,
+                initials: initials.into_iter().collect(),
+                trigrams: grams.into_iter().collect(),
+            },
+        );
+    }
+
+    fn remove(&mut self, node_id: NodeId) {
+        let Some(entry) = self.entries.remove(&node_id) else {
+            return;
+        };
+        for initial in entry.initials {
+            remove_posting(&mut self.by_initial, initial, node_id);
+        }
+        for gram in entry.trigrams {
+            remove_posting(&mut self.by_trigram, gram, node_id);
+        }
+    }
+}
+
+fn remove_posting<K: std::hash::Hash + Eq>(
+    postings: &mut HashMap<K, HashSet<NodeId>>,
+    key: K,
+    node_id: NodeId,
+) {
+    if let Some(nodes) = postings.get_mut(&key) {
+        nodes.remove(&node_id);
+        if nodes.is_empty() {
+            postings.remove(&key);
+        }
+    }
+}
+
+/// Lowercase alphanumeric characters of a string.
+fn normalize(text: &str) -> Vec<char> {
+    text.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn trigrams(chars: &[char]) -> Vec<Trigram> {
+    chars.windows(3).map(|w| [w[0], w[1], w[2]]).collect()
+}
+
+/// Positions where a word starts: the first character, after a separator, at a
+/// lower-to-upper case change, and at the last capital of an acronym (`HTTPServer`).
+fn word_starts(chars: &[char]) -> Vec<bool> {
+    (0..chars.len())
+        .map(|i| {
+            let c = chars[i];
+            if !c.is_alphanumeric() {
+                return false;
+            }
+            let Some(&prev) = i.checked_sub(1).map(|p| &chars[p]) else {
+                return true;
+            };
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            !prev.is_alphanumeric()
+                || (c.is_uppercase() && (prev.is_lowercase() || prev.is_numeric()))
+                || (c.is_uppercase() && prev.is_uppercase() && next_is_lower)
+        })
+        .collect()
+}
+
+/// Score a symbol, or `None` if it does not match.
+///
+/// Subsequence matches score in `(1, 3]`; others score their trigram coverage in
+/// `[MIN_TRIGRAM_COVERAGE, 1]`.
+fn score(query: &[char], text: &str, trigram_coverage: f64) -> Option<f64> {
+    if !is_subsequence(query, text) {
+        return (trigram_coverage >= MIN_TRIGRAM_COVERAGE).then_some(trigram_coverage);
+    }
+    if let Some(quality) = subsequence_quality(query, text) {
+        let exact = normalize(text) == query;
+        return Some(1.0 + quality + if exact { 1.0 } else { 0.0 });
+    }
+    (trigram_coverage >= MIN_TRIGRAM_COVERAGE).then_some(trigram_coverage)
+}
+
+/// Cheap check before the alignment search.
+fn is_subsequence(query: &[char], text: &str) -> bool {
+    let mut remaining = query.iter().peekable();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if remaining.peek() == Some(&&c) {
+            remaining.next();
+        }
+    }
+    remaining.peek().is_none()
+}
+
+const BOUNDARY_BONUS: f64 = 2.0;
+const CONSECUTIVE_BONUS: f64 = 1.5;
+const GAP_PENALTY: f64 = 0.1;
+const LENGTH_PENALTY: f64 = 0.01;
+
+/// Best alignment of `query` as a case-insensitive subsequence of `text`, scaled
+/// to `(0, 1]`, or `None` if it is not a subsequence.
+fn subsequence_quality(query: &[char], text: &str) -> Option<f64> {
+    let chars: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let starts = word_starts(&chars);
+    let n = chars.len();
+    if query.len() > n {
+        return None;
+    }
+
+    // best[j]: best score with the current query character matched at text[j]
+    let mut best = vec![f64::NEG_INFINITY; n];
+    for (i, &q) in query.iter().enumerate() {
+        let mut next = vec![f64::NEG_INFINITY; n];
+        // Best previous score ending before j - 1, less the gap penalty up to j
+        let mut gapped = f64::NEG_INFINITY;
+        for j in 0..n {
+            if i > 0 && j >= 2 {
+                gapped = gapped.max(best[j - 2]) - GAP_PENALTY;
+            }
+            if lower[j] != q {
+                continue;
+            }
+            let base = 1.0 + if starts[j] { BOUNDARY_BONUS } else { 0.0 };
+            next[j] = if i == 0 {
+                // Leading unmatched characters count as a gap
+                base - GAP_PENALTY * j as f64
+            } else {
+                let consecutive = if j >= 1 {
+                    best[j - 1] + CONSECUTIVE_BONUS
+                } else {
+                    f64::NEG_INFINITY
+                };
+                base + consecutive.max(gapped)
+            };
+        }
+        best = next;
+    }
+
+    let raw = best.into_iter().fold(f64::NEG_INFINITY, f64::max);
+    if raw == f64::NEG_INFINITY {
+        return None;
+    }
+    let perfect = query.len() as f64 * (1.0 + BOUNDARY_BONUS + CONSECUTIVE_BONUS);
+    let penalty = LENGTH_PENALTY * (n - query.len()) as f64;
+    Some(((raw - penalty) / perfect).clamp(f64::EPSILON, 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quality(query: &str, text: &str) -> Option<f64> {
+        subsequence_quality(&normalize(query), text)
+    }
+
+    #[test]
+    fn test_word_starts() {
+        let chars: Vec<char> = "getHTTPServer_v2".chars().collect();
+        let starts: String = word_starts(&chars)
+            .iter()
+            .zip(&chars)
+            .filter(|(start, _)| **start)
+            .map(|(_, c)| *c)
+            .collect();
+        assert_eq!(starts, "gHSv");
+    }
+
+    #[test]
+    fn test_subsequence_quality() {
+        assert!(quality("hndlReq", "handle_request").is_some());
+        assert!(quality("hndlReq", "HttpRequestHandler").is_none());
+        assert!(quality("xyz", "handle_request").is_none());
+
+        // Boundary matches beat matches inside words
+        assert!(quality("hr", "handle_request") > quality("hr", "through"));
+        // Consecutive runs beat scattered matches
+        assert!(quality("req", "request") > quality("req", "ranked_query"));
+        // Shorter symbols win when alignments are equally good
+        assert!(quality("parse", "parse") > quality("parse", "parse_all_the_things"));
+    }
+}
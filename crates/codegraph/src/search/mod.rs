@@ -0,0 +1,43 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Search indexes over node text.
+//!
+//! - [`TextIndex`]: inverted index over `name`, `signature` and `doc` with
+//!   identifier-aware tokenization and BM25 ranking
+//! - [`SymbolIndex`]: typo-tolerant, abbreviation-friendly symbol lookup over
+//!   `name` and `qualified_name` using trigrams and subsequence scoring
+//! - [`VectorIndex`]: HNSW nearest-neighbour search over an embedding property,
+//!   with filtered k-NN and persistence through the storage backend
+//!
+//! [`CodeGraph`](crate::CodeGraph) keeps enabled indexes in sync with node
+//! insertions, property updates and deletions.
+//!
+//! ```
+//! use codegraph::search::TextIndexConfig;
+//! use codegraph::{helpers, CodeGraph};
+//!
+//! # fn example() -> codegraph::Result<()> {
+//! let mut graph = CodeGraph::in_memory()?;
+//! graph.build_search_index(TextIndexConfig::default());
+//!
+//! let file = helpers::add_file(&mut graph, "src/config.rs", "rust")?;
+//! let parse = helpers::add_function(&mut graph, file, "parseConfigFile", 1, 20)?;
+//!
+//! let hits = graph.search("config file", 10)?;
+//! assert_eq!(hits[0].node_id, parse);
+//! # Ok(())
+//! # }
+//! ```
+
+mod fuzzy;
+mod text;
+mod tokenizer;
+mod vector;
+
+pub use fuzzy::{SymbolFilter, SymbolIndex, SymbolMatch};
+pub use text::{SearchHit, TextIndex, TextIndexConfig};
+pub use tokenizer::tokenize;
+pub use vector::{Metric, VectorIndex, VectorIndexConfig, VectorMatch};
+
+pub(crate) use vector::{HEADER_KEY as VECTOR_HEADER_KEY, RECORD_PREFIX as VECTOR_RECORD_PREFIX};
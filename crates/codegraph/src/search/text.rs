@@ -0,0 +1,275 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Inverted index with BM25 ranking.
+//!
+//! Every configured property is indexed as a separate field with its own postings
+//! and length statistics. A query term scores BM25 in each field, and the field
+//! scores are combined with the field boosts, so a match in `name` outranks the
+//! same match in `doc`.
+
+use super::tokenize;
+use crate::graph::{CodeGraph, Node, NodeId};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Fields and ranking parameters of a [`TextIndex`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextIndexConfig {
+    /// Indexed string properties and their score boosts
+    pub fields: Vec<(String, f64)>,
+    /// BM25 term frequency saturation
+    pub k1: f64,
+    /// BM25 length normalization (0 = none, 1 = full)
+    pub b: f64,
+}
+
+impl Default for TextIndexConfig {
+    fn default() -> Self {
+        Self {
+            fields: vec![
+                ("name".to_string(), 3.0),
+                ("signature".to_string(), 1.5),
+                ("doc".to_string(), 1.0),
+            ],
+            k1: 1.2,
+            b: 0.75,
+        }
+    }
+}
+
+/// A ranked search result.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SearchHit {
+    /// Matching node
+    pub node_id: NodeId,
+    /// BM25 score summed over fields (higher is better)
+    pub score: f64,
+}
+
+/// Postings and length statistics of one field.
+#[derive(Debug, Clone, Default)]
+struct FieldIndex {
+    /// Term frequency per node, by term
+    postings: HashMap<String, HashMap<NodeId, u32>>,
+    /// Number of terms per node
+    lengths: HashMap<NodeId, u32>,
+    total_length: u64,
+}
+
+impl FieldIndex {
+    fn average_length(&self) -> f64 {
+        if self.lengths.is_empty() {
+            0.0
+        } else {
+            self.total_length as f64 / self.lengths.len() as f64
+        }
+    }
+}
+
+/// Full-text index over node properties.
+#[derive(Debug, Clone)]
+pub struct TextIndex {
+    config: TextIndexConfig,
+    fields: Vec<FieldIndex>,
+    /// Distinct terms of every indexed node, per field, for removal
+    documents: HashMap<NodeId, Vec<Vec<String>>>,
+}
+
+impl TextIndex {
+    /// Create an empty index.
+    pub fn new(config: TextIndexConfig) -> Self {
+        let fields = vec![FieldIndex::default(); config.fields.len()];
+        Self {
+            config,
+            fields,
+            documents: HashMap::new(),
+        }
+    }
+
+    /// Build an index over every node of a graph.
+    pub fn build(graph: &CodeGraph, config: TextIndexConfig) -> Self {
+        let mut index = Self::new(config);
+        for (node_id, node) in graph.iter_nodes() {
+            index.insert(node_id, node);
+        }
+        index
+    }
+
+    /// The index configuration.
+    pub fn config(&self) -> &TextIndexConfig {
+        &self.config
+    }
+
+    /// Number of indexed nodes (nodes with text in at least one field).
+    pub fn len(&self) -> usize {
+        self.documents.len()
+    }
+
+    /// Check if no node is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.documents.is_empty()
+    }
+
+    /// Check whether a node is indexed.
+    pub fn contains(&self, node_id: NodeId) -> bool {
+        self.documents.contains_key(&node_id)
+    }
+
+    /// Rank nodes against a free-text query.
+    ///
+    /// The query is tokenized like the indexed text; a node matches if it contains
+    /// any query term. Returns at most `limit` hits, best first, with ties broken
+    /// by ascending node ID.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let mut seen = HashSet::new();
+        let terms: Vec<String> = tokenize(query)
+            .into_iter()
+            .filter(|term| seen.insert(term.clone()))
+            .collect();
+
+        let doc_count = self.documents.len() as f64;
+        let (k1, b) = (self.config.k1, self.config.b);
+        let mut scores: HashMap<NodeId, f64> = HashMap::new();
+
+        for (field, (_, boost)) in self.fields.iter().zip(&self.config.fields) {
+            let average_length = field.average_length();
+            for term in &terms {
+                let Some(postings) = field.postings.get(term) else {
+                    continue;
+                };
+                let df = postings.len() as f64;
+                let idf = (1.0 + (doc_count - df + 0.5) / (df + 0.5)).ln();
+                for (&node_id, &tf) in postings {
+                    let tf = f64::from(tf);
+                    let length = f64::from(field.lengths[&node_id]);
+                    let norm = 1.0 - b + b * length / average_length;
+                    let score = idf * tf * (k1 + 1.0) / (tf + k1 * norm);
+                    *scores.entry(node_id).or_default() += boost * score;
+                }
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = scores
+            .into_iter()
+            .map(|(node_id, score)| SearchHit { node_id, score })
+            .collect();
+        let rank = |a: &SearchHit, b: &SearchHit| {
+            b.score.total_cmp(&a.score).then(a.node_id.cmp(&b.node_id))
+        };
+        if limit < hits.len() {
+            hits.select_nth_unstable_by(limit, rank);
+            hits.truncate(limit);
+        }
+        hits.sort_by(rank);
+        hits
+    }
+
+    /// Index a new node, or re-index one whose properties changed.
+    pub(crate) fn on_node_changed(&mut self, node_id: NodeId, node: &Node) {
+        self.remove(node_id);
+        self.insert(node_id, node);
+    }
+
+    /// Drop a deleted node.
+    pub(crate) fn on_node_removed(&mut self, node_id: NodeId) {
+        self.remove(node_id);
+    }
+
+    /// Drop every node.
+    pub(crate) fn clear(&mut self) {
+        *self = Self::new(self.config.clone());
+    }
+
+    fn insert(&mut self, node_id: NodeId, node: &Node) {
+        let mut document = Vec::with_capacity(self.fields.len());
+        for (field, (key, _)) in self.fields.iter_mut().zip(&self.config.fields) {
+            let tokens = node
+                .properties
+                .get_string(key)
+                .map(tokenize)
+                .unwrap_or_default();
+            if tokens.is_empty() {
+                document.push(Vec::new());
+                continue;
+            }
+
+            let mut frequencies: HashMap<String, u32> = HashMap::new();
+            for token in &tokens {
+                *frequencies.entry(token.clone()).or_default() += 1;
+            }
+            field.lengths.insert(node_id, tokens.len() as u32);
+            field.total_length += tokens.len() as u64;
+
+            let mut terms = Vec::with_capacity(frequencies.len());
+            for (term, tf) in frequencies {
+                field
+                    .postings
+                    .entry(term.clone())
+                    .or_default()
+                    .insert(node_id, tf);
+                terms.push(term);
+            }
+            document.push(terms);
+        }
+
+        if document.iter().any(|terms| !terms.is_empty()) {
+            self.documents.insert(node_id, document);
+        }
+    }
+
+    fn remove(&mut self, node_id: NodeId) {
+        let Some(document) = self.documents.remove(&node_id) else {
+            return;
+        };
+        for (field, terms) in self.fields.iter_mut().zip(document) {
+            for term in terms {
+                if let Some(postings) = field.postings.get_mut(&term) {
+                    postings.remove(&node_id);
+                    if postings.is_empty() {
+                        field.postings.remove(&term);
+                    }
+                }
+            }
+            if let Some(length) = field.lengths.remove(&node_id) {
+                field.total_length -= u64::from(length);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{NodeType, PropertyMap};
+
+    fn node(id: NodeId, name: &str, doc: &str) -> Node {
+        Node::new(
+            id,
+            NodeType::Function,
+            PropertyMap::new().with("name", name).with("doc", doc),
+        )
+    }
+
+    #[test]
+    fn test_remove_cleans_postings() {
+        let mut index = TextIndex::new(TextIndexConfig::default());
+        index.on_node_changed(1, &node(1, "parseConfig", "Parse the config"));
+        index.on_node_changed(2, &node(2, "render", "Render output"));
+        assert_eq!(index.len(), 2);
+
+        index.on_node_removed(1);
+        assert_eq!(index.len(), 1);
+        assert!(!index.fields[0].postings.contains_key("parse"));
+        assert_eq!(index.fields[2].total_length, 2);
+        assert!(index.search("config", 10).is_empty());
+    }
+
+    #[test]
+    fn test_nodes_without_text_are_not_indexed() {
+        let mut index = TextIndex::new(TextIndexConfig::default());
+        let empty = Node::new(1, NodeType::Generic, PropertyMap::new().with("line", 3i64));
+        index.on_node_changed(1, &empty);
+        assert!(index.is_empty());
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Identifier-aware tokenization.
+
+/// Split text into lowercase search terms.
+///
+/// Words are split on non-alphanumeric characters, then identifiers are split into
+/// their camelCase and snake_case parts. Acronyms stay together (`HTTPServer` gives
+/// `http` and `server`) and digits stay attached to the preceding part (`utf8`).
+/// A multi-part identifier also yields the parts joined together, so a query for
+/// `parse_config` or `parseConfig` ranks the exact identifier above partial matches.
+///
+/// # Examples
+///
+/// ```
+/// use codegraph::search::tokenize;
+///
+/// assert_eq!(tokenize("getHTTPResponse"), vec!["get", "http", "response", "gethttpresponse"]);
+/// assert_eq!(tokenize("load_utf8 file"), vec!["load", "utf8", "loadutf8", "file"]);
+/// ```
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for word in text.split(|c: char| !(c.is_alphanumeric() || c == '_')) {
+        let parts = split_identifier(word);
+        if parts.len() > 1 {
+            let joined = parts.concat();
+            tokens.extend(parts);
+            tokens.push(joined);
+        } else {
+            tokens.extend(parts);
+        }
+    }
+    tokens
+}
+
+/// Split one identifier at underscores and case changes.
+fn split_identifier(word: &str) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let mut parts = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if !current.is_empty() && c.is_uppercase() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            // fooBar, utf8Decode, HTTPServer (boundary before the last capital)
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_is_lower) {
+                parts.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_identifier() {
+        assert_eq!(split_identifier("parseConfig"), vec!["parse", "config"]);
+        assert_eq!(split_identifier("ParseConfig"), vec!["parse", "config"]);
+        assert_eq!(split_identifier("parse_config"), vec!["parse", "config"]);
+        assert_eq!(split_identifier("__init__"), vec!["init"]);
+        assert_eq!(split_identifier("HTTPServer"), vec!["http", "server"]);
+        assert_eq!(split_identifier("MAX_SIZE"), vec!["max", "size"]);
+        assert_eq!(split_identifier("utf8Decode"), vec!["utf8", "decode"]);
+        assert!(split_identifier("___").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_text() {
+        assert_eq!(
+            tokenize("fn parse(input: &str) -> Config"),
+            vec!["fn", "parse", "input", "str", "config"]
+        );
+        assert_eq!(tokenize("Données"), vec!["données"]);
+        assert!(tokenize("  ... ").is_empty());
+    }
+}
@@ -0,0 +1,884 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Approximate nearest-neighbour search over vector properties.
+//!
+//! [`VectorIndex`] is a hierarchical navigable small world (HNSW) graph over the
+//! nodes that carry a [`PropertyValue::Vector`](crate::PropertyValue::Vector) under
+//! the configured key. Every node links to its closest neighbours on layer 0 and,
+//! with exponentially decreasing probability, on sparser upper layers. A query
+//! descends greedily through the upper layers and then runs a beam search of width
+//! `ef_search` on layer 0.
+//!
+//! Filtered queries traverse the graph as usual but only collect accepted nodes, so
+//! a selective filter costs more visits rather than fewer results.
+//!
+//! Node levels are derived from a hash of the node ID, so the same sequence of
+//! insertions always produces the same graph. The links of every node are persisted
+//! under `vector:{id}` keys and the index header under `meta:vector_index`; the
+//! vectors themselves are read back from the node properties.
+
+use crate::error::{GraphError, Result};
+use crate::graph::{CodeGraph, Node, NodeId};
+use crate::storage::{BatchOperation, KeyValue};
+use serde::{Deserialize, Serialize};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Storage key of the index header.
+pub(crate) const HEADER_KEY: &[u8] = b"meta:vector_index";
+
+/// Storage key prefix of per-node link records.
+pub(crate) const RECORD_PREFIX: &[u8] = b"vector:";
+
+/// Highest layer a node can be assigned to.
+const MAX_LEVEL: usize = 16;
+
+/// Dense position of an indexed node.
+type Slot = u32;
+
+/// Distance function of a [`VectorIndex`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Metric {
+    /// `1 - cos(a, b)`; zero vectors are not indexed
+    Cosine,
+    /// Squared Euclidean distance
+    Euclidean,
+}
+
+/// Indexed property and graph parameters of a [`VectorIndex`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VectorIndexConfig {
+    /// Vector property to index
+    pub property: String,
+    /// Distance function
+    pub metric: Metric,
+    /// Links per node on upper layers (twice as many on layer 0)
+    pub m: usize,
+    /// Beam width while inserting (higher builds a better graph, more slowly)
+    pub ef_construction: usize,
+    /// Beam width while searching, raised to `k` when smaller
+    pub ef_search: usize,
+}
+
+impl Default for VectorIndexConfig {
+    fn default() -> Self {
+        Self {
+            property: "embedding".to_string(),
+            metric: Metric::Cosine,
+            m: 16,
+            ef_construction: 100,
+            ef_search: 64,
+        }
+    }
+}
+
+/// A nearest-neighbour result.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct VectorMatch {
+    /// Matching node
+    pub node_id: NodeId,
+    /// Distance to the query (lower is closer)
+    pub distance: f32,
+}
+
+/// A slot and its distance to some base vector, ordered by distance then slot.
+#[derive(Debug, Clone, Copy)]
+struct Scored {
+    distance: f32,
+    slot: Slot,
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance
+            .total_cmp(&other.distance)
+            .then(self.slot.cmp(&other.slot))
+    }
+}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Scored {}
+
+/// Persisted index state besides the links.
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    config: VectorIndexConfig,
+    dimensions: Option<usize>,
+    entry_point: Option<NodeId>,
+}
+
+/// HNSW index over a vector property.
+///
+/// Nodes are stored in dense slots so that vectors sit back to back in memory and
+/// traversals index arrays instead of hashing node IDs.
+#[derive(Debug, Clone)]
+pub struct VectorIndex {
+    config: VectorIndexConfig,
+    /// Length of the indexed vectors, fixed by the first one
+    dimensions: Option<usize>,
+    /// Slot of every indexed node
+    slots: HashMap<NodeId, Slot>,
+    /// Node in each slot, `None` for free slots
+    nodes: Vec<Option<NodeId>>,
+    /// Vectors of all slots, back to back (normalized for cosine)
+    vectors: Vec<f32>,
+    /// Outgoing links of each slot, by layer
+    links: Vec<Vec<Vec<Slot>>>,
+    /// Slots linking to each slot, on any layer
+    incoming: Vec<Vec<Slot>>,
+    free: Vec<Slot>,
+    entry_point: Option<Slot>,
+    /// Nodes whose links changed since they were last persisted
+    dirty: HashSet<NodeId>,
+}
+
+impl VectorIndex {
+    /// Create an empty index.
+    pub fn new(config: VectorIndexConfig) -> Self {
+        Self {
+            config,
+            dimensions: None,
+            slots: HashMap::new(),
+            nodes: Vec::new(),
+            vectors: Vec::new(),
+            links: Vec::new(),
+            incoming: Vec::new(),
+            free: Vec::new(),
+            entry_point: None,
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Build an index over every node of a graph, inserting nodes by ascending ID.
+    pub fn build(graph: &CodeGraph, config: VectorIndexConfig) -> Self {
+        let mut index = Self::new(config);
+        let mut nodes: Vec<(NodeId, &Node)> = graph.iter_nodes().collect();
+        nodes.sort_unstable_by_key(|(node_id, _)| *node_id);
+        for (node_id, node) in nodes {
+            index.on_node_changed(node_id, node);
+        }
+        index
+    }
+
+    /// The index configuration.
+    pub fn config(&self) -> &VectorIndexConfig {
+        &self.config
+    }
+
+    /// Length of the indexed vectors, `None` while the index is empty.
+    ///
+    /// The first indexed vector fixes the length; vectors of another length are
+    /// not indexed.
+    pub fn dimensions(&self) -> Option<usize> {
+        self.dimensions
+    }
+
+    /// Number of indexed nodes.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Check if no node is indexed.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Check whether a node is indexed.
+    pub fn contains(&self, node_id: NodeId) -> bool {
+        self.slots.contains_key(&node_id)
+    }
+
+    /// Find the `k` indexed nodes closest to a query vector among those `accept`
+    /// returns `true` for.
+    ///
+    /// Results are closest first, with ties broken by ascending node ID. The search
+    /// is approximate; raise `ef_search` to trade speed for recall.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`GraphError::InvalidOperation`] if the query length differs from the
+    /// indexed vectors, or the query is not finite (or is zero, for cosine).
+    pub fn search(
+        &self,
+        query: &[f32],
+        k: usize,
+        accept: impl Fn(NodeId) -> bool,
+    ) -> Result<Vec<VectorMatch>> {
+        let (Some(entry_point), Some(dimensions)) = (self.entry_point, self.dimensions) else {
+            return Ok(Vec::new());
+        };
+        if query.len() != dimensions {
+            return Err(GraphError::InvalidOperation {
+                message: format!(
+                    "Query vector has {} dimensions, the index has {dimensions}",
+                    query.len()
+                ),
+            });
+        }
+        let query = self
+            .prepare(query)
+            .ok_or_else(|| GraphError::InvalidOperation {
+                message: "Query vector must be finite and, for cosine, non-zero".to_string(),
+            })?;
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut points = vec![self.score(&query, entry_point)];
+        for layer in (1..self.links[entry_point as usize].len()).rev() {
+            points = self.search_layer(&query, &points, 1, layer, |_| true);
+        }
+        let ef = self.config.ef_search.max(k);
+        let mut matches: Vec<VectorMatch> = self
+            .search_layer(&query, &points, ef, 0, |slot| accept(self.node_id(slot)))
+            .into_iter()
+            .map(|scored| VectorMatch {
+                node_id: self.node_id(scored.slot),
+                distance: scored.distance,
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then(a.node_id.cmp(&b.node_id))
+        });
+        matches.truncate(k);
+        Ok(matches)
+    }
+
+    /// Index a new node, or re-index one whose vector changed.
+    pub(crate) fn on_node_changed(&mut self, node_id: NodeId, node: &Node) {
+        let vector = self.node_vector(node);
+        if let (Some(vector), Some(&slot)) = (&vector, self.slots.get(&node_id)) {
+            if *vector == self.vector(slot) {
+                return;
+            }
+        }
+        self.remove(node_id);
+        // Removing the last node frees the dimensions for a vector of another length
+        if let Some(vector) = vector.or_else(|| self.node_vector(node)) {
+            self.insert(node_id, &vector);
+        }
+    }
+
+    /// Drop a deleted node.
+    pub(crate) fn on_node_removed(&mut self, node_id: NodeId) {
+        self.remove(node_id);
+    }
+
+    /// Drop every node.
+    pub(crate) fn clear(&mut self) {
+        let mut dirty = std::mem::take(&mut self.dirty);
+        dirty.extend(self.slots.keys());
+        *self = Self::new(self.config.clone());
+        self.dirty = dirty;
+    }
+
+    /// Storage writes for the header and for every node whose links changed since
+    /// they were last written.
+    pub(crate) fn changes(&self) -> Result<Vec<BatchOperation>> {
+        if self.dirty.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut dirty: Vec<NodeId> = self.dirty.iter().copied().collect();
+        dirty.sort_unstable();
+        self.records(dirty)
+    }
+
+    /// Mark the [`changes`](Self::changes) as written to storage.
+    pub(crate) fn changes_written(&mut self) {
+        self.dirty.clear();
+    }
+
+    /// Storage writes for the header and the links of every indexed node.
+    pub(crate) fn snapshot(&self) -> Result<Vec<BatchOperation>> {
+        let mut node_ids: Vec<NodeId> = self.slots.keys().copied().collect();
+        node_ids.sort_unstable();
+        self.records(node_ids)
+    }
+
+    /// The header record followed by the link records of some nodes, deleting the
+    /// records of nodes that are no longer indexed.
+    fn records(&self, node_ids: Vec<NodeId>) -> Result<Vec<BatchOperation>> {
+        let header = Header {
+            config: self.config.clone(),
+            dimensions: self.dimensions,
+            entry_point: self.entry_point.map(|slot| self.node_id(slot)),
+        };
+        let mut operations = Vec::with_capacity(node_ids.len() + 1);
+        operations.push(BatchOperation::Put {
+            key: HEADER_KEY.to_vec(),
+            value: serde_json::to_vec(&header).map_err(|e| {
+                GraphError::serialization("Failed to serialize vector index", Some(e))
+            })?,
+        });
+
+        for node_id in node_ids {
+            let key = format!("vector:{node_id}").into_bytes();
+            operations.push(match self.slots.get(&node_id) {
+                Some(&slot) => {
+                    let layers: Vec<Vec<NodeId>> = self.links[slot as usize]
+                        .iter()
+                        .map(|links| links.iter().map(|&s| self.node_id(s)).collect())
+                        .collect();
+                    BatchOperation::Put {
+                        key,
+                        value: serde_json::to_vec(&layers).map_err(|e| {
+                            GraphError::serialization("Failed to serialize vector links", Some(e))
+                        })?,
+                    }
+                }
+                None => BatchOperation::Delete { key },
+            });
+        }
+        Ok(operations)
+    }
+
+    /// Restore a persisted index.
+    ///
+    /// Falls back to rebuilding from the graph when the records do not match the
+    /// graph's vectors; stale records are then reported by
+    /// [`changes`](Self::changes).
+    pub(crate) fn restore(
+        graph: &CodeGraph,
+        header: &[u8],
+        records: Vec<KeyValue>,
+    ) -> Result<Self> {
+        let header: Header = serde_json::from_slice(header).map_err(|e| {
+            GraphError::serialization("Failed to deserialize vector index", Some(e))
+        })?;
+        let mut index = Self::new(header.config);
+        index.dimensions = header.dimensions;
+
+        let mut stored = Vec::with_capacity(records.len());
+        let mut consistent = true;
+        for (key, value) in records {
+            let node_id = std::str::from_utf8(&key[RECORD_PREFIX.len()..])
+                .ok()
+                .and_then(|id| id.parse::<NodeId>().ok())
+                .ok_or_else(|| {
+                    GraphError::serialization::<serde_json::Error>(
+                        "Invalid vector record key",
+                        None,
+                    )
+                })?;
+            let layers: Vec<Vec<NodeId>> = serde_json::from_slice(&value).map_err(|e| {
+                GraphError::serialization("Failed to deserialize vector links", Some(e))
+            })?;
+            let vector = graph
+                .get_node(node_id)
+                .ok()
+                .and_then(|node| index.node_vector(node));
+            match vector {
+                Some(vector) if !layers.is_empty() => {
+                    index.allocate(node_id, &vector, layers.len());
+                    stored.push((node_id, layers));
+                }
+                _ => {
+                    consistent = false;
+                    stored.push((node_id, Vec::new()));
+                }
+            }
+        }
+
+        let indexable = graph
+            .iter_nodes()
+            .filter(|(_, node)| index.node_vector(node).is_some())
+            .count();
+        index.entry_point = header
+            .entry_point
+            .and_then(|node_id| index.slots.get(&node_id).copied());
+        consistent = consistent
+            && indexable == index.slots.len()
+            && index.entry_point.is_some() != index.slots.is_empty()
+            && stored
+                .iter()
+                .flat_map(|(_, layers)| layers.iter().flatten())
+                .all(|node_id| index.slots.contains_key(node_id));
+        if !consistent {
+            let mut rebuilt = Self::build(graph, index.config);
+            rebuilt
+                .dirty
+                .extend(stored.into_iter().map(|(node_id, _)| node_id));
+            return Ok(rebuilt);
+        }
+
+        index.dirty.clear();
+        for (node_id, layers) in stored {
+            let slot = index.slots[&node_id];
+            for (layer, links) in layers.into_iter().enumerate() {
+                let links: Vec<Slot> = links.iter().map(|id| index.slots[id]).collect();
+                for &neighbor in &links {
+                    index.add_incoming(neighbor, slot);
+                }
+                index.links[slot as usize][layer] = links;
+            }
+        }
+        Ok(index)
+    }
+
+    /// The indexable vector of a node: present, of the index length, finite and
+    /// (for cosine) non-zero.
+    fn node_vector(&self, node: &Node) -> Option<Vec<f32>> {
+        let vector = node.properties.get_vector(&self.config.property)?;
+        if self.dimensions.is_some_and(|d| d != vector.len()) || vector.is_empty() {
+            return None;
+        }
+        self.prepare(vector)
+    }
+
+    fn prepare(&self, vector: &[f32]) -> Option<Vec<f32>> {
+        if !vector.iter().all(|x| x.is_finite()) {
+            return None;
+        }
+        match self.config.metric {
+            Metric::Cosine => {
+                let norm = dot(vector, vector).sqrt();
+                (norm > 0.0).then(|| vector.iter().map(|x| x / norm).collect())
+            }
+            Metric::Euclidean => Some(vector.to_vec()),
+        }
+    }
+
+    fn vector(&self, slot: Slot) -> &[f32] {
+        let dimensions = self.dimensions.unwrap_or(0);
+        let start = slot as usize * dimensions;
+        &self.vectors[start..start + dimensions]
+    }
+
+    fn node_id(&self, slot: Slot) -> NodeId {
+        self.nodes[slot as usize].expect("live slot")
+    }
+
+    fn distance(&self, a: &[f32], b: &[f32]) -> f32 {
+        match self.config.metric {
+            Metric::Cosine => (1.0 - dot(a, b)).max(0.0),
+            Metric::Euclidean => squared_distance(a, b),
+        }
+    }
+
+    fn score(&self, vector: &[f32], slot: Slot) -> Scored {
+        Scored {
+            distance: self.distance(vector, self.vector(slot)),
+            slot,
+        }
+    }
+
+    fn max_links(&self, layer: usize) -> usize {
+        let m = self.config.m.max(2);
+        if layer == 0 {
+            2 * m
+        } else {
+            m
+        }
+    }
+
+    /// Layer of a node, drawn from a geometric distribution seeded by its ID.
+    fn level(&self, node_id: NodeId) -> usize {
+        // splitmix64
+        let mut x = node_id.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        x ^= x >> 31;
+        let uniform = ((x >> 11) as f64 + 1.0) / (1u64 << 53) as f64;
+        let scale = 1.0 / (self.config.m.max(2) as f64).ln();
+        ((-uniform.ln() * scale) as usize).min(MAX_LEVEL)
+    }
+
+    /// Beam search on one layer, returning up to `ef` accepted slots, closest first.
+    ///
+    /// Rejected slots are still traversed, so the search keeps going until `ef`
+    /// accepted slots are found or the reachable graph is exhausted.
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry_points: &[Scored],
+        ef: usize,
+        layer: usize,
+        accept: impl Fn(Slot) -> bool,
+    ) -> Vec<Scored> {
+        let mut visited = vec![0u64; self.nodes.len().div_ceil(64)];
+        let mut visit = |slot: Slot| {
+            let (word, bit) = (slot as usize / 64, 1u64 << (slot % 64));
+            let first = visited[word] & bit == 0;
+            visited[word] |= bit;
+            first
+        };
+        let mut candidates = BinaryHeap::with_capacity(ef * 4);
+        let mut results: BinaryHeap<Scored> = BinaryHeap::with_capacity(ef + 1);
+        for &point in entry_points {
+            visit(point.slot);
+            candidates.push(Reverse(point));
+            if accept(point.slot) {
+                results.push(point);
+                if results.len() > ef {
+                    results.pop();
+                }
+            }
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            if results.len() >= ef && results.peek().is_some_and(|worst| current > *worst) {
+                break;
+            }
+            let Some(links) = self.links[current.slot as usize].get(layer) else {
+                continue;
+            };
+            for &neighbor in links {
+                if !visit(neighbor) {
+                    continue;
+                }
+                let scored = self.score(query, neighbor);
+                if results.len() >= ef && results.peek().is_some_and(|worst| scored > *worst) {
+                    continue;
+                }
+                candidates.push(Reverse(scored));
+                if accept(neighbor) {
+                    results.push(scored);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+        results.into_sorted_vec()
+    }
+
+    /// Pick up to `max` links from candidates sorted by distance to the base slot.
+    ///
+    /// A candidate is preferred when it is closer to the base than to every link
+    /// already picked, which spreads links in different directions; the remaining
+    /// slots are filled with the closest of the other candidates.
+    fn select_neighbors(&self, candidates: &[Scored], max: usize) -> Vec<Slot> {
+        let mut selected: Vec<Scored> = Vec::with_capacity(max);
+        let mut pruned = Vec::new();
+        for &candidate in candidates {
+            if selected.len() >= max {
+                break;
+            }
+            let vector = self.vector(candidate.slot);
+            let diverse = selected
+                .iter()
+                .all(|s| self.score(vector, s.slot).distance > candidate.distance);
+            if diverse {
+                selected.push(candidate);
+            } else {
+                pruned.push(candidate);
+            }
+        }
+        let free = max.saturating_sub(selected.len());
+        selected.extend(pruned.into_iter().take(free));
+        selected.into_iter().map(|s| s.slot).collect()
+    }
+
+    /// Place a node in a free slot, without links.
+    fn allocate(&mut self, node_id: NodeId, vector: &[f32], layers: usize) -> Slot {
+        self.dimensions = Some(vector.len());
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                let start = slot as usize * vector.len();
+                self.vectors[start..start + vector.len()].copy_from_slice(vector);
+                self.nodes[slot as usize] = Some(node_id);
+                self.links[slot as usize] = vec![Vec::new(); layers];
+                slot
+            }
+            None => {
+                self.vectors.extend_from_slice(vector);
+                self.nodes.push(Some(node_id));
+                self.links.push(vec![Vec::new(); layers]);
+                self.incoming.push(Vec::new());
+                (self.nodes.len() - 1) as Slot
+            }
+        };
+        self.slots.insert(node_id, slot);
+        self.dirty.insert(node_id);
+        slot
+    }
+
+    fn insert(&mut self, node_id: NodeId, vector: &[f32]) {
+        let level = self.level(node_id);
+        let slot = self.allocate(node_id, vector, level + 1);
+        let Some(entry_point) = self.entry_point else {
+            self.entry_point = Some(slot);
+            return;
+        };
+
+        let top = self.links[entry_point as usize].len() - 1;
+        let mut points = vec![self.score(vector, entry_point)];
+        for layer in (level + 1..=top).rev() {
+            points = self.search_layer(vector, &points, 1, layer, |_| true);
+        }
+        let ef = self.config.ef_construction;
+        for layer in (0..=level.min(top)).rev() {
+            let found = self.search_layer(vector, &points, ef, layer, |s| s != slot);
+            let links = self.select_neighbors(&found, self.max_links(layer));
+            for &neighbor in &links {
+                self.add_incoming(neighbor, slot);
+                self.link(neighbor, slot, layer);
+            }
+            self.links[slot as usize][layer] = links;
+            points = found;
+        }
+        if level > top {
+            self.entry_point = Some(slot);
+        }
+    }
+
+    /// Add a link, dropping the farthest one if the slot has too many.
+    ///
+    /// Re-running the neighbour selection here would cost a distance computation
+    /// per pair of links on every insertion, for no measurable gain in recall.
+    fn link(&mut self, from: Slot, to: Slot, layer: usize) {
+        let max = self.max_links(layer);
+        let Some(links) = self.links[from as usize].get_mut(layer) else {
+            return;
+        };
+        if links.contains(&to) {
+            return;
+        }
+        links.push(to);
+        let overflow = links.len() > max;
+        self.add_incoming(to, from);
+        self.dirty.insert(self.node_id(from));
+        if overflow {
+            let vector = self.vector(from);
+            let links = &self.links[from as usize][layer];
+            let farthest = (0..links.len()).max_by_key(|&i| self.score(vector, links[i]));
+            if let Some(farthest) = farthest {
+                let dropped = self.links[from as usize][layer].swap_remove(farthest);
+                self.forget_link(from, dropped);
+            }
+        }
+    }
+
+    /// Replace a slot's links on a layer with a selection from `candidates`.
+    fn relink(&mut self, slot: Slot, layer: usize, candidates: Vec<Slot>) {
+        let vector = self.vector(slot);
+        let mut scored: Vec<Scored> = candidates
+            .into_iter()
+            .map(|candidate| self.score(vector, candidate))
+            .collect();
+        scored.sort_unstable();
+        let selected = self.select_neighbors(&scored, self.max_links(layer));
+
+        let previous = std::mem::replace(&mut self.links[slot as usize][layer], selected.clone());
+        self.dirty.insert(self.node_id(slot));
+        for &neighbor in &selected {
+            self.add_incoming(neighbor, slot);
+        }
+        for neighbor in previous {
+            if !selected.contains(&neighbor) {
+                self.forget_link(slot, neighbor);
+            }
+        }
+    }
+
+    fn add_incoming(&mut self, to: Slot, from: Slot) {
+        let sources = &mut self.incoming[to as usize];
+        if !sources.contains(&from) {
+            sources.push(from);
+        }
+    }
+
+    /// Drop `from` from the incoming links of `to` unless it still links there.
+    fn forget_link(&mut self, from: Slot, to: Slot) {
+        let still_linked = self.links[from as usize]
+            .iter()
+            .any(|links| links.contains(&to));
+        if !still_linked {
+            self.incoming[to as usize].retain(|&source| source != from);
+        }
+    }
+
+    /// Remove a node, reconnecting the slots that linked to it through its links.
+    fn remove(&mut self, node_id: NodeId) {
+        let Some(slot) = self.slots.remove(&node_id) else {
+            return;
+        };
+        self.dirty.insert(node_id);
+        if self.slots.is_empty() {
+            self.clear();
+            return;
+        }
+
+        let removed = std::mem::take(&mut self.links[slot as usize]);
+        self.nodes[slot as usize] = None;
+        for &neighbor in removed.iter().flatten() {
+            self.forget_link(slot, neighbor);
+        }
+
+        let mut sources = std::mem::take(&mut self.incoming[slot as usize]);
+        sources.sort_unstable();
+        for source in sources {
+            for layer in 0..self.links[source as usize].len() {
+                let links = &self.links[source as usize][layer];
+                if !links.contains(&slot) {
+                    continue;
+                }
+                let mut candidates: Vec<Slot> =
+                    links.iter().copied().filter(|&s| s != slot).collect();
+                for &neighbor in removed.get(layer).into_iter().flatten() {
+                    if neighbor != source && !candidates.contains(&neighbor) {
+                        candidates.push(neighbor);
+                    }
+                }
+                self.relink(source, layer, candidates);
+            }
+        }
+        self.free.push(slot);
+
+        if self.entry_point == Some(slot) {
+            self.entry_point = (0..self.nodes.len() as Slot)
+                .filter(|&s| self.nodes[s as usize].is_some())
+                .max_by_key(|&s| (self.links[s as usize].len(), Reverse(self.node_id(s))));
+        }
+    }
+}
+
+/// Dot product, accumulated in eight lanes so that it vectorizes.
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; 8];
+    let (a_chunks, b_chunks) = (a.chunks_exact(8), b.chunks_exact(8));
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| x * y)
+        .sum();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for ((lane, x), y) in lanes.iter_mut().zip(x).zip(y) {
+            *lane += x * y;
+        }
+    }
+    lanes.iter().sum::<f32>() + tail
+}
+
+/// Squared Euclidean distance, accumulated like [`dot`].
+fn squared_distance(a: &[f32], b: &[f32]) -> f32 {
+    let mut lanes = [0.0f32; 8];
+    let (a_chunks, b_chunks) = (a.chunks_exact(8), b.chunks_exact(8));
+    let tail: f32 = a_chunks
+        .remainder()
+        .iter()
+        .zip(b_chunks.remainder())
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum();
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for ((lane, x), y) in lanes.iter_mut().zip(x).zip(y) {
+            *lane += (x - y) * (x - y);
+        }
+    }
+    lanes.iter().sum::<f32>() + tail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{NodeType, PropertyMap};
+
+    fn node(id: NodeId, vector: Vec<f32>) -> Node {
+        Node::new(
+            id,
+            NodeType::Function,
+            PropertyMap::new().with("embedding", vector),
+        )
+    }
+
+    /// Check that `incoming` mirrors the links exactly.
+    fn assert_incoming_consistent(index: &VectorIndex) {
+        for (slot, node) in index.nodes.iter().enumerate() {
+            let slot = slot as Slot;
+            let mut expected: Vec<Slot> = (0..index.nodes.len() as Slot)
+                .filter(|&s| index.links[s as usize].iter().flatten().any(|&n| n == slot))
+                .collect();
+            let mut actual = index.incoming[slot as usize].clone();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected, "incoming links of slot {slot}");
+            if node.is_none() {
+                assert!(actual.is_empty(), "links to free slot {slot}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_links_stay_consistent_under_removal() {
+        let config = VectorIndexConfig {
+            m: 2,
+            ..VectorIndexConfig::default()
+        };
+        let mut index = VectorIndex::new(config);
+        for id in 0..60u64 {
+            let angle = id as f32 * 0.1;
+            index.on_node_changed(id, &node(id, vec![angle.cos(), angle.sin(), 0.5]));
+        }
+        assert_incoming_consistent(&index);
+        assert!(index.links.iter().all(|l| l[0].len() <= index.max_links(0)));
+
+        for id in (0..60u64).step_by(3) {
+            index.on_node_removed(id);
+        }
+        assert_incoming_consistent(&index);
+        assert_eq!(index.len(), 40);
+        assert!(index
+            .entry_point
+            .is_some_and(|slot| index.nodes[slot as usize].is_some()));
+
+        // Freed slots are reused
+        index.on_node_changed(100, &node(100, vec![1.0, 0.0, 0.0]));
+        assert_eq!(index.nodes.len(), 60);
+        assert_incoming_consistent(&index);
+    }
+
+    #[test]
+    fn test_unindexable_vectors_are_skipped() {
+        let mut index = VectorIndex::new(VectorIndexConfig::default());
+        index.on_node_changed(1, &node(1, vec![0.0, 0.0]));
+        index.on_node_changed(2, &node(2, vec![f32::NAN, 1.0]));
+        assert!(index.is_empty());
+
+        index.on_node_changed(3, &node(3, vec![1.0, 0.0]));
+        index.on_node_changed(4, &node(4, vec![1.0, 0.0, 0.0]));
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.dimensions(), Some(2));
+
+        // The last node may change length
+        index.on_node_changed(3, &node(3, vec![1.0, 0.0, 0.0]));
+        assert_eq!(index.dimensions(), Some(3));
+    }
+
+    #[test]
+    fn test_levels_are_deterministic() {
+        let index = VectorIndex::new(VectorIndexConfig::default());
+        let levels: Vec<usize> = (0..1000).map(|id| index.level(id)).collect();
+        assert_eq!(
+            levels,
+            (0..1000).map(|id| index.level(id)).collect::<Vec<_>>()
+        );
+        // About 1/16 of nodes reach layer 1 with m = 16
+        let upper = levels.iter().filter(|&&level| level > 0).count();
+        assert!((30..100).contains(&upper), "{upper} nodes above layer 0");
+    }
+
+    #[test]
+    fn test_lane_accumulation() {
+        let a: Vec<f32> = (0..19).map(|i| i as f32).collect();
+        let b: Vec<f32> = (0..19).map(|i| 1.0 - i as f32).collect();
+        let expected: f32 = a.iter().zip(&b).map(|(x, y)| x * y).sum();
+        assert_eq!(dot(&a, &b), expected);
+        let expected: f32 = a.iter().zip(&b).map(|(x, y)| (x - y) * (x - y)).sum();
+        assert_eq!(squared_distance(&a, &b), expected);
+    }
+}
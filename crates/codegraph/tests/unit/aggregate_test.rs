@@ -0,0 +1,238 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for query aggregations
+//!
+//! Tests cover:
+//! - count/sum/avg/min/max without grouping
+//! - Grouping by property, file, file property and directory
+//! - Missing and non-numeric values, sorting result tables
+
+use codegraph::query::{Aggregate, GroupBy, SortOrder};
+use codegraph::{helpers, CodeGraph, GraphError, NodeType, PropertyMap, PropertyValue};
+
+// src/core/a.py: f1 (3), f2 (7, public); src/core/b.rs: f3 (10, public);
+// src/util/c.py: f4 (no complexity, public)
+fn create_graph() -> CodeGraph {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let a = helpers::add_file(&mut graph, "src/core/a.py", "python").unwrap();
+    let b = helpers::add_file(&mut graph, "src/core/b.rs", "rust").unwrap();
+    let c = helpers::add_file(&mut graph, "src/util/c.py", "python").unwrap();
+
+    for (file, name, complexity, visibility) in [
+        (a, "f1", Some(3), "private"),
+        (a, "f2", Some(7), "public"),
+        (b, "f3", Some(10), "public"),
+        (c, "f4", None, "public"),
+    ] {
+        let id = helpers::add_function(&mut graph, file, name, 1, 10).unwrap();
+        let mut props = PropertyMap::new().with("visibility", visibility);
+        if let Some(complexity) = complexity {
+            props = props.with("complexity", complexity as i64);
+        }
+        graph.update_node_properties(id, props).unwrap();
+    }
+    graph
+}
+
+fn functions(graph: &CodeGraph) -> codegraph::QueryBuilder<'_> {
+    graph.query().node_type(NodeType::Function)
+}
+
+#[test]
+fn test_ungrouped_aggregates() {
+    let graph = create_graph();
+    let result = functions(&graph)
+        .aggregate(&[
+            Aggregate::count(),
+            Aggregate::sum("complexity"),
+            Aggregate::avg("complexity"),
+            Aggregate::min("complexity"),
+            Aggregate::max("complexity"),
+        ])
+        .unwrap();
+
+    assert_eq!(
+        result.columns,
+        vec![
+            "count",
+            "sum(complexity)",
+            "avg(complexity)",
+            "min(complexity)",
+            "max(complexity)"
+        ]
+    );
+    // Missing values are skipped by sum/avg/min/max but counted by count
+    assert_eq!(
+        result.rows,
+        vec![vec![
+            PropertyValue::Int(4),
+            PropertyValue::Int(20),
+            PropertyValue::Float(20.0 / 3.0),
+            PropertyValue::Int(3),
+            PropertyValue::Int(10),
+        ]]
+    );
+
+    let empty = graph
+        .query()
+        .node_type(NodeType::Class)
+        .aggregate(&[Aggregate::count(), Aggregate::avg("complexity")])
+        .unwrap();
+    assert_eq!(
+        empty.rows,
+        vec![vec![PropertyValue::Int(0), PropertyValue::Null]]
+    );
+}
+
+#[test]
+fn test_group_by_file_and_directory() {
+    let graph = create_graph();
+
+    let per_file = functions(&graph)
+        .group_by(GroupBy::File)
+        .aggregate(&[Aggregate::count(), Aggregate::avg("complexity")])
+        .unwrap();
+    assert_eq!(per_file.columns, vec!["file", "count", "avg(complexity)"]);
+    assert_eq!(
+        per_file.rows,
+        vec![
+            vec![
+                "src/core/a.py".into(),
+                PropertyValue::Int(2),
+                PropertyValue::Float(5.0)
+            ],
+            vec![
+                "src/core/b.rs".into(),
+                PropertyValue::Int(1),
+                PropertyValue::Float(10.0)
+            ],
+            vec![
+                "src/util/c.py".into(),
+                PropertyValue::Int(1),
+                PropertyValue::Null
+            ],
+        ]
+    );
+
+    // Public API count per package
+    let public_api = functions(&graph)
+        .property("visibility", "public")
+        .group_by(GroupBy::Directory)
+        .aggregate(&[Aggregate::count()])
+        .unwrap();
+    assert_eq!(public_api.columns, vec!["directory", "count"]);
+    assert_eq!(
+        public_api.rows,
+        vec![
+            vec!["src/core".into(), PropertyValue::Int(2)],
+            vec!["src/util".into(), PropertyValue::Int(1)],
+        ]
+    );
+}
+
+#[test]
+fn test_group_by_properties() {
+    let mut graph = create_graph();
+
+    let per_language = functions(&graph)
+        .group_by(GroupBy::file_property("language"))
+        .aggregate(&[Aggregate::count()])
+        .unwrap();
+    assert_eq!(per_language.columns, vec!["file.language", "count"]);
+    assert_eq!(
+        per_language.rows,
+        vec![
+            vec!["python".into(), PropertyValue::Int(3)],
+            vec!["rust".into(), PropertyValue::Int(1)],
+        ]
+    );
+
+    // Nodes without the group property form a trailing Null group
+    let file = helpers::add_file(&mut graph, "src/d.py", "python").unwrap();
+    helpers::add_function(&mut graph, file, "f5", 1, 2).unwrap();
+    let per_visibility = functions(&graph)
+        .group_by(GroupBy::property("visibility"))
+        .aggregate(&[Aggregate::count(), Aggregate::max("complexity")])
+        .unwrap();
+    assert_eq!(
+        per_visibility.rows,
+        vec![
+            vec![
+                "private".into(),
+                PropertyValue::Int(1),
+                PropertyValue::Int(3)
+            ],
+            vec![
+                "public".into(),
+                PropertyValue::Int(3),
+                PropertyValue::Int(10)
+            ],
+            vec![
+                PropertyValue::Null,
+                PropertyValue::Int(1),
+                PropertyValue::Null
+            ],
+        ]
+    );
+}
+
+#[test]
+fn test_mixed_numeric_values() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "m.py", "python").unwrap();
+    for (name, score) in [
+        ("a", PropertyValue::Float(0.5)),
+        ("b", PropertyValue::Int(2)),
+        ("c", PropertyValue::from("1.5")),
+        ("d", PropertyValue::from("n/a")),
+    ] {
+        let id = helpers::add_function(&mut graph, file, name, 1, 2).unwrap();
+        graph
+            .update_node_properties(id, PropertyMap::new().with("score", score))
+            .unwrap();
+    }
+
+    let result = functions(&graph)
+        .aggregate(&[
+            Aggregate::sum("score"),
+            Aggregate::min("score"),
+            Aggregate::max("score"),
+        ])
+        .unwrap();
+    assert_eq!(
+        result.rows,
+        vec![vec![
+            PropertyValue::Float(4.0),
+            PropertyValue::Float(0.5),
+            PropertyValue::Int(2),
+        ]]
+    );
+}
+
+#[test]
+fn test_sort_result_table() {
+    let graph = create_graph();
+    let mut per_file = functions(&graph)
+        .group_by(GroupBy::File)
+        .aggregate(&[Aggregate::avg("complexity")])
+        .unwrap();
+
+    per_file
+        .sort_by("avg(complexity)", SortOrder::Descending)
+        .unwrap();
+    let files: Vec<_> = per_file.rows.iter().map(|row| row[0].clone()).collect();
+    assert_eq!(
+        files,
+        vec![
+            PropertyValue::from("src/core/b.rs"),
+            PropertyValue::from("src/core/a.py"),
+            PropertyValue::from("src/util/c.py"),
+        ]
+    );
+
+    assert!(matches!(
+        per_file.sort_by("median", SortOrder::Ascending),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+}
@@ -310,3 +310,99 @@ fn test_bfs_handles_cycles() {
     // Should visit all nodes exactly once
     assert_eq!(result.len(), 2); // B and C (not A itself)
 }
+
+// Feedback edge set: removing the proposed edges must leave the graph acyclic
+#[test]
+fn test_minimum_feedback_edges_breaks_simple_cycle() {
+    use codegraph::graph::algorithms::{
+        minimum_feedback_edges, strongly_connected_components_by_type,
+    };
+    use codegraph::EdgeType;
+
+    let (mut graph, _) = create_circular_chain().unwrap();
+    let removed = minimum_feedback_edges(&graph, &[EdgeType::Imports], |_| 1.0).unwrap();
+    assert_eq!(removed.len(), 1);
+
+    for edge_id in removed {
+        graph.delete_edge(edge_id).unwrap();
+    }
+    let sccs = strongly_connected_components_by_type(&graph, &[EdgeType::Imports]);
+    assert!(sccs.iter().all(|scc| scc.len() == 1));
+}
+
+#[test]
+fn test_minimum_feedback_edges_prefers_light_edges() {
+    use codegraph::graph::algorithms::minimum_feedback_edges;
+    use codegraph::EdgeType;
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let a = helpers::add_file(&mut graph, "a.py", "python").unwrap();
+    let b = helpers::add_file(&mut graph, "b.py", "python").unwrap();
+
+    let heavy = helpers::add_import(&mut graph, a, b, vec!["x", "y", "z"]).unwrap();
+    let light = helpers::add_import(&mut graph, b, a, vec!["w"]).unwrap();
+
+    let removed = minimum_feedback_edges(&graph, &[EdgeType::Imports], |edge| {
+        edge.properties
+            .get_string_list("symbols")
+            .map_or(1.0, |s| s.len() as f64)
+    })
+    .unwrap();
+
+    assert_eq!(removed, vec![light]);
+    assert!(!removed.contains(&heavy));
+}
+
+#[test]
+fn test_minimum_feedback_edges_acyclic_graph() {
+    use codegraph::graph::algorithms::minimum_feedback_edges;
+    use codegraph::EdgeType;
+
+    let (graph, _) = create_linear_chain().unwrap();
+    let removed = minimum_feedback_edges(&graph, &[EdgeType::Imports], |_| 1.0).unwrap();
+    assert!(removed.is_empty());
+}
+
+#[test]
+fn test_minimum_feedback_edges_overlapping_cycles() {
+    use codegraph::graph::algorithms::{
+        minimum_feedback_edges, strongly_connected_components_by_type,
+    };
+    use codegraph::EdgeType;
+
+    // Two cycles sharing the edge c -> a: a->b->c->a and a->d->c->a
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let a = helpers::add_file(&mut graph, "a.py", "python").unwrap();
+    let b = helpers::add_file(&mut graph, "b.py", "python").unwrap();
+    let c = helpers::add_file(&mut graph, "c.py", "python").unwrap();
+    let d = helpers::add_file(&mut graph, "d.py", "python").unwrap();
+    helpers::add_import(&mut graph, a, b, vec![]).unwrap();
+    helpers::add_import(&mut graph, b, c, vec![]).unwrap();
+    helpers::add_import(&mut graph, a, d, vec![]).unwrap();
+    helpers::add_import(&mut graph, d, c, vec![]).unwrap();
+    let shared = helpers::add_import(&mut graph, c, a, vec![]).unwrap();
+
+    let removed = minimum_feedback_edges(&graph, &[EdgeType::Imports], |_| 1.0).unwrap();
+    assert_eq!(removed, vec![shared]);
+
+    graph.delete_edge(shared).unwrap();
+    let sccs = strongly_connected_components_by_type(&graph, &[EdgeType::Imports]);
+    assert_eq!(sccs.len(), 4);
+}
+
+#[test]
+fn test_strongly_connected_components_by_type_filters_edges() {
+    use codegraph::graph::algorithms::strongly_connected_components_by_type;
+    use codegraph::EdgeType;
+
+    let (mut graph, nodes) = create_linear_chain().unwrap();
+    // A call edge closing the loop must not create an import cycle
+    helpers::add_call(&mut graph, nodes[3], nodes[0], 1).unwrap();
+
+    let imports = strongly_connected_components_by_type(&graph, &[EdgeType::Imports]);
+    assert_eq!(imports.len(), 4);
+
+    let all = strongly_connected_components_by_type(&graph, &[EdgeType::Imports, EdgeType::Calls]);
+    assert_eq!(all.len(), 1);
+    assert_eq!(all[0], nodes);
+}
@@ -0,0 +1,148 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for community detection
+//!
+//! Tests cover:
+//! - Louvain clustering of two dense groups joined by a bridge
+//! - Label propagation on the same structure
+//! - Function-level clustering over Calls edges
+//! - Layout report: misplaced nodes and split directories
+
+use codegraph::graph::community::{label_propagation, layout_report, louvain};
+use codegraph::{helpers, CodeGraph, EdgeType, NodeId};
+
+// Two triangles of files (src/api, src/db) joined by a single import, plus one
+// file from src/db that only talks to the api group.
+fn create_two_groups() -> codegraph::Result<(CodeGraph, Vec<NodeId>)> {
+    let mut graph = CodeGraph::in_memory()?;
+
+    let a1 = helpers::add_file(&mut graph, "src/api/routes.rs", "rust")?;
+    let a2 = helpers::add_file(&mut graph, "src/api/handlers.rs", "rust")?;
+    let a3 = helpers::add_file(&mut graph, "src/api/auth.rs", "rust")?;
+    let d1 = helpers::add_file(&mut graph, "src/db/pool.rs", "rust")?;
+    let d2 = helpers::add_file(&mut graph, "src/db/query.rs", "rust")?;
+    let d3 = helpers::add_file(&mut graph, "src/db/schema.rs", "rust")?;
+    let stray = helpers::add_file(&mut graph, "src/db/api_helpers.rs", "rust")?;
+
+    for (from, to) in [(a1, a2), (a2, a3), (a3, a1), (d1, d2), (d2, d3), (d3, d1)] {
+        helpers::add_import(&mut graph, from, to, vec![])?;
+    }
+    helpers::add_import(&mut graph, a2, d1, vec![])?;
+    helpers::add_import(&mut graph, a1, stray, vec![])?;
+    helpers::add_import(&mut graph, a3, stray, vec![])?;
+
+    Ok((graph, vec![a1, a2, a3, d1, d2, d3, stray]))
+}
+
+#[test]
+fn test_louvain_separates_dense_groups() {
+    let (graph, n) = create_two_groups().unwrap();
+    let communities = louvain(&graph, &[EdgeType::Imports]).unwrap();
+
+    assert_eq!(communities.len(), 2);
+    assert_eq!(communities.assignments.len(), 7);
+    assert!(communities.modularity > 0.3);
+
+    let api = communities.community_of(n[0]).unwrap();
+    let db = communities.community_of(n[3]).unwrap();
+    assert_ne!(api, db);
+    assert_eq!(communities.community_of(n[1]), Some(api));
+    assert_eq!(communities.community_of(n[2]), Some(api));
+    assert_eq!(communities.community_of(n[4]), Some(db));
+    assert_eq!(communities.community_of(n[5]), Some(db));
+    assert_eq!(communities.community_of(n[6]), Some(api));
+}
+
+#[test]
+fn test_louvain_is_deterministic() {
+    let (graph, _) = create_two_groups().unwrap();
+    let first = louvain(&graph, &[EdgeType::Imports]).unwrap();
+    let second = louvain(&graph, &[EdgeType::Imports]).unwrap();
+    assert_eq!(first.assignments, second.assignments);
+}
+
+#[test]
+fn test_louvain_ignores_other_edge_types() {
+    let (graph, _) = create_two_groups().unwrap();
+    let communities = louvain(&graph, &[EdgeType::Calls]).unwrap();
+    assert!(communities.is_empty());
+    assert_eq!(communities.modularity, 0.0);
+}
+
+#[test]
+fn test_label_propagation_finds_groups() {
+    let (graph, n) = create_two_groups().unwrap();
+    let communities = label_propagation(&graph, &[EdgeType::Imports], 20).unwrap();
+
+    let api = communities.community_of(n[0]).unwrap();
+    let db = communities.community_of(n[3]).unwrap();
+    assert_ne!(api, db);
+    assert_eq!(communities.community_of(n[2]), Some(api));
+    assert_eq!(communities.community_of(n[5]), Some(db));
+}
+
+#[test]
+fn test_louvain_on_call_graph() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    let parse = helpers::add_function(&mut graph, file, "parse", 1, 10).unwrap();
+    let lex = helpers::add_function(&mut graph, file, "lex", 11, 20).unwrap();
+    let render = helpers::add_function(&mut graph, file, "render", 21, 30).unwrap();
+    let layout = helpers::add_function(&mut graph, file, "layout", 31, 40).unwrap();
+
+    helpers::add_call(&mut graph, parse, lex, 2).unwrap();
+    helpers::add_call(&mut graph, parse, lex, 3).unwrap();
+    helpers::add_call(&mut graph, render, layout, 22).unwrap();
+    helpers::add_call(&mut graph, layout, render, 33).unwrap();
+
+    let communities = louvain(&graph, &[EdgeType::Calls]).unwrap();
+    assert_eq!(communities.len(), 2);
+    assert!(communities.community_of(file).is_none());
+    assert_eq!(
+        communities.community_of(parse),
+        communities.community_of(lex)
+    );
+    assert_ne!(
+        communities.community_of(parse),
+        communities.community_of(render)
+    );
+}
+
+#[test]
+fn test_layout_report_flags_misplaced_file() {
+    let (graph, n) = create_two_groups().unwrap();
+    let communities = louvain(&graph, &[EdgeType::Imports]).unwrap();
+    let report = layout_report(&graph, &communities).unwrap();
+
+    assert_eq!(report.clusters.len(), 2);
+    assert_eq!(report.misplaced.len(), 1);
+    assert_eq!(report.misplaced[0].node_id, n[6]);
+    assert_eq!(report.misplaced[0].directory, "src/db");
+    assert_eq!(report.misplaced[0].expected_directory, "src/api");
+
+    assert!(report.split_directories.contains_key("src/db"));
+    assert!(!report.split_directories.contains_key("src/api"));
+    assert!((report.purity - 6.0 / 7.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_layout_report_resolves_functions_through_contains() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/core/engine.rs", "rust").unwrap();
+    let class = helpers::add_class(&mut graph, file, "Engine", 1, 50).unwrap();
+    let start = helpers::add_method(&mut graph, class, "start", 2, 10).unwrap();
+    let stop = helpers::add_method(&mut graph, class, "stop", 11, 20).unwrap();
+    helpers::add_call(&mut graph, start, stop, 5).unwrap();
+
+    let communities = louvain(&graph, &[EdgeType::Calls]).unwrap();
+    let report = layout_report(&graph, &communities).unwrap();
+
+    assert_eq!(report.clusters.len(), 1);
+    assert_eq!(
+        report.clusters[0].dominant_directory.as_deref(),
+        Some("src/core")
+    );
+    assert_eq!(report.clusters[0].purity, 1.0);
+    assert!(report.misplaced.is_empty());
+}
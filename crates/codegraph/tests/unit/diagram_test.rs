@@ -0,0 +1,219 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for Mermaid and PlantUML diagram export
+//!
+//! Tests cover:
+//! - Class diagrams with fields, methods, inheritance and file grouping
+//! - Flowcharts grouped by file, and collapsing large graphs to files
+//! - Component diagrams with aggregated dependencies
+//! - Member collapsing and node trimming limits
+
+use codegraph::export::{DiagramOptions, ExportFilter};
+use codegraph::{helpers, CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
+
+struct Fixture {
+    graph: CodeGraph,
+    server: NodeId,
+    handler: NodeId,
+}
+
+// src/net/server.rs: interface Handler, class Server implements Handler
+// src/net/tls.rs: class TlsServer extends Server
+// src/main.rs: fn main calls Server::start
+fn create_graph() -> Fixture {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let server_rs = helpers::add_file(&mut graph, "src/net/server.rs", "rust").unwrap();
+    let tls_rs = helpers::add_file(&mut graph, "src/net/tls.rs", "rust").unwrap();
+    let main_rs = helpers::add_file(&mut graph, "src/main.rs", "rust").unwrap();
+
+    let handler = graph
+        .add_node(
+            NodeType::Interface,
+            PropertyMap::new().with("name", "Handler"),
+        )
+        .unwrap();
+    graph
+        .add_edge(server_rs, handler, EdgeType::Contains, PropertyMap::new())
+        .unwrap();
+    let server = helpers::add_class(&mut graph, server_rs, "Server", 10, 60).unwrap();
+    let port = graph
+        .add_node(
+            NodeType::Variable,
+            PropertyMap::new()
+                .with("name", "port")
+                .with("type_annotation", "u16")
+                .with("visibility", "private"),
+        )
+        .unwrap();
+    graph
+        .add_edge(server, port, EdgeType::Contains, PropertyMap::new())
+        .unwrap();
+    let start = helpers::add_method(&mut graph, server, "start", 20, 30).unwrap();
+    graph
+        .update_node_properties(
+            start,
+            PropertyMap::new()
+                .with("visibility", "public")
+                .with("return_type", "Result<(), Error>"),
+        )
+        .unwrap();
+    let create = helpers::add_method(&mut graph, server, "new", 12, 18).unwrap();
+    graph
+        .update_node_properties(create, PropertyMap::new().with("is_static", true))
+        .unwrap();
+    graph
+        .add_edge(server, handler, EdgeType::Implements, PropertyMap::new())
+        .unwrap();
+
+    let tls = helpers::add_class(&mut graph, tls_rs, "TlsServer", 1, 40).unwrap();
+    graph
+        .add_edge(tls, server, EdgeType::Extends, PropertyMap::new())
+        .unwrap();
+
+    let main = helpers::add_function(&mut graph, main_rs, "main", 1, 10).unwrap();
+    helpers::add_call(&mut graph, main, start, 3).unwrap();
+    helpers::add_call(&mut graph, main, create, 2).unwrap();
+    helpers::add_import(&mut graph, main_rs, server_rs, vec!["Server"]).unwrap();
+
+    Fixture {
+        graph,
+        server,
+        handler,
+    }
+}
+
+#[test]
+fn test_mermaid_class_diagram() {
+    let Fixture {
+        graph,
+        server,
+        handler,
+    } = create_graph();
+
+    let diagram = graph
+        .export_mermaid_class_diagram(&DiagramOptions::default())
+        .unwrap();
+
+    assert!(diagram.starts_with("classDiagram\n"));
+    assert!(diagram.contains("namespace src_net_server_rs {"));
+    assert!(diagram.contains(&format!("class n{server}[\"Server\"] {{")));
+    assert!(diagram.contains("-u16 port"));
+    assert!(diagram.contains("+start() Result~(), Error~"));
+    assert!(diagram.contains("new()$"));
+    assert!(diagram.contains(&format!("<<interface>> n{handler}")));
+    assert!(diagram.contains(&format!("n{handler} <|.. n{server}")));
+    assert!(diagram.contains(&format!("n{server} <|-- n")));
+    // Functions outside classes are not drawn
+    assert!(!diagram.contains("main"));
+}
+
+#[test]
+fn test_plantuml_class_diagram() {
+    let Fixture {
+        graph,
+        server,
+        handler,
+    } = create_graph();
+
+    let diagram = graph
+        .export_plantuml_class_diagram(&DiagramOptions::default())
+        .unwrap();
+
+    assert!(diagram.starts_with("@startuml\n"));
+    assert!(diagram.trim_end().ends_with("@enduml"));
+    assert!(diagram.contains("package \"src/net/server.rs\" {"));
+    assert!(diagram.contains(&format!("interface \"Handler\" as n{handler} {{")));
+    assert!(diagram.contains("-port : u16"));
+    assert!(diagram.contains("+start() : Result<(), Error>"));
+    assert!(diagram.contains("{static} new()"));
+    assert!(diagram.contains(&format!("n{handler} <|.. n{server}")));
+    assert!(diagram.contains(&format!("n{server} <|-- n")));
+}
+
+#[test]
+fn test_class_diagram_collapses_members() {
+    let Fixture { graph, .. } = create_graph();
+    let options = DiagramOptions {
+        max_members: 1,
+        ..DiagramOptions::default()
+    };
+
+    let mermaid = graph.export_mermaid_class_diagram(&options).unwrap();
+    assert!(mermaid.contains("...2 more"));
+    // Members are listed in source order, unpositioned ones last
+    assert!(mermaid.contains("new()$"));
+    assert!(!mermaid.contains("start()"));
+
+    let plantuml = graph.export_plantuml_class_diagram(&options).unwrap();
+    assert!(plantuml.contains(".. 2 more .."));
+}
+
+#[test]
+fn test_class_diagram_trims_to_best_connected() {
+    let Fixture { graph, server, .. } = create_graph();
+    let options = DiagramOptions {
+        max_nodes: 1,
+        group_by_module: false,
+        ..DiagramOptions::default()
+    };
+
+    let diagram = graph.export_plantuml_class_diagram(&options).unwrap();
+
+    assert!(diagram.contains("' 2 classes omitted"));
+    assert!(diagram.contains(&format!("as n{server}")));
+    assert!(!diagram.contains("package"));
+    assert!(!diagram.contains("<|"));
+}
+
+#[test]
+fn test_mermaid_flowchart_groups_by_file() {
+    let Fixture { graph, server, .. } = create_graph();
+    let options = DiagramOptions {
+        filter: ExportFilter::new().edge_types(&[EdgeType::Calls, EdgeType::Contains]),
+        ..DiagramOptions::default()
+    };
+
+    let diagram = graph.export_mermaid_flowchart(&options).unwrap();
+
+    assert!(diagram.starts_with("flowchart LR\n"));
+    assert!(diagram.contains("subgraph n0[\"src/net/server.rs\"]"));
+    assert!(diagram.contains(&format!("n{server}[[\"Server\"]]")));
+    assert!(diagram.contains("-->|\"Calls\"|"));
+    // Containment by files is shown by the subgraphs, not as edges
+    assert!(!diagram.contains(&format!("n0 -->|\"Contains\"| n{server}")));
+    assert!(diagram.contains(&format!("n{server} -->|\"Contains\"|")));
+}
+
+#[test]
+fn test_mermaid_flowchart_collapses_to_files() {
+    let Fixture { graph, .. } = create_graph();
+    let options = DiagramOptions {
+        max_nodes: 5,
+        ..DiagramOptions::default()
+    };
+
+    let diagram = graph.export_mermaid_flowchart(&options).unwrap();
+
+    assert!(diagram.contains("%% Collapsed to one node per file"));
+    assert_eq!(diagram.matches("[/\"").count(), 3);
+    assert!(diagram.contains("-->|\"Calls (2)\"|"));
+    assert!(diagram.contains("-->|\"Imports\"|"));
+    assert!(!diagram.contains("Contains"));
+}
+
+#[test]
+fn test_plantuml_component_diagram() {
+    let Fixture { graph, .. } = create_graph();
+
+    let diagram = graph
+        .export_plantuml_component_diagram(&DiagramOptions::default())
+        .unwrap();
+
+    assert!(diagram.contains("package \"src/net\" {"));
+    assert!(diagram.contains("component \"src/net/server.rs\" as n0"));
+    assert!(diagram.contains("component \"src/main.rs\" as n2"));
+    assert!(diagram.contains("n2 --> n0 : Calls (2)"));
+    assert!(diagram.contains("n2 --> n0 : Imports"));
+    assert!(diagram.contains("n1 --> n0 : Extends"));
+}
@@ -0,0 +1,226 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for EdgeQueryBuilder
+//!
+//! Tests cover:
+//! - Edge type and property filters
+//! - Endpoint restrictions and endpoint node predicates
+//! - Handing edge endpoints to node queries
+
+use codegraph::query::Predicate;
+use codegraph::{helpers, CodeGraph, EdgeId, EdgeType, NodeId, NodeType, PropertyMap};
+
+// app imports util (wildcard) and models (symbols); util imports models
+fn create_graph() -> (CodeGraph, Vec<NodeId>, Vec<EdgeId>) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let app = helpers::add_file(&mut graph, "src/app.py", "python").unwrap();
+    let util = helpers::add_file(&mut graph, "src/util.py", "python").unwrap();
+    let models = helpers::add_file(&mut graph, "src/models.py", "python").unwrap();
+    let run = helpers::add_function(&mut graph, app, "run", 1, 10).unwrap();
+    let save = helpers::add_function(&mut graph, models, "save", 1, 10).unwrap();
+
+    let wildcard = graph
+        .add_edge(
+            app,
+            util,
+            EdgeType::Imports,
+            PropertyMap::new().with("is_wildcard", true),
+        )
+        .unwrap();
+    let symbols = graph
+        .add_edge(
+            app,
+            models,
+            EdgeType::Imports,
+            PropertyMap::new()
+                .with("is_wildcard", false)
+                .with("symbols", vec!["User".to_string(), "Order".to_string()]),
+        )
+        .unwrap();
+    let util_models = graph
+        .add_edge(
+            util,
+            models,
+            EdgeType::Imports,
+            PropertyMap::new().with("is_wildcard", true),
+        )
+        .unwrap();
+    let indirect = graph
+        .add_edge(
+            run,
+            save,
+            EdgeType::Calls,
+            PropertyMap::new()
+                .with("is_direct", false)
+                .with("call_site_line", 7i64),
+        )
+        .unwrap();
+
+    (
+        graph,
+        vec![app, util, models, run, save],
+        vec![wildcard, symbols, util_models, indirect],
+    )
+}
+
+fn sorted<T: Ord>(mut items: Vec<T>) -> Vec<T> {
+    items.sort_unstable();
+    items
+}
+
+#[test]
+fn test_edge_type_and_property_filters() {
+    let (graph, _, edges) = create_graph();
+
+    let indirect = graph
+        .edge_query()
+        .edge_type(EdgeType::Calls)
+        .property("is_direct", false)
+        .execute()
+        .unwrap();
+    assert_eq!(indirect, vec![edges[3]]);
+
+    let wildcard = graph
+        .edge_query()
+        .edge_type(EdgeType::Imports)
+        .property("is_wildcard", true)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(wildcard), vec![edges[0], edges[2]]);
+
+    let user = graph
+        .edge_query()
+        .list_contains("symbols", "User")
+        .execute()
+        .unwrap();
+    assert_eq!(user, vec![edges[1]]);
+
+    assert_eq!(
+        graph
+            .edge_query()
+            .edge_types(&[EdgeType::Calls, EdgeType::Contains])
+            .count()
+            .unwrap(),
+        3
+    );
+    assert_eq!(
+        graph
+            .edge_query()
+            .between("call_site_line", 5, 10)
+            .execute()
+            .unwrap(),
+        vec![edges[3]]
+    );
+    assert_eq!(
+        graph
+            .edge_query()
+            .edge_type(EdgeType::Imports)
+            .not(Predicate::property("is_wildcard", true))
+            .execute()
+            .unwrap(),
+        vec![edges[1]]
+    );
+}
+
+#[test]
+fn test_endpoint_filters() {
+    let (graph, nodes, edges) = create_graph();
+    let (app, models) = (nodes[0], nodes[2]);
+
+    let from_app = graph
+        .edge_query()
+        .from(app)
+        .edge_type(EdgeType::Imports)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(from_app), vec![edges[0], edges[1]]);
+
+    let into_models = graph
+        .edge_query()
+        .to(models)
+        .edge_type(EdgeType::Imports)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(into_models), vec![edges[1], edges[2]]);
+
+    assert_eq!(
+        graph.edge_query().from(app).to(models).execute().unwrap(),
+        vec![edges[1]]
+    );
+
+    let from_functions = graph
+        .edge_query()
+        .source(Predicate::node_type(NodeType::Function))
+        .target(Predicate::property("name", "save"))
+        .edges()
+        .unwrap();
+    assert_eq!(from_functions.len(), 1);
+    assert_eq!(from_functions[0].edge_type, EdgeType::Calls);
+}
+
+#[test]
+fn test_endpoints_continue_as_node_query() {
+    let (graph, nodes, _) = create_graph();
+    let (app, util, models) = (nodes[0], nodes[1], nodes[2]);
+
+    let targets = graph
+        .edge_query()
+        .edge_type(EdgeType::Imports)
+        .property("is_wildcard", true)
+        .targets()
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(targets), vec![util, models]);
+
+    let importers = graph
+        .edge_query()
+        .edge_type(EdgeType::Imports)
+        .sources()
+        .dedup()
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(importers), vec![app, util]);
+
+    // Node filters and traversal steps compose after the hand-off
+    let functions = graph
+        .edge_query()
+        .edge_type(EdgeType::Imports)
+        .property("is_wildcard", false)
+        .targets()
+        .out(EdgeType::Contains)
+        .node_type(NodeType::Function)
+        .execute()
+        .unwrap();
+    assert_eq!(functions, vec![nodes[4]]);
+}
+
+#[test]
+fn test_limit_exists_and_errors() {
+    let (graph, _, _) = create_graph();
+
+    assert_eq!(graph.edge_query().limit(2).execute().unwrap().len(), 2);
+    assert!(graph
+        .edge_query()
+        .edge_type(EdgeType::Calls)
+        .exists()
+        .unwrap());
+    assert!(!graph
+        .edge_query()
+        .edge_type(EdgeType::Extends)
+        .exists()
+        .unwrap());
+
+    assert!(graph
+        .edge_query()
+        .property_matches("symbols", "(")
+        .execute()
+        .is_err());
+    // Errors carry over to the node query
+    assert!(graph
+        .edge_query()
+        .property_matches("alias", "[")
+        .targets()
+        .execute()
+        .is_err());
+}
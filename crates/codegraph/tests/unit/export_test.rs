@@ -12,8 +12,20 @@
 //! - T111: export_csv_edges()
 //! - T112: export_triples() RDF format
 //! - T113: Size limit warnings (>10K nodes)
-
-use codegraph::{helpers, CodeGraph, EdgeType, NodeType};
+//! - GraphML and GEXF typed attribute declarations and type filters
+//! - Streaming exporters writing to `io::Write`, and graphs with deleted nodes
+//! - RDF (N-Triples, Turtle) with the codegraph ontology
+//! - Neo4j import CSVs and Cypher MERGE scripts
+//! - LSIF code intelligence dumps
+//! - Self-contained HTML report
+//! - DOT clustering, type filters, node budget, edge aggregation and file-level
+//!   edge lifting
+
+use codegraph::export::{
+    export_neo4j_nodes_to, CypherDialect, CypherOptions, EdgePropertyStyle, ExportFilter,
+    HtmlOptions, RdfFormat, RdfOptions,
+};
+use codegraph::{helpers, CodeGraph, EdgeType, NodeType, PropertyMap};
 use std::fs;
 use tempfile::TempDir;
 
@@ -111,6 +123,7 @@ fn test_export_dot_styled() {
         node_shapes,
         rankdir: "TB".to_string(),
         show_properties: vec!["visibility".to_string()],
+        ..DotOptions::default()
     };
 
     let dot = graph.export_dot_styled(options).unwrap();
@@ -328,3 +341,651 @@ fn test_export_csv_convenience() {
     assert!(!nodes_content.is_empty());
     assert!(!edges_content.is_empty());
 }
+
+// GraphML declares typed keys and exports every node and edge
+#[test]
+fn test_export_graphml() {
+    let graph = create_test_graph().unwrap();
+
+    let graphml = graph.export_graphml().unwrap();
+
+    assert!(graphml.starts_with("<?xml"));
+    assert!(graphml.contains("<graph id=\"codegraph\" edgedefault=\"directed\">"));
+    assert!(graphml.contains("attr.name=\"line_start\" attr.type=\"long\""));
+    assert!(graphml.contains("attr.name=\"is_async\" attr.type=\"boolean\""));
+    assert!(graphml.contains("attr.name=\"name\" attr.type=\"string\""));
+    assert!(graphml.contains("<data key=\"node_type\">Function</data>"));
+    assert!(graphml.contains("<data key=\"edge_type\">Calls</data>"));
+    assert_eq!(graphml.matches("<node ").count(), graph.node_count());
+    assert_eq!(graphml.matches("<edge ").count(), graph.edge_count());
+    assert!(graphml.trim_end().ends_with("</graphml>"));
+}
+
+// GEXF declares typed attributes, including lists
+#[test]
+fn test_export_gexf() {
+    let graph = create_test_graph().unwrap();
+
+    let gexf = graph.export_gexf().unwrap();
+
+    assert!(gexf.contains("<gexf xmlns=\"http://www.gexf.net/1.2draft\" version=\"1.2\">"));
+    assert!(gexf.contains("<attributes class=\"node\">"));
+    assert!(gexf.contains("title=\"line_start\" type=\"long\""));
+    // Import edges carry the imported symbols
+    assert!(gexf.contains("title=\"symbols\" type=\"liststring\""));
+    assert!(gexf.contains("label=\"main\""));
+    assert!(gexf.contains("label=\"Calls\""));
+    assert_eq!(gexf.matches("<node ").count(), graph.node_count());
+    assert_eq!(gexf.matches("<edge ").count(), graph.edge_count());
+}
+
+// Attribute types follow the property values present, and values are escaped
+#[test]
+fn test_export_xml_attribute_types() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new()
+                .with("name", "a<b>")
+                .with("complexity", 3i64),
+        )
+        .unwrap();
+    graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new()
+                .with("name", "c&d")
+                .with("complexity", 4.5f64),
+        )
+        .unwrap();
+
+    let graphml = graph.export_graphml().unwrap();
+    assert!(graphml.contains("attr.name=\"complexity\" attr.type=\"double\""));
+    assert!(graphml.contains(">a&lt;b&gt;</data>"));
+
+    let gexf = graph.export_gexf().unwrap();
+    assert!(gexf.contains("title=\"complexity\" type=\"double\""));
+    assert!(gexf.contains("label=\"c&amp;d\""));
+}
+
+// Properties named like a built-in attribute are declared under another name
+#[test]
+fn test_export_xml_reserved_property_names() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new()
+                .with("name", "run")
+                .with("label", "entry point")
+                .with("node_type", "async"),
+        )
+        .unwrap();
+
+    let graphml = graph.export_graphml().unwrap();
+    assert_eq!(graphml.matches("attr.name=\"label\"").count(), 1);
+    assert_eq!(graphml.matches("attr.name=\"node_type\"").count(), 1);
+    assert!(graphml.contains("attr.name=\"prop_label\" attr.type=\"string\""));
+    assert!(graphml.contains("attr.name=\"prop_node_type\" attr.type=\"string\""));
+    assert!(graphml.contains("<data key=\"label\">run</data>"));
+    assert!(graphml.contains(">entry point</data>"));
+
+    let gexf = graph.export_gexf().unwrap();
+    assert_eq!(gexf.matches("title=\"node_type\"").count(), 1);
+    assert!(gexf.contains("title=\"prop_node_type\" type=\"string\""));
+    assert!(gexf.contains("title=\"label\" type=\"string\""));
+}
+
+// Type filters restrict nodes and edges, dropping edges to filtered nodes
+#[test]
+fn test_export_xml_filtered() {
+    let graph = create_test_graph().unwrap();
+    let filter = ExportFilter::new()
+        .node_types(&[NodeType::Function])
+        .edge_types(&[EdgeType::Calls, EdgeType::Contains]);
+
+    let graphml = graph.export_graphml_filtered(&filter).unwrap();
+    assert_eq!(graphml.matches("<node ").count(), 2);
+    assert_eq!(graphml.matches("<edge ").count(), 1);
+    assert!(!graphml.contains("CodeFile"));
+
+    let gexf = graph
+        .export_gexf_filtered(&ExportFilter::new().edge_types(&[EdgeType::Imports]))
+        .unwrap();
+    assert_eq!(gexf.matches("<node ").count(), graph.node_count());
+    assert_eq!(gexf.matches("<edge ").count(), 1);
+    assert!(!gexf.contains("title=\"line\""));
+}
+
+// Streaming exporters write exactly what the String exporters return
+#[test]
+fn test_streaming_exports_match_strings() {
+    let graph = create_test_graph().unwrap();
+    let stream = |export: &dyn Fn(&mut Vec<u8>) -> codegraph::Result<()>| {
+        let mut buffer = Vec::new();
+        export(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+
+    assert_eq!(
+        stream(&|w| graph.export_dot_to(w)),
+        graph.export_dot().unwrap()
+    );
+    assert_eq!(
+        stream(&|w| graph.export_json_to(w)),
+        graph.export_json().unwrap()
+    );
+    assert_eq!(
+        stream(&|w| graph.export_triples_to(w)),
+        graph.export_triples().unwrap()
+    );
+    assert_eq!(
+        stream(&|w| graph.export_graphml_to(w)),
+        graph.export_graphml().unwrap()
+    );
+    assert_eq!(
+        stream(&|w| graph.export_gexf_to(w)),
+        graph.export_gexf().unwrap()
+    );
+
+    let temp_dir = TempDir::new().unwrap();
+    let nodes_path = temp_dir.path().join("nodes.csv");
+    graph.export_csv_nodes(&nodes_path).unwrap();
+    assert_eq!(
+        stream(&|w| graph.export_csv_nodes_to(w)),
+        fs::read_to_string(&nodes_path).unwrap()
+    );
+}
+
+// Exporters walk the nodes that exist, not 0..node_count()
+#[test]
+fn test_exports_after_deleting_nodes() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    let removed = helpers::add_function(&mut graph, file, "removed", 1, 2).unwrap();
+    let first = helpers::add_function(&mut graph, file, "first", 3, 4).unwrap();
+    let last = helpers::add_function(&mut graph, file, "last", 5, 6).unwrap();
+    helpers::add_call(&mut graph, first, last, 3).unwrap();
+    graph.delete_node(removed).unwrap();
+
+    let triples = graph.export_triples().unwrap();
+    assert!(triples.contains(&format!("<node:{last}> <rdf:type>")));
+    assert!(triples.contains("<edge:Calls>"));
+    assert!(!triples.contains(&format!("<node:{removed}>")));
+
+    let dot = graph.export_dot().unwrap();
+    assert!(dot.contains(&format!("n{first} -> n{last}")));
+
+    let mut csv = Vec::new();
+    graph.export_csv_nodes_to(&mut csv).unwrap();
+    // Header plus file, first and last
+    assert_eq!(String::from_utf8(csv).unwrap().lines().count(), 4);
+}
+
+// Write failures surface as storage errors
+#[test]
+fn test_streaming_export_write_error() {
+    struct Failing;
+    impl std::io::Write for Failing {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk full"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let graph = create_test_graph().unwrap();
+    let result = graph.export_json_to(Failing);
+    assert!(matches!(result, Err(codegraph::GraphError::Storage { .. })));
+}
+
+fn rdf_graph() -> (CodeGraph, codegraph::NodeId, codegraph::NodeId) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    let caller = helpers::add_function(&mut graph, file, "caller", 1, 5).unwrap();
+    let callee = helpers::add_function(&mut graph, file, "callee", 7, 9).unwrap();
+    helpers::add_call(&mut graph, caller, callee, 3).unwrap();
+    (graph, caller, callee)
+}
+
+// N-Triples uses absolute IRIs and typed literals throughout
+#[test]
+fn test_export_rdf_ntriples() {
+    let (graph, caller, callee) = rdf_graph();
+    let options = RdfOptions {
+        base_iri: "https://example.org/repo/".to_string(),
+        ..Default::default()
+    };
+    let rdf = graph.export_rdf(&options).unwrap();
+
+    for line in rdf.lines() {
+        assert!(line.starts_with('<') && line.ends_with(" ."), "{line}");
+        assert!(
+            !line.contains("<node:") && !line.contains("<prop:"),
+            "{line}"
+        );
+    }
+
+    let cg = codegraph::export::rdf::VOCABULARY;
+    let node = format!("<https://example.org/repo/node/{caller}>");
+    assert!(rdf.contains(&format!(
+        "{node} <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <{cg}Function> ."
+    )));
+    assert!(rdf.contains(&format!(
+        "{node} <{cg}lineStart> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> ."
+    )));
+    assert!(rdf.contains(&format!("{node} <{cg}name> \"caller\" .")));
+    assert!(rdf.contains(&format!(
+        "{node} <{cg}calls> <https://example.org/repo/node/{callee}> ."
+    )));
+}
+
+// Turtle abbreviates with prefixes and reifies edges with properties
+#[test]
+fn test_export_rdf_turtle_reification() {
+    let (graph, caller, callee) = rdf_graph();
+    let options = RdfOptions {
+        format: RdfFormat::Turtle,
+        ..Default::default()
+    };
+    let rdf = graph.export_rdf(&options).unwrap();
+
+    assert!(rdf.starts_with("@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> ."));
+    assert!(rdf.contains("@prefix n: <urn:codegraph:node/> ."));
+    assert!(rdf.contains(&format!("n:{caller} a cg:Function .")));
+    assert!(rdf.contains(&format!("n:{caller} cg:calls n:{callee} .")));
+
+    let statement = rdf
+        .lines()
+        .find(|line| line.ends_with(" a rdf:Statement ."))
+        .unwrap()
+        .split(' ')
+        .next()
+        .unwrap();
+    assert!(rdf.contains(&format!("{statement} rdf:subject n:{caller} .")));
+    assert!(rdf.contains(&format!("{statement} rdf:predicate cg:calls .")));
+    assert!(rdf.contains(&format!("{statement} cg:line \"3\"^^xsd:integer .")));
+}
+
+// RDF-star annotates the quoted edge triple instead
+#[test]
+fn test_export_rdf_star_edge_properties() {
+    let (graph, caller, callee) = rdf_graph();
+    let options = RdfOptions {
+        format: RdfFormat::Turtle,
+        edge_properties: EdgePropertyStyle::RdfStar,
+        ..Default::default()
+    };
+    let rdf = graph.export_rdf(&options).unwrap();
+
+    assert!(rdf.contains(&format!(
+        "<< n:{caller} cg:calls n:{callee} >> cg:line \"3\"^^xsd:integer ."
+    )));
+    assert!(!rdf.contains("rdf:Statement"));
+}
+
+#[test]
+fn test_export_rdf_rejects_relative_base() {
+    let (graph, _, _) = rdf_graph();
+    let options = RdfOptions {
+        base_iri: "graph/".to_string(),
+        ..Default::default()
+    };
+    assert!(graph.export_rdf(&options).is_err());
+}
+
+// The ontology declares every node and edge type used by exports
+#[test]
+fn test_export_rdf_ontology() {
+    let ontology = codegraph::export::export_rdf_ontology(RdfFormat::Turtle).unwrap();
+
+    assert!(ontology.contains("a owl:Ontology ."));
+    for node_type in NodeType::ALL {
+        assert!(ontology.contains(&format!("cg:{node_type} rdfs:subClassOf cg:Node .")));
+    }
+    for edge_type in EdgeType::ALL {
+        let name = edge_type.to_string();
+        let property = format!("cg:{}{}", name[..1].to_lowercase(), &name[1..]);
+        assert!(ontology.contains(&format!("{property} a owl:ObjectProperty .")));
+    }
+    assert!(ontology.contains("cg:lineStart rdfs:range xsd:integer ."));
+    assert!(ontology.contains("cg:isAsync rdfs:range xsd:boolean ."));
+}
+
+// neo4j-admin CSVs carry typed headers, labels and relationship types
+#[test]
+fn test_export_neo4j_csv() {
+    let (mut graph, caller, callee) = rdf_graph();
+    graph
+        .update_node_properties(caller, PropertyMap::new().with("complexity", 4i64))
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let nodes_path = temp_dir.path().join("nodes.csv");
+    let relationships_path = temp_dir.path().join("relationships.csv");
+    graph
+        .export_neo4j_csv(&nodes_path, &relationships_path)
+        .unwrap();
+
+    let nodes = fs::read_to_string(&nodes_path).unwrap();
+    let mut lines = nodes.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "id:ID,:LABEL,complexity:int,language:string,line_end:int,line_start:int,name:string,path:string"
+    );
+    assert!(nodes.contains(&format!("\n{caller},CodeNode;Function,4,,5,1,caller,\n")));
+    assert_eq!(lines.count(), 3);
+
+    let relationships = fs::read_to_string(&relationships_path).unwrap();
+    assert!(relationships.starts_with(":START_ID,:END_ID,:TYPE,id:long,"));
+    assert!(relationships.contains(&format!("{caller},{callee},CALLS,")));
+    assert!(relationships.contains(",CONTAINS,"));
+}
+
+// Multi-line values are quoted; `;` inside string arrays cannot be imported
+#[test]
+fn test_export_neo4j_csv_multiline_and_array_delimiter() {
+    let (mut graph, caller, _) = rdf_graph();
+    graph
+        .update_node_properties(
+            caller,
+            PropertyMap::new().with("doc", "Calls the callee.\nTwice, \"maybe\"."),
+        )
+        .unwrap();
+    let mut nodes = Vec::new();
+    export_neo4j_nodes_to(&graph, &mut nodes).unwrap();
+    let nodes = String::from_utf8(nodes).unwrap();
+    assert!(nodes.contains(",\"Calls the callee.\nTwice, \"\"maybe\"\".\","));
+
+    graph
+        .update_node_properties(
+            caller,
+            PropertyMap::new().with("symbols", vec!["a;b".to_string()]),
+        )
+        .unwrap();
+    let err = export_neo4j_nodes_to(&graph, Vec::new()).unwrap_err();
+    assert!(err.to_string().contains("'a;b'"));
+}
+
+// Cypher scripts MERGE on stable IDs so they can be re-run
+#[test]
+fn test_export_cypher_merge_script() {
+    let (graph, caller, callee) = rdf_graph();
+    let cypher = graph.export_cypher(&CypherOptions::default()).unwrap();
+
+    let mut lines = cypher.lines();
+    assert!(lines
+        .next()
+        .unwrap()
+        .starts_with("CREATE CONSTRAINT code_node_id IF NOT EXISTS"));
+    assert!(cypher.contains(&format!(
+        "MERGE (n:CodeNode {{id: {caller}}}) SET n = {{id: {caller}, line_end: 5, line_start: 1, name: 'caller'}}, n:Function;"
+    )));
+    assert!(cypher.contains(&format!(
+        "MATCH (a:CodeNode {{id: {caller}}}), (b:CodeNode {{id: {callee}}}) MERGE (a)-[r:CALLS {{id: "
+    )));
+    assert!(cypher.lines().all(|line| line.ends_with(';')));
+    assert!(!cypher.contains("CREATE (") && !cypher.contains("CREATE ()"));
+
+    let memgraph = graph
+        .export_cypher(&CypherOptions {
+            dialect: CypherDialect::Memgraph,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(memgraph.starts_with("CREATE CONSTRAINT ON (n:CodeNode) ASSERT n.id IS UNIQUE;"));
+}
+
+// LSIF dumps are well-formed and link definitions, references and hovers
+#[test]
+fn test_export_lsif() {
+    let (mut graph, caller, callee) = rdf_graph();
+    let file = helpers::containing_file(&graph, caller).unwrap().unwrap();
+    let helper = helpers::add_function(&mut graph, file, "helper", 11, 12).unwrap();
+    // A repeated call, a second callee on the same line and a call without a
+    // valid line
+    helpers::add_call(&mut graph, caller, callee, 3).unwrap();
+    helpers::add_call(&mut graph, caller, helper, 3).unwrap();
+    helpers::add_call(&mut graph, caller, helper, 0).unwrap();
+    // An import of an external module, which has no location of its own
+    let serde = graph
+        .add_node(NodeType::Module, PropertyMap::new().with("name", "serde"))
+        .unwrap();
+    graph
+        .add_edge(
+            file,
+            serde,
+            EdgeType::Imports,
+            PropertyMap::new().with("line", 1i64),
+        )
+        .unwrap();
+    graph
+        .update_node_properties(
+            callee,
+            PropertyMap::new()
+                .with("signature", "fn callee()")
+                .with("doc", "Does the work."),
+        )
+        .unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("dump.lsif");
+    graph
+        .export_lsif(std::path::Path::new("/work/repo"), &path)
+        .unwrap();
+
+    let elements: Vec<serde_json::Value> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(elements[0]["label"], "metaData");
+    assert_eq!(elements[0]["projectRoot"], "file:///work/repo/");
+
+    // IDs are sequential and edges only point back at emitted vertices
+    for (i, element) in elements.iter().enumerate() {
+        assert_eq!(element["id"], i as u64 + 1);
+        let targets = element["inVs"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .chain([element["outV"].clone(), element["inV"].clone()]);
+        for target in targets.filter(|v| !v.is_null()) {
+            assert!(target.as_u64().unwrap() <= i as u64, "{element}");
+        }
+    }
+
+    let find = |label: &'static str| elements.iter().filter(move |e| e["label"] == label);
+    let document = find("document").next().unwrap();
+    assert_eq!(document["uri"], "file:///work/repo/src/lib.rs");
+    assert_eq!(document["languageId"], "rust");
+
+    // Definitions cover the name
+    let definition = find("range").find(|r| r["start"]["line"] == 6).unwrap();
+    assert_eq!(definition["start"]["character"], 0);
+    assert_eq!(
+        definition["end"],
+        serde_json::json!({"line": 6, "character": 6})
+    );
+
+    // The calls on line 3 share one reference range on zero-based line 2, listed
+    // in the references of both callees
+    let calls: Vec<_> = find("range").filter(|r| r["start"]["line"] == 2).collect();
+    assert_eq!(calls.len(), 1);
+    let call = calls[0];
+    assert_eq!(call["start"]["character"], 0);
+    let referencing = find("item").filter(|item| {
+        item["property"] == "references" && item["inVs"].as_array().unwrap().contains(&call["id"])
+    });
+    assert_eq!(referencing.count(), 2);
+    assert!(find("range").all(|r| r["start"]["line"].as_i64().unwrap() >= 0));
+
+    // The import is a reference on line 1 to a symbol without a definition
+    let import = find("range")
+        .find(|r| r["start"]["line"] == 0 && r["end"]["line"] == 1)
+        .unwrap();
+    assert!(find("item").any(|item| {
+        item["property"] == "references" && item["inVs"].as_array().unwrap().contains(&import["id"])
+    }));
+
+    let hover = find("hoverResult").find(|h| {
+        h["result"]["contents"]["value"]
+            .as_str()
+            .unwrap()
+            .contains("Does the work.")
+    });
+    assert!(hover.unwrap()["result"]["contents"]["value"]
+        .as_str()
+        .unwrap()
+        .starts_with("```\nfn callee()\n```"));
+}
+
+// The HTML report embeds its data and loads nothing from the network
+#[test]
+fn test_export_html_report() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let lib = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    let util = helpers::add_file(&mut graph, "src/util.rs", "rust").unwrap();
+    let simple = helpers::add_function(&mut graph, lib, "simple", 1, 3).unwrap();
+    let tangled = helpers::add_function(&mut graph, lib, "</script>", 5, 40).unwrap();
+    let helper = helpers::add_function(&mut graph, util, "helper", 1, 9).unwrap();
+    for (id, complexity) in [(simple, 1i64), (tangled, 12), (helper, 4)] {
+        graph
+            .update_node_properties(id, PropertyMap::new().with("complexity", complexity))
+            .unwrap();
+    }
+    helpers::add_call(&mut graph, tangled, helper, 7).unwrap();
+
+    let html = graph
+        .export_html(&HtmlOptions {
+            title: "Report <draft>".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>Report &lt;draft&gt;</title>"));
+    assert!(!html.contains("src=\"") && !html.contains("<link"));
+
+    let start = html.find("type=\"application/json\">").unwrap() + 24;
+    let end = start + html[start..].find("</script>").unwrap();
+    let data: serde_json::Value = serde_json::from_str(&html[start..end]).unwrap();
+
+    assert_eq!(data["symbols"].as_array().unwrap().len(), 3);
+    let hotspots: Vec<_> = data["hotspots"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|h| h["complexity"].as_i64().unwrap())
+        .collect();
+    assert_eq!(hotspots, vec![12, 4, 1]);
+    assert_eq!(data["hotspots"][0]["name"], "</script>");
+
+    let lib_summary = &data["files"][0];
+    assert_eq!(lib_summary["path"], "src/lib.rs");
+    assert_eq!(lib_summary["functions"], 2);
+    assert_eq!(lib_summary["complexity"], 13);
+    assert_eq!(lib_summary["dependsOn"], 1);
+    assert_eq!(data["files"][1]["dependedOnBy"], 1);
+    assert_eq!(data["graph"]["edges"].as_array().unwrap().len(), 1);
+}
+
+// Files and their contents are drawn as clusters without Contains edges
+#[test]
+fn test_export_dot_clusters_by_file() {
+    let (graph, caller, callee) = rdf_graph();
+    let file = graph
+        .query()
+        .node_type(NodeType::CodeFile)
+        .execute()
+        .unwrap()[0];
+    let options = codegraph::export::DotOptions {
+        cluster_by_file: true,
+        ..Default::default()
+    };
+    let dot = graph.export_dot_styled(options).unwrap();
+
+    let cluster_start = dot.find(&format!("subgraph cluster_n{file} {{")).unwrap();
+    let cluster = &dot[cluster_start..cluster_start + dot[cluster_start..].find("    }").unwrap()];
+    assert!(cluster.contains("label=\"src/lib.rs\";"));
+    assert!(cluster.contains(&format!("n{caller} [label=\"caller\"")));
+    assert!(cluster.contains(&format!("n{callee} [label=\"callee\"")));
+    assert!(!dot.contains("Contains"));
+    assert!(dot.contains(&format!("n{caller} -> n{callee} [label=\"Calls\"];")));
+}
+
+#[test]
+fn test_export_dot_type_filter() {
+    let graph = create_test_graph().unwrap();
+    let options = codegraph::export::DotOptions {
+        filter: ExportFilter::new()
+            .node_types(&[NodeType::Function])
+            .edge_types(&[EdgeType::Calls]),
+        ..Default::default()
+    };
+    let dot = graph.export_dot_styled(options).unwrap();
+
+    assert!(dot.contains("Calls"));
+    assert!(!dot.contains("shape=folder"));
+    assert!(!dot.contains("Contains") && !dot.contains("Imports"));
+}
+
+// Over budget, the least connected nodes collapse into their files and
+// their edges are merged with counts
+#[test]
+fn test_export_dot_budget_collapses_and_aggregates() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let app = helpers::add_file(&mut graph, "src/app.rs", "rust").unwrap();
+    let util = helpers::add_file(&mut graph, "src/util.rs", "rust").unwrap();
+    let helper = helpers::add_function(&mut graph, util, "helper", 1, 3).unwrap();
+    for i in 0..5 {
+        let line = i * 10 + 1;
+        let caller =
+            helpers::add_function(&mut graph, app, &format!("caller{i}"), line, line + 5).unwrap();
+        helpers::add_call(&mut graph, caller, helper, line + 1).unwrap();
+    }
+
+    let dot = graph
+        .export_dot_styled(codegraph::export::DotOptions::compact(3))
+        .unwrap();
+
+    assert!(dot.contains("// 5 nodes collapsed into their files"));
+    assert!(dot.contains("(+5 collapsed)"));
+    assert!(!dot.contains("caller0"));
+    assert!(dot.contains(&format!("n{app} -> n{helper} [label=\"Calls (5)\"];")));
+    assert!(dot.contains(&format!("subgraph cluster_n{util} {{")));
+    assert_eq!(
+        dot.matches(" [label=\"").count() - dot.matches(" -> ").count(),
+        3
+    );
+}
+
+// The file-level view lifts every edge to the files of its endpoints and counts
+// them, without a node budget
+#[test]
+fn test_export_dot_lifts_edges_to_files() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let app = helpers::add_file(&mut graph, "src/app.rs", "rust").unwrap();
+    let util = helpers::add_file(&mut graph, "src/util.rs", "rust").unwrap();
+    let run = helpers::add_function(&mut graph, app, "run", 1, 5).unwrap();
+    let main = helpers::add_function(&mut graph, app, "main", 6, 9).unwrap();
+    let parse = helpers::add_function(&mut graph, util, "parse", 1, 3).unwrap();
+    let format = helpers::add_function(&mut graph, util, "format", 4, 6).unwrap();
+    helpers::add_call(&mut graph, run, parse, 2).unwrap();
+    helpers::add_call(&mut graph, main, parse, 7).unwrap();
+    helpers::add_call(&mut graph, main, format, 8).unwrap();
+    helpers::add_call(&mut graph, main, run, 9).unwrap();
+    helpers::add_import(&mut graph, app, util, vec!["parse"]).unwrap();
+
+    let dot = graph
+        .export_dot_styled(codegraph::export::DotOptions::file_level())
+        .unwrap();
+
+    assert!(dot.contains("// 4 nodes collapsed into their files"));
+    assert!(dot.contains(&format!("n{app} [label=\"src/app.rs\\n(+2 collapsed)\"")));
+    assert!(!dot.contains("parse\""));
+    assert!(dot.contains(&format!("n{app} -> n{util} [label=\"Calls (3)\"];")));
+    assert!(dot.contains(&format!("n{app} -> n{util} [label=\"Imports\"];")));
+    // Calls within a file and Contains edges disappear
+    assert_eq!(dot.matches(" -> ").count(), 2);
+}
@@ -3,7 +3,14 @@
 
 //! Unit tests for core graph operations (add_node, get_node, add_edge, etc.).
 
-use codegraph::{CodeGraph, Direction, EdgeType, NodeType, PropertyMap};
+use codegraph::search::VectorIndexConfig;
+use codegraph::storage::{BatchOperation, KeyValue};
+use codegraph::{
+    CodeGraph, Direction, EdgeType, GraphError, MemoryBackend, NodeType, PropertyMap,
+    StorageBackend,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 #[test]
 fn test_add_node() {
@@ -243,3 +250,97 @@ fn test_batch_add_edges() {
         assert!(graph.get_edge(edge_id).is_ok());
     }
 }
+
+/// Memory storage whose batch writes fail while `failing` is set.
+struct FlakyBackend {
+    inner: MemoryBackend,
+    failing: Arc<AtomicBool>,
+}
+
+impl StorageBackend for FlakyBackend {
+    fn put(&mut self, key: &[u8], value: &[u8]) -> codegraph::Result<()> {
+        self.inner.put(key, value)
+    }
+
+    fn get(&self, key: &[u8]) -> codegraph::Result<Option<Vec<u8>>> {
+        self.inner.get(key)
+    }
+
+    fn delete(&mut self, key: &[u8]) -> codegraph::Result<()> {
+        self.inner.delete(key)
+    }
+
+    fn exists(&self, key: &[u8]) -> codegraph::Result<bool> {
+        self.inner.exists(key)
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> codegraph::Result<Vec<KeyValue>> {
+        self.inner.scan_prefix(prefix)
+    }
+
+    fn write_batch(&mut self, operations: Vec<BatchOperation>) -> codegraph::Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            return Err(GraphError::storage("write failed", None::<std::io::Error>));
+        }
+        self.inner.write_batch(operations)
+    }
+
+    fn flush(&mut self) -> codegraph::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn test_failed_writes_leave_graph_unchanged() {
+    let backend = MemoryBackend::new();
+    let failing = Arc::new(AtomicBool::new(false));
+    let mut graph = CodeGraph::with_backend(Box::new(FlakyBackend {
+        inner: backend.clone(),
+        failing: failing.clone(),
+    }))
+    .unwrap();
+    graph
+        .build_vector_index(VectorIndexConfig::default())
+        .unwrap();
+    let props = |line: i64, embedding: Vec<f32>| {
+        PropertyMap::new()
+            .with("path", "a.rs")
+            .with("line_start", line)
+            .with("line_end", line)
+            .with("embedding", embedding)
+    };
+    let kept = graph
+        .add_node(NodeType::Function, props(1, vec![1.0, 0.0]))
+        .unwrap();
+
+    failing.store(true, Ordering::SeqCst);
+    assert!(graph
+        .add_node(NodeType::Function, props(2, vec![0.0, 1.0]))
+        .is_err());
+    assert!(graph
+        .add_nodes_batch(vec![(NodeType::Function, props(3, vec![0.5, 0.5]))])
+        .is_err());
+    assert!(graph
+        .update_node_properties(kept, props(4, vec![0.0, 1.0]))
+        .is_err());
+    assert!(graph.delete_node(kept).is_err());
+
+    assert_eq!(graph.node_count(), 1);
+    assert_eq!(graph.entity_at("a.rs", 1, 0), vec![kept]);
+    assert!(graph.entity_at("a.rs", 2, 0).is_empty());
+    assert!(graph.entity_at("a.rs", 4, 0).is_empty());
+    assert_eq!(graph.vector_index().unwrap().len(), 1);
+
+    // IDs of failed additions are not used up
+    failing.store(false, Ordering::SeqCst);
+    let added = graph
+        .add_node(NodeType::Function, props(2, vec![0.0, 1.0]))
+        .unwrap();
+    assert_eq!(added, kept + 1);
+
+    let reopened = CodeGraph::with_backend(Box::new(backend)).unwrap();
+    assert_eq!(reopened.node_count(), 2);
+    let stored = reopened.get_node(kept).unwrap();
+    assert_eq!(stored.properties.get_int("line_start"), Some(1));
+    assert_eq!(reopened.vector_index().unwrap().len(), 2);
+}
@@ -261,3 +261,48 @@ fn test_get_file_dependents() {
     assert!(dependents.contains(&main_id));
     assert!(dependents.contains(&lib_id));
 }
+
+#[test]
+fn test_suggest_cycle_breaks() {
+    use codegraph::{helpers, PropertyMap};
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let models = helpers::add_file(&mut graph, "src/models.py", "python").unwrap();
+    let views = helpers::add_file(&mut graph, "src/views.py", "python").unwrap();
+    let utils = helpers::add_file(&mut graph, "src/utils.py", "python").unwrap();
+
+    helpers::add_import(&mut graph, views, models, vec!["User", "Order"]).unwrap();
+    helpers::add_import(&mut graph, models, utils, vec!["slugify", "now"]).unwrap();
+    let back_edge = graph
+        .add_edge(
+            utils,
+            views,
+            EdgeType::Imports,
+            PropertyMap::new()
+                .with("symbols", vec!["render".to_string()])
+                .with("line", 12i64),
+        )
+        .unwrap();
+
+    let plan = helpers::suggest_cycle_breaks(&graph).unwrap();
+    assert_eq!(plan.len(), 1);
+
+    let suggestion = &plan[0];
+    assert_eq!(suggestion.edge_id, back_edge);
+    assert_eq!(suggestion.from_path, "src/utils.py");
+    assert_eq!(suggestion.to_path, "src/views.py");
+    assert_eq!(suggestion.line, Some(12));
+    assert_eq!(suggestion.symbols, vec!["render".to_string()]);
+    assert_eq!(suggestion.cycle.len(), 3);
+}
+
+#[test]
+fn test_suggest_cycle_breaks_without_cycles() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let a = codegraph::helpers::add_file(&mut graph, "a.rs", "rust").unwrap();
+    let b = codegraph::helpers::add_file(&mut graph, "b.rs", "rust").unwrap();
+    codegraph::helpers::add_import(&mut graph, a, b, vec!["x"]).unwrap();
+
+    let plan = codegraph::helpers::suggest_cycle_breaks(&graph).unwrap();
+    assert!(plan.is_empty());
+}
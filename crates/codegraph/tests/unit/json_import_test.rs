@@ -0,0 +1,220 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for JSON import
+//!
+//! Tests cover:
+//! - Round-tripping every node type, edge type and property variant, and
+//!   non-finite vector items
+//! - ID preservation, including sparse IDs after deletions
+//! - Importing into an existing graph with ID remapping
+//! - Schema validation errors
+
+use codegraph::export::{import_json, import_json_reader};
+use codegraph::graph::subgraph::MergeOptions;
+use codegraph::{
+    helpers, CodeGraph, Direction, EdgeType, GraphError, NodeType, PropertyMap, PropertyValue,
+};
+
+fn all_properties() -> PropertyMap {
+    PropertyMap::new()
+        .with("string", "text")
+        .with("int", -42i64)
+        .with("float", 2.5f64)
+        .with("whole_float", 3.0f64)
+        .with("bool", true)
+        .with("strings", vec!["a".to_string(), "b".to_string()])
+        .with("ints", vec![1i64, 2, 3])
+        .with("vector", PropertyValue::Vector(vec![0.5, 1.0, -2.0]))
+        .with("empty_strings", Vec::<String>::new())
+        .with("empty_ints", Vec::<i64>::new())
+        .with("empty_vector", PropertyValue::Vector(vec![]))
+        .with("null", PropertyValue::Null)
+}
+
+fn assert_properties_eq(actual: &PropertyMap, expected: &PropertyMap) {
+    assert_eq!(actual.len(), expected.len());
+    for (key, value) in expected.iter() {
+        assert_eq!(actual.get(key), Some(value), "property '{key}'");
+    }
+}
+
+fn assert_schema_error(json: &str, expected: &str) {
+    match import_json(json) {
+        Err(GraphError::Serialization { message, .. }) => {
+            assert!(message.contains(expected), "unexpected message: {message}")
+        }
+        Err(e) => panic!("unexpected error: {e}"),
+        Ok(_) => panic!("import should fail for {json}"),
+    }
+}
+
+#[test]
+fn test_round_trip_all_types_and_properties() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let nodes: Vec<_> = NodeType::ALL
+        .iter()
+        .map(|&node_type| graph.add_node(node_type, all_properties()).unwrap())
+        .collect();
+    for (i, &edge_type) in EdgeType::ALL.iter().enumerate() {
+        let source = nodes[i % nodes.len()];
+        let target = nodes[(i + 1) % nodes.len()];
+        graph
+            .add_edge(source, target, edge_type, all_properties())
+            .unwrap();
+    }
+
+    let imported = import_json(&graph.export_json().unwrap()).unwrap();
+
+    assert_eq!(imported.node_count(), graph.node_count());
+    assert_eq!(imported.edge_count(), graph.edge_count());
+    for (id, node) in graph.iter_nodes() {
+        let copy = imported.get_node(id).unwrap();
+        assert_eq!(copy.node_type, node.node_type);
+        assert_properties_eq(&copy.properties, &node.properties);
+    }
+    for (id, edge) in graph.iter_edges() {
+        let copy = imported.get_edge(id).unwrap();
+        assert_eq!(
+            (copy.source_id, copy.target_id, copy.edge_type),
+            (edge.source_id, edge.target_id, edge.edge_type)
+        );
+        assert_properties_eq(&copy.properties, &edge.properties);
+    }
+}
+
+#[test]
+fn test_round_trip_preserves_sparse_ids() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/main.rs", "rust").unwrap();
+    let removed = helpers::add_function(&mut graph, file, "old", 1, 5).unwrap();
+    let main = helpers::add_function(&mut graph, file, "main", 7, 12).unwrap();
+    let run = helpers::add_function(&mut graph, file, "run", 14, 20).unwrap();
+    helpers::add_call(&mut graph, main, run, 9).unwrap();
+    graph.delete_node(removed).unwrap();
+
+    let mut imported = import_json(&graph.export_json().unwrap()).unwrap();
+
+    assert!(imported.get_node(removed).is_err());
+    assert_eq!(
+        imported.get_neighbors(main, Direction::Outgoing).unwrap(),
+        vec![run]
+    );
+    // New nodes never collide with imported IDs
+    let added = imported
+        .add_node(NodeType::Function, PropertyMap::new())
+        .unwrap();
+    assert!(added > run);
+}
+
+#[test]
+fn test_round_trip_non_finite_vector_items() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let id = graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new().with("embedding", vec![1.0f32, f32::NAN, f32::INFINITY]),
+        )
+        .unwrap();
+
+    let imported = import_json(&graph.export_json().unwrap()).unwrap();
+    let vector = imported
+        .get_node(id)
+        .unwrap()
+        .properties
+        .get_vector("embedding")
+        .unwrap();
+    assert_eq!(vector.len(), 3);
+    assert_eq!(vector[0], 1.0);
+    assert!(vector[1].is_nan() && vector[2].is_nan());
+}
+
+#[test]
+fn test_import_reader() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    let json = graph.export_json().unwrap();
+
+    let imported = import_json_reader(json.as_bytes()).unwrap();
+    assert_eq!(imported.node_count(), 1);
+}
+
+#[test]
+fn test_import_into_remaps_ids() {
+    let mut shard = CodeGraph::in_memory().unwrap();
+    let lib = helpers::add_file(&mut shard, "src/lib.rs", "rust").unwrap();
+    let util = helpers::add_file(&mut shard, "src/util.rs", "rust").unwrap();
+    helpers::add_import(&mut shard, lib, util, vec![]).unwrap();
+    let json = shard.export_json().unwrap();
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let existing = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    helpers::add_file(&mut graph, "src/other.rs", "rust").unwrap();
+
+    let mapping = graph
+        .import_json_into(&json, MergeOptions::new().dedup_by_path())
+        .unwrap();
+
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(mapping.node(lib), Some(existing));
+    let new_util = mapping.node(util).unwrap();
+    assert_ne!(new_util, util);
+    assert_eq!(
+        graph.get_neighbors(existing, Direction::Outgoing).unwrap(),
+        vec![new_util]
+    );
+}
+
+#[test]
+fn test_import_into_invalid_json_leaves_graph_unchanged() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+
+    assert!(graph
+        .import_json_into(r#"{"nodes": [{"id": 0}]}"#, MergeOptions::new())
+        .is_err());
+    assert_eq!(graph.node_count(), 1);
+}
+
+#[test]
+fn test_import_validation_errors() {
+    assert_schema_error("not json", "Invalid JSON graph");
+    assert_schema_error("[]", "expected an object");
+    assert_schema_error(r#"{"links": []}"#, "\"nodes\" array");
+    assert_schema_error(
+        r#"{"nodes": [{"id": -1, "type": "Function"}]}"#,
+        "nodes[0]: expected a non-negative integer \"id\"",
+    );
+    assert_schema_error(
+        r#"{"nodes": [{"id": 0, "type": "Widget"}]}"#,
+        "nodes[0]: unknown node type",
+    );
+    assert_schema_error(
+        r#"{"nodes": [{"id": 0, "type": "Function"}, {"id": 0, "type": "Class"}]}"#,
+        "nodes[1]: duplicate node id 0",
+    );
+    assert_schema_error(
+        r#"{"nodes": [{"id": 0, "type": "Function", "properties": {"meta": {"a": 1}}}]}"#,
+        "unsupported value for property 'meta'",
+    );
+    assert_schema_error(
+        r#"{"nodes": [{"id": 0, "type": "Function"}],
+            "links": [{"id": 0, "source": 0, "target": 7, "type": "Calls"}]}"#,
+        "links[0]: links missing node 7",
+    );
+    assert_schema_error(
+        r#"{"nodes": [{"id": 0, "type": "Function"}],
+            "links": [{"id": 0, "source": 0, "target": 0, "type": "Knows"}]}"#,
+        "links[0]: unknown edge type",
+    );
+}
+
+#[test]
+fn test_import_minimal_document() {
+    // Properties and links are optional, and type names are case-insensitive
+    let graph = import_json(r#"{"nodes": [{"id": 5, "type": "function"}]}"#).unwrap();
+    let node = graph.get_node(5).unwrap();
+    assert_eq!(node.node_type, NodeType::Function);
+    assert!(node.properties.is_empty());
+    assert_eq!(graph.edge_count(), 0);
+}
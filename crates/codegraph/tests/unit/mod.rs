@@ -1,11 +1,26 @@
 // Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
 // SPDX-License-Identifier: Apache-2.0
 
+mod aggregate_test;
 mod algorithms_test;
+mod community_test;
+mod diagram_test;
+mod edge_query_test;
 mod edge_test;
 mod export_test;
 mod graph_ops_test;
 mod helpers_test;
+mod json_import_test;
 mod node_test;
+mod pattern_test;
+mod position_test;
 mod property_test;
 mod query_builder_test;
+mod query_language_test;
+mod query_order_test;
+mod reachability_test;
+mod search_test;
+mod sequence_test;
+mod subgraph_test;
+mod symbol_search_test;
+mod vector_index_test;
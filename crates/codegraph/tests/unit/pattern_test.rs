@@ -0,0 +1,222 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for subgraph pattern matching
+//!
+//! Tests cover:
+//! - Multi-variable patterns joined over typed edges
+//! - Node matchers (type, property, containing file)
+//! - Edge predicates, match filters, distinct bindings and limits
+//! - Error handling for undeclared variables
+
+use codegraph::query::{NodeMatcher, Pattern};
+use codegraph::{helpers, CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
+
+struct Fixture {
+    graph: CodeGraph,
+    repo: NodeId,
+    cache: NodeId,
+    storage: NodeId,
+    save: NodeId,
+    load: NodeId,
+    test_save: NodeId,
+    main: NodeId,
+}
+
+// Repo and Cache implement Storage; a test and main both call Repo.save
+fn create_graph() -> codegraph::Result<Fixture> {
+    let mut graph = CodeGraph::in_memory()?;
+    let src = helpers::add_file(&mut graph, "src/repo.rs", "rust")?;
+    let test_file = helpers::add_file(&mut graph, "tests/repo_test.rs", "rust")?;
+    let main_file = helpers::add_file(&mut graph, "src/main.rs", "rust")?;
+
+    let storage = graph.add_node(
+        NodeType::Interface,
+        PropertyMap::new().with("name", "Storage"),
+    )?;
+    graph.add_edge(src, storage, EdgeType::Contains, PropertyMap::new())?;
+
+    let repo = helpers::add_class(&mut graph, src, "Repo", 1, 40)?;
+    let cache = helpers::add_class(&mut graph, src, "Cache", 41, 80)?;
+    graph.add_edge(repo, storage, EdgeType::Implements, PropertyMap::new())?;
+    graph.add_edge(cache, storage, EdgeType::Implements, PropertyMap::new())?;
+
+    let save = helpers::add_method(&mut graph, repo, "save", 2, 10)?;
+    let load = helpers::add_method(&mut graph, repo, "load", 11, 20)?;
+    helpers::add_method(&mut graph, cache, "get", 42, 50)?;
+
+    let test_save = helpers::add_function(&mut graph, test_file, "test_save", 1, 10)?;
+    let main = helpers::add_function(&mut graph, main_file, "main", 1, 10)?;
+    helpers::add_call(&mut graph, test_save, save, 5)?;
+    helpers::add_call(&mut graph, main, save, 3)?;
+    graph.add_edge(
+        main,
+        load,
+        EdgeType::Calls,
+        PropertyMap::new().with("is_direct", false),
+    )?;
+
+    Ok(Fixture {
+        graph,
+        repo,
+        cache,
+        storage,
+        save,
+        load,
+        test_save,
+        main,
+    })
+}
+
+#[test]
+fn test_single_edge_pattern() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("c", NodeMatcher::of_type(NodeType::Class))
+        .node("i", NodeMatcher::of_type(NodeType::Interface))
+        .edge("c", EdgeType::Implements, "i");
+
+    let matches = f.graph.match_pattern(&pattern).unwrap();
+    assert_eq!(matches.len(), 2);
+    let classes: Vec<NodeId> = matches.iter().filter_map(|m| m.get("c")).collect();
+    assert_eq!(classes, vec![f.repo, f.cache]);
+    assert!(matches.iter().all(|m| m.get("i") == Some(f.storage)));
+}
+
+#[test]
+fn test_test_function_calls_implementation_method() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("c", NodeMatcher::of_type(NodeType::Class))
+        .node("i", NodeMatcher::of_type(NodeType::Interface))
+        .node("m", NodeMatcher::of_type(NodeType::Function))
+        .node(
+            "f",
+            NodeMatcher::of_type(NodeType::Function).in_file_matching("tests/**"),
+        )
+        .edge("c", EdgeType::Implements, "i")
+        .edge("c", EdgeType::Contains, "m")
+        .edge("f", EdgeType::Calls, "m");
+
+    let matches = f.graph.match_pattern(&pattern).unwrap();
+    assert_eq!(matches.len(), 1);
+    let found = &matches[0];
+    assert_eq!(found.get("c"), Some(f.repo));
+    assert_eq!(found.get("i"), Some(f.storage));
+    assert_eq!(found.get("m"), Some(f.save));
+    assert_eq!(found.get("f"), Some(f.test_save));
+    assert_eq!(found.get("missing"), None);
+
+    let names: Vec<&str> = found.iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["c", "i", "m", "f"]);
+    assert_eq!(found.to_map().len(), 4);
+}
+
+#[test]
+fn test_property_matcher() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("caller", NodeMatcher::any())
+        .node("callee", NodeMatcher::any().name("load"))
+        .edge("caller", EdgeType::Calls, "callee");
+
+    let matches = f.graph.match_pattern(&pattern).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].get("caller"), Some(f.main));
+}
+
+#[test]
+fn test_edge_predicate() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("a", NodeMatcher::of_type(NodeType::Function))
+        .node("b", NodeMatcher::of_type(NodeType::Function))
+        .edge_where("a", EdgeType::Calls, "b", |edge| {
+            edge.properties.get_bool("is_direct") == Some(false)
+        });
+
+    let matches = f.graph.match_pattern(&pattern).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].get("b"), Some(f.load));
+}
+
+#[test]
+fn test_any_edge_type() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("a", NodeMatcher::any())
+        .node("b", NodeMatcher::any().name("Storage"))
+        .edge_of_types("a", &[], "b");
+
+    // src/repo.rs contains Storage, Repo and Cache implement it
+    assert_eq!(f.graph.match_pattern(&pattern).unwrap().len(), 3);
+}
+
+#[test]
+fn test_filter_and_distinct() {
+    let f = create_graph().unwrap();
+
+    // Two classes implementing the same interface
+    let pattern = Pattern::new()
+        .node("a", NodeMatcher::of_type(NodeType::Class))
+        .node("b", NodeMatcher::of_type(NodeType::Class))
+        .node("i", NodeMatcher::of_type(NodeType::Interface))
+        .edge("a", EdgeType::Implements, "i")
+        .edge("b", EdgeType::Implements, "i");
+
+    assert_eq!(f.graph.match_pattern(&pattern).unwrap().len(), 4);
+
+    let distinct = Pattern::new()
+        .node("a", NodeMatcher::of_type(NodeType::Class))
+        .node("b", NodeMatcher::of_type(NodeType::Class))
+        .node("i", NodeMatcher::of_type(NodeType::Interface))
+        .edge("a", EdgeType::Implements, "i")
+        .edge("b", EdgeType::Implements, "i")
+        .distinct_nodes();
+    assert_eq!(f.graph.match_pattern(&distinct).unwrap().len(), 2);
+
+    let ordered = Pattern::new()
+        .node("a", NodeMatcher::of_type(NodeType::Class))
+        .node("b", NodeMatcher::of_type(NodeType::Class))
+        .node("i", NodeMatcher::of_type(NodeType::Interface))
+        .edge("a", EdgeType::Implements, "i")
+        .edge("b", EdgeType::Implements, "i")
+        .filter(|_, m| m.get("a") < m.get("b"));
+    let matches = f.graph.match_pattern(&ordered).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].get("a"), Some(f.repo));
+    assert_eq!(matches[0].get("b"), Some(f.cache));
+}
+
+#[test]
+fn test_limit() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("a", NodeMatcher::of_type(NodeType::Function))
+        .node("b", NodeMatcher::of_type(NodeType::Function))
+        .edge("a", EdgeType::Calls, "b")
+        .limit(2);
+
+    assert_eq!(f.graph.match_pattern(&pattern).unwrap().len(), 2);
+}
+
+#[test]
+fn test_no_matches() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("i", NodeMatcher::of_type(NodeType::Interface))
+        .node("c", NodeMatcher::of_type(NodeType::Class))
+        .edge("i", EdgeType::Implements, "c");
+
+    assert!(f.graph.match_pattern(&pattern).unwrap().is_empty());
+}
+
+#[test]
+fn test_undeclared_variable_error() {
+    let f = create_graph().unwrap();
+    let pattern = Pattern::new()
+        .node("a", NodeMatcher::any())
+        .edge("a", EdgeType::Calls, "b");
+
+    assert!(f.graph.match_pattern(&pattern).is_err());
+}
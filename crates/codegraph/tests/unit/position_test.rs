@@ -0,0 +1,214 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for position lookup
+//!
+//! Tests cover:
+//! - Innermost-first enclosing chains with and without columns
+//! - Boundaries of end-exclusive spans
+//! - Files kept apart by path
+//! - Incremental updates, deletions and clearing
+//! - Partially overlapping spans, including their deletion
+//! - Rebuilding the index when a graph is reopened
+
+use codegraph::{CodeGraph, MemoryBackend, NodeId, NodeType, PropertyMap};
+
+fn span(path: &str, start: (i64, i64), end: (i64, i64)) -> PropertyMap {
+    PropertyMap::new()
+        .with("path", path)
+        .with("line_start", start.0)
+        .with("col_start", start.1)
+        .with("line_end", end.0)
+        .with("col_end", end.1)
+}
+
+/// A class with two methods, the first holding a closure.
+///
+/// ```text
+/// class Server        (1,0)..(9,14)
+///     def handle      (2,4)..(5,19)
+///         lambda      (3,13)..(3,24)
+///     def stop        (7,4)..(9,14)
+/// ```
+fn create_graph() -> (CodeGraph, [NodeId; 4]) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    // Added out of nesting order on purpose
+    let handle = graph
+        .add_node(NodeType::Function, span("server.py", (2, 4), (5, 19)))
+        .unwrap();
+    let closure = graph
+        .add_node(NodeType::Function, span("server.py", (3, 13), (3, 24)))
+        .unwrap();
+    let class = graph
+        .add_node(NodeType::Class, span("server.py", (1, 0), (9, 14)))
+        .unwrap();
+    let stop = graph
+        .add_node(NodeType::Function, span("server.py", (7, 4), (9, 14)))
+        .unwrap();
+    (graph, [class, handle, closure, stop])
+}
+
+#[test]
+fn test_innermost_first_chain() {
+    let (graph, [class, handle, closure, stop]) = create_graph();
+
+    assert_eq!(
+        graph.entity_at("server.py", 3, 15),
+        vec![closure, handle, class]
+    );
+    assert_eq!(graph.entity_at("server.py", 4, 0), vec![handle, class]);
+    assert_eq!(graph.entity_at("server.py", 8, 2), vec![stop, class]);
+    // Between the methods
+    assert_eq!(graph.entity_at("server.py", 6, 0), vec![class]);
+}
+
+#[test]
+fn test_span_boundaries() {
+    let (graph, [class, handle, closure, _]) = create_graph();
+
+    // Starts are inclusive, ends exclusive
+    assert_eq!(
+        graph.entity_at("server.py", 3, 13),
+        vec![closure, handle, class]
+    );
+    assert_eq!(graph.entity_at("server.py", 3, 24), vec![handle, class]);
+    assert_eq!(graph.entity_at("server.py", 2, 3), vec![class]);
+    assert!(graph.entity_at("server.py", 9, 14).is_empty());
+    assert!(graph.entity_at("server.py", 10, 0).is_empty());
+}
+
+#[test]
+fn test_nodes_without_columns_cover_whole_lines() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let props = |start: i64, end: i64| {
+        PropertyMap::new()
+            .with("path", "lib.rs")
+            .with("line_start", start)
+            .with("line_end", end)
+    };
+    let module = graph.add_node(NodeType::Module, props(1, 50)).unwrap();
+    let func = graph.add_node(NodeType::Function, props(10, 20)).unwrap();
+
+    assert_eq!(graph.entity_at("lib.rs", 10, 0), vec![func, module]);
+    assert_eq!(graph.entity_at("lib.rs", 20, 500), vec![func, module]);
+    assert_eq!(graph.entity_at("lib.rs", 21, 0), vec![module]);
+
+    // Nodes missing a path or line range are not indexed
+    graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new()
+                .with("line_start", 1i64)
+                .with("line_end", 99i64),
+        )
+        .unwrap();
+    graph
+        .add_node(
+            NodeType::CodeFile,
+            PropertyMap::new().with("path", "lib.rs"),
+        )
+        .unwrap();
+    assert_eq!(graph.entity_at("lib.rs", 10, 0), vec![func, module]);
+}
+
+#[test]
+fn test_files_are_separate() {
+    let (mut graph, [class, ..]) = create_graph();
+    let other = graph
+        .add_node(NodeType::Function, span("client.py", (1, 0), (9, 0)))
+        .unwrap();
+
+    assert_eq!(graph.entity_at("client.py", 4, 0), vec![other]);
+    assert_eq!(*graph.entity_at("server.py", 4, 0).last().unwrap(), class);
+    assert!(graph.entity_at("missing.py", 4, 0).is_empty());
+}
+
+#[test]
+fn test_identical_spans_nest_by_id() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let outer = graph
+        .add_node(NodeType::Function, span("a.ts", (1, 0), (5, 1)))
+        .unwrap();
+    let inner = graph
+        .add_node(NodeType::Function, span("a.ts", (1, 0), (5, 1)))
+        .unwrap();
+    assert_eq!(graph.entity_at("a.ts", 2, 0), vec![inner, outer]);
+}
+
+#[test]
+fn test_updates_and_deletions() {
+    let (mut graph, [class, handle, closure, stop]) = create_graph();
+
+    // Removing a middle level re-parents its children
+    graph.delete_node(handle).unwrap();
+    assert_eq!(graph.entity_at("server.py", 3, 15), vec![closure, class]);
+
+    // Moving a node moves it in the index
+    graph
+        .update_node_properties(stop, span("server.py", (3, 14), (3, 20)))
+        .unwrap();
+    assert_eq!(
+        graph.entity_at("server.py", 3, 15),
+        vec![stop, closure, class]
+    );
+    assert_eq!(graph.entity_at("server.py", 8, 2), vec![class]);
+
+    // Unrelated property changes leave it in place
+    graph
+        .update_node_properties(stop, PropertyMap::new().with("complexity", 4i64))
+        .unwrap();
+    assert_eq!(graph.entity_at("server.py", 3, 15)[0], stop);
+
+    // A new outer node adopts existing ones
+    let module = graph
+        .add_node(NodeType::Module, span("server.py", (1, 0), (20, 0)))
+        .unwrap();
+    assert_eq!(
+        graph.entity_at("server.py", 3, 15),
+        vec![stop, closure, class, module]
+    );
+
+    graph.clear().unwrap();
+    assert!(graph.entity_at("server.py", 3, 15).is_empty());
+}
+
+#[test]
+fn test_partially_overlapping_spans() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let lines = |start: i64, end: i64| {
+        PropertyMap::new()
+            .with("path", "a.rs")
+            .with("line_start", start)
+            .with("line_end", end)
+    };
+    let a = graph.add_node(NodeType::Function, lines(1, 10)).unwrap();
+    let c = graph.add_node(NodeType::Function, lines(6, 7)).unwrap();
+    let b = graph.add_node(NodeType::Function, lines(5, 15)).unwrap();
+
+    assert_eq!(graph.entity_at("a.rs", 6, 0), vec![c, b, a]);
+    assert_eq!(graph.entity_at("a.rs", 12, 0), vec![b]);
+
+    graph.delete_node(a).unwrap();
+    assert_eq!(graph.entity_at("a.rs", 6, 0), vec![c, b]);
+    graph.delete_node(b).unwrap();
+    assert_eq!(graph.entity_at("a.rs", 6, 0), vec![c]);
+    assert!(graph.entity_at("a.rs", 12, 0).is_empty());
+}
+
+#[test]
+fn test_index_rebuilt_on_reopen() {
+    let backend = MemoryBackend::new();
+    let expected = {
+        let mut graph = CodeGraph::with_backend(Box::new(backend.clone())).unwrap();
+        let class = graph
+            .add_node(NodeType::Class, span("app.go", (1, 0), (30, 1)))
+            .unwrap();
+        let method = graph
+            .add_node(NodeType::Function, span("app.go", (5, 0), (9, 1)))
+            .unwrap();
+        vec![method, class]
+    };
+
+    let reopened = CodeGraph::with_backend(Box::new(backend)).unwrap();
+    assert_eq!(reopened.entity_at("app.go", 6, 4), expected);
+}
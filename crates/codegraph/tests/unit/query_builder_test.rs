@@ -5,7 +5,8 @@
 //!
 //! These tests verify the fluent query interface for finding code patterns.
 
-use codegraph::{helpers, CodeGraph, NodeType};
+use codegraph::query::Predicate;
+use codegraph::{helpers, CodeGraph, EdgeType, NodeId, NodeType, PropertyMap};
 
 #[test]
 fn test_query_builder_node_type_filter() {
@@ -435,3 +436,379 @@ fn test_query_builder_performance_10k_nodes() {
 
     assert!(exists);
 }
+
+// Functions with complexity, line ranges and attribute lists
+fn create_metrics_graph() -> (CodeGraph, Vec<NodeId>) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file_id = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+
+    let mut ids = Vec::new();
+    for (i, (name, complexity, attributes)) in [
+        ("parse", 12, vec!["inline"]),
+        ("test_parse", 3, vec!["test"]),
+        ("render", 7, vec!["deprecated", "inline"]),
+        ("test_render", 15, vec!["test", "ignore"]),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let start = i as i64 * 10 + 1;
+        let id = helpers::add_function(&mut graph, file_id, name, start, start + 8).unwrap();
+        let attributes: Vec<String> = attributes.into_iter().map(String::from).collect();
+        graph
+            .update_node_properties(
+                id,
+                PropertyMap::new()
+                    .with("complexity", complexity as i64)
+                    .with("attributes", attributes),
+            )
+            .unwrap();
+        ids.push(id);
+    }
+
+    (graph, ids)
+}
+
+fn sorted(mut ids: Vec<NodeId>) -> Vec<NodeId> {
+    ids.sort_unstable();
+    ids
+}
+
+#[test]
+fn test_query_builder_numeric_ranges() {
+    let (graph, ids) = create_metrics_graph();
+
+    let results = graph
+        .query()
+        .property_gt("complexity", 10)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[0], ids[3]]);
+
+    let results = graph
+        .query()
+        .property_ge("complexity", 12)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[0], ids[3]]);
+
+    let results = graph
+        .query()
+        .property_lt("complexity", 7)
+        .execute()
+        .unwrap();
+    assert_eq!(results, vec![ids[1]]);
+
+    let results = graph
+        .query()
+        .property_le("complexity", 7.0)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[1], ids[2]]);
+
+    // Inclusive on both ends
+    let results = graph
+        .query()
+        .node_type(NodeType::Function)
+        .between("line_start", 11, 21)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[1], ids[2]]);
+
+    // Missing property never matches
+    assert_eq!(
+        graph.query().property_lt("missing", 100).count().unwrap(),
+        0
+    );
+}
+
+#[test]
+fn test_query_builder_or_and_not() {
+    let (graph, ids) = create_metrics_graph();
+
+    let results = graph
+        .query()
+        .or(
+            Predicate::property_gt("complexity", 10),
+            Predicate::list_contains("attributes", "deprecated"),
+        )
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[0], ids[2], ids[3]]);
+
+    let results = graph
+        .query()
+        .node_type(NodeType::Function)
+        .not(Predicate::name_matches("^test_").unwrap())
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[0], ids[2]]);
+
+    // Composed predicates
+    let predicate = Predicate::list_contains("attributes", "test")
+        .and(!Predicate::list_contains("attributes", "ignore"))
+        .or(Predicate::any([
+            Predicate::property("name", "render"),
+            Predicate::property("name", "nothing"),
+        ]));
+    let results = graph.query().filter(predicate).execute().unwrap();
+    assert_eq!(sorted(results), vec![ids[1], ids[2]]);
+}
+
+#[test]
+fn test_query_builder_real_regex() {
+    let (graph, ids) = create_metrics_graph();
+
+    let results = graph.query().name_matches(r"^test_\w+$").execute().unwrap();
+    assert_eq!(sorted(results), vec![ids[1], ids[3]]);
+
+    let results = graph
+        .query()
+        .name_matches("(parse|render)$")
+        .execute()
+        .unwrap();
+    assert_eq!(results.len(), 4);
+
+    let results = graph.query().name_matches("^re.d").execute().unwrap();
+    assert_eq!(results, vec![ids[2]]);
+
+    let err = graph
+        .query()
+        .name_matches("(unclosed")
+        .execute()
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("Invalid operation: Invalid regex '(unclosed'"));
+    assert!(graph.query().name_matches("[").count().is_err());
+}
+
+#[test]
+fn test_query_builder_list_contains() {
+    let (mut graph, ids) = create_metrics_graph();
+
+    let results = graph
+        .query()
+        .list_contains("attributes", "inline")
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[0], ids[2]]);
+
+    // Legacy comma-separated strings are treated as lists
+    graph
+        .update_node_properties(
+            ids[1],
+            PropertyMap::new().with("attributes", "test, inline"),
+        )
+        .unwrap();
+    let results = graph
+        .query()
+        .list_contains("attributes", "inline")
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(results), vec![ids[0], ids[1], ids[2]]);
+
+    graph
+        .update_node_properties(ids[3], PropertyMap::new().with("lines", vec![3i64, 5]))
+        .unwrap();
+    assert_eq!(
+        graph.query().list_contains("lines", 5).execute().unwrap(),
+        vec![ids[3]]
+    );
+}
+
+#[test]
+fn test_predicates_apply_to_edges() {
+    let (mut graph, ids) = create_metrics_graph();
+    helpers::add_call(&mut graph, ids[1], ids[0], 4).unwrap();
+    graph
+        .add_edge(
+            ids[3],
+            ids[2],
+            EdgeType::Calls,
+            PropertyMap::new()
+                .with("is_direct", false)
+                .with("call_site_line", 35i64),
+        )
+        .unwrap();
+
+    let indirect_late = Predicate::edge_type(EdgeType::Calls)
+        .and(Predicate::property("is_direct", false))
+        .and(Predicate::property_gt("call_site_line", 30));
+
+    let matching: Vec<_> = graph
+        .iter_edges()
+        .filter(|(_, edge)| indirect_late.test(edge))
+        .map(|(_, edge)| (edge.source_id, edge.target_id))
+        .collect();
+    assert_eq!(matching, vec![(ids[3], ids[2])]);
+}
+
+// main calls parse and render; both call log; test_parse calls parse
+fn create_call_graph() -> (CodeGraph, Vec<NodeId>) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let src = helpers::add_file(&mut graph, "src/main.rs", "rust").unwrap();
+    let tests = helpers::add_file(&mut graph, "tests/parse_test.rs", "rust").unwrap();
+
+    let main = helpers::add_function(&mut graph, src, "main", 1, 10).unwrap();
+    let parse = helpers::add_function(&mut graph, src, "parse", 11, 20).unwrap();
+    let render = helpers::add_function(&mut graph, src, "render", 21, 30).unwrap();
+    let log = helpers::add_function(&mut graph, src, "log", 31, 40).unwrap();
+    let test_parse = helpers::add_function(&mut graph, tests, "test_parse", 1, 10).unwrap();
+
+    for (from, to) in [
+        (main, parse),
+        (main, render),
+        (parse, log),
+        (render, log),
+        (test_parse, parse),
+    ] {
+        helpers::add_call(&mut graph, from, to, 1).unwrap();
+    }
+
+    (
+        graph,
+        vec![src, tests, main, parse, render, log, test_parse],
+    )
+}
+
+#[test]
+fn test_query_builder_out_and_dedup() {
+    let (graph, ids) = create_call_graph();
+    let (parse, render, log) = (ids[3], ids[4], ids[5]);
+
+    let callees = graph
+        .query()
+        .property("name", "main")
+        .out(EdgeType::Calls)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(callees), vec![parse, render]);
+
+    // Two hops reach log once per path
+    let two_hops = graph
+        .query()
+        .property("name", "main")
+        .out(EdgeType::Calls)
+        .out(EdgeType::Calls)
+        .execute()
+        .unwrap();
+    assert_eq!(two_hops, vec![log, log]);
+
+    let deduped = graph
+        .query()
+        .property("name", "main")
+        .out(EdgeType::Calls)
+        .out(EdgeType::Calls)
+        .dedup()
+        .execute()
+        .unwrap();
+    assert_eq!(deduped, vec![log]);
+    assert_eq!(
+        graph
+            .query()
+            .property("name", "main")
+            .out(EdgeType::Calls)
+            .out(EdgeType::Calls)
+            .dedup()
+            .count()
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+fn test_query_builder_in_both_and_filters_after_steps() {
+    let (graph, ids) = create_call_graph();
+    let (tests, main, parse, test_parse) = (ids[1], ids[2], ids[3], ids[6]);
+
+    let callers = graph
+        .query()
+        .property("name", "parse")
+        .in_(EdgeType::Calls)
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(callers), vec![main, test_parse]);
+
+    // Filters after a step apply to the reached nodes
+    let test_callers = graph
+        .query()
+        .property("name", "parse")
+        .in_(EdgeType::Calls)
+        .name_matches("^test_")
+        .execute()
+        .unwrap();
+    assert_eq!(test_callers, vec![test_parse]);
+
+    // Files containing callers of parse
+    let files = graph
+        .query()
+        .property("name", "parse")
+        .in_(EdgeType::Calls)
+        .in_(EdgeType::Contains)
+        .dedup()
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(files), vec![ids[0], tests]);
+
+    let neighbors = graph
+        .query()
+        .property("name", "parse")
+        .both(EdgeType::Calls)
+        .dedup()
+        .execute()
+        .unwrap();
+    assert_eq!(sorted(neighbors), vec![main, ids[5], test_parse]);
+
+    // Siblings of render through a shared caller, excluding render itself
+    let siblings = graph
+        .query()
+        .property("name", "render")
+        .in_(EdgeType::Calls)
+        .out(EdgeType::Calls)
+        .not(Predicate::property("name", "render"))
+        .execute()
+        .unwrap();
+    assert_eq!(siblings, vec![parse]);
+}
+
+#[test]
+fn test_query_builder_traverse_with_edge_predicate_and_limit() {
+    let (mut graph, ids) = create_call_graph();
+    let (main, log) = (ids[2], ids[5]);
+    graph
+        .add_edge(
+            main,
+            log,
+            EdgeType::Calls,
+            PropertyMap::new().with("is_direct", false),
+        )
+        .unwrap();
+
+    let indirect = graph
+        .query()
+        .property("name", "main")
+        .traverse(
+            codegraph::Direction::Outgoing,
+            Predicate::edge_type(EdgeType::Calls).and(Predicate::property("is_direct", false)),
+        )
+        .execute()
+        .unwrap();
+    assert_eq!(indirect, vec![log]);
+
+    let limited = graph
+        .query()
+        .node_type(NodeType::Function)
+        .out(EdgeType::Calls)
+        .limit(2)
+        .execute()
+        .unwrap();
+    assert_eq!(limited.len(), 2);
+    assert!(graph
+        .query()
+        .property("name", "log")
+        .out(EdgeType::Calls)
+        .exists()
+        .map(|found| !found)
+        .unwrap());
+}
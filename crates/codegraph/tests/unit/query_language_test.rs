@@ -0,0 +1,326 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for the textual query language
+//!
+//! Tests cover:
+//! - MATCH with labels, inline properties and variable-length paths
+//! - WHERE operators and null handling
+//! - RETURN projections, aliases, DISTINCT (with numeric equality) and LIMIT
+//! - Returned lists: stored vectors and computed lists of floats
+//! - Syntax and validation errors with positions
+
+use codegraph::query::GraphQuery;
+use codegraph::{helpers, CodeGraph, EdgeType, GraphError, NodeId, PropertyMap, PropertyValue};
+
+// main -> handle -> run -> exec, helper -> exec; handle is complex
+fn create_graph() -> codegraph::Result<(CodeGraph, Vec<NodeId>)> {
+    let mut graph = CodeGraph::in_memory()?;
+    let app = helpers::add_file(&mut graph, "src/app.py", "python")?;
+    let util = helpers::add_file(&mut graph, "src/util.py", "python")?;
+
+    let mut ids = Vec::new();
+    for (file, name, complexity) in [
+        (app, "main", 2),
+        (app, "handle", 14),
+        (app, "run", 5),
+        (util, "exec", 1),
+        (util, "helper", 11),
+    ] {
+        let id = helpers::add_function(&mut graph, file, name, 1, 10)?;
+        graph.update_node_properties(
+            id,
+            PropertyMap::new()
+                .with("complexity", complexity as i64)
+                .with(
+                    "path",
+                    if file == app {
+                        "src/app.py"
+                    } else {
+                        "src/util.py"
+                    },
+                ),
+        )?;
+        ids.push(id);
+    }
+
+    helpers::add_call(&mut graph, ids[0], ids[1], 3)?;
+    helpers::add_call(&mut graph, ids[1], ids[2], 4)?;
+    helpers::add_call(&mut graph, ids[2], ids[3], 5)?;
+    graph.add_edge(
+        ids[4],
+        ids[3],
+        EdgeType::Calls,
+        PropertyMap::new().with("is_direct", false),
+    )?;
+
+    Ok((graph, ids))
+}
+
+fn strings(result: &codegraph::query::QueryResult, column: &str) -> Vec<String> {
+    (0..result.len())
+        .map(|row| match result.get(row, column) {
+            Some(PropertyValue::String(s)) => s.clone(),
+            other => panic!("expected a string, got {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn test_variable_length_match_with_where() {
+    let (graph, _) = create_graph().unwrap();
+    let result = graph
+        .execute_query(
+            r#"MATCH (f:Function)-[:Calls*1..3]->(g:Function {name: "exec"})
+               WHERE f.complexity > 10
+               RETURN f.name, f.path"#,
+        )
+        .unwrap();
+
+    assert_eq!(result.columns, vec!["f.name", "f.path"]);
+    assert_eq!(strings(&result, "f.name"), vec!["handle", "helper"]);
+    assert_eq!(
+        strings(&result, "f.path"),
+        vec!["src/app.py", "src/util.py"]
+    );
+}
+
+#[test]
+fn test_hop_bounds() {
+    let (graph, _) = create_graph().unwrap();
+    let callers = |hops: &str| {
+        let query = format!("MATCH (f)-[:Calls{hops}]->(g {{name: 'exec'}}) RETURN f.name");
+        strings(&graph.execute_query(&query).unwrap(), "f.name")
+    };
+
+    assert_eq!(callers(""), vec!["run", "helper"]);
+    assert_eq!(callers("*2"), vec!["handle"]);
+    assert_eq!(callers("*2..3"), vec!["main", "handle"]);
+    assert_eq!(callers("*"), vec!["main", "handle", "run", "helper"]);
+}
+
+#[test]
+fn test_incoming_and_multiple_paths() {
+    let (graph, _) = create_graph().unwrap();
+    let result = graph
+        .execute_query(
+            "MATCH (g:Function)<-[:Calls]-(f), (file:CodeFile)-[:Contains]->(f)
+             WHERE file.path ENDS WITH 'util.py'
+             RETURN g.name AS callee, f.name AS caller",
+        )
+        .unwrap();
+
+    assert_eq!(strings(&result, "callee"), vec!["exec"]);
+    assert_eq!(strings(&result, "caller"), vec!["helper"]);
+}
+
+#[test]
+fn test_edge_property_map() {
+    let (graph, _) = create_graph().unwrap();
+    let result = graph
+        .execute_query("MATCH (f)-[:Calls {is_direct: false}]->(g) RETURN f.name")
+        .unwrap();
+    assert_eq!(strings(&result, "f.name"), vec!["helper"]);
+}
+
+#[test]
+fn test_where_operators() {
+    let (graph, _) = create_graph().unwrap();
+    let names = |condition: &str| {
+        let query = format!("MATCH (f:Function) WHERE {condition} RETURN f.name");
+        strings(&graph.execute_query(&query).unwrap(), "f.name")
+    };
+
+    assert_eq!(
+        names("f.complexity >= 5 AND NOT f.name = 'run'"),
+        vec!["handle", "helper"]
+    );
+    assert_eq!(
+        names("f.name STARTS WITH 'h' OR f.complexity < 2"),
+        vec!["handle", "exec", "helper"]
+    );
+    assert_eq!(names("f.name IN ['main', 'exec']"), vec!["main", "exec"]);
+    assert_eq!(names("f.name =~ 'h.*r'"), vec!["helper"]);
+    assert_eq!(names("f.name CONTAINS 'xe'"), vec!["exec"]);
+    // Missing properties are unknown, never true
+    assert!(names("f.owner = 'x'").is_empty());
+    assert!(names("NOT f.owner = 'x'").is_empty());
+    assert_eq!(names("f.owner IS NULL").len(), 5);
+}
+
+#[test]
+fn test_return_ids_types_distinct_and_limit() {
+    let (graph, ids) = create_graph().unwrap();
+
+    let result = graph
+        .execute_query("MATCH (f {name: 'main'}) RETURN f, id(f), type(f)")
+        .unwrap();
+    assert_eq!(
+        result.rows,
+        vec![vec![
+            PropertyValue::Int(ids[0] as i64),
+            PropertyValue::Int(ids[0] as i64),
+            PropertyValue::from("Function"),
+        ]]
+    );
+
+    let result = graph
+        .execute_query("MATCH (f:Function)-[:Calls]->(g) RETURN DISTINCT f.path")
+        .unwrap();
+    assert_eq!(
+        strings(&result, "f.path"),
+        vec!["src/app.py", "src/util.py"]
+    );
+
+    let result = graph
+        .execute_query("MATCH (f:Function) RETURN f.name LIMIT 2")
+        .unwrap();
+    assert_eq!(result.len(), 2);
+}
+
+#[test]
+fn test_distinct_numbers_and_negation_overflow() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    for score in [PropertyValue::Int(1), PropertyValue::Float(1.0)] {
+        graph
+            .add_node(
+                codegraph::NodeType::Function,
+                PropertyMap::new().with("score", score),
+            )
+            .unwrap();
+    }
+    graph
+        .add_node(
+            codegraph::NodeType::Variable,
+            PropertyMap::new().with("score", i64::MIN),
+        )
+        .unwrap();
+
+    // 1 and 1.0 are equal, so DISTINCT keeps one of them
+    let result = graph
+        .execute_query("MATCH (f:Function) RETURN DISTINCT f.score")
+        .unwrap();
+    assert_eq!(result.rows, vec![vec![PropertyValue::Int(1)]]);
+
+    // Negating i64::MIN overflows to null
+    let result = graph
+        .execute_query("MATCH (v:Variable) RETURN -v.score")
+        .unwrap();
+    assert_eq!(result.rows, vec![vec![PropertyValue::Null]]);
+}
+
+#[test]
+fn test_return_lists() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let embedding = vec![0.1f32, 0.7, -2.3];
+    graph
+        .add_node(
+            codegraph::NodeType::Function,
+            PropertyMap::new().with("embedding", embedding.clone()),
+        )
+        .unwrap();
+
+    // Vector properties come back exactly as stored
+    let result = graph
+        .execute_query("MATCH (f:Function) RETURN f.embedding, [1, 2]")
+        .unwrap();
+    assert_eq!(
+        result.rows,
+        vec![vec![
+            PropertyValue::Vector(embedding),
+            PropertyValue::IntList(vec![1, 2]),
+        ]]
+    );
+
+    // A computed list of floats is not narrowed to a vector
+    assert!(matches!(
+        graph.execute_query("MATCH (f:Function) RETURN [1.5, 2.25]"),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+}
+
+#[test]
+fn test_missing_property_returns_null() {
+    let (graph, _) = create_graph().unwrap();
+    let result = graph
+        .execute_query("MATCH (f {name: 'exec'}) RETURN f.owner")
+        .unwrap();
+    assert_eq!(result.rows, vec![vec![PropertyValue::Null]]);
+}
+
+#[test]
+fn test_parsed_query_is_reusable() {
+    let (graph, _) = create_graph().unwrap();
+    let query = GraphQuery::parse("MATCH (f:CodeFile) RETURN f.path AS path").unwrap();
+    assert_eq!(query.columns(), vec!["path"]);
+    assert_eq!(query.execute(&graph).unwrap().len(), 2);
+
+    let empty = CodeGraph::in_memory().unwrap();
+    assert!(query.execute(&empty).unwrap().is_empty());
+}
+
+fn syntax_error(query: &str) -> (String, usize, usize) {
+    match GraphQuery::parse(query) {
+        Err(GraphError::QuerySyntax {
+            message,
+            line,
+            column,
+        }) => (message, line, column),
+        other => panic!("expected a syntax error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_syntax_errors() {
+    let (message, line, column) = syntax_error("MATCH (f:Function RETURN f");
+    assert_eq!((line, column), (1, 19));
+    assert_eq!(
+        message,
+        "expected ')' to close the node pattern, found 'RETURN'"
+    );
+
+    let (message, _, column) = syntax_error("MATCH (f:Func) RETURN f");
+    assert_eq!(column, 10);
+    assert!(message.starts_with("unknown node label 'Func'; expected one of CodeFile"));
+
+    let (message, _, _) = syntax_error("MATCH (f)-[:Call]->(g) RETURN f");
+    assert!(message.starts_with("unknown relationship type 'Call'"));
+
+    let (message, line, column) = syntax_error("MATCH (f)\nWHERE g.name = 'x'\nRETURN f");
+    assert_eq!((line, column), (2, 7));
+    assert_eq!(message, "variable 'g' is not defined in MATCH");
+
+    let (message, _, _) = syntax_error("MATCH (f)-[:Calls]-(g) RETURN f");
+    assert!(message.contains("undirected relationships are not supported"));
+
+    let (message, _, _) = syntax_error("MATCH (f)-[r:Calls]->(g) RETURN f");
+    assert!(message.starts_with("relationship variables are not supported"));
+
+    let (message, _, _) = syntax_error("MATCH (f)-[*3..1]->(g) RETURN f");
+    assert!(message.contains("smaller than the minimum"));
+
+    let (message, _, _) = syntax_error("MATCH (f:Function), (f:Class) RETURN f");
+    assert_eq!(message, "variable 'f' is labelled both Function and Class");
+
+    let (message, _, _) = syntax_error("MATCH (f) RETURN f LIMIT 1 2");
+    assert_eq!(message, "expected end of query, found number 2");
+
+    let (message, _, _) = syntax_error("MATCH (f) WHERE f.name = 'x RETURN f");
+    assert_eq!(message, "unterminated string literal");
+}
+
+#[test]
+fn test_error_display() {
+    let err = GraphQuery::parse("MATCH f RETURN f").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Query error at line 1, column 7: expected '(' to start a node pattern, found 'f'"
+    );
+}
+
+#[test]
+fn test_invalid_regex_literal() {
+    let (message, _, column) = syntax_error("MATCH (f) WHERE f.name =~ '(' RETURN f");
+    assert_eq!(column, 27);
+    assert!(message.starts_with("invalid regex"));
+}
@@ -0,0 +1,240 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for ordered queries
+//!
+//! Tests cover:
+//! - order_by with limit, secondary keys and missing values
+//! - Cursor pagination, including stability across graph changes
+//! - Projections of property values
+
+use codegraph::query::{Cursor, SortOrder};
+use codegraph::{helpers, CodeGraph, GraphError, NodeId, NodeType, PropertyMap, PropertyValue};
+
+/// Functions f0..f{n} with complexity `i % 5` and a file each `i % 2`.
+fn create_graph(n: usize) -> (CodeGraph, Vec<NodeId>) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let files = [
+        helpers::add_file(&mut graph, "src/a.rs", "rust").unwrap(),
+        helpers::add_file(&mut graph, "src/b.rs", "rust").unwrap(),
+    ];
+    let ids = (0..n)
+        .map(|i| {
+            let id =
+                helpers::add_function(&mut graph, files[i % 2], &format!("f{i}"), 1, 10).unwrap();
+            graph
+                .update_node_properties(id, PropertyMap::new().with("complexity", (i % 5) as i64))
+                .unwrap();
+            id
+        })
+        .collect();
+    (graph, ids)
+}
+
+fn complexities(graph: &CodeGraph, nodes: &[NodeId]) -> Vec<i64> {
+    nodes
+        .iter()
+        .map(|&id| {
+            graph
+                .get_node(id)
+                .unwrap()
+                .properties
+                .get_int("complexity")
+                .unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_order_by_with_limit_is_deterministic() {
+    let (graph, ids) = create_graph(20);
+    let top = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Descending)
+        .limit(6)
+        .execute()
+        .unwrap();
+
+    assert_eq!(complexities(&graph, &top), vec![4, 4, 4, 4, 3, 3]);
+    // Ties are broken by ascending node ID
+    assert_eq!(&top[..4], &[ids[4], ids[9], ids[14], ids[19]]);
+
+    let ascending = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Ascending)
+        .execute()
+        .unwrap();
+    assert_eq!(ascending.len(), 20);
+    assert!(complexities(&graph, &ascending)
+        .windows(2)
+        .all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn test_secondary_keys_and_missing_values() {
+    let (mut graph, ids) = create_graph(4);
+    let file = helpers::add_file(&mut graph, "src/c.rs", "rust").unwrap();
+    let unscored = helpers::add_function(&mut graph, file, "unscored", 1, 2).unwrap();
+
+    for direction in [SortOrder::Ascending, SortOrder::Descending] {
+        let ordered = graph
+            .query()
+            .node_type(NodeType::Function)
+            .order_by("complexity", direction)
+            .execute()
+            .unwrap();
+        assert_eq!(ordered.last(), Some(&unscored));
+    }
+
+    graph
+        .update_node_properties(ids[1], PropertyMap::new().with("complexity", 0i64))
+        .unwrap();
+    let by_name = graph
+        .query()
+        .node_type(NodeType::Function)
+        .property_exists("complexity")
+        .order_by("complexity", SortOrder::Ascending)
+        .order_by("name", SortOrder::Descending)
+        .execute()
+        .unwrap();
+    assert_eq!(by_name, vec![ids[1], ids[0], ids[2], ids[3]]);
+}
+
+#[test]
+fn test_pagination_covers_every_node_once() {
+    let (graph, _) = create_graph(23);
+    let query = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Descending);
+    let all = query.execute().unwrap();
+
+    let mut paged: Vec<NodeId> = Vec::new();
+    let mut cursor: Option<Cursor> = None;
+    let mut pages = 0;
+    loop {
+        let page = query.page(5, cursor.as_ref()).unwrap();
+        paged.extend(&page.nodes);
+        pages += 1;
+        match page.next {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 5);
+    assert_eq!(paged, all);
+}
+
+#[test]
+fn test_pages_are_stable_across_graph_changes() {
+    let (mut graph, ids) = create_graph(10);
+    let first = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Descending)
+        .page(4, None)
+        .unwrap();
+    assert_eq!(complexities(&graph, &first.nodes), vec![4, 4, 3, 3]);
+    let cursor = first.next.clone().unwrap();
+
+    // A new top node and a removed node from the first page do not shift the next page
+    let file = helpers::add_file(&mut graph, "src/new.rs", "rust").unwrap();
+    let hot = helpers::add_function(&mut graph, file, "hot", 1, 2).unwrap();
+    graph
+        .update_node_properties(hot, PropertyMap::new().with("complexity", 99i64))
+        .unwrap();
+    graph.delete_node(first.nodes[0]).unwrap();
+
+    let second = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Descending)
+        .page(4, Some(&cursor))
+        .unwrap();
+    assert_eq!(complexities(&graph, &second.nodes), vec![2, 2, 1, 1]);
+    assert_eq!(second.nodes[0], ids[2]);
+    assert!(!second.nodes.contains(&hot));
+}
+
+#[test]
+fn test_cursor_round_trip_and_mismatch() {
+    let (graph, ids) = create_graph(6);
+
+    // Without order_by, pages follow node IDs
+    let first = graph
+        .query()
+        .node_type(NodeType::Function)
+        .page(2, None)
+        .unwrap();
+    assert_eq!(first.nodes, vec![ids[0], ids[1]]);
+
+    let token = serde_json::to_string(first.next.as_ref().unwrap()).unwrap();
+    let cursor: Cursor = serde_json::from_str(&token).unwrap();
+    let second = graph
+        .query()
+        .node_type(NodeType::Function)
+        .page(2, Some(&cursor))
+        .unwrap();
+    assert_eq!(second.nodes, vec![ids[2], ids[3]]);
+
+    let err = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Ascending)
+        .page(2, Some(&cursor))
+        .unwrap_err();
+    assert!(matches!(err, GraphError::InvalidOperation { .. }));
+
+    let last = graph
+        .query()
+        .node_type(NodeType::Function)
+        .page(10, None)
+        .unwrap();
+    assert_eq!(last.nodes.len(), 6);
+    assert!(last.next.is_none());
+}
+
+#[test]
+fn test_projection() {
+    let (mut graph, ids) = create_graph(5);
+    graph
+        .update_node_properties(ids[4], PropertyMap::new().with("owner", "core"))
+        .unwrap();
+
+    let result = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("complexity", SortOrder::Descending)
+        .limit(2)
+        .project(&["name", "complexity", "owner"])
+        .unwrap();
+
+    assert_eq!(result.columns, vec!["name", "complexity", "owner"]);
+    assert_eq!(
+        result.rows,
+        vec![
+            vec![
+                PropertyValue::from("f4"),
+                PropertyValue::Int(4),
+                PropertyValue::from("core"),
+            ],
+            vec![
+                PropertyValue::from("f3"),
+                PropertyValue::Int(3),
+                PropertyValue::Null,
+            ],
+        ]
+    );
+
+    let page = graph
+        .query()
+        .node_type(NodeType::Function)
+        .order_by("name", SortOrder::Ascending)
+        .page(3, None)
+        .unwrap();
+    let rows = page.project(&graph, &["name"]);
+    assert_eq!(rows.get(2, "name"), Some(&PropertyValue::from("f2")));
+}
@@ -0,0 +1,206 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for the reachability index
+//!
+//! Tests cover:
+//! - Index answers match transitive_dependencies() on DAGs and cycles
+//! - Incremental updates on node/edge insertion
+//! - Staleness and traversal fallback on edge and node removal
+//! - Edge type filtering
+
+use codegraph::{helpers, CodeGraph, EdgeType, NodeId};
+
+const IMPORTS: [EdgeType; 2] = [EdgeType::Imports, EdgeType::ImportsFrom];
+
+// Diamond with a tail and a cycle: a -> b, a -> c, b -> d, c -> d, d -> e -> f -> d
+fn create_graph() -> codegraph::Result<(CodeGraph, Vec<NodeId>)> {
+    let mut graph = CodeGraph::in_memory()?;
+    let ids: Vec<NodeId> = ["a", "b", "c", "d", "e", "f", "g"]
+        .iter()
+        .map(|name| helpers::add_file(&mut graph, &format!("{name}.py"), "python"))
+        .collect::<codegraph::Result<_>>()?;
+
+    for (from, to) in [(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (4, 5), (5, 3)] {
+        helpers::add_import(&mut graph, ids[from], ids[to], vec![])?;
+    }
+
+    Ok((graph, ids))
+}
+
+fn assert_matches_traversal(graph: &CodeGraph, ids: &[NodeId]) {
+    for &from in ids {
+        let deps = helpers::transitive_dependencies(graph, from, None).unwrap();
+        for &to in ids {
+            let expected = from == to || deps.contains(&to);
+            assert_eq!(
+                graph.reaches(from, to).unwrap(),
+                expected,
+                "reaches({from}, {to})"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_reaches_requires_index() {
+    let (graph, ids) = create_graph().unwrap();
+    assert!(graph.reaches(ids[0], ids[1]).is_err());
+}
+
+#[test]
+fn test_index_matches_traversal() {
+    let (mut graph, ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    let index = graph.reachability_index().unwrap();
+    assert_eq!(index.len(), ids.len());
+    assert!(!index.is_stale());
+    assert_matches_traversal(&graph, &ids);
+}
+
+#[test]
+fn test_index_cycle_members_reach_each_other() {
+    let (mut graph, ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    assert!(graph.reaches(ids[3], ids[5]).unwrap());
+    assert!(graph.reaches(ids[5], ids[3]).unwrap());
+    assert!(!graph.reaches(ids[5], ids[0]).unwrap());
+    assert!(!graph.reaches(ids[6], ids[0]).unwrap());
+}
+
+#[test]
+fn test_index_updates_incrementally_on_insert() {
+    let (mut graph, mut ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    // g is isolated; connect it below the cycle
+    helpers::add_import(&mut graph, ids[5], ids[6], vec![]).unwrap();
+    let h = helpers::add_file(&mut graph, "h.py", "python").unwrap();
+    helpers::add_import(&mut graph, ids[6], h, vec![]).unwrap();
+    ids.push(h);
+
+    assert!(!graph.reachability_index().unwrap().is_stale());
+    assert!(graph.reaches(ids[0], h).unwrap());
+    assert!(graph.reaches(ids[4], h).unwrap());
+    assert!(!graph.reaches(h, ids[0]).unwrap());
+    assert_matches_traversal(&graph, &ids);
+}
+
+#[test]
+fn test_index_updates_for_all_cycle_predecessors() {
+    let (mut graph, mut ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    // Enter the d-e-f cycle through e while the new edge leaves from f
+    let x = helpers::add_file(&mut graph, "x.py", "python").unwrap();
+    let y = helpers::add_file(&mut graph, "y.py", "python").unwrap();
+    helpers::add_import(&mut graph, x, ids[4], vec![]).unwrap();
+    helpers::add_import(&mut graph, ids[5], y, vec![]).unwrap();
+    ids.extend([x, y]);
+
+    assert!(graph.reaches(x, y).unwrap());
+    assert_matches_traversal(&graph, &ids);
+}
+
+#[test]
+fn test_index_goes_stale_when_cycle_is_created() {
+    let (mut graph, ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    helpers::add_import(&mut graph, ids[3], ids[0], vec![]).unwrap();
+    assert!(graph.reachability_index().unwrap().is_stale());
+
+    // Fallback traversal still answers correctly
+    assert!(graph.reaches(ids[4], ids[1]).unwrap());
+
+    graph.rebuild_reachability_index();
+    assert!(!graph.reachability_index().unwrap().is_stale());
+    assert_matches_traversal(&graph, &ids);
+}
+
+#[test]
+fn test_index_goes_stale_on_edge_removal() {
+    let (mut graph, ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    let edge = graph.get_edges_between(ids[3], ids[4]).unwrap()[0];
+    graph.delete_edge(edge).unwrap();
+
+    assert!(graph.reachability_index().unwrap().is_stale());
+    assert!(!graph.reaches(ids[0], ids[5]).unwrap());
+    assert_matches_traversal(&graph, &ids);
+
+    graph.rebuild_reachability_index();
+    assert_matches_traversal(&graph, &ids);
+}
+
+#[test]
+fn test_index_goes_stale_when_node_deletion_disconnects() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let ids: Vec<NodeId> = ["a", "b", "c"]
+        .iter()
+        .map(|name| helpers::add_file(&mut graph, &format!("{name}.py"), "python").unwrap())
+        .collect();
+    let first = helpers::add_import(&mut graph, ids[0], ids[1], vec![]).unwrap();
+    helpers::add_import(&mut graph, ids[0], ids[1], vec!["x"]).unwrap();
+    helpers::add_import(&mut graph, ids[1], ids[2], vec![]).unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    // A parallel edge keeps the index valid
+    graph.delete_edge(first).unwrap();
+    assert!(!graph.reachability_index().unwrap().is_stale());
+    assert!(graph.reaches(ids[0], ids[2]).unwrap());
+
+    // Deleting the middle node removes its edges and invalidates the index
+    graph.delete_node(ids[1]).unwrap();
+    assert!(graph.reachability_index().unwrap().is_stale());
+    assert_eq!(
+        graph.reachability_index().unwrap().reaches(ids[0], ids[2]),
+        None
+    );
+    assert!(!graph.reaches(ids[0], ids[2]).unwrap());
+
+    graph.rebuild_reachability_index();
+    assert!(!graph.reaches(ids[0], ids[2]).unwrap());
+}
+
+#[test]
+fn test_index_survives_node_deletion() {
+    let (mut graph, mut ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    let removed = ids.remove(6);
+    graph.delete_node(removed).unwrap();
+    assert!(!graph.reachability_index().unwrap().is_stale());
+    assert_eq!(graph.reachability_index().unwrap().len(), ids.len());
+    assert_matches_traversal(&graph, &ids);
+}
+
+#[test]
+fn test_index_ignores_other_edge_types() {
+    let (mut graph, ids) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+
+    helpers::add_call(&mut graph, ids[6], ids[0], 1).unwrap();
+    assert!(!graph.reachability_index().unwrap().is_stale());
+    assert!(!graph.reaches(ids[6], ids[0]).unwrap());
+
+    graph.build_reachability_index(&[EdgeType::Calls]);
+    assert!(graph.reaches(ids[6], ids[0]).unwrap());
+    assert!(!graph.reaches(ids[0], ids[1]).unwrap());
+}
+
+#[test]
+fn test_index_after_clear() {
+    let (mut graph, _) = create_graph().unwrap();
+    graph.build_reachability_index(&IMPORTS);
+    graph.clear().unwrap();
+
+    assert!(graph.reachability_index().unwrap().is_empty());
+    let a = helpers::add_file(&mut graph, "a.py", "python").unwrap();
+    let b = helpers::add_file(&mut graph, "b.py", "python").unwrap();
+    helpers::add_import(&mut graph, a, b, vec![]).unwrap();
+    assert!(graph.reaches(a, b).unwrap());
+}
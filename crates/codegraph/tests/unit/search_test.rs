@@ -0,0 +1,168 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for the full-text search index
+//!
+//! Tests cover:
+//! - Identifier-aware matching and BM25 ranking with field boosts
+//! - Incremental updates on insert, property update, delete and clear
+//! - Custom field configuration and error handling
+
+use codegraph::search::{TextIndex, TextIndexConfig};
+use codegraph::{helpers, CodeGraph, GraphError, NodeId, NodeType, PropertyMap, PropertyValue};
+
+fn add_documented(
+    graph: &mut CodeGraph,
+    file: NodeId,
+    name: &str,
+    signature: &str,
+    doc: &str,
+) -> NodeId {
+    let id = helpers::add_function(graph, file, name, 1, 10).unwrap();
+    graph
+        .update_node_properties(
+            id,
+            PropertyMap::new()
+                .with("signature", signature)
+                .with("doc", doc),
+        )
+        .unwrap();
+    id
+}
+
+fn ids(hits: &[codegraph::search::SearchHit]) -> Vec<NodeId> {
+    hits.iter().map(|hit| hit.node_id).collect()
+}
+
+#[test]
+fn test_identifier_aware_matching() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/config.rs", "rust").unwrap();
+    let camel = helpers::add_function(&mut graph, file, "parseConfigFile", 1, 5).unwrap();
+    let snake = helpers::add_function(&mut graph, file, "load_config", 6, 9).unwrap();
+    let acronym = helpers::add_function(&mut graph, file, "HTTPServer", 10, 20).unwrap();
+    graph.build_search_index(TextIndexConfig::default());
+
+    assert_eq!(ids(&graph.search("config", 10).unwrap()).len(), 2);
+    assert_eq!(
+        ids(&graph.search("parse_config_file", 10).unwrap())[0],
+        camel
+    );
+    assert_eq!(ids(&graph.search("loadConfig", 10).unwrap())[0], snake);
+    assert_eq!(ids(&graph.search("http", 10).unwrap()), vec![acronym]);
+    assert_eq!(ids(&graph.search("server", 10).unwrap()), vec![acronym]);
+    assert!(graph.search("yaml", 10).unwrap().is_empty());
+    assert!(graph.search("", 10).unwrap().is_empty());
+}
+
+#[test]
+fn test_ranking_and_field_boosts() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/cache.rs", "rust").unwrap();
+    let in_doc = add_documented(
+        &mut graph,
+        file,
+        "store",
+        "fn store(key: &str)",
+        "Write an entry to the cache",
+    );
+    let in_name = add_documented(
+        &mut graph,
+        file,
+        "evict_cache",
+        "fn evict_cache()",
+        "Drop stale entries",
+    );
+    let unrelated = add_documented(&mut graph, file, "render", "fn render()", "Draw output");
+    graph.build_search_index(TextIndexConfig::default());
+
+    let hits = graph.search("cache", 10).unwrap();
+    assert_eq!(ids(&hits), vec![in_name, in_doc]);
+    assert!(hits[0].score > hits[1].score);
+
+    // More matching terms rank higher; limit keeps the best
+    let hits = graph.search("stale cache entries", 1).unwrap();
+    assert_eq!(ids(&hits), vec![in_name]);
+    assert!(!ids(&graph.search("cache entry", 10).unwrap()).contains(&unrelated));
+}
+
+#[test]
+fn test_index_follows_graph_mutations() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    graph.build_search_index(TextIndexConfig::default());
+    let file = helpers::add_file(&mut graph, "src/auth.rs", "rust").unwrap();
+
+    let login = helpers::add_function(&mut graph, file, "login", 1, 5).unwrap();
+    assert_eq!(ids(&graph.search("login", 10).unwrap()), vec![login]);
+
+    let batch = graph
+        .add_nodes_batch(vec![
+            (
+                NodeType::Function,
+                PropertyMap::new().with("name", "logout"),
+            ),
+            (
+                NodeType::Function,
+                PropertyMap::new().with("name", "refreshToken"),
+            ),
+        ])
+        .unwrap();
+    assert_eq!(ids(&graph.search("token", 10).unwrap()), vec![batch[1]]);
+
+    graph
+        .update_node_properties(login, PropertyMap::new().with("name", "sign_in"))
+        .unwrap();
+    assert!(graph.search("login", 10).unwrap().is_empty());
+    assert_eq!(ids(&graph.search("signIn", 10).unwrap()), vec![login]);
+
+    graph.delete_node(batch[0]).unwrap();
+    assert!(graph.search("logout", 10).unwrap().is_empty());
+
+    graph.clear().unwrap();
+    assert!(graph.search_index().unwrap().is_empty());
+}
+
+#[test]
+fn test_rebuild_after_direct_edit() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/a.rs", "rust").unwrap();
+    let id = helpers::add_function(&mut graph, file, "old_name", 1, 2).unwrap();
+    graph.build_search_index(TextIndexConfig::default());
+
+    graph
+        .get_node_mut(id)
+        .unwrap()
+        .properties
+        .insert("name".to_string(), PropertyValue::from("new_name"));
+    assert!(graph.search("new", 10).unwrap().is_empty());
+
+    graph.rebuild_search_index();
+    assert_eq!(ids(&graph.search("new", 10).unwrap()), vec![id]);
+}
+
+#[test]
+fn test_custom_fields_and_errors() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    assert!(matches!(
+        graph.search("x", 10),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+
+    let file = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+    let id = add_documented(&mut graph, file, "run", "fn run()", "Start the scheduler");
+
+    let config = TextIndexConfig {
+        fields: vec![("name".to_string(), 1.0)],
+        ..TextIndexConfig::default()
+    };
+    let index = TextIndex::build(&graph, config);
+    assert!(index.search("scheduler", 10).is_empty());
+    assert_eq!(ids(&index.search("run", 10)), vec![id]);
+    // The file node has no `name`, so only the function is indexed
+    assert_eq!(index.len(), 1);
+
+    graph.build_search_index(TextIndexConfig::default());
+    assert_eq!(ids(&graph.search("scheduler", 10).unwrap()), vec![id]);
+    graph.drop_search_index();
+    assert!(graph.search_index().is_none());
+}
@@ -0,0 +1,139 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for sequence diagrams built from call chains
+//!
+//! Tests cover:
+//! - Call-site ordering via `call_site_line` and `line` edge properties
+//! - Class and file participants
+//! - Recursion, depth and message limits
+//! - Mermaid and PlantUML output
+
+use codegraph::export::SequenceOptions;
+use codegraph::{helpers, CodeGraph, EdgeType, GraphError, NodeId, PropertyMap};
+
+struct Fixture {
+    graph: CodeGraph,
+    handle: NodeId,
+    file: NodeId,
+}
+
+fn call(graph: &mut CodeGraph, caller: NodeId, callee: NodeId, line: i64) {
+    graph
+        .add_edge(
+            caller,
+            callee,
+            EdgeType::Calls,
+            PropertyMap::new().with("call_site_line", line),
+        )
+        .unwrap();
+}
+
+// handle (src/main.rs) calls Server.start at line 5 and Logger.log at line 3;
+// start queries Db, which calls back into start; start also calls util's helper
+fn create_graph() -> Fixture {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let file = helpers::add_file(&mut graph, "src/main.rs", "rust").unwrap();
+    let util = helpers::add_file(&mut graph, "src/util.rs", "rust").unwrap();
+    let server = helpers::add_class(&mut graph, file, "Server", 10, 50).unwrap();
+    let logger = helpers::add_class(&mut graph, file, "Logger", 60, 80).unwrap();
+    let db = helpers::add_class(&mut graph, file, "Db", 90, 120).unwrap();
+
+    let handle = helpers::add_function(&mut graph, file, "handle", 1, 8).unwrap();
+    let start = helpers::add_method(&mut graph, server, "start", 12, 30).unwrap();
+    let log = helpers::add_method(&mut graph, logger, "log", 62, 70).unwrap();
+    let query = helpers::add_method(&mut graph, db, "query", 92, 100).unwrap();
+    let helper = helpers::add_function(&mut graph, util, "helper", 1, 5).unwrap();
+
+    call(&mut graph, handle, start, 5);
+    call(&mut graph, handle, log, 3);
+    helpers::add_call(&mut graph, start, helper, 20).unwrap();
+    helpers::add_call(&mut graph, start, query, 14).unwrap();
+    call(&mut graph, query, start, 95);
+
+    Fixture {
+        graph,
+        handle,
+        file,
+    }
+}
+
+#[test]
+fn test_mermaid_sequence() {
+    let Fixture { graph, handle, .. } = create_graph();
+
+    let diagram = graph
+        .export_mermaid_sequence(handle, &SequenceOptions::default())
+        .unwrap();
+
+    let expected = "\
+sequenceDiagram
+    participant p0 as src/main.rs
+    participant p1 as Logger
+    participant p2 as Server
+    participant p3 as Db
+    participant p4 as src/util.rs
+    activate p0
+    p0->>+p1: log()
+    p1-->>-p0: return
+    p0->>+p2: start()
+    p2->>+p3: query()
+    p3->>+p2: start() (recursive)
+    p2-->>-p3: return
+    p3-->>-p2: return
+    p2->>+p4: helper()
+    p4-->>-p2: return
+    p2-->>-p0: return
+    deactivate p0
+";
+    assert_eq!(diagram, expected);
+}
+
+#[test]
+fn test_plantuml_sequence() {
+    let Fixture { graph, handle, .. } = create_graph();
+    let options = SequenceOptions {
+        show_returns: false,
+        ..SequenceOptions::default()
+    };
+
+    let diagram = graph.export_plantuml_sequence(handle, &options).unwrap();
+
+    assert!(diagram.starts_with("@startuml\nparticipant \"src/main.rs\" as p0\n"));
+    assert!(diagram.contains("p0 -> p1 : log()\np0 -> p2 : start()\np2 -> p3 : query()\n"));
+    assert!(!diagram.contains("-->"));
+    assert!(diagram.trim_end().ends_with("deactivate p0\n@enduml"));
+}
+
+#[test]
+fn test_sequence_depth_and_message_limits() {
+    let Fixture { graph, handle, .. } = create_graph();
+
+    let shallow = SequenceOptions {
+        max_depth: 1,
+        ..SequenceOptions::default()
+    };
+    let diagram = graph.export_plantuml_sequence(handle, &shallow).unwrap();
+    assert!(diagram.contains("start()"));
+    assert!(!diagram.contains("query()"));
+    assert!(!diagram.contains("Db"));
+
+    let short = SequenceOptions {
+        max_messages: 2,
+        ..SequenceOptions::default()
+    };
+    let diagram = graph.export_mermaid_sequence(handle, &short).unwrap();
+    assert_eq!(diagram.matches("->>+").count(), 2);
+    assert!(diagram.contains("Note over p0: Truncated after 2 calls"));
+}
+
+#[test]
+fn test_sequence_requires_function_entry() {
+    let Fixture { graph, file, .. } = create_graph();
+
+    let result = graph.export_mermaid_sequence(file, &SequenceOptions::default());
+    assert!(matches!(result, Err(GraphError::InvalidOperation { .. })));
+
+    let result = graph.export_mermaid_sequence(999, &SequenceOptions::default());
+    assert!(matches!(result, Err(GraphError::NodeNotFound { .. })));
+}
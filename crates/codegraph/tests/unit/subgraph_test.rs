@@ -0,0 +1,211 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for subgraph extraction and graph merging
+//!
+//! Tests cover:
+//! - Extraction by node list and by k-hop neighbourhood
+//! - Direction and edge type restrictions
+//! - ID preservation in extracted subgraphs
+//! - Merging with ID remapping
+//! - Deduplication by path and qualified name
+
+use codegraph::graph::subgraph::{MergeOptions, SubgraphSpec};
+use codegraph::{helpers, CodeGraph, Direction, EdgeType, NodeId, NodeType, PropertyMap};
+
+// main -> run -> exec -> log, all contained in one file
+fn create_graph() -> codegraph::Result<(CodeGraph, NodeId, Vec<NodeId>)> {
+    let mut graph = CodeGraph::in_memory()?;
+    let file = helpers::add_file(&mut graph, "src/main.rs", "rust")?;
+    let funcs: Vec<NodeId> = ["main", "run", "exec", "log"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let line = i as i64 * 10 + 1;
+            helpers::add_function(&mut graph, file, name, line, line + 8)
+        })
+        .collect::<codegraph::Result<_>>()?;
+    for pair in funcs.windows(2) {
+        helpers::add_call(&mut graph, pair[0], pair[1], 1)?;
+    }
+    Ok((graph, file, funcs))
+}
+
+fn qualified(name: &str) -> PropertyMap {
+    PropertyMap::new()
+        .with("name", name.rsplit("::").next().unwrap())
+        .with("qualified_name", name)
+}
+
+#[test]
+fn test_extract_nodes_copies_edges_between_them() {
+    let (graph, file, funcs) = create_graph().unwrap();
+
+    let sub = graph
+        .extract_subgraph(vec![funcs[0], funcs[1], funcs[3]])
+        .unwrap();
+
+    assert_eq!(sub.node_count(), 3);
+    assert!(sub.get_node(file).is_err());
+    // Only main -> run has both endpoints selected
+    assert_eq!(sub.edge_count(), 1);
+    assert_eq!(
+        sub.get_neighbors(funcs[0], Direction::Outgoing).unwrap(),
+        vec![funcs[1]]
+    );
+    assert_eq!(
+        sub.get_node(funcs[3])
+            .unwrap()
+            .properties
+            .get_string("name"),
+        Some("log")
+    );
+}
+
+#[test]
+fn test_extract_neighborhood() {
+    let (graph, file, funcs) = create_graph().unwrap();
+
+    let sub = graph
+        .extract_subgraph(SubgraphSpec::neighborhood([funcs[1]], 1))
+        .unwrap();
+    // run, its caller, its callee and the containing file
+    assert_eq!(sub.node_count(), 4);
+    assert!(sub.get_node(file).is_ok());
+    assert!(sub.get_node(funcs[3]).is_err());
+
+    let sub = graph
+        .extract_subgraph(
+            SubgraphSpec::neighborhood([funcs[0]], 2)
+                .direction(Direction::Outgoing)
+                .edge_types(&[EdgeType::Calls]),
+        )
+        .unwrap();
+    let mut ids: Vec<NodeId> = sub.iter_nodes().map(|(id, _)| id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, funcs[..3].to_vec());
+    assert!(sub
+        .iter_edges()
+        .all(|(_, e)| e.edge_type == EdgeType::Calls));
+}
+
+#[test]
+fn test_extract_missing_seed_fails() {
+    let (graph, _, _) = create_graph().unwrap();
+    assert!(graph.extract_subgraph(vec![999]).is_err());
+}
+
+#[test]
+fn test_extracted_subgraph_assigns_fresh_ids() {
+    let (graph, _, funcs) = create_graph().unwrap();
+    let mut sub = graph
+        .extract_subgraph(SubgraphSpec::nodes(funcs.clone()))
+        .unwrap();
+
+    let added = sub
+        .add_node(NodeType::Function, PropertyMap::new())
+        .unwrap();
+    assert!(graph.get_node(added).is_err());
+    assert!(!funcs.contains(&added));
+}
+
+#[test]
+fn test_merge_remaps_ids() {
+    let (mut graph, _, _) = create_graph().unwrap();
+    let (other, other_file, other_funcs) = create_graph().unwrap();
+    let nodes_before = graph.node_count();
+    let edges_before = graph.edge_count();
+
+    let mapping = graph.merge(&other, MergeOptions::new()).unwrap();
+
+    assert_eq!(graph.node_count(), nodes_before * 2);
+    assert_eq!(graph.edge_count(), edges_before * 2);
+    assert_eq!(mapping.deduplicated_nodes, 0);
+    assert_eq!(mapping.nodes.len(), other.node_count());
+    assert_eq!(mapping.edges.len(), other.edge_count());
+
+    let main = mapping.node(other_funcs[0]).unwrap();
+    let run = mapping.node(other_funcs[1]).unwrap();
+    assert_ne!(main, other_funcs[0]);
+    assert_eq!(
+        graph.get_neighbors(main, Direction::Outgoing).unwrap(),
+        vec![run]
+    );
+    assert_eq!(
+        graph
+            .get_node(mapping.node(other_file).unwrap())
+            .unwrap()
+            .properties
+            .get_string("path"),
+        Some("src/main.rs")
+    );
+}
+
+#[test]
+fn test_merge_dedup_by_path() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let lib = helpers::add_file(&mut graph, "src/lib.rs", "rust").unwrap();
+
+    let mut shard = CodeGraph::in_memory().unwrap();
+    let shard_lib = helpers::add_file(&mut shard, "src/lib.rs", "rust").unwrap();
+    shard
+        .update_node_properties(shard_lib, PropertyMap::new().with("line_count", 40i64))
+        .unwrap();
+    let shard_util = helpers::add_file(&mut shard, "src/util.rs", "rust").unwrap();
+    helpers::add_import(&mut shard, shard_lib, shard_util, vec![]).unwrap();
+
+    let mapping = graph
+        .merge(&shard, MergeOptions::new().dedup_by_path())
+        .unwrap();
+
+    assert_eq!(mapping.node(shard_lib), Some(lib));
+    assert_eq!(mapping.deduplicated_nodes, 1);
+    assert_eq!(graph.node_count(), 2);
+    // Missing properties are copied onto the existing node
+    let lib_node = graph.get_node(lib).unwrap();
+    assert_eq!(lib_node.properties.get_int("line_count"), Some(40));
+    let util = mapping.node(shard_util).unwrap();
+    assert_eq!(
+        graph.get_neighbors(lib, Direction::Outgoing).unwrap(),
+        vec![util]
+    );
+}
+
+#[test]
+fn test_merge_dedup_by_qualified_name_and_edges() {
+    let mut shards = Vec::new();
+    for _ in 0..2 {
+        let mut shard = CodeGraph::in_memory().unwrap();
+        let new = shard
+            .add_node(NodeType::Function, qualified("crate::Server::new"))
+            .unwrap();
+        let connect = shard
+            .add_node(NodeType::Function, qualified("crate::net::connect"))
+            .unwrap();
+        helpers::add_call(&mut shard, new, connect, 3).unwrap();
+        shards.push(shard);
+    }
+    // A class with the same qualified name is a different entity
+    shards[1]
+        .add_node(NodeType::Class, qualified("crate::Server::new"))
+        .unwrap();
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let options = MergeOptions::new().dedup_by_qualified_name();
+    graph.merge(&shards[0], options).unwrap();
+    let mapping = graph.merge(&shards[1], options).unwrap();
+
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 1);
+    assert_eq!(mapping.deduplicated_nodes, 2);
+    assert_eq!(mapping.deduplicated_edges, 1);
+    assert_eq!(
+        graph
+            .query()
+            .node_type(NodeType::Function)
+            .execute()
+            .unwrap()
+            .len(),
+        2
+    );
+}
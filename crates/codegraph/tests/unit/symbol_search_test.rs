@@ -0,0 +1,190 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for fuzzy symbol lookup
+//!
+//! Tests cover:
+//! - Abbreviations, typos and exact-match ranking
+//! - Matching on qualified names
+//! - Node type and language filters, including on large candidate sets
+//! - Incremental updates and error handling
+
+use codegraph::search::SymbolFilter;
+use codegraph::{helpers, CodeGraph, GraphError, NodeId, NodeType, PropertyMap};
+
+fn names(graph: &CodeGraph, query: &str, filter: &SymbolFilter) -> Vec<String> {
+    graph
+        .find_symbols(query, filter, 10)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.text)
+        .collect()
+}
+
+fn create_graph() -> (CodeGraph, Vec<NodeId>) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let py = helpers::add_file(&mut graph, "app/server.py", "python").unwrap();
+    let rs = helpers::add_file(&mut graph, "src/server.rs", "rust").unwrap();
+
+    let ids = vec![
+        helpers::add_function(&mut graph, py, "handle_request", 1, 10).unwrap(),
+        helpers::add_class(&mut graph, py, "HttpRequestHandler", 11, 40).unwrap(),
+        helpers::add_function(&mut graph, rs, "parse", 1, 5).unwrap(),
+        helpers::add_function(&mut graph, rs, "parse_args", 6, 9).unwrap(),
+        helpers::add_function(&mut graph, rs, "sparse_matrix", 10, 20).unwrap(),
+        helpers::add_function(&mut graph, rs, "render", 21, 30).unwrap(),
+    ];
+    graph.build_symbol_index();
+    (graph, ids)
+}
+
+#[test]
+fn test_abbreviation_and_reordered_words() {
+    let (graph, ids) = create_graph();
+    let matches = graph
+        .find_symbols("hndlReq", &SymbolFilter::default(), 10)
+        .unwrap();
+
+    // Subsequence match first, trigram match on reordered words second
+    assert_eq!(matches[0].node_id, ids[0]);
+    assert_eq!(matches[1].node_id, ids[1]);
+    assert!(matches[0].score > 1.0);
+    assert!(matches[1].score <= 1.0);
+    assert_eq!(matches.len(), 2);
+}
+
+#[test]
+fn test_typos_and_exact_matches() {
+    let (graph, _) = create_graph();
+    let all = SymbolFilter::default();
+
+    assert_eq!(names(&graph, "hadnle_request", &all)[0], "handle_request");
+    assert_eq!(
+        names(&graph, "parse", &all),
+        vec!["parse", "parse_args", "sparse_matrix"]
+    );
+    assert_eq!(names(&graph, "pa", &all)[..2], ["parse", "parse_args"]);
+    assert!(names(&graph, "zzz", &all).is_empty());
+    assert!(names(&graph, "__", &all).is_empty());
+
+    let limited = graph.find_symbols("parse", &all, 1).unwrap();
+    assert_eq!(limited.len(), 1);
+}
+
+#[test]
+fn test_filters() {
+    let (graph, ids) = create_graph();
+
+    let classes = SymbolFilter::default().node_type(NodeType::Class);
+    assert_eq!(
+        names(&graph, "hndlReq", &classes),
+        vec!["HttpRequestHandler"]
+    );
+
+    let rust = SymbolFilter::default().language("Rust");
+    assert!(names(&graph, "hndlReq", &rust).is_empty());
+    assert_eq!(names(&graph, "rndr", &rust), vec!["render"]);
+
+    let python_functions = SymbolFilter::default()
+        .node_type(NodeType::Function)
+        .language("python");
+    let matches = graph.find_symbols("req", &python_functions, 10).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].node_id, ids[0]);
+
+    // Either language is accepted
+    let both = SymbolFilter::default().language("rust").language("python");
+    assert_eq!(names(&graph, "r", &both).len(), 3);
+}
+
+#[test]
+fn test_candidate_cut_keeps_filtered_and_subsequence_matches() {
+    // More close trigram matches than a lookup scores
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let noise: Vec<(NodeType, PropertyMap)> = (0..10_050)
+        .map(|i| {
+            let props = PropertyMap::new()
+                .with("name", format!("req_hndl_re_{i}"))
+                .with("language", "python");
+            (NodeType::Function, props)
+        })
+        .collect();
+    graph.add_nodes_batch(noise).unwrap();
+    let go = graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new()
+                .with("name", "reqHndlRe")
+                .with("language", "go"),
+        )
+        .unwrap();
+    let handle = graph
+        .add_node(
+            NodeType::Function,
+            PropertyMap::new()
+                .with("name", "handle_request")
+                .with("language", "python"),
+        )
+        .unwrap();
+    graph.build_symbol_index();
+
+    // The language filter applies before the cut
+    let go_only = SymbolFilter::default().language("go");
+    let matches = graph.find_symbols("reqhndlre", &go_only, 10).unwrap();
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].node_id, go);
+
+    // Subsequence matches are scored despite their low trigram overlap
+    let matches = graph
+        .find_symbols("hndlreq", &SymbolFilter::default(), 1)
+        .unwrap();
+    assert_eq!(matches[0].node_id, handle);
+}
+
+#[test]
+fn test_qualified_names() {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    graph.build_symbol_index();
+    let client = graph
+        .add_node(
+            NodeType::Class,
+            PropertyMap::new()
+                .with("name", "Client")
+                .with("qualified_name", "net::http::Client"),
+        )
+        .unwrap();
+
+    let matches = graph
+        .find_symbols("nethttpcl", &SymbolFilter::default(), 10)
+        .unwrap();
+    assert_eq!(matches[0].node_id, client);
+    assert_eq!(matches[0].text, "net::http::Client");
+
+    let matches = graph
+        .find_symbols("Client", &SymbolFilter::default(), 10)
+        .unwrap();
+    assert_eq!(matches[0].text, "Client");
+}
+
+#[test]
+fn test_index_follows_graph_mutations() {
+    let (mut graph, ids) = create_graph();
+    let all = SymbolFilter::default();
+
+    graph
+        .update_node_properties(ids[5], PropertyMap::new().with("name", "draw_frame"))
+        .unwrap();
+    assert!(!names(&graph, "render", &all).contains(&"render".to_string()));
+    assert_eq!(names(&graph, "drwFrm", &all), vec!["draw_frame"]);
+
+    graph.delete_node(ids[0]).unwrap();
+    assert_eq!(names(&graph, "hndlReq", &all), vec!["HttpRequestHandler"]);
+
+    graph.clear().unwrap();
+    assert!(graph.symbol_index().unwrap().is_empty());
+    graph.drop_symbol_index();
+    assert!(matches!(
+        graph.find_symbols("x", &all, 10),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+}
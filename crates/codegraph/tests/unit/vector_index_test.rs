@@ -0,0 +1,372 @@
+// Copyright 2024-2026 Andrey Vasilevsky <anvanster@gmail.com>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Unit tests for the vector similarity index
+//!
+//! Tests cover:
+//! - Vector properties
+//! - Recall against exact search, for both metrics
+//! - Node type and language filters, and similarity to a node
+//! - Incremental updates and deletions
+//! - Persistence through the storage backend and `persist_to`
+//! - Error handling
+
+use codegraph::search::{Metric, SymbolFilter, VectorIndexConfig};
+use codegraph::{
+    helpers, CodeGraph, GraphError, MemoryBackend, NodeId, NodeType, PropertyMap, PropertyValue,
+    StorageBackend,
+};
+use std::collections::HashSet;
+
+/// Deterministic pseudo-random vectors.
+fn random_vectors(count: usize, dimensions: usize, seed: u64) -> Vec<Vec<f32>> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((state >> 40) as f32 / (1u64 << 24) as f32) * 2.0 - 1.0
+    };
+    (0..count)
+        .map(|_| (0..dimensions).map(|_| next()).collect())
+        .collect()
+}
+
+fn embedding(vector: &[f32]) -> PropertyMap {
+    PropertyMap::new().with("embedding", vector.to_vec())
+}
+
+fn distance(metric: Metric, a: &[f32], b: &[f32]) -> f32 {
+    match metric {
+        Metric::Cosine => {
+            let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+            let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            1.0 - dot / (norm(a) * norm(b))
+        }
+        Metric::Euclidean => a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum(),
+    }
+}
+
+/// Average share of the exact top-k found by the index.
+fn recall(metric: Metric) -> f64 {
+    let vectors = random_vectors(1000, 12, 7);
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let config = VectorIndexConfig {
+        metric,
+        ..VectorIndexConfig::default()
+    };
+    graph.build_vector_index(config).unwrap();
+    let ids = graph
+        .add_nodes_batch(
+            vectors
+                .iter()
+                .map(|v| (NodeType::Function, embedding(v)))
+                .collect(),
+        )
+        .unwrap();
+
+    let k = 10;
+    let queries = random_vectors(50, 12, 99);
+    let mut found = 0;
+    for query in &queries {
+        let mut exact: Vec<(f32, NodeId)> = vectors
+            .iter()
+            .zip(&ids)
+            .map(|(v, &id)| (distance(metric, query, v), id))
+            .collect();
+        exact.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let exact: HashSet<NodeId> = exact.iter().take(k).map(|(_, id)| *id).collect();
+
+        let matches = graph
+            .nearest_neighbors(query, k, &SymbolFilter::default())
+            .unwrap();
+        assert_eq!(matches.len(), k);
+        assert!(matches.windows(2).all(|w| w[0].distance <= w[1].distance));
+        found += matches
+            .iter()
+            .filter(|m| exact.contains(&m.node_id))
+            .count();
+    }
+    found as f64 / (queries.len() * k) as f64
+}
+
+#[test]
+fn test_vector_property() {
+    let props = PropertyMap::new().with("embedding", vec![0.5f32, -1.0]);
+    assert_eq!(props.get_vector("embedding"), Some(&[0.5f32, -1.0][..]));
+    assert_eq!(props.get_string("embedding"), None);
+    assert!(matches!(
+        PropertyValue::from(vec![1.0f32]),
+        PropertyValue::Vector(_)
+    ));
+
+    // Vectors survive storage round trips
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let id = graph.add_node(NodeType::Function, props).unwrap();
+    assert_eq!(
+        graph
+            .get_node(id)
+            .unwrap()
+            .properties
+            .get_vector("embedding"),
+        Some(&[0.5f32, -1.0][..])
+    );
+}
+
+#[test]
+fn test_recall_cosine() {
+    let recall = recall(Metric::Cosine);
+    assert!(recall >= 0.95, "recall {recall}");
+}
+
+#[test]
+fn test_recall_euclidean() {
+    let recall = recall(Metric::Euclidean);
+    assert!(recall >= 0.95, "recall {recall}");
+}
+
+fn create_graph() -> (CodeGraph, Vec<NodeId>) {
+    let mut graph = CodeGraph::in_memory().unwrap();
+    let go = helpers::add_file(&mut graph, "pkg/server.go", "go").unwrap();
+    let py = helpers::add_file(&mut graph, "app/server.py", "python").unwrap();
+
+    let ids = vec![
+        helpers::add_function(&mut graph, go, "HandleRequest", 1, 10).unwrap(),
+        helpers::add_function(&mut graph, py, "handle_request", 1, 10).unwrap(),
+        helpers::add_function(&mut graph, go, "ServeHTTP", 11, 20).unwrap(),
+        helpers::add_class(&mut graph, go, "Server", 21, 40).unwrap(),
+        helpers::add_function(&mut graph, go, "parseFlags", 41, 50).unwrap(),
+    ];
+    let vectors = [
+        [1.0, 0.0, 0.0],
+        [0.98, 0.05, 0.0],
+        [0.9, 0.3, 0.0],
+        [0.95, 0.1, 0.1],
+        [0.0, 0.0, 1.0],
+    ];
+    for (&id, vector) in ids.iter().zip(&vectors) {
+        graph.update_node_properties(id, embedding(vector)).unwrap();
+    }
+    graph
+        .build_vector_index(VectorIndexConfig::default())
+        .unwrap();
+    (graph, ids)
+}
+
+fn neighbor_ids(graph: &CodeGraph, node_id: NodeId, filter: &SymbolFilter) -> Vec<NodeId> {
+    graph
+        .similar_nodes(node_id, 10, filter)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.node_id)
+        .collect()
+}
+
+#[test]
+fn test_similar_nodes_with_filters() {
+    let (graph, ids) = create_graph();
+    assert_eq!(graph.vector_index().unwrap().len(), 5);
+
+    let all = SymbolFilter::default();
+    assert_eq!(
+        neighbor_ids(&graph, ids[0], &all),
+        vec![ids[1], ids[3], ids[2], ids[4]]
+    );
+
+    // "Functions similar to this one, only in Go"
+    let go_functions = SymbolFilter::default()
+        .node_type(NodeType::Function)
+        .language("go");
+    assert_eq!(
+        neighbor_ids(&graph, ids[0], &go_functions),
+        vec![ids[2], ids[4]]
+    );
+
+    let matches = graph.nearest_neighbors(&[0.0, 0.0, 2.0], 1, &all).unwrap();
+    assert_eq!(matches[0].node_id, ids[4]);
+    assert!(matches[0].distance.abs() < 1e-6);
+}
+
+#[test]
+fn test_filter_matching_nothing() {
+    let (graph, ids) = create_graph();
+    let rust = SymbolFilter::default().language("rust");
+    assert!(neighbor_ids(&graph, ids[0], &rust).is_empty());
+}
+
+#[test]
+fn test_incremental_updates() {
+    let (mut graph, ids) = create_graph();
+    let all = SymbolFilter::default();
+
+    // Moving a vector moves the node in the results
+    graph
+        .update_node_properties(ids[4], embedding(&[1.0, 0.01, 0.0]))
+        .unwrap();
+    assert_eq!(neighbor_ids(&graph, ids[0], &all)[0], ids[4]);
+
+    // Unrelated property changes keep the node indexed
+    graph
+        .update_node_properties(ids[4], PropertyMap::new().with("complexity", 3i64))
+        .unwrap();
+    assert_eq!(neighbor_ids(&graph, ids[0], &all)[0], ids[4]);
+
+    graph.delete_node(ids[4]).unwrap();
+    graph.delete_node(ids[1]).unwrap();
+    assert_eq!(neighbor_ids(&graph, ids[0], &all), vec![ids[3], ids[2]]);
+
+    // New nodes are indexed on insertion; other properties are ignored
+    let new = graph
+        .add_node(NodeType::Function, embedding(&[1.0, 0.0, 0.001]))
+        .unwrap();
+    graph
+        .add_node(NodeType::Function, PropertyMap::new().with("name", "plain"))
+        .unwrap();
+    assert_eq!(neighbor_ids(&graph, ids[0], &all)[0], new);
+    assert_eq!(graph.vector_index().unwrap().len(), 4);
+
+    graph.clear().unwrap();
+    assert!(graph.vector_index().unwrap().is_empty());
+}
+
+#[test]
+fn test_index_persists_through_storage() {
+    let backend = MemoryBackend::new();
+    let vectors = random_vectors(300, 8, 3);
+    let query = &vectors[17];
+    let all = SymbolFilter::default();
+
+    let (ids, expected) = {
+        let mut graph = CodeGraph::with_backend(Box::new(backend.clone())).unwrap();
+        graph
+            .build_vector_index(VectorIndexConfig::default())
+            .unwrap();
+        let ids: Vec<NodeId> = vectors
+            .iter()
+            .map(|v| graph.add_node(NodeType::Function, embedding(v)).unwrap())
+            .collect();
+        for &id in ids.iter().step_by(4) {
+            graph.delete_node(id).unwrap();
+        }
+        let expected = graph.nearest_neighbors(query, 10, &all).unwrap();
+        (ids, expected)
+    };
+
+    let mut reopened = CodeGraph::with_backend(Box::new(backend.clone())).unwrap();
+    let index = reopened.vector_index().expect("index restored");
+    assert_eq!(index.len(), 225);
+    assert_eq!(index.dimensions(), Some(8));
+    assert_eq!(
+        reopened.nearest_neighbors(query, 10, &all).unwrap(),
+        expected
+    );
+
+    // The reopened index keeps tracking mutations
+    reopened.delete_node(expected[0].node_id).unwrap();
+    assert_ne!(
+        reopened.nearest_neighbors(query, 1, &all).unwrap()[0].node_id,
+        expected[0].node_id
+    );
+    assert!(ids.contains(&expected[0].node_id));
+
+    // Dropping removes it from storage
+    reopened.drop_vector_index().unwrap();
+    drop(reopened);
+    let reopened = CodeGraph::with_backend(Box::new(backend)).unwrap();
+    assert!(reopened.vector_index().is_none());
+}
+
+#[test]
+fn test_index_persists_through_persist_to() {
+    let vectors = random_vectors(200, 6, 5);
+    let query = &vectors[42];
+    let all = SymbolFilter::default();
+
+    let mut graph = CodeGraph::in_memory().unwrap();
+    graph
+        .build_vector_index(VectorIndexConfig::default())
+        .unwrap();
+    for v in &vectors {
+        graph.add_node(NodeType::Function, embedding(v)).unwrap();
+    }
+    let expected = graph.nearest_neighbors(query, 10, &all).unwrap();
+
+    let backend = MemoryBackend::new();
+    graph.persist_to(Box::new(backend.clone())).unwrap();
+    assert_eq!(
+        backend.scan_prefix(b"vector:").unwrap().len(),
+        vectors.len()
+    );
+
+    let reopened = CodeGraph::with_backend(Box::new(backend)).unwrap();
+    assert_eq!(reopened.vector_index().expect("index restored").len(), 200);
+    assert_eq!(
+        reopened.nearest_neighbors(query, 10, &all).unwrap(),
+        expected
+    );
+}
+
+#[test]
+fn test_stale_stored_index_is_rebuilt() {
+    let backend = MemoryBackend::new();
+    let ids: Vec<NodeId> = {
+        let mut graph = CodeGraph::with_backend(Box::new(backend.clone())).unwrap();
+        graph
+            .build_vector_index(VectorIndexConfig::default())
+            .unwrap();
+        random_vectors(50, 4, 11)
+            .iter()
+            .map(|v| graph.add_node(NodeType::Function, embedding(v)).unwrap())
+            .collect()
+    };
+
+    // Lose the links of one node, leaving dangling links to it
+    let mut storage = backend.clone();
+    let key = format!("vector:{}", ids[5]);
+    assert!(storage.exists(key.as_bytes()).unwrap());
+    storage.delete(key.as_bytes()).unwrap();
+
+    let graph = CodeGraph::with_backend(Box::new(backend.clone())).unwrap();
+    let index = graph.vector_index().unwrap();
+    assert_eq!(index.len(), 50);
+    assert!(index.contains(ids[5]));
+    assert!(storage.exists(key.as_bytes()).unwrap());
+}
+
+#[test]
+fn test_errors() {
+    let (mut graph, ids) = create_graph();
+    let all = SymbolFilter::default();
+
+    assert!(matches!(
+        graph.nearest_neighbors(&[1.0, 0.0], 5, &all),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+    assert!(matches!(
+        graph.nearest_neighbors(&[0.0, 0.0, 0.0], 5, &all),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+    assert!(graph
+        .nearest_neighbors(&[1.0, 0.0, 0.0], 0, &all)
+        .unwrap()
+        .is_empty());
+
+    // Files carry no embedding
+    let file = helpers::find_file_by_path(&graph, "pkg/server.go")
+        .unwrap()
+        .unwrap();
+    assert!(matches!(
+        graph.similar_nodes(file, 5, &all),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+    assert!(matches!(
+        graph.similar_nodes(999, 5, &all),
+        Err(GraphError::NodeNotFound { .. })
+    ));
+
+    graph.drop_vector_index().unwrap();
+    assert!(matches!(
+        graph.similar_nodes(ids[0], 5, &all),
+        Err(GraphError::InvalidOperation { .. })
+    ));
+}